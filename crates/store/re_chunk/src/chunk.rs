@@ -285,6 +285,16 @@ impl Chunk {
         self
     }
 
+    /// Returns a version of us that belongs to a different [`EntityPath`].
+    ///
+    /// The data itself is left untouched: same [`ChunkId`], same [`RowId`]s.
+    #[must_use]
+    #[inline]
+    pub fn with_entity_path(mut self, entity_path: EntityPath) -> Self {
+        self.entity_path = entity_path;
+        self
+    }
+
     /// Returns `Ok` if two [`Chunk`]s are _similar_, although not byte-for-byte equal.
     ///
     /// In particular, this ignores chunks and row IDs, as well as `log_time` timestamps.
@@ -0,0 +1,265 @@
+use re_log_types::{TimeInt, TimelineName};
+
+use crate::{Chunk, TimeColumn};
+
+// ---
+
+/// A linear correction of the clock that produced the times of a timeline.
+///
+/// `corrected = time + offset + (time - pivot) * drift`
+///
+/// Useful to align recordings coming from different machines with skewed clocks: `offset` accounts
+/// for a constant difference between the clocks, while `drift` accounts for one clock running
+/// slightly faster or slower than the other (e.g. a `drift` of `1e-5` is 10 ppm).
+///
+/// The `pivot` is the time at which the drift has no effect. It should be close to the corrected
+/// times (e.g. the start of the recording): for timestamps, pivoting around the epoch would turn
+/// a tiny drift into an offset of hours.
+///
+/// Results saturate to the valid range of temporal [`TimeInt`]s.
+#[derive(Debug, Clone, Copy)]
+pub struct ClockCorrection {
+    /// Added to every time, in the unit of the timeline (e.g. nanoseconds for timestamps).
+    pub offset: i64,
+
+    /// Relative rate difference between the clocks.
+    ///
+    /// Must be greater than `-1.0` for the correction to preserve the order of the times.
+    pub drift: f64,
+
+    /// The time around which `drift` applies.
+    pub pivot: i64,
+}
+
+impl PartialEq for ClockCorrection {
+    fn eq(&self, other: &Self) -> bool {
+        // The pivot is irrelevant when there is no drift.
+        self.offset == other.offset
+            && self.drift == other.drift
+            && (self.drift == 0.0 || self.pivot == other.pivot)
+    }
+}
+
+impl Default for ClockCorrection {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl ClockCorrection {
+    /// Leaves the times untouched.
+    pub const IDENTITY: Self = Self {
+        offset: 0,
+        drift: 0.0,
+        pivot: 0,
+    };
+
+    /// Shifts every time by a constant `offset`.
+    #[inline]
+    pub fn from_offset(offset: i64) -> Self {
+        Self {
+            offset,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Scales the times by `1 + drift` around `pivot`.
+    #[inline]
+    pub fn from_drift(drift: f64, pivot: i64) -> Self {
+        Self {
+            offset: 0,
+            drift,
+            pivot,
+        }
+    }
+
+    #[inline]
+    pub fn is_identity(&self) -> bool {
+        self.offset == 0 && self.drift == 0.0
+    }
+
+    /// Does this correction preserve the order of the times?
+    #[inline]
+    pub fn is_monotonic(&self) -> bool {
+        self.drift > -1.0
+    }
+
+    /// Applies the correction to a single time.
+    #[inline]
+    pub fn apply(&self, time: i64) -> i64 {
+        #[expect(clippy::cast_possible_truncation)] // `as` saturates, which is what we want.
+        let drift =
+            ((i128::from(time) - i128::from(self.pivot)) as f64 * self.drift).round() as i64;
+        TimeInt::saturated_temporal_i64(time.saturating_add(self.offset).saturating_add(drift))
+            .as_i64()
+    }
+
+    /// Returns a correction equivalent to applying `self`, and then `other`.
+    #[must_use]
+    pub fn then(&self, other: &Self) -> Self {
+        if self.is_identity() {
+            return *other;
+        }
+
+        // t'  = t + o1 + d1·(t - p1)
+        // t'' = t' + o2 + d2·(t' - p2)
+        //     = t + (o1 + o2 + d2·(p1 + o1 - p2)) + (d1 + d2 + d1·d2)·(t - p1)
+        let pivot_shift =
+            i128::from(self.pivot) + i128::from(self.offset) - i128::from(other.pivot);
+        #[expect(clippy::cast_possible_truncation)] // `as` saturates, which is what we want.
+        let offset = self
+            .offset
+            .saturating_add(other.offset)
+            .saturating_add((pivot_shift as f64 * other.drift).round() as i64);
+
+        Self {
+            offset,
+            drift: self.drift + other.drift + self.drift * other.drift,
+            pivot: self.pivot,
+        }
+    }
+
+    /// Estimates the correction that best maps `local` times onto `reference` times, given pairs
+    /// of `(local, reference)` times at which the same event was observed by both clocks.
+    ///
+    /// A single pair yields a pure offset, more pairs yield a least-squares linear fit.
+    ///
+    /// Returns `None` if `samples` is empty.
+    pub fn estimate(samples: &[(i64, i64)]) -> Option<Self> {
+        re_tracing::profile_function!();
+
+        if samples.is_empty() {
+            return None;
+        }
+
+        let n = samples.len() as i128;
+        let mean_local = samples.iter().map(|&(t, _)| i128::from(t)).sum::<i128>() / n;
+        let mean_reference = samples.iter().map(|&(_, t)| i128::from(t)).sum::<i128>() / n;
+
+        // Work relative to the means so that we don't lose any precision on large timestamps.
+        let (mut covariance, mut variance) = (0.0, 0.0);
+        for &(local, reference) in samples {
+            let local = (i128::from(local) - mean_local) as f64;
+            let reference = (i128::from(reference) - mean_reference) as f64;
+            covariance += local * reference;
+            variance += local * local;
+        }
+
+        let drift = if variance > 0.0 {
+            covariance / variance - 1.0
+        } else {
+            0.0
+        };
+
+        // Pivoting around the local mean, through which the fitted line goes.
+        #[expect(clippy::cast_possible_truncation)] // `as` saturates, which is what we want.
+        Some(Self {
+            offset: (mean_reference - mean_local) as i64,
+            drift,
+            pivot: mean_local as i64,
+        })
+    }
+}
+
+impl TimeColumn {
+    /// Applies a [`ClockCorrection`] to every time of this column.
+    #[must_use]
+    pub fn with_clock_correction(&self, correction: &ClockCorrection) -> Self {
+        re_tracing::profile_function!();
+
+        if correction.is_identity() {
+            return self.clone();
+        }
+
+        let times = self
+            .times_raw()
+            .iter()
+            .map(|&time| correction.apply(time))
+            .collect();
+
+        // Monotonic corrections cannot change whether the times are sorted.
+        let is_sorted = correction.is_monotonic().then_some(self.is_sorted);
+
+        Self::new(is_sorted, self.timeline, times)
+    }
+}
+
+impl Chunk {
+    /// Applies a [`ClockCorrection`] to every time of the given timeline, if the chunk has it.
+    ///
+    /// WARNING: the returned chunk has the same old [`crate::ChunkId`]! Change it with [`Self::with_id`].
+    #[must_use]
+    pub fn with_clock_correction(
+        mut self,
+        timeline: &TimelineName,
+        correction: &ClockCorrection,
+    ) -> Self {
+        if let Some(time_column) = self.timelines.get_mut(timeline) {
+            *time_column = time_column.with_clock_correction(correction);
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_and_compose() {
+        let offset = ClockCorrection::from_offset(100);
+        assert_eq!(142, offset.apply(42));
+
+        let drift = ClockCorrection::from_drift(0.5, 0);
+        assert_eq!(63, drift.apply(42));
+
+        let drift = ClockCorrection::from_drift(0.5, 40);
+        assert_eq!(40, drift.apply(40));
+        assert_eq!(43, drift.apply(42));
+
+        let both = offset.then(&drift);
+        assert_eq!(drift.apply(offset.apply(42)), both.apply(42));
+
+        // Saturates rather than overflowing into `TimeInt::STATIC`.
+        assert_eq!(
+            TimeInt::MIN.as_i64(),
+            ClockCorrection::from_offset(-10).apply(i64::MIN + 5)
+        );
+        assert_eq!(
+            TimeInt::MAX.as_i64(),
+            ClockCorrection::from_offset(10).apply(i64::MAX - 5)
+        );
+    }
+
+    #[test]
+    fn estimate() {
+        assert_eq!(None, ClockCorrection::estimate(&[]));
+
+        // Single sample: pure offset.
+        assert_eq!(
+            Some(ClockCorrection::from_offset(-1_000)),
+            ClockCorrection::estimate(&[(5_000, 4_000)])
+        );
+
+        // Nanosecond timestamps from a clock that is 1.5s ahead and runs 10ppm too fast.
+        let epoch = 1_700_000_000_000_000_000_i64;
+        let truth = ClockCorrection {
+            offset: -1_500_000_000,
+            drift: -1e-5,
+            pivot: epoch,
+        };
+        let samples = (0..10)
+            .map(|i| {
+                let local = epoch + i * 1_000_000_000;
+                (local, truth.apply(local))
+            })
+            .collect::<Vec<_>>();
+
+        let estimated = ClockCorrection::estimate(&samples).unwrap();
+        for &(local, reference) in &samples {
+            assert!((estimated.apply(local) - reference).abs() <= 1);
+        }
+    }
+}
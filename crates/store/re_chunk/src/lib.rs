@@ -6,6 +6,7 @@
 
 mod builder;
 mod chunk;
+mod clock;
 mod helpers;
 mod iter;
 mod latest_at;
@@ -22,6 +23,7 @@ pub use self::builder::{ChunkBuilder, TimeColumnBuilder};
 pub use self::chunk::{
     Chunk, ChunkComponents, ChunkError, ChunkResult, TimeColumn, TimeColumnError,
 };
+pub use self::clock::ClockCorrection;
 pub use self::helpers::{ChunkShared, UnitChunkShared};
 pub use self::iter::{
     ChunkComponentIter, ChunkComponentIterItem, ChunkComponentSlicer, ChunkIndicesIter,
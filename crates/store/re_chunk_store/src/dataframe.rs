@@ -34,8 +34,30 @@ pub enum SparseFillStrategy {
     /// The latest-at semantics are applied on the entire dataset as opposed to just the current
     /// view contents: it is possible to end up with values from outside the view!
     LatestAtGlobal,
-    //
-    // TODO(cmc): `LatestAtView`?
+
+    /// Fill null values using latest-at semantics, restricted to the view.
+    ///
+    /// Only static values and values indexed within the query's index range (see
+    /// [`QueryExpression::max_range`]) are used: values from before the view are never pulled in.
+    /// Without an index range, this is the same as [`Self::LatestAtGlobal`].
+    LatestAtView,
+
+    /// Fill null values by interpolating between the surrounding values, using global-scope
+    /// semantics.
+    ///
+    /// Floating-point components (scalars, translations, fixed-size float arrays, …) are linearly
+    /// interpolated element-wise, while `rerun.components.RotationQuat` components are spherically
+    /// interpolated (slerp).
+    /// Components that cannot be interpolated, or that have no value after the current index, are
+    /// filled using [`Self::LatestAtGlobal`] semantics instead.
+    ///
+    /// This is most useful when resampling with [`QueryExpression::using_index_values`].
+    InterpolateGlobal,
+
+    /// Same as [`Self::InterpolateGlobal`], but only using values from within the view.
+    ///
+    /// See [`Self::LatestAtView`] for what is considered to be part of the view.
+    InterpolateView,
 }
 
 impl std::fmt::Display for SparseFillStrategy {
@@ -43,10 +65,447 @@ impl std::fmt::Display for SparseFillStrategy {
         match self {
             Self::None => f.write_str("none"),
             Self::LatestAtGlobal => f.write_str("latest-at (global)"),
+            Self::LatestAtView => f.write_str("latest-at (view)"),
+            Self::InterpolateGlobal => f.write_str("interpolate (global)"),
+            Self::InterpolateView => f.write_str("interpolate (view)"),
+        }
+    }
+}
+
+/// A predicate over the component columns of a row, used to filter out _rows_ from the view contents.
+///
+/// Predicates are evaluated on the data of the row as it is returned, i.e. after sparse-filling
+/// (see [`QueryExpression::sparse_fill_strategy`]).
+/// Static data always wins, as usual.
+///
+/// Predicates can be parsed from their textual form, see the `FromStr` implementation.
+///
+/// See [`QueryExpression::filtered_by`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RowFilter {
+    /// The column contains non-null data.
+    IsNotNull(ComponentColumnSelector),
+
+    /// At least one instance of the column satisfies the comparison.
+    Compare {
+        column: ComponentColumnSelector,
+
+        /// The field to compare, for struct components (e.g. `x` for a 2D point).
+        ///
+        /// `None` compares the component data itself, which must then be primitive.
+        field: Option<String>,
+
+        op: ComparisonOperator,
+
+        value: FilterValue,
+    },
+
+    /// All the predicates hold. Always holds if empty.
+    And(Vec<Self>),
+
+    /// At least one of the predicates holds. Never holds if empty.
+    Or(Vec<Self>),
+}
+
+impl RowFilter {
+    /// Returns all the columns that this predicate depends on, deduplicated.
+    pub fn columns(&self) -> Vec<&ComponentColumnSelector> {
+        fn collect<'a>(filter: &'a RowFilter, columns: &mut Vec<&'a ComponentColumnSelector>) {
+            match filter {
+                RowFilter::IsNotNull(column) | RowFilter::Compare { column, .. } => {
+                    if !columns.contains(&column) {
+                        columns.push(column);
+                    }
+                }
+                RowFilter::And(filters) | RowFilter::Or(filters) => {
+                    for filter in filters {
+                        collect(filter, columns);
+                    }
+                }
+            }
+        }
+
+        let mut columns = Vec::new();
+        collect(self, &mut columns);
+        columns
+    }
+}
+
+impl std::fmt::Display for RowFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IsNotNull(column) => write!(f, "{column} IS NOT NULL"),
+            Self::Compare {
+                column,
+                field,
+                op,
+                value,
+            } => {
+                if let Some(field) = field {
+                    write!(f, "{column}.{field} {op} {value}")
+                } else {
+                    write!(f, "{column} {op} {value}")
+                }
+            }
+            Self::And(filters) | Self::Or(filters) => {
+                let sep = if matches!(self, Self::And(_)) {
+                    " AND "
+                } else {
+                    " OR "
+                };
+                write!(
+                    f,
+                    "({})",
+                    filters.iter().map(|filter| filter.to_string()).join(sep)
+                )
+            }
+        }
+    }
+}
+
+/// The comparison operators supported by [`RowFilter::Compare`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComparisonOperator {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl std::fmt::Display for ComparisonOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Eq => "=",
+            Self::NotEq => "!=",
+            Self::Lt => "<",
+            Self::LtEq => "<=",
+            Self::Gt => ">",
+            Self::GtEq => ">=",
+        })
+    }
+}
+
+/// A literal value, as used by [`RowFilter::Compare`].
+///
+/// Numeric values are compared to any numeric data, regardless of their exact datatypes.
+#[derive(Debug, Clone)]
+pub enum FilterValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+impl PartialEq for FilterValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::Int(a), Self::Int(b)) => a == b,
+            (Self::Float(a), Self::Float(b)) => a.to_bits() == b.to_bits(),
+            (Self::String(a), Self::String(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for FilterValue {}
+
+impl std::hash::Hash for FilterValue {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::Bool(value) => value.hash(state),
+            Self::Int(value) => value.hash(state),
+            Self::Float(value) => value.to_bits().hash(state),
+            Self::String(value) => value.hash(state),
+        }
+    }
+}
+
+impl std::fmt::Display for FilterValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => value.fmt(f),
+            Self::Int(value) => value.fmt(f),
+            Self::Float(value) => value.fmt(f),
+            Self::String(value) => write!(f, "{value:?}"),
+        }
+    }
+}
+
+/// The error returned when parsing a [`RowFilter`] fails.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid row filter: {0}")]
+pub struct RowFilterParseError(String);
+
+impl std::str::FromStr for RowFilter {
+    type Err = RowFilterParseError;
+
+    /// Parses a predicate in the form returned by [`RowFilter`]'s `Display` implementation.
+    ///
+    /// Columns are written as `entity_path:component`, optionally followed by `.field`.
+    /// Predicates are either `column IS NOT NULL` or `column <op> <value>`, where `<op>` is one of
+    /// `=`, `!=`, `<`, `<=`, `>`, `>=` and `<value>` is `true`, `false`, a number, or a
+    /// double-quoted string. They can be combined with `AND`, `OR` and parentheses, `AND` binding
+    /// tighter than `OR`. Keywords are case-insensitive.
+    ///
+    /// Example: `/camera:Points3D:positions IS NOT NULL AND /gripper:Gripper:state = "closed"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize_row_filter(s)?;
+        let mut tokens = tokens.iter().peekable();
+
+        let filter = parse_row_filter_or(&mut tokens)?;
+        if let Some(token) = tokens.next() {
+            return Err(RowFilterParseError(format!("unexpected {token}")));
         }
+
+        Ok(filter)
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+enum RowFilterToken {
+    OpenParen,
+    CloseParen,
+    Operator(ComparisonOperator),
+    String(String),
+    Word(String),
+}
+
+impl std::fmt::Display for RowFilterToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OpenParen => f.write_str("'('"),
+            Self::CloseParen => f.write_str("')'"),
+            Self::Operator(op) => write!(f, "'{op}'"),
+            Self::String(value) => write!(f, "{value:?}"),
+            Self::Word(word) => write!(f, "'{word}'"),
+        }
+    }
+}
+
+type RowFilterTokens<'a> = std::iter::Peekable<std::slice::Iter<'a, RowFilterToken>>;
+
+fn tokenize_row_filter(s: &str) -> Result<Vec<RowFilterToken>, RowFilterParseError> {
+    let is_operator_char = |c: char| matches!(c, '=' | '!' | '<' | '>');
+
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+
+            '(' => tokens.push(RowFilterToken::OpenParen),
+            ')' => tokens.push(RowFilterToken::CloseParen),
+
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some('r') => value.push('\r'),
+                            Some(c @ ('"' | '\\')) => value.push(c),
+                            Some(c) => {
+                                return Err(RowFilterParseError(format!(
+                                    "unsupported escape sequence '\\{c}'"
+                                )));
+                            }
+                            None => {
+                                return Err(RowFilterParseError("unterminated string".to_owned()));
+                            }
+                        },
+                        Some(c) => value.push(c),
+                        None => {
+                            return Err(RowFilterParseError("unterminated string".to_owned()));
+                        }
+                    }
+                }
+                tokens.push(RowFilterToken::String(value));
+            }
+
+            c if is_operator_char(c) => {
+                let followed_by_eq = chars.next_if_eq(&'=').is_some();
+                let op = match (c, followed_by_eq) {
+                    ('=', false) => ComparisonOperator::Eq,
+                    ('!', true) => ComparisonOperator::NotEq,
+                    ('<', false) => ComparisonOperator::Lt,
+                    ('<', true) => ComparisonOperator::LtEq,
+                    ('>', false) => ComparisonOperator::Gt,
+                    ('>', true) => ComparisonOperator::GtEq,
+                    _ => {
+                        return Err(RowFilterParseError(format!("unknown operator at '{c}'")));
+                    }
+                };
+                tokens.push(RowFilterToken::Operator(op));
+            }
+
+            c => {
+                let mut word = String::from(c);
+                while let Some(c) = chars.next_if(|c| {
+                    !c.is_whitespace() && !matches!(c, '(' | ')' | '"') && !is_operator_char(*c)
+                }) {
+                    word.push(c);
+                }
+                tokens.push(RowFilterToken::Word(word));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn is_keyword(token: Option<&&RowFilterToken>, keyword: &str) -> bool {
+    matches!(token, Some(RowFilterToken::Word(word)) if word.eq_ignore_ascii_case(keyword))
+}
+
+fn expect_keyword(
+    tokens: &mut RowFilterTokens<'_>,
+    keyword: &str,
+) -> Result<(), RowFilterParseError> {
+    if is_keyword(tokens.peek(), keyword) {
+        tokens.next();
+        Ok(())
+    } else {
+        Err(RowFilterParseError(match tokens.next() {
+            Some(token) => format!("expected {keyword}, found {token}"),
+            None => format!("expected {keyword}"),
+        }))
+    }
+}
+
+fn parse_row_filter_or(tokens: &mut RowFilterTokens<'_>) -> Result<RowFilter, RowFilterParseError> {
+    let mut filters = vec![parse_row_filter_and(tokens)?];
+    while is_keyword(tokens.peek(), "OR") {
+        tokens.next();
+        filters.push(parse_row_filter_and(tokens)?);
+    }
+
+    Ok(if filters.len() == 1 {
+        filters.remove(0)
+    } else {
+        RowFilter::Or(filters)
+    })
+}
+
+fn parse_row_filter_and(
+    tokens: &mut RowFilterTokens<'_>,
+) -> Result<RowFilter, RowFilterParseError> {
+    let mut filters = vec![parse_row_filter_atom(tokens)?];
+    while is_keyword(tokens.peek(), "AND") {
+        tokens.next();
+        filters.push(parse_row_filter_atom(tokens)?);
+    }
+
+    Ok(if filters.len() == 1 {
+        filters.remove(0)
+    } else {
+        RowFilter::And(filters)
+    })
+}
+
+fn parse_row_filter_atom(
+    tokens: &mut RowFilterTokens<'_>,
+) -> Result<RowFilter, RowFilterParseError> {
+    let column = match tokens.next() {
+        Some(RowFilterToken::OpenParen) => {
+            let filter = parse_row_filter_or(tokens)?;
+            return match tokens.next() {
+                Some(RowFilterToken::CloseParen) => Ok(filter),
+                Some(token) => Err(RowFilterParseError(format!("expected ')', found {token}"))),
+                None => Err(RowFilterParseError("expected ')'".to_owned())),
+            };
+        }
+        Some(RowFilterToken::Word(column)) => column,
+        Some(token) => {
+            return Err(RowFilterParseError(format!(
+                "expected a column, found {token}"
+            )));
+        }
+        None => return Err(RowFilterParseError("expected a column".to_owned())),
+    };
+
+    // A trailing `.field` can only come after the component.
+    let (column, field) = match column.rsplit_once('.') {
+        Some((selector, field))
+            if !field.contains(':')
+                && !field.is_empty()
+                && field.chars().all(|c| c.is_alphanumeric() || c == '_') =>
+        {
+            (selector, Some(field.to_owned()))
+        }
+        _ => (column.as_str(), None),
+    };
+    let column: ComponentColumnSelector = column
+        .parse()
+        .map_err(|err| RowFilterParseError(format!("invalid column '{column}': {err}")))?;
+
+    if is_keyword(tokens.peek(), "IS") {
+        tokens.next();
+        expect_keyword(tokens, "NOT")?;
+        expect_keyword(tokens, "NULL")?;
+
+        if let Some(field) = field {
+            return Err(RowFilterParseError(format!(
+                "cannot check a field for nullity, found '.{field}'"
+            )));
+        }
+
+        return Ok(RowFilter::IsNotNull(column));
+    }
+
+    let op = match tokens.next() {
+        Some(RowFilterToken::Operator(op)) => *op,
+        Some(token) => {
+            return Err(RowFilterParseError(format!(
+                "expected IS NOT NULL or a comparison operator, found {token}"
+            )));
+        }
+        None => {
+            return Err(RowFilterParseError(
+                "expected IS NOT NULL or a comparison operator".to_owned(),
+            ));
+        }
+    };
+
+    let value = match tokens.next() {
+        Some(RowFilterToken::String(value)) => FilterValue::String(value.clone()),
+        Some(RowFilterToken::Word(word)) if word.eq_ignore_ascii_case("true") => {
+            FilterValue::Bool(true)
+        }
+        Some(RowFilterToken::Word(word)) if word.eq_ignore_ascii_case("false") => {
+            FilterValue::Bool(false)
+        }
+        Some(RowFilterToken::Word(word)) => {
+            if let Ok(value) = word.parse::<i64>() {
+                FilterValue::Int(value)
+            } else if let Ok(value) = word.parse::<f64>() {
+                FilterValue::Float(value)
+            } else {
+                return Err(RowFilterParseError(format!("invalid value '{word}'")));
+            }
+        }
+        Some(token) => {
+            return Err(RowFilterParseError(format!(
+                "expected a value, found {token}"
+            )));
+        }
+        None => return Err(RowFilterParseError("expected a value".to_owned())),
+    };
+
+    Ok(RowFilter::Compare {
+        column,
+        field,
+        op,
+        value,
+    })
+}
+
 /// The view contents specify which subset of the database (i.e., which columns) the query runs on.
 ///
 /// Contents are expressed as a set of [`EntityPath`]s and their associated [`re_types_core::ComponentIdentifier`]s.
@@ -181,10 +640,25 @@ pub struct QueryExpression {
     /// If left unspecified, the results will only contain static data.
     ///
     /// Examples: `Some(TimelineName("frame"))`, `None` (only static data).
-    //
-    // TODO(cmc): this has to be a selector otherwise this is a horrible UX.
+    ///
+    /// See also [`QueryExpression::parse_index_selector`].
     pub filtered_index: Option<Index>,
 
+    /// The indices to fall back to, in order, for entities that have no data on the `filtered_index`.
+    ///
+    /// Each entity is indexed by the first index of the chain `filtered_index, filtered_index_fallbacks…`
+    /// that it has temporal data on. The resulting values are all reported in the `filtered_index`
+    /// column, which makes it possible to query entities indexed by different clocks together.
+    ///
+    /// * This has no effect if `filtered_index` isn't set, or isn't known to the store.
+    /// * Fallbacks whose type is different from that of the `filtered_index` are ignored.
+    /// * Rows containing values resolved through a fallback only report the `filtered_index`: all
+    ///   other index columns are null, since their values come from a different clock.
+    ///
+    /// Example: `filtered_index = "sensor_time"` and `filtered_index_fallbacks = ["log_time"]`, i.e.
+    /// "`sensor_time` else `log_time`".
+    pub filtered_index_fallbacks: Vec<Index>,
+
     /// The range of index values used to filter out _rows_ from the view contents.
     ///
     /// Only rows where at least 1 of the view-contents contains non-null data within that range will be kept in
@@ -232,6 +706,22 @@ pub struct QueryExpression {
     // TODO(cmc): multi-pov support
     pub filtered_is_not_null: Option<ComponentColumnSelector>,
 
+    /// A predicate used to filter out _rows_ from the view contents.
+    ///
+    /// Only rows for which the predicate holds will be kept in the final dataset.
+    /// This applies on top of [`QueryExpression::filtered_is_not_null`].
+    ///
+    /// The predicate is evaluated on the rows as they are returned, i.e. after sparse-filling
+    /// (see [`QueryExpression::sparse_fill_strategy`]).
+    ///
+    /// * This has no effect if `filtered_index` isn't set.
+    /// * This also applies to the rows sampled with [`QueryExpression::using_index_values`].
+    /// * Columns which are not part of the view contents never contain any data.
+    ///
+    /// Example: `"/camera:Points3D:positions IS NOT NULL AND /gripper:Gripper:state = \"closed\"".parse()`,
+    /// see [`RowFilter`]'s `FromStr` implementation.
+    pub filtered_by: Option<RowFilter>,
+
     /// Specifies how null values should be filled in the returned dataframe.
     ///
     /// Defaults to [`SparseFillStrategy::None`].
@@ -250,6 +740,22 @@ pub struct QueryExpression {
 }
 
 impl QueryExpression {
+    /// Parses an index selector, i.e. one or more index names separated by `else`, in order of
+    /// preference.
+    ///
+    /// The first index is meant to be used as the [`QueryExpression::filtered_index`], and the
+    /// remaining ones as its [`QueryExpression::filtered_index_fallbacks`].
+    ///
+    /// Example: `"sensor_time else log_time"` yields `["sensor_time", "log_time"]`.
+    pub fn parse_index_selector(selector: &str) -> Vec<Index> {
+        selector
+            .split(" else ")
+            .map(str::trim)
+            .filter(|index| !index.is_empty())
+            .map(Index::from)
+            .collect()
+    }
+
     pub fn is_static(&self) -> bool {
         self.filtered_index.is_none()
     }
@@ -475,10 +981,12 @@ impl ChunkStore {
             include_tombstone_columns,
             include_static_columns,
             filtered_index: _,
+            filtered_index_fallbacks: _,
             filtered_index_range: _,
             filtered_index_values: _,
             using_index_values: _,
             filtered_is_not_null: _,
+            filtered_by: _,
             sparse_fill_strategy: _,
             selection: _,
         } = query;
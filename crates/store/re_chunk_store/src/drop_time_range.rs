@@ -22,6 +22,20 @@ impl ChunkStore {
             return Default::default();
         }
 
+        // The chunks that need to be dropped or split must be decoded first:
+        let virtual_chunk_ids = self
+            .virtual_chunks_per_chunk_id
+            .iter()
+            .filter(|(_, virtual_chunk)| {
+                virtual_chunk
+                    .time_range_per_timeline
+                    .get(timeline)
+                    .is_some_and(|time_range| drop_range.intersects(*time_range))
+            })
+            .map(|(chunk_id, _)| *chunk_id)
+            .collect::<Vec<_>>();
+        self.devirtualize_chunks(virtual_chunk_ids);
+
         // Prepare the changes:
 
         let mut chunk_ids_to_drop = vec![];
@@ -112,6 +112,12 @@ impl ChunkStore {
     /// store's internal references to that data (the `Chunk`s), which will be deallocated once
     /// their reference count reaches 0.
     ///
+    /// Virtual chunks (see [`ChunkStore::from_rrd_filepath_lazy`]) are never removed: their decoded
+    /// payloads are evicted instead, and will be decoded again the next time a query needs them.
+    /// Evictions do not generate any events, since the data is still in the store: downstream
+    /// caches might therefore keep the decoded payloads alive, which is why evictions do not count
+    /// towards the number of bytes the GC has to drop.
+    ///
     /// ## Limitations
     ///
     /// The garbage collector has limited support for latest-at semantics. The configuration option:
@@ -274,6 +280,7 @@ impl ChunkStore {
         let mut chunk_ids_to_be_removed =
             RemovableChunkIdPerTimePerComponentPerTimelinePerEntity::default();
         let mut chunk_ids_dangling = HashSet::default();
+        let mut chunk_ids_to_be_evicted = Vec::new();
 
         let start_time = Instant::now();
 
@@ -285,7 +292,17 @@ impl ChunkStore {
                 .values()
                 .filter(|chunk_id| !protected_chunk_ids.contains(chunk_id))
             {
-                if let Some(chunk) = self.chunks_per_chunk_id.get(chunk_id) {
+                if let Some(virtual_chunk) = self.virtual_chunks_per_chunk_id.get(chunk_id) {
+                    // Virtual chunks are backed by their RRD file: they are never removed, only
+                    // evicted back to their virtual state, which is lossless.
+                    // NOTE: No event is sent for evictions, so query caches might still be holding
+                    // on to the decoded chunk: we cannot count on any memory being reclaimed here.
+                    if let Some(chunk) = virtual_chunk.materialized()
+                        && !options.is_chunk_protected(chunk)
+                    {
+                        chunk_ids_to_be_evicted.push(*chunk_id);
+                    }
+                } else if let Some(chunk) = self.chunks_per_chunk_id.get(chunk_id) {
                     if options.is_chunk_protected(chunk) {
                         continue;
                     }
@@ -333,6 +350,16 @@ impl ChunkStore {
             }
         }
 
+        {
+            re_tracing::profile_scope!("evict");
+
+            for chunk_id in chunk_ids_to_be_evicted {
+                if let Some(virtual_chunk) = self.virtual_chunks_per_chunk_id.get_mut(&chunk_id) {
+                    virtual_chunk.evict();
+                }
+            }
+        }
+
         {
             re_tracing::profile_scope!("sweep");
 
@@ -343,6 +370,7 @@ impl ChunkStore {
                 type_registry: _,
                 per_column_metadata: _, // column metadata is additive only
                 chunks_per_chunk_id,
+                virtual_chunks_per_chunk_id: _, // virtual chunks are only ever evicted
                 chunk_ids_per_min_row_id,
                 temporal_chunk_ids_per_entity_per_component,
                 temporal_chunk_ids_per_entity,
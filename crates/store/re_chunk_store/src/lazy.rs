@@ -0,0 +1,430 @@
+use std::sync::{Arc, OnceLock};
+
+use nohash_hasher::IntMap;
+
+use re_chunk::{Chunk, ChunkId, ComponentIdentifier, Span, TimelineName};
+use re_log_encoding::CrateVersion;
+use re_log_types::{AbsoluteTimeRange, ApplicationId};
+
+use crate::{ChunkStore, ChunkStoreChunkStats};
+
+#[cfg(not(target_arch = "wasm32"))]
+use {
+    re_log_types::{LogMsg, StoreId},
+    std::collections::BTreeMap,
+};
+
+// ---
+
+/// A [`Chunk`] that is fully indexed by the store, but whose payload still lives in its encoded
+/// form in the RRD file it came from (usually memory-mapped).
+///
+/// The payload is decoded the first time a query needs it, and can be evicted back to its
+/// virtual state by the garbage collector at any time, since it can always be decoded again.
+///
+/// See [`ChunkStore::from_rrd_filepath_lazy`].
+#[derive(Clone)]
+pub(crate) struct VirtualChunk {
+    /// The encoded `ArrowMsg`, excluding headers.
+    ///
+    /// This is a slice of the memory-mapped file: it is paged in and out by the OS as needed.
+    payload: bytes::Bytes,
+
+    /// Where [`Self::payload`] lives in the original RRD file.
+    byte_span: Span<u64>,
+
+    /// The Rerun version used to encode the RRD data, for migration purposes.
+    version: Option<CrateVersion>,
+
+    /// Only used for migrating legacy data, see [`re_log_encoding::ApplicationIdInjector`].
+    application_id: ApplicationId,
+
+    /// The stats of the chunk, computed once and for all when it was indexed.
+    pub(crate) stats: ChunkStoreChunkStats,
+
+    /// The number of events for each component in the chunk, see [`Chunk::num_events_for_component`].
+    pub(crate) num_events_per_component: IntMap<ComponentIdentifier, u64>,
+
+    /// The time range covered by the chunk on each of its timelines.
+    pub(crate) time_range_per_timeline: IntMap<TimelineName, AbsoluteTimeRange>,
+
+    /// The decoded chunk, if a query has needed it since the last eviction.
+    materialized: OnceLock<Arc<Chunk>>,
+}
+
+impl std::fmt::Debug for VirtualChunk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VirtualChunk")
+            .field("byte_span", &self.byte_span)
+            .field("version", &self.version)
+            .field("stats", &self.stats)
+            .field("is_materialized", &self.materialized.get().is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl VirtualChunk {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn new(
+        chunk: &Arc<Chunk>,
+        payload: bytes::Bytes,
+        byte_span: Span<u64>,
+        version: Option<CrateVersion>,
+        application_id: ApplicationId,
+    ) -> Self {
+        Self {
+            payload,
+            byte_span,
+            version,
+            application_id,
+            stats: ChunkStoreChunkStats::from_chunk(chunk),
+            num_events_per_component: chunk
+                .components_identifiers()
+                .filter_map(|component| {
+                    chunk
+                        .num_events_for_component(component)
+                        .map(|num_events| (component, num_events))
+                })
+                .collect(),
+            time_range_per_timeline: chunk
+                .timelines()
+                .iter()
+                .map(|(timeline, time_column)| (*timeline, time_column.time_range()))
+                .collect(),
+            materialized: OnceLock::new(),
+        }
+    }
+
+    /// Returns the decoded chunk, if it is currently materialized.
+    #[inline]
+    pub(crate) fn materialized(&self) -> Option<&Arc<Chunk>> {
+        self.materialized.get()
+    }
+
+    /// Returns the decoded chunk, decoding it first if needed.
+    ///
+    /// The decoded chunk is kept around until the next eviction.
+    /// Decoding errors are logged, and the chunk is then treated as if it were missing.
+    pub(crate) fn materialize(&self) -> Option<&Arc<Chunk>> {
+        if let Some(chunk) = self.materialized.get() {
+            return Some(chunk);
+        }
+
+        re_tracing::profile_function!();
+
+        let chunk = self.try_decode()?;
+        Some(self.materialized.get_or_init(|| Arc::new(chunk)))
+    }
+
+    /// Returns the decoded chunk, decoding it first if needed.
+    ///
+    /// Unlike [`Self::materialize`], a freshly decoded chunk is _not_ kept around: it is freed as
+    /// soon as the caller drops it.
+    /// Decoding errors are logged, and the chunk is then treated as if it were missing.
+    pub(crate) fn materialize_transient(&self) -> Option<Arc<Chunk>> {
+        if let Some(chunk) = self.materialized.get() {
+            return Some(Arc::clone(chunk));
+        }
+
+        re_tracing::profile_function!();
+
+        self.try_decode().map(Arc::new)
+    }
+
+    /// Drops the decoded chunk, if any, going back to the virtual state.
+    #[inline]
+    pub(crate) fn evict(&mut self) -> Option<Arc<Chunk>> {
+        self.materialized.take()
+    }
+
+    fn try_decode(&self) -> Option<Chunk> {
+        self.decode()
+            .inspect_err(|err| {
+                re_log::error_once!(
+                    "Failed to decode virtual chunk at byte offset {}: {err:#}",
+                    self.byte_span.start
+                );
+            })
+            .ok()
+    }
+
+    fn decode(&self) -> anyhow::Result<Chunk> {
+        let msg = <re_log_types::LogMsg as re_log_encoding::DecoderEntrypoint>::decode(
+            self.payload.clone(),
+            self.byte_span,
+            re_log_encoding::MessageKind::ArrowMsg,
+            &mut re_log_encoding::DummyApplicationIdInjector::new(self.application_id.clone()),
+            self.version,
+        )?;
+
+        let Some(re_log_types::LogMsg::ArrowMsg(_, msg)) = msg else {
+            anyhow::bail!("expected an ArrowMsg");
+        };
+
+        Ok(Chunk::from_arrow_msg(&msg)?)
+    }
+}
+
+// ---
+
+/// A [`LogMsg`] along with where its payload was found in the RRD stream.
+#[cfg(not(target_arch = "wasm32"))]
+struct SpannedLogMsg {
+    msg: LogMsg,
+    byte_span: Span<u64>,
+    version: Option<CrateVersion>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl re_log_encoding::DecoderEntrypoint for SpannedLogMsg {
+    fn decode(
+        data_excluding_headers: bytes::Bytes,
+        byte_span_excluding_headers: Span<u64>,
+        message_kind: re_log_encoding::MessageKind,
+        app_id_injector: &mut impl re_log_encoding::ApplicationIdInjector,
+        patched_version: Option<CrateVersion>,
+    ) -> Result<Option<Self>, re_log_encoding::CodecError> {
+        let msg = <LogMsg as re_log_encoding::DecoderEntrypoint>::decode(
+            data_excluding_headers,
+            byte_span_excluding_headers,
+            message_kind,
+            app_id_injector,
+            patched_version,
+        )?;
+
+        Ok(msg.map(|msg| Self {
+            msg,
+            byte_span: byte_span_excluding_headers,
+            version: patched_version,
+        }))
+    }
+}
+
+impl ChunkStore {
+    /// Instantiate a new `ChunkStore` with the given [`ChunkStoreConfig`], backed by a
+    /// memory-mapped RRD file.
+    ///
+    /// The stores will be fully indexed, but the Arrow payloads of the chunks are left in the file:
+    /// they only get decoded when a query actually needs them, and can be evicted back to disk by
+    /// the garbage collector.
+    /// This makes it possible to open recordings that are larger than the available memory.
+    ///
+    /// Indexing still requires decoding every chunk once.
+    ///
+    /// Compaction (see [`ChunkStoreConfig`]) happens as usual while indexing: chunks that are
+    /// small enough to be compacted are kept in memory until loading is done, in case one of their
+    /// neighbors gets merged into them. Compacted chunks don't correspond to any single message
+    /// in the file anymore, and therefore stay in memory for good.
+    /// Use [`ChunkStoreConfig::COMPACTION_DISABLED`](crate::ChunkStoreConfig::COMPACTION_DISABLED)
+    /// to keep every chunk virtual.
+    ///
+    /// The file must not be modified while the stores are alive.
+    ///
+    /// See also:
+    /// * [`ChunkStore::from_rrd_filepath`]
+    ///
+    /// [`ChunkStoreConfig`]: crate::ChunkStoreConfig
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_rrd_filepath_lazy(
+        store_config: &crate::ChunkStoreConfig,
+        path_to_rrd: impl AsRef<std::path::Path>,
+    ) -> anyhow::Result<BTreeMap<StoreId, Self>> {
+        let path_to_rrd = path_to_rrd.as_ref();
+
+        re_tracing::profile_function!(path_to_rrd.to_string_lossy());
+
+        use anyhow::Context as _;
+
+        let mut stores: BTreeMap<StoreId, Self> = BTreeMap::new();
+
+        let rrd_file = std::fs::File::open(path_to_rrd)
+            .with_context(|| format!("couldn't open {path_to_rrd:?}"))?;
+
+        // SAFETY: file-backed memory maps are marked unsafe because of potential UB when using the map and the underlying file is modified.
+        #[expect(unsafe_code)]
+        let rrd_mmap = unsafe { memmap2::Mmap::map(&rrd_file) }
+            .with_context(|| format!("couldn't map {path_to_rrd:?}"))?;
+        let rrd_bytes = bytes::Bytes::from_owner(rrd_mmap);
+
+        let decoder = re_log_encoding::Decoder::<SpannedLogMsg>::decode_eager(
+            std::io::BufReader::new(&rrd_bytes[..]),
+        )
+        .with_context(|| format!("couldn't decode {path_to_rrd:?}"))?;
+
+        // Chunks that were stored as-is, but that might still get compacted with the next ones:
+        // these can only be virtualized once loading is done.
+        // Only the IDs are kept around, so that chunks that do get compacted can be freed early.
+        let mut pending_virtual_chunks = Vec::new();
+
+        for res in decoder {
+            let SpannedLogMsg {
+                msg,
+                byte_span,
+                version,
+            } = res.with_context(|| format!("couldn't decode message {path_to_rrd:?}"))?;
+
+            match msg {
+                LogMsg::SetStoreInfo(info) => {
+                    stores.entry(info.info.store_id.clone()).or_insert_with(|| {
+                        Self::new(info.info.store_id.clone(), store_config.clone())
+                    });
+                }
+
+                LogMsg::ArrowMsg(store_id, msg) => {
+                    let Some(store) = stores.get_mut(&store_id) else {
+                        anyhow::bail!("unknown store ID: {store_id:?}");
+                    };
+
+                    let chunk = Arc::new(
+                        Chunk::from_arrow_msg(&msg)
+                            .with_context(|| format!("couldn't decode chunk {path_to_rrd:?}"))?,
+                    );
+
+                    store
+                        .insert_chunk(&chunk)
+                        .with_context(|| format!("couldn't insert chunk {path_to_rrd:?}"))?;
+
+                    let payload = byte_span
+                        .try_cast::<usize>()
+                        .and_then(|span| rrd_bytes.get(span.range()))
+                        .with_context(|| {
+                            format!("chunk is out of bounds ({byte_span:?}) {path_to_rrd:?}")
+                        })?;
+                    let payload = rrd_bytes.slice_ref(payload);
+
+                    if !chunk.is_static() && store.is_below_compaction_thresholds(&chunk) {
+                        pending_virtual_chunks.push((
+                            store_id,
+                            chunk.id(),
+                            payload,
+                            byte_span,
+                            version,
+                        ));
+                    } else {
+                        store.virtualize_chunk(&chunk, |chunk| {
+                            VirtualChunk::new(
+                                chunk,
+                                payload,
+                                byte_span,
+                                version,
+                                store_id.application_id().clone(),
+                            )
+                        });
+                    }
+                }
+
+                LogMsg::BlueprintActivationCommand(_) => {}
+            }
+        }
+
+        // Whatever hasn't been compacted away by now is still stored as-is.
+        for (store_id, chunk_id, payload, byte_span, version) in pending_virtual_chunks {
+            // Compaction always results in a new chunk ID.
+            let Some(store) = stores.get_mut(&store_id) else {
+                continue;
+            };
+            if let Some(chunk) = store.chunks_per_chunk_id.get(&chunk_id).cloned() {
+                store.virtualize_chunk(&chunk, |chunk| {
+                    VirtualChunk::new(
+                        chunk,
+                        payload,
+                        byte_span,
+                        version,
+                        store_id.application_id().clone(),
+                    )
+                });
+            }
+        }
+
+        Ok(stores)
+    }
+
+    /// Instantiate a new `ChunkStore` with the given [`ChunkStoreConfig`], backed by a
+    /// memory-mapped RRD file.
+    ///
+    /// Wraps the results in [`ChunkStoreHandle`]s.
+    ///
+    /// See [`ChunkStore::from_rrd_filepath_lazy`] for more information.
+    ///
+    /// [`ChunkStoreConfig`]: crate::ChunkStoreConfig
+    /// [`ChunkStoreHandle`]: crate::ChunkStoreHandle
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn handle_from_rrd_filepath_lazy(
+        store_config: &crate::ChunkStoreConfig,
+        path_to_rrd: impl AsRef<std::path::Path>,
+    ) -> anyhow::Result<BTreeMap<StoreId, crate::ChunkStoreHandle>> {
+        Ok(Self::from_rrd_filepath_lazy(store_config, path_to_rrd)?
+            .into_iter()
+            .map(|(store_id, store)| (store_id, crate::ChunkStoreHandle::new(store)))
+            .collect())
+    }
+
+    /// Replaces the given chunk with a virtual one, if it was stored as-is (i.e. it wasn't
+    /// compacted into another chunk, nor dropped).
+    #[cfg(not(target_arch = "wasm32"))]
+    fn virtualize_chunk(
+        &mut self,
+        chunk: &Arc<Chunk>,
+        to_virtual: impl FnOnce(&Arc<Chunk>) -> VirtualChunk,
+    ) {
+        let is_stored_as_is = self
+            .chunks_per_chunk_id
+            .get(&chunk.id())
+            .is_some_and(|stored| Arc::ptr_eq(stored, chunk));
+
+        if is_stored_as_is {
+            self.chunks_per_chunk_id.remove(&chunk.id());
+            self.virtual_chunks_per_chunk_id
+                .insert(chunk.id(), to_virtual(chunk));
+        }
+    }
+
+    /// Decodes the given virtual chunks and turns them back into regular chunks.
+    ///
+    /// This is needed before any operation that modifies or removes chunks, since these all
+    /// require the actual data.
+    pub(crate) fn devirtualize_chunks(&mut self, chunk_ids: impl IntoIterator<Item = ChunkId>) {
+        for chunk_id in chunk_ids {
+            let Some(virtual_chunk) = self.virtual_chunks_per_chunk_id.get(&chunk_id) else {
+                continue;
+            };
+
+            // If this fails, the chunk stays virtual: the error has already been logged, and
+            // there's nothing better we can do.
+            let Some(chunk) = virtual_chunk.materialize().cloned() else {
+                continue;
+            };
+
+            self.virtual_chunks_per_chunk_id.remove(&chunk_id);
+            self.chunks_per_chunk_id.insert(chunk_id, chunk);
+        }
+    }
+
+    /// Does the store contain a chunk with this ID, whether virtual or not?
+    #[inline]
+    pub(crate) fn contains_chunk(&self, chunk_id: &ChunkId) -> bool {
+        self.chunks_per_chunk_id.contains_key(chunk_id)
+            || self.virtual_chunks_per_chunk_id.contains_key(chunk_id)
+    }
+
+    /// Is this chunk virtual, i.e. backed by an RRD file?
+    ///
+    /// See [`ChunkStore::from_rrd_filepath_lazy`].
+    #[inline]
+    pub fn is_virtual_chunk(&self, chunk_id: &ChunkId) -> bool {
+        self.virtual_chunks_per_chunk_id.contains_key(chunk_id)
+    }
+
+    /// The number of virtual chunks in the store, see [`ChunkStore::from_rrd_filepath_lazy`].
+    ///
+    /// The second value is how many of these are currently materialized in memory.
+    pub fn num_virtual_chunks(&self) -> (usize, usize) {
+        let num_materialized = self
+            .virtual_chunks_per_chunk_id
+            .values()
+            .filter(|virtual_chunk| virtual_chunk.materialized().is_some())
+            .count();
+
+        (self.virtual_chunks_per_chunk_id.len(), num_materialized)
+    }
+}
@@ -18,6 +18,7 @@ mod dataframe;
 mod drop_time_range;
 mod events;
 mod gc;
+mod lazy;
 mod properties;
 mod query;
 mod stats;
@@ -27,14 +28,17 @@ mod writes;
 
 pub use self::{
     dataframe::{
-        Index, IndexRange, IndexValue, QueryExpression, SparseFillStrategy, StaticColumnSelection,
-        ViewContentsSelector,
+        ComparisonOperator, FilterValue, Index, IndexRange, IndexValue, QueryExpression, RowFilter,
+        RowFilterParseError, SparseFillStrategy, StaticColumnSelection, ViewContentsSelector,
     },
     events::{ChunkCompactionReport, ChunkStoreDiff, ChunkStoreDiffKind, ChunkStoreEvent},
     gc::{GarbageCollectionOptions, GarbageCollectionTarget},
     properties::ExtractPropertiesError,
     stats::{ChunkStoreChunkStats, ChunkStoreStats},
-    store::{ChunkStore, ChunkStoreConfig, ChunkStoreGeneration, ChunkStoreHandle, ColumnMetadata},
+    store::{
+        ChunkStore, ChunkStoreConfig, ChunkStoreGeneration, ChunkStoreHandle, ColumnMetadata,
+        WeakChunkStoreHandle,
+    },
     subscribers::{ChunkStoreSubscriber, ChunkStoreSubscriberHandle, PerStoreChunkSubscriber},
 };
 pub use re_sorbet::{ColumnDescriptor, ComponentColumnDescriptor, IndexColumnDescriptor};
@@ -419,7 +419,7 @@ impl ChunkStore {
             .is_some_and(|static_chunk_ids_per_component| {
                 static_chunk_ids_per_component
                     .values()
-                    .any(|chunk_id| self.chunks_per_chunk_id.contains_key(chunk_id))
+                    .any(|chunk_id| self.contains_chunk(chunk_id))
             })
     }
 
@@ -441,7 +441,7 @@ impl ChunkStore {
                     })
                     .flat_map(|chunk_id_sets| chunk_id_sets.per_start_time.values())
                     .flat_map(|chunk_id_set| chunk_id_set.iter())
-                    .any(|chunk_id| self.chunks_per_chunk_id.contains_key(chunk_id))
+                    .any(|chunk_id| self.contains_chunk(chunk_id))
             })
     }
 
@@ -465,7 +465,7 @@ impl ChunkStore {
                     .values()
                     .flat_map(|chunk_id_sets| chunk_id_sets.per_start_time.values())
                     .flat_map(|chunk_id_set| chunk_id_set.iter())
-                    .any(|chunk_id| self.chunks_per_chunk_id.contains_key(chunk_id))
+                    .any(|chunk_id| self.contains_chunk(chunk_id))
             })
     }
 
@@ -570,7 +570,7 @@ impl ChunkStore {
             .static_chunk_ids_per_entity
             .get(entity_path)
             .and_then(|static_chunks_per_component| static_chunks_per_component.get(&component))
-            .and_then(|chunk_id| self.chunks_per_chunk_id.get(chunk_id))
+            .and_then(|chunk_id| self.chunk(chunk_id))
         {
             return vec![Arc::clone(static_chunk)];
         }
@@ -628,7 +628,7 @@ impl ChunkStore {
             // All static chunks for the given entity
             let static_chunks = static_chunks_per_component
                 .values()
-                .filter_map(|chunk_id| self.chunks_per_chunk_id.get(chunk_id))
+                .filter_map(|chunk_id| self.chunk(chunk_id))
                 .cloned();
 
             // All temporal chunks for the given entity, filtered by components
@@ -726,7 +726,7 @@ impl ChunkStore {
         Some(
             temporal_chunk_ids
                 .iter()
-                .filter_map(|chunk_id| self.chunks_per_chunk_id.get(chunk_id).cloned())
+                .filter_map(|chunk_id| self.chunk(chunk_id).cloned())
                 .collect(),
         )
     }
@@ -758,7 +758,7 @@ impl ChunkStore {
             .static_chunk_ids_per_entity
             .get(entity_path)
             .and_then(|static_chunks_per_component| static_chunks_per_component.get(&component))
-            .and_then(|chunk_id| self.chunks_per_chunk_id.get(chunk_id))
+            .and_then(|chunk_id| self.chunk(chunk_id))
         {
             return vec![Arc::clone(static_chunk)];
         }
@@ -825,7 +825,7 @@ impl ChunkStore {
             // All static chunks for the given entity
             let static_chunks = static_chunks_per_component
                 .values()
-                .filter_map(|chunk_id| self.chunks_per_chunk_id.get(chunk_id))
+                .filter_map(|chunk_id| self.chunk(chunk_id))
                 .cloned();
 
             // All temporal chunks for the given entity, filtered by components
@@ -968,7 +968,7 @@ impl ChunkStore {
             .flat_map(|temporal_chunk_ids| {
                 temporal_chunk_ids
                     .iter()
-                    .filter_map(|chunk_id| self.chunks_per_chunk_id.get(chunk_id).cloned())
+                    .filter_map(|chunk_id| self.chunk(chunk_id).cloned())
             })
             .collect()
     }
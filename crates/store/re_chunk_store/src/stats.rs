@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use re_byte_size::SizeBytes;
-use re_chunk::{Chunk, ComponentIdentifier, EntityPath, TimelineName};
+use re_chunk::{Chunk, ChunkId, ComponentIdentifier, EntityPath, TimelineName};
 
 use crate::ChunkStore;
 
@@ -210,6 +210,41 @@ impl ChunkStoreChunkStats {
 
 // ----------------------------------------------------------------------------
 
+/// ## Chunk stats
+impl ChunkStore {
+    /// Stats about a single chunk.
+    ///
+    /// Never decodes virtual chunks, see [`ChunkStore::from_rrd_filepath_lazy`].
+    fn chunk_stats(&self, chunk_id: &ChunkId) -> Option<ChunkStoreChunkStats> {
+        if let Some(chunk) = self.chunks_per_chunk_id.get(chunk_id) {
+            return Some(ChunkStoreChunkStats::from_chunk(chunk));
+        }
+
+        self.virtual_chunks_per_chunk_id
+            .get(chunk_id)
+            .map(|virtual_chunk| virtual_chunk.stats)
+    }
+
+    /// See [`Chunk::num_events_for_component`].
+    ///
+    /// Never decodes virtual chunks, see [`ChunkStore::from_rrd_filepath_lazy`].
+    fn chunk_num_events_for_component(
+        &self,
+        chunk_id: &ChunkId,
+        component: ComponentIdentifier,
+    ) -> Option<u64> {
+        if let Some(chunk) = self.chunks_per_chunk_id.get(chunk_id) {
+            return chunk.num_events_for_component(component);
+        }
+
+        let virtual_chunk = self.virtual_chunks_per_chunk_id.get(chunk_id)?;
+        virtual_chunk
+            .num_events_per_component
+            .get(&component)
+            .copied()
+    }
+}
+
 /// ## Entity stats
 impl ChunkStore {
     /// Stats about all chunks with static data for an entity.
@@ -224,8 +259,7 @@ impl ChunkStore {
 
                 chunk_ids
                     .into_iter()
-                    .filter_map(|chunk_id| self.chunks_per_chunk_id.get(&chunk_id))
-                    .map(ChunkStoreChunkStats::from_chunk)
+                    .filter_map(|chunk_id| self.chunk_stats(&chunk_id))
                     .sum()
             },
         )
@@ -253,8 +287,7 @@ impl ChunkStore {
                         .per_start_time
                         .values()
                         .flat_map(|chunk_ids| chunk_ids.iter())
-                        .filter_map(|id| self.chunks_per_chunk_id.get(id))
-                        .map(ChunkStoreChunkStats::from_chunk)
+                        .filter_map(|id| self.chunk_stats(id))
                         .sum()
                 },
             )
@@ -276,8 +309,7 @@ impl ChunkStore {
         self.static_chunk_ids_per_entity
             .get(entity_path)
             .and_then(|static_chunks_per_component| static_chunks_per_component.get(&component))
-            .and_then(|chunk_id| self.chunks_per_chunk_id.get(chunk_id))
-            .and_then(|chunk| chunk.num_events_for_component(component))
+            .and_then(|chunk_id| self.chunk_num_events_for_component(chunk_id, component))
             .unwrap_or(0)
     }
 
@@ -305,8 +337,7 @@ impl ChunkStore {
                     .per_start_time
                     .values()
                     .flat_map(|chunk_ids| chunk_ids.iter())
-                    .filter_map(|chunk_id| self.chunks_per_chunk_id.get(chunk_id))
-                    .filter_map(|chunk| chunk.num_events_for_component(component))
+                    .filter_map(|chunk_id| self.chunk_num_events_for_component(chunk_id, component))
                     .sum()
             })
     }
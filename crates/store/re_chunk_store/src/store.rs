@@ -9,7 +9,7 @@ use re_chunk::{Chunk, ChunkId, ComponentIdentifier, RowId, TimelineName};
 use re_log_types::{EntityPath, StoreId, TimeInt, TimeType};
 use re_types_core::{ComponentDescriptor, ComponentType};
 
-use crate::{ChunkStoreChunkStats, ChunkStoreError, ChunkStoreResult};
+use crate::{ChunkStoreChunkStats, ChunkStoreError, ChunkStoreResult, lazy::VirtualChunk};
 
 // ---
 
@@ -338,6 +338,24 @@ impl ChunkStoreHandle {
     pub fn into_inner(self) -> Arc<parking_lot::RwLock<ChunkStore>> {
         self.0
     }
+
+    /// Creates a [`WeakChunkStoreHandle`], which doesn't keep the store alive.
+    #[inline]
+    pub fn downgrade(&self) -> WeakChunkStoreHandle {
+        WeakChunkStoreHandle(Arc::downgrade(&self.0))
+    }
+}
+
+/// A weak reference to a [`ChunkStore`], see [`ChunkStoreHandle::downgrade`].
+#[derive(Clone, Default)]
+pub struct WeakChunkStoreHandle(std::sync::Weak<parking_lot::RwLock<ChunkStore>>);
+
+impl WeakChunkStoreHandle {
+    /// Returns a strong handle to the store, if it is still alive.
+    #[inline]
+    pub fn upgrade(&self) -> Option<ChunkStoreHandle> {
+        self.0.upgrade().map(ChunkStoreHandle)
+    }
 }
 
 impl ChunkStoreHandle {
@@ -423,6 +441,12 @@ pub struct ChunkStore {
 
     pub(crate) chunks_per_chunk_id: BTreeMap<ChunkId, Arc<Chunk>>,
 
+    /// All chunks that are indexed by the store, but whose payload lives in an RRD file.
+    ///
+    /// These are disjoint from [`Self::chunks_per_chunk_id`].
+    /// See [`ChunkStore::from_rrd_filepath_lazy`].
+    pub(crate) virtual_chunks_per_chunk_id: BTreeMap<ChunkId, VirtualChunk>,
+
     /// All [`ChunkId`]s currently in the store, indexed by the smallest [`RowId`] in each of them.
     ///
     /// This is effectively all chunks in global data order. Used for garbage collection.
@@ -495,6 +519,7 @@ impl Clone for ChunkStore {
             type_registry: self.type_registry.clone(),
             per_column_metadata: self.per_column_metadata.clone(),
             chunks_per_chunk_id: self.chunks_per_chunk_id.clone(),
+            virtual_chunks_per_chunk_id: self.virtual_chunks_per_chunk_id.clone(),
             chunk_ids_per_min_row_id: self.chunk_ids_per_min_row_id.clone(),
             temporal_chunk_ids_per_entity_per_component: self
                 .temporal_chunk_ids_per_entity_per_component
@@ -519,6 +544,7 @@ impl std::fmt::Display for ChunkStore {
             type_registry: _,
             per_column_metadata: _,
             chunks_per_chunk_id,
+            virtual_chunks_per_chunk_id,
             chunk_ids_per_min_row_id: chunk_id_per_min_row_id,
             temporal_chunk_ids_per_entity_per_component: _,
             temporal_chunk_ids_per_entity: _,
@@ -551,6 +577,8 @@ impl std::fmt::Display for ChunkStore {
                 } else {
                     f.write_str(&indent::indent_all_by(8, format!("{chunk}\n")))?;
                 }
+            } else if virtual_chunks_per_chunk_id.contains_key(chunk_id) {
+                f.write_str(&indent::indent_all_by(8, format!("<virtual {chunk_id}>\n")))?;
             } else {
                 f.write_str(&indent::indent_all_by(8, "<not_found>\n"))?;
             }
@@ -581,6 +609,7 @@ impl ChunkStore {
             per_column_metadata: Default::default(),
             chunk_ids_per_min_row_id: Default::default(),
             chunks_per_chunk_id: Default::default(),
+            virtual_chunks_per_chunk_id: Default::default(),
             temporal_chunk_ids_per_entity_per_component: Default::default(),
             temporal_chunk_ids_per_entity: Default::default(),
             temporal_chunks_stats: Default::default(),
@@ -625,21 +654,43 @@ impl ChunkStore {
     }
 
     /// Iterate over all chunks in the store, in ascending [`ChunkId`] order.
+    ///
+    /// Virtual chunks are decoded on the fly, but the store doesn't hold on to them: they are
+    /// freed as soon as the caller is done with them, see [`ChunkStore::from_rrd_filepath_lazy`].
     #[inline]
-    pub fn iter_chunks(&self) -> impl Iterator<Item = &Arc<Chunk>> + '_ {
-        self.chunks_per_chunk_id.values()
+    pub fn iter_chunks(&self) -> impl Iterator<Item = Arc<Chunk>> + '_ {
+        use itertools::Itertools as _;
+
+        self.chunks_per_chunk_id
+            .keys()
+            .merge(self.virtual_chunks_per_chunk_id.keys())
+            .filter_map(|chunk_id| {
+                if let Some(chunk) = self.chunks_per_chunk_id.get(chunk_id) {
+                    return Some(Arc::clone(chunk));
+                }
+
+                self.virtual_chunks_per_chunk_id
+                    .get(chunk_id)
+                    .and_then(VirtualChunk::materialize_transient)
+            })
     }
 
     /// Get a chunk based on its ID.
+    ///
+    /// Virtual chunks are decoded on the fly, see [`ChunkStore::from_rrd_filepath_lazy`].
     #[inline]
     pub fn chunk(&self, id: &ChunkId) -> Option<&Arc<Chunk>> {
-        self.chunks_per_chunk_id.get(id)
+        self.chunks_per_chunk_id.get(id).or_else(|| {
+            self.virtual_chunks_per_chunk_id
+                .get(id)
+                .and_then(VirtualChunk::materialize)
+        })
     }
 
-    /// Get the number of chunks.
+    /// Get the number of chunks, including virtual ones.
     #[inline]
     pub fn num_chunks(&self) -> usize {
-        self.chunks_per_chunk_id.len()
+        self.chunks_per_chunk_id.len() + self.virtual_chunks_per_chunk_id.len()
     }
 
     /// Lookup the _latest_ [`TimeType`] used by a specific [`TimelineName`].
@@ -695,6 +746,7 @@ impl ChunkStore {
     ///
     /// See also:
     /// * [`ChunkStore::new`]
+    /// * [`ChunkStore::from_rrd_filepath_lazy`]
     #[cfg(not(target_arch = "wasm32"))]
     pub fn from_rrd_filepath(
         store_config: &ChunkStoreConfig,
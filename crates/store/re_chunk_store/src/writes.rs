@@ -38,7 +38,7 @@ impl ChunkStore {
             return Ok(vec![]);
         }
 
-        if self.chunks_per_chunk_id.contains_key(&chunk.id()) {
+        if self.contains_chunk(&chunk.id()) {
             // We assume that chunk IDs are unique, and that reinserting a chunk has no effect.
             re_log::debug_once!(
                 "Chunk #{} was inserted more than once (this has no effect)",
@@ -65,6 +65,21 @@ impl ChunkStore {
             // Static data: make sure to keep the most recent chunk available for each component column.
             re_tracing::profile_scope!("static");
 
+            if !self.virtual_chunks_per_chunk_id.is_empty() {
+                // We need the actual data of the chunks that we might be about to overwrite.
+                let chunk_ids = self
+                    .static_chunk_ids_per_entity
+                    .get(chunk.entity_path())
+                    .map(|per_component| {
+                        chunk
+                            .components_identifiers()
+                            .filter_map(|component| per_component.get(&component).copied())
+                            .collect_vec()
+                    })
+                    .unwrap_or_default();
+                self.devirtualize_chunks(chunk_ids);
+            }
+
             let row_id_range_per_component = chunk.row_id_range_per_component();
 
             let mut overwritten_chunk_ids = HashMap::default();
@@ -447,6 +462,30 @@ impl ChunkStore {
         Ok(events)
     }
 
+    /// Is the given chunk small enough, on its own, to be compacted with another one?
+    ///
+    /// Chunks that aren't can neither elect a compaction candidate nor be elected as one.
+    pub(crate) fn is_below_compaction_thresholds(&self, chunk: &Chunk) -> bool {
+        let ChunkStoreConfig {
+            enable_changelog: _,
+            chunk_max_bytes,
+            chunk_max_rows,
+            chunk_max_rows_if_unsorted,
+        } = self.config;
+
+        let total_bytes = <Chunk as SizeBytes>::total_size_bytes(chunk);
+        let is_below_bytes_threshold = total_bytes <= chunk_max_bytes;
+
+        let total_rows = (chunk.num_rows()) as u64;
+        let is_below_rows_threshold = if chunk.is_time_sorted() {
+            total_rows <= chunk_max_rows
+        } else {
+            total_rows <= chunk_max_rows_if_unsorted
+        };
+
+        is_below_bytes_threshold && is_below_rows_threshold
+    }
+
     /// Finds the most appropriate candidate for compaction.
     ///
     /// The algorithm is simple: for each incoming [`Chunk`], we take a look at its future neighbors.
@@ -460,30 +499,10 @@ impl ChunkStore {
     fn find_and_elect_compaction_candidate(&self, chunk: &Arc<Chunk>) -> Option<Arc<Chunk>> {
         re_tracing::profile_function!();
 
-        {
-            // Make sure to early exit if the newly added Chunk is already beyond the compaction thresholds
-            // on its own.
-
-            let ChunkStoreConfig {
-                enable_changelog: _,
-                chunk_max_bytes,
-                chunk_max_rows,
-                chunk_max_rows_if_unsorted,
-            } = self.config;
-
-            let total_bytes = <Chunk as SizeBytes>::total_size_bytes(chunk);
-            let is_below_bytes_threshold = total_bytes <= chunk_max_bytes;
-
-            let total_rows = (chunk.num_rows()) as u64;
-            let is_below_rows_threshold = if chunk.is_time_sorted() {
-                total_rows <= chunk_max_rows
-            } else {
-                total_rows <= chunk_max_rows_if_unsorted
-            };
-
-            if !(is_below_bytes_threshold && is_below_rows_threshold) {
-                return None;
-            }
+        // Make sure to early exit if the newly added Chunk is already beyond the compaction thresholds
+        // on its own.
+        if !self.is_below_compaction_thresholds(chunk) {
+            return None;
         }
 
         let mut candidates_below_threshold: HashMap<ChunkId, bool> = HashMap::default();
@@ -609,6 +628,25 @@ impl ChunkStore {
 
         self.gc_id += 1; // close enough
 
+        if !self.virtual_chunks_per_chunk_id.is_empty() {
+            // Deletion events carry the actual data, so virtual chunks must be decoded first.
+            let chunk_ids = self
+                .static_chunk_ids_per_entity
+                .get(entity_path)
+                .into_iter()
+                .flat_map(|per_component| per_component.values().copied())
+                .chain(
+                    self.temporal_chunk_ids_per_entity
+                        .get(entity_path)
+                        .into_iter()
+                        .flat_map(|per_timeline| per_timeline.values())
+                        .flat_map(|chunk_id_sets| chunk_id_sets.per_start_time.values())
+                        .flat_map(|chunk_ids| chunk_ids.iter().copied()),
+                )
+                .collect_vec();
+            self.devirtualize_chunks(chunk_ids);
+        }
+
         let generation = self.generation();
 
         let Self {
@@ -618,6 +656,7 @@ impl ChunkStore {
             type_registry: _,
             per_column_metadata,
             chunks_per_chunk_id,
+            virtual_chunks_per_chunk_id: _, // devirtualized above
             chunk_ids_per_min_row_id,
             temporal_chunk_ids_per_entity_per_component,
             temporal_chunk_ids_per_entity,
@@ -117,10 +117,12 @@ fn schema_for_query() -> anyhow::Result<()> {
         include_tombstone_columns: false,
         include_static_columns: StaticColumnSelection::Both,
         filtered_index: Some(TimelineName::new("frame_nr")),
+        filtered_index_fallbacks: Vec::new(),
         filtered_index_range: None,
         filtered_index_values: None,
         using_index_values: None,
         filtered_is_not_null: None,
+        filtered_by: None,
         sparse_fill_strategy: re_chunk_store::SparseFillStrategy::None,
         selection: None,
     };
@@ -182,10 +184,12 @@ fn schema_static_columns() -> anyhow::Result<()> {
         include_tombstone_columns: false,
         include_static_columns: StaticColumnSelection::Both,
         filtered_index: None,
+        filtered_index_fallbacks: Vec::new(),
         filtered_index_range: None,
         filtered_index_values: None,
         using_index_values: None,
         filtered_is_not_null: None,
+        filtered_by: None,
         sparse_fill_strategy: re_chunk_store::SparseFillStrategy::None,
         selection: None,
     };
@@ -235,3 +239,76 @@ fn schema_static_columns() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn parse_index_selector() {
+    assert_eq!(
+        QueryExpression::parse_index_selector("log_time"),
+        vec![TimelineName::new("log_time")]
+    );
+    assert_eq!(
+        QueryExpression::parse_index_selector("sensor_time else  log_time "),
+        vec![
+            TimelineName::new("sensor_time"),
+            TimelineName::new("log_time")
+        ]
+    );
+    assert!(QueryExpression::parse_index_selector("").is_empty());
+}
+
+#[test]
+fn parse_row_filter() {
+    use re_chunk_store::{ComparisonOperator, FilterValue, RowFilter};
+    use re_sorbet::ComponentColumnSelector;
+
+    let column = |selector: &str| selector.parse::<ComponentColumnSelector>().unwrap();
+
+    let filter: RowFilter = r#"/camera:Points3D:positions IS NOT NULL AND (/gripper:Gripper:state = "closed" or /gripper:Gripper:pose.x>=-1.5)"#
+        .parse()
+        .unwrap();
+    assert_eq!(
+        filter,
+        RowFilter::And(vec![
+            RowFilter::IsNotNull(column("/camera:Points3D:positions")),
+            RowFilter::Or(vec![
+                RowFilter::Compare {
+                    column: column("/gripper:Gripper:state"),
+                    field: None,
+                    op: ComparisonOperator::Eq,
+                    value: FilterValue::String("closed".to_owned()),
+                },
+                RowFilter::Compare {
+                    column: column("/gripper:Gripper:pose"),
+                    field: Some("x".to_owned()),
+                    op: ComparisonOperator::GtEq,
+                    value: FilterValue::Float(-1.5),
+                },
+            ]),
+        ])
+    );
+
+    // `AND` binds tighter than `OR`, and the textual form round-trips.
+    let filter: RowFilter = "/a:A:x != 3 OR /b:B:y < 2 AND /c:C:z > true"
+        .parse()
+        .unwrap();
+    assert!(matches!(&filter, RowFilter::Or(filters) if matches!(filters[1], RowFilter::And(_))));
+    assert_eq!(filter.to_string().parse::<RowFilter>().unwrap(), filter);
+
+    for invalid in [
+        "",
+        "/a:A:x",
+        "/a:A:x IS NULL",
+        "/a:A:x.f IS NOT NULL",
+        "/a:A:x = ",
+        "/a:A:x == 1",
+        "/a:A:x = \"unterminated",
+        "(/a:A:x = 1",
+        "/a:A:x = 1 /b:B:y = 2",
+        "no_component = 1",
+    ] {
+        assert!(
+            invalid.parse::<RowFilter>().is_err(),
+            "{invalid:?} should not parse"
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use re_chunk::{Chunk, RowId, TimePoint, Timeline};
+use re_chunk_store::{
+    ChunkStore, ChunkStoreConfig, GarbageCollectionOptions, LatestAtQuery, RangeQuery, TimeInt,
+};
+use re_log_types::{
+    AbsoluteTimeRange, EntityPath, LogMsg, SetStoreInfo, StoreId, StoreInfo, StoreKind,
+    StoreSource,
+    example_components::{MyColor, MyPoint, MyPoints},
+};
+
+/// Ten temporal chunks on `points`, one per frame, and a static chunk on `label`.
+fn write_rrd(path: &std::path::Path) -> anyhow::Result<StoreId> {
+    let store_id = StoreId::random(StoreKind::Recording, "test_app");
+    let timeline = Timeline::new_sequence("frame");
+
+    let mut messages = vec![LogMsg::SetStoreInfo(SetStoreInfo {
+        row_id: *RowId::new(),
+        info: StoreInfo::new(store_id.clone(), StoreSource::Unknown),
+    })];
+
+    for frame in 0..10u32 {
+        let chunk = Chunk::builder("points")
+            .with_component_batches(
+                RowId::new(),
+                TimePoint::default().with(timeline, i64::from(frame)),
+                [
+                    (
+                        MyPoints::descriptor_points(),
+                        &MyPoint::from_iter(frame..frame + 1) as _,
+                    ),
+                    (
+                        MyPoints::descriptor_colors(),
+                        &MyColor::from_iter(frame..frame + 1) as _,
+                    ),
+                ],
+            )
+            .build()?;
+        messages.push(LogMsg::ArrowMsg(store_id.clone(), chunk.to_arrow_msg()?));
+    }
+
+    let chunk = Chunk::builder("label")
+        .with_component_batches(
+            RowId::new(),
+            TimePoint::default(),
+            [(
+                MyPoints::descriptor_colors(),
+                &MyColor::from_iter(0..1) as _,
+            )],
+        )
+        .build()?;
+    messages.push(LogMsg::ArrowMsg(store_id.clone(), chunk.to_arrow_msg()?));
+
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    re_log_encoding::Encoder::encode_into(
+        re_log_encoding::CrateVersion::LOCAL,
+        re_log_encoding::EncodingOptions::PROTOBUF_COMPRESSED,
+        messages.into_iter().map(Ok),
+        &mut file,
+    )?;
+
+    Ok(store_id)
+}
+
+fn load(path: &std::path::Path, store_id: &StoreId) -> anyhow::Result<(ChunkStore, ChunkStore)> {
+    let config = ChunkStoreConfig::COMPACTION_DISABLED;
+
+    let eager = ChunkStore::from_rrd_filepath(&config, path)?
+        .remove(store_id)
+        .ok_or_else(|| anyhow::anyhow!("missing store"))?;
+    let lazy = ChunkStore::from_rrd_filepath_lazy(&config, path)?
+        .remove(store_id)
+        .ok_or_else(|| anyhow::anyhow!("missing store"))?;
+
+    Ok((eager, lazy))
+}
+
+#[test]
+fn lazy_indexing() -> anyhow::Result<()> {
+    re_log::setup_logging();
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("recording.rrd");
+    let store_id = write_rrd(&path)?;
+    let (eager, lazy) = load(&path, &store_id)?;
+
+    let points = EntityPath::from("points");
+    let component = MyPoints::descriptor_points().component;
+
+    // Everything is indexed, but nothing has been decoded yet.
+    assert_eq!((11, 0), lazy.num_virtual_chunks());
+    assert_eq!(eager.num_chunks(), lazy.num_chunks());
+    assert_eq!(eager.all_entities_sorted(), lazy.all_entities_sorted());
+    assert_eq!(eager.all_components_sorted(), lazy.all_components_sorted());
+    assert_eq!(
+        eager.time_range(&"frame".into()),
+        lazy.time_range(&"frame".into())
+    );
+    assert_eq!(
+        eager.stats().total().total_size_bytes,
+        lazy.stats().total().total_size_bytes
+    );
+    assert_eq!(
+        eager
+            .entity_stats_on_timeline(&points, &"frame".into())
+            .num_rows,
+        lazy.entity_stats_on_timeline(&points, &"frame".into())
+            .num_rows
+    );
+    assert_eq!(
+        eager.num_temporal_events_for_component_on_all_timelines(&points, component),
+        lazy.num_temporal_events_for_component_on_all_timelines(&points, component)
+    );
+    assert_eq!((11, 0), lazy.num_virtual_chunks());
+
+    // Iterating decodes the chunks, but doesn't keep them around.
+    assert_eq!(11, lazy.iter_chunks().count());
+    assert_eq!((11, 0), lazy.num_virtual_chunks());
+
+    Ok(())
+}
+
+#[test]
+fn lazy_compaction() -> anyhow::Result<()> {
+    re_log::setup_logging();
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("recording.rrd");
+    let store_id = write_rrd(&path)?;
+
+    let config = ChunkStoreConfig::DEFAULT;
+    let eager = ChunkStore::from_rrd_filepath(&config, &path)?
+        .remove(&store_id)
+        .ok_or_else(|| anyhow::anyhow!("missing store"))?;
+    let lazy = ChunkStore::from_rrd_filepath_lazy(&config, &path)?
+        .remove(&store_id)
+        .ok_or_else(|| anyhow::anyhow!("missing store"))?;
+
+    // The `points` chunks get compacted together, just like they do when loading eagerly…
+    assert_eq!(2, eager.num_chunks());
+    assert_eq!(eager.num_chunks(), lazy.num_chunks());
+
+    // …and only the static `label` chunk, which was stored as-is, is left virtual.
+    assert_eq!((1, 0), lazy.num_virtual_chunks());
+
+    let points = EntityPath::from("points");
+    let component = MyPoints::descriptor_points().component;
+    let query = RangeQuery::new("frame".into(), AbsoluteTimeRange::EVERYTHING);
+    similar_asserts::assert_eq!(
+        eager
+            .range_relevant_chunks(&query, &points, component)
+            .iter()
+            .map(|chunk| (**chunk).clone())
+            .collect::<Vec<_>>(),
+        lazy.range_relevant_chunks(&query, &points, component)
+            .iter()
+            .map(|chunk| (**chunk).clone())
+            .collect::<Vec<_>>(),
+    );
+
+    Ok(())
+}
+
+#[test]
+fn lazy_queries() -> anyhow::Result<()> {
+    re_log::setup_logging();
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("recording.rrd");
+    let store_id = write_rrd(&path)?;
+    let (eager, mut lazy) = load(&path, &store_id)?;
+
+    let points = EntityPath::from("points");
+    let component = MyPoints::descriptor_points().component;
+
+    // Queries only decode the chunks they need.
+    let query = LatestAtQuery::new("frame".into(), TimeInt::new_temporal(4));
+    let expected = eager.latest_at_relevant_chunks(&query, &points, component);
+    let chunks = lazy.latest_at_relevant_chunks(&query, &points, component);
+    assert!(!chunks.is_empty());
+    similar_asserts::assert_eq!(
+        expected
+            .iter()
+            .map(|chunk| (**chunk).clone())
+            .collect::<Vec<_>>(),
+        chunks
+            .iter()
+            .map(|chunk| (**chunk).clone())
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!((11, chunks.len()), lazy.num_virtual_chunks());
+
+    let query = RangeQuery::new(
+        "frame".into(),
+        AbsoluteTimeRange::new(TimeInt::new_temporal(2), TimeInt::new_temporal(5)),
+    );
+    let expected = eager.range_relevant_chunks(&query, &points, component);
+    let chunks = lazy.range_relevant_chunks(&query, &points, component);
+    assert_eq!(4, chunks.len());
+    similar_asserts::assert_eq!(
+        expected
+            .iter()
+            .map(|chunk| (**chunk).clone())
+            .collect::<Vec<_>>(),
+        chunks
+            .iter()
+            .map(|chunk| (**chunk).clone())
+            .collect::<Vec<_>>(),
+    );
+
+    // The GC evicts decoded payloads, but never drops virtual chunks.
+    lazy.gc(&GarbageCollectionOptions::gc_everything());
+    assert_eq!((11, 0), lazy.num_virtual_chunks());
+    assert_eq!(eager.num_chunks(), lazy.num_chunks());
+
+    // …and they can be decoded again at any time.
+    let chunks = lazy.range_relevant_chunks(&query, &points, component);
+    assert_eq!(4, chunks.len());
+    assert_eq!((11, 4), lazy.num_virtual_chunks());
+
+    Ok(())
+}
+
+#[test]
+fn lazy_modifications() -> anyhow::Result<()> {
+    re_log::setup_logging();
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("recording.rrd");
+    let store_id = write_rrd(&path)?;
+    let (eager, mut lazy) = load(&path, &store_id)?;
+
+    // Modifications turn the chunks they touch back into regular chunks.
+    lazy.drop_time_range(
+        &"frame".into(),
+        AbsoluteTimeRange::new(TimeInt::new_temporal(0), TimeInt::new_temporal(1)),
+    );
+    assert_eq!(eager.num_chunks() - 2, lazy.num_chunks());
+    assert_eq!((9, 0), lazy.num_virtual_chunks());
+
+    lazy.drop_entity_path(&"label".into());
+    assert_eq!(eager.num_chunks() - 3, lazy.num_chunks());
+    assert_eq!((8, 0), lazy.num_virtual_chunks());
+    assert!(!lazy.entity_has_data(&"label".into()));
+
+    Ok(())
+}
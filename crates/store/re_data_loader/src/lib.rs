@@ -23,10 +23,14 @@ pub mod loader_lerobot;
 // This loader currently uses native-only features under the hood, and we cannot do that on web yet.
 pub mod loader_mcap;
 
+pub mod loader_ros1_bag;
+pub mod ros1_bag;
+
 #[cfg(not(target_arch = "wasm32"))]
 mod loader_external;
 
 pub use self::loader_mcap::McapLoader;
+pub use self::loader_ros1_bag::Ros1BagLoader;
 
 pub use self::{
     load_file::load_from_file_contents, loader_archetype::ArchetypeLoader,
@@ -421,6 +425,7 @@ static BUILTIN_LOADERS: LazyLock<Vec<Arc<dyn DataLoader>>> = LazyLock::new(|| {
         Arc::new(ArchetypeLoader),
         Arc::new(DirectoryLoader),
         Arc::new(McapLoader::default()),
+        Arc::new(Ros1BagLoader),
         #[cfg(not(target_arch = "wasm32"))]
         Arc::new(LeRobotDatasetLoader),
         #[cfg(not(target_arch = "wasm32"))]
@@ -483,7 +488,7 @@ pub const SUPPORTED_POINT_CLOUD_EXTENSIONS: &[&str] = &["ply"];
 pub const SUPPORTED_RERUN_EXTENSIONS: &[&str] = &["rbl", "rrd"];
 
 /// 3rd party formats with built-in support.
-pub const SUPPORTED_THIRD_PARTY_FORMATS: &[&str] = &["mcap"];
+pub const SUPPORTED_THIRD_PARTY_FORMATS: &[&str] = &["bag", "mcap"];
 
 // TODO(#4555): Add catch-all builtin `DataLoader` for text files
 pub const SUPPORTED_TEXT_EXTENSIONS: &[&str] = &["txt", "md"];
@@ -515,5 +520,6 @@ pub fn is_supported_file_extension(extension: &str) -> bool {
 fn test_supported_extensions() {
     assert!(is_supported_file_extension("rrd"));
     assert!(is_supported_file_extension("mcap"));
+    assert!(is_supported_file_extension("bag"));
     assert!(is_supported_file_extension("png"));
 }
@@ -2,7 +2,7 @@
 
 use std::{io::Cursor, path::Path, sync::mpsc::Sender};
 
-use re_chunk::RowId;
+use re_chunk::{Chunk, RowId};
 use re_log_types::{SetStoreInfo, StoreId, StoreInfo};
 use re_mcap::{LayerRegistry, SelectedLayers};
 
@@ -189,6 +189,28 @@ pub fn load_mcap(
     raw_fallback_enabled: bool,
 ) -> Result<(), DataLoaderError> {
     re_tracing::profile_function!();
+
+    send_mcap_chunks(settings, tx, |send_chunk| {
+        let reader = Cursor::new(&mcap);
+
+        let summary = re_mcap::read_summary(reader)?
+            .ok_or_else(|| anyhow::anyhow!("MCAP file does not contain a summary"))?;
+
+        // TODO(#10862): Add warning for channel that miss semantic information.
+        LayerRegistry::all_builtin(raw_fallback_enabled)
+            .select(selected_layers)
+            .plan(&summary)?
+            .run(mcap, &summary, send_chunk)
+    })
+}
+
+/// Sends the store info of the recording, followed by all chunks that `load` passes to its
+/// callback.
+pub(crate) fn send_mcap_chunks(
+    settings: &DataLoaderSettings,
+    tx: &Sender<LoadedData>,
+    load: impl FnOnce(&mut dyn FnMut(Chunk)) -> anyhow::Result<()>,
+) -> Result<(), DataLoaderError> {
     let store_id = settings.recommended_store_id();
 
     if tx
@@ -221,16 +243,7 @@ pub fn load_mcap(
         }
     };
 
-    let reader = Cursor::new(&mcap);
-
-    let summary = re_mcap::read_summary(reader)?
-        .ok_or_else(|| anyhow::anyhow!("MCAP file does not contain a summary"))?;
-
-    // TODO(#10862): Add warning for channel that miss semantic information.
-    LayerRegistry::all_builtin(raw_fallback_enabled)
-        .select(selected_layers)
-        .plan(&summary)?
-        .run(mcap, &summary, &mut send_chunk)?;
+    load(&mut send_chunk)?;
 
     Ok(())
 }
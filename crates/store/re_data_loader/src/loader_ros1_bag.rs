@@ -0,0 +1,144 @@
+//! Rerun dataloader for ROS 1 bag files.
+
+use std::{path::Path, sync::mpsc::Sender};
+
+use re_mcap::LayerRegistry;
+
+use crate::{
+    DataLoader, DataLoaderError, DataLoaderSettings, LoadedData, loader_mcap::send_mcap_chunks,
+    ros1_bag::Ros1Bag,
+};
+
+const ROS1_BAG_LOADER_NAME: &str = "Ros1BagLoader";
+
+/// A [`DataLoader`] for ROS 1 bag files.
+///
+/// The messages of bags are presented as MCAP messages using the `ros1` message encoding, see
+/// [`Ros1Bag`], which are then decoded by the same layers as the ones of the [`crate::McapLoader`].
+pub struct Ros1BagLoader;
+
+impl DataLoader for Ros1BagLoader {
+    fn name(&self) -> crate::DataLoaderName {
+        ROS1_BAG_LOADER_NAME.into()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_from_path(
+        &self,
+        settings: &crate::DataLoaderSettings,
+        path: std::path::PathBuf,
+        tx: Sender<crate::LoadedData>,
+    ) -> std::result::Result<(), DataLoaderError> {
+        if !is_ros1_bag_file(&path) {
+            return Err(DataLoaderError::Incompatible(path)); // simply not interested
+        }
+
+        re_tracing::profile_function!();
+
+        // NOTE(1): `spawn` is fine, this whole function is native-only.
+        // NOTE(2): this must spawned on a dedicated thread to avoid a deadlock!
+        // `load` will spawn a bunch of loaders on the common rayon thread pool and wait for
+        // their response via channels: we cannot be waiting for these responses on the
+        // common rayon thread pool.
+        let settings = settings.clone();
+        std::thread::Builder::new()
+            .name(format!("load_ros1_bag({path:?}"))
+            .spawn(move || {
+                if let Err(err) = load_ros1_bag_mmap(&path, &settings, &tx) {
+                    re_log::error!("Failed to load ROS 1 bag file: {err}");
+                }
+            })
+            .map_err(|err| DataLoaderError::Other(err.into()))?;
+
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_from_file_contents(
+        &self,
+        settings: &crate::DataLoaderSettings,
+        filepath: std::path::PathBuf,
+        contents: std::borrow::Cow<'_, [u8]>,
+        tx: Sender<crate::LoadedData>,
+    ) -> std::result::Result<(), crate::DataLoaderError> {
+        if !is_ros1_bag_file(&filepath) {
+            return Err(DataLoaderError::Incompatible(filepath)); // simply not interested
+        }
+
+        re_tracing::profile_function!();
+
+        let settings = settings.clone();
+        let contents = contents.into_owned();
+
+        // NOTE: this must spawned on a dedicated thread to avoid a deadlock, see `load_from_path`.
+        std::thread::Builder::new()
+            .name(format!("load_ros1_bag({filepath:?}"))
+            .spawn(move || {
+                if let Err(err) = load_ros1_bag(&contents, &settings, &tx) {
+                    re_log::error!("Failed to load ROS 1 bag file: {err}");
+                }
+            })
+            .map_err(|err| DataLoaderError::Other(err.into()))?;
+
+        Ok(())
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn load_from_file_contents(
+        &self,
+        settings: &crate::DataLoaderSettings,
+        filepath: std::path::PathBuf,
+        contents: std::borrow::Cow<'_, [u8]>,
+        tx: Sender<crate::LoadedData>,
+    ) -> std::result::Result<(), DataLoaderError> {
+        if !is_ros1_bag_file(&filepath) {
+            return Err(DataLoaderError::Incompatible(filepath)); // simply not interested
+        }
+
+        load_ros1_bag(&contents, settings, &tx)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_ros1_bag_mmap(
+    filepath: &std::path::PathBuf,
+    settings: &DataLoaderSettings,
+    tx: &Sender<LoadedData>,
+) -> std::result::Result<(), DataLoaderError> {
+    use std::fs::File;
+    let file = File::open(filepath)?;
+
+    // SAFETY: file-backed memory maps are marked unsafe because of potential UB when using the map and the underlying file is modified.
+    #[expect(unsafe_code)]
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+
+    load_ros1_bag(&mmap, settings, tx)
+}
+
+pub fn load_ros1_bag(
+    bag: &[u8],
+    settings: &DataLoaderSettings,
+    tx: &Sender<LoadedData>,
+) -> Result<(), DataLoaderError> {
+    re_tracing::profile_function!();
+
+    let bag = Ros1Bag::new(bag).map_err(anyhow::Error::from)?;
+    let summary = bag.summary();
+
+    send_mcap_chunks(settings, tx, |send_chunk| {
+        LayerRegistry::all_builtin(true).plan(summary)?.run_batches(
+            summary,
+            |decode_batch| Ok(bag.for_each_batch(decode_batch)?),
+            send_chunk,
+        )
+    })
+}
+
+/// Checks if a file is a ROS 1 bag file.
+fn is_ros1_bag_file(filepath: &Path) -> bool {
+    filepath.is_file()
+        && filepath
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("bag"))
+            .unwrap_or(false)
+}
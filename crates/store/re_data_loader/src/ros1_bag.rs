@@ -0,0 +1,738 @@
+//! A module for reading ROS 1 bag files.
+//!
+//! Only version 2.0 of the format is supported, which is the one written by all ROS 1
+//! distributions since ROS C Turtle. See <https://wiki.ros.org/Bags/Format/2.0> for a description
+//! of the format.
+//!
+//! Rather than interpreting the messages directly, the connections and messages of a bag are
+//! presented as MCAP channels and messages that follow the [`ros1` profile](https://mcap.dev/spec/registry#ros1),
+//! so that they can be handled by the same [`re_mcap`] layers as the contents of any MCAP file.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::Read as _;
+use std::sync::Arc;
+
+use ahash::HashMap;
+
+/// Every bag file starts with this version line.
+const MAGIC: &[u8] = b"#ROSBAG V2.0\n";
+
+/// The most memory we reserve up front when decompressing a chunk.
+///
+/// The uncompressed size stored in the header of a chunk is only used as a hint, since nothing
+/// prevents it from being absurdly large.
+const MAX_CHUNK_CAPACITY: usize = 16 * 1024 * 1024;
+
+/// The `op` codes of the different record types.
+mod op {
+    pub const MESSAGE_DATA: u8 = 0x02;
+    pub const BAG_HEADER: u8 = 0x03;
+    pub const CHUNK: u8 = 0x05;
+    pub const CHUNK_INFO: u8 = 0x06;
+    pub const CONNECTION: u8 = 0x07;
+}
+
+/// Errors that might happen when reading a ROS 1 bag file.
+#[derive(thiserror::Error, Debug)]
+pub enum Ros1BagError {
+    #[error("Not a ROS 1 bag file, or not using version 2.0 of the bag format")]
+    InvalidMagic,
+
+    #[error("Unexpected end of file while reading a record")]
+    UnexpectedEof,
+
+    #[error("Record is missing the `{0}` field")]
+    MissingField(&'static str),
+
+    #[error("Field `{0}` of a record has an invalid value")]
+    InvalidField(&'static str),
+
+    #[error("Chunk uses unsupported compression `{0}`")]
+    UnsupportedCompression(String),
+
+    #[error("Failed to decompress chunk: {0}")]
+    Decompression(#[from] std::io::Error),
+
+    #[error("Message refers to unknown connection {0}")]
+    UnknownConnection(u32),
+
+    #[error("Bag has more than {} connections", u16::MAX)]
+    TooManyConnections,
+}
+
+/// Check whether the provided bytes start like a ROS 1 bag file.
+pub fn is_ros1_bag(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// A ROS 1 bag file, whose connections and messages are presented as MCAP channels and messages.
+///
+/// Connections are turned into channels with `ros1` message encoding, their message
+/// definitions into `ros1msg` schemas. The receive time of each message is used as both
+/// `log_time` and `publish_time`.
+///
+/// The messages are read straight from the bag, one chunk at a time.
+pub struct Ros1Bag<'a> {
+    /// The records of the bag, following the version line.
+    records: &'a [u8],
+
+    /// Maps the connection ids of the bag to the corresponding MCAP channels.
+    channels: HashMap<u32, Arc<::mcap::Channel<'static>>>,
+
+    summary: ::mcap::Summary,
+}
+
+impl<'a> Ros1Bag<'a> {
+    /// Reads the connections of the bag, as well as statistics about its messages.
+    ///
+    /// These are taken from the index at the end of the bag. Bags that were not closed properly
+    /// don't have an index, in which case all of their chunks have to be decompressed instead.
+    pub fn new(bag: &'a [u8]) -> Result<Self, Ros1BagError> {
+        re_tracing::profile_function!();
+
+        let records = bag.strip_prefix(MAGIC).ok_or(Ros1BagError::InvalidMagic)?;
+
+        let mut index = None;
+        if let Some(Ok(record)) = Records::new(records).next()
+            && record.op()? == op::BAG_HEADER
+        {
+            let index_pos = usize::try_from(record.header.u64("index_pos")?)
+                .map_err(|_err| Ros1BagError::InvalidField("index_pos"))?;
+
+            // The index position is zero until the bag is closed.
+            index = (index_pos > 0).then(|| bag.get(index_pos..)).flatten();
+        }
+
+        let mut summary = SummaryBuilder::default();
+        if let Some(index) = index {
+            summary.add_records(index)?;
+        } else {
+            re_log::debug!("Bag file has no index, scanning all of its chunks instead");
+            summary.add_records(records)?;
+        }
+
+        let (channels, summary) = summary.finish();
+
+        Ok(Self {
+            records,
+            channels,
+            summary,
+        })
+    }
+
+    /// The channels and schemas of the bag, along with statistics about its messages.
+    pub fn summary(&self) -> &::mcap::Summary {
+        &self.summary
+    }
+
+    /// Passes the messages of the bag to `on_batch`, one chunk at a time, in the order in which
+    /// they are stored.
+    pub fn for_each_batch(
+        &self,
+        on_batch: &mut dyn FnMut(&[::mcap::Message<'_>]),
+    ) -> Result<(), Ros1BagError> {
+        re_tracing::profile_function!();
+
+        // ROS always stores messages in chunks, but they are valid at the top level too.
+        let mut loose_messages = Vec::new();
+
+        for record in Records::new(self.records) {
+            let record = match record {
+                Ok(record) => record,
+                Err(Ros1BagError::UnexpectedEof) => {
+                    re_log::warn!(
+                        "Bag file ends with an incomplete record, it might not have been closed properly"
+                    );
+                    break;
+                }
+                Err(err) => return Err(err),
+            };
+
+            match record.op()? {
+                op::CHUNK => {
+                    if !loose_messages.is_empty() {
+                        on_batch(&loose_messages);
+                        loose_messages.clear();
+                    }
+
+                    let chunk = decompress_chunk(&record)?;
+                    let mut messages = Vec::new();
+                    for record in Records::new(&chunk) {
+                        let record = record?;
+                        if record.op()? == op::MESSAGE_DATA {
+                            messages.push(self.message(&record)?);
+                        }
+                    }
+                    on_batch(&messages);
+                }
+
+                op::MESSAGE_DATA => loose_messages.push(self.message(&record)?),
+
+                // Connections are already part of the summary, the other records are only needed for random access.
+                _ => {}
+            }
+        }
+
+        if !loose_messages.is_empty() {
+            on_batch(&loose_messages);
+        }
+
+        Ok(())
+    }
+
+    fn message<'r>(&self, record: &Record<'r>) -> Result<::mcap::Message<'r>, Ros1BagError> {
+        let conn = record.header.u32("conn")?;
+        let time = record.header.time("time")?;
+        let channel = self
+            .channels
+            .get(&conn)
+            .ok_or(Ros1BagError::UnknownConnection(conn))?;
+
+        Ok(::mcap::Message {
+            channel: Arc::clone(channel),
+            sequence: 0,
+            log_time: time,
+            publish_time: time,
+            data: Cow::Borrowed(record.data),
+        })
+    }
+}
+
+/// Collects the connections of a bag, and how many messages were received on each of them.
+#[derive(Default)]
+struct SummaryBuilder {
+    /// Maps the type and message definition of connections to their schema.
+    schemas: HashMap<(String, String), Arc<::mcap::Schema<'static>>>,
+
+    /// Maps the connection ids of the bag to the corresponding MCAP channels.
+    channels: HashMap<u32, Arc<::mcap::Channel<'static>>>,
+
+    message_counts: HashMap<u32, u64>,
+
+    /// The receive times of the first and last messages.
+    time_range: Option<(u64, u64)>,
+
+    chunk_count: u32,
+}
+
+impl SummaryBuilder {
+    fn add_records(&mut self, records: &[u8]) -> Result<(), Ros1BagError> {
+        for record in Records::new(records) {
+            let record = match record {
+                Ok(record) => record,
+                // Reported when reading the messages.
+                Err(Ros1BagError::UnexpectedEof) => break,
+                Err(err) => return Err(err),
+            };
+
+            match record.op()? {
+                op::CONNECTION => self.add_connection(&record)?,
+
+                // Only found in the index.
+                op::CHUNK_INFO => {
+                    self.chunk_count += 1;
+                    self.add_time(record.header.time("start_time")?);
+                    self.add_time(record.header.time("end_time")?);
+
+                    let mut counts = record.data;
+                    while let Some((conn, rest)) = counts.split_first_chunk::<4>() {
+                        let (count, rest) = rest
+                            .split_first_chunk::<4>()
+                            .ok_or(Ros1BagError::UnexpectedEof)?;
+                        *self
+                            .message_counts
+                            .entry(u32::from_le_bytes(*conn))
+                            .or_default() += u64::from(u32::from_le_bytes(*count));
+                        counts = rest;
+                    }
+                }
+
+                // Only found when there is no index.
+                op::CHUNK => {
+                    self.chunk_count += 1;
+                    self.add_records(&decompress_chunk(&record)?)?;
+                }
+                op::MESSAGE_DATA => {
+                    self.add_time(record.header.time("time")?);
+                    *self
+                        .message_counts
+                        .entry(record.header.u32("conn")?)
+                        .or_default() += 1;
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_connection(&mut self, record: &Record<'_>) -> Result<(), Ros1BagError> {
+        // Connections are stored both in the chunks and in the index section at the end of the file.
+        let conn = record.header.u32("conn")?;
+        if self.channels.contains_key(&conn) {
+            return Ok(());
+        }
+
+        let topic = record.header.string("topic")?;
+        let connection_header = Fields::parse(record.data)?;
+        let message_type = connection_header.string("type")?;
+        let message_definition = connection_header.string("message_definition")?;
+
+        // Schema ids start at 1, as 0 means that a channel has no schema.
+        let next_schema_id = u16::try_from(self.schemas.len() + 1)
+            .map_err(|_err| Ros1BagError::TooManyConnections)?;
+        let schema = self
+            .schemas
+            .entry((message_type.to_owned(), message_definition.to_owned()))
+            .or_insert_with(|| {
+                Arc::new(::mcap::Schema {
+                    id: next_schema_id,
+                    name: message_type.to_owned(),
+                    encoding: "ros1msg".to_owned(),
+                    data: Cow::Owned(message_definition.as_bytes().to_vec()),
+                })
+            });
+
+        let metadata = ["callerid", "latching"]
+            .into_iter()
+            .filter_map(|key| {
+                let value = connection_header.string(key).ok()?;
+                Some((key.to_owned(), value.to_owned()))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let channel = ::mcap::Channel {
+            id: u16::try_from(self.channels.len())
+                .map_err(|_err| Ros1BagError::TooManyConnections)?,
+            topic: topic.to_owned(),
+            schema: Some(Arc::clone(schema)),
+            message_encoding: "ros1".to_owned(),
+            metadata,
+        };
+        self.channels.insert(conn, Arc::new(channel));
+
+        Ok(())
+    }
+
+    fn add_time(&mut self, time: u64) {
+        self.time_range = Some(match self.time_range {
+            Some((start, end)) => (start.min(time), end.max(time)),
+            None => (time, time),
+        });
+    }
+
+    fn finish(self) -> (HashMap<u32, Arc<::mcap::Channel<'static>>>, ::mcap::Summary) {
+        let Self {
+            schemas,
+            channels,
+            message_counts,
+            time_range,
+            chunk_count,
+        } = self;
+
+        // Messages on unknown connections are reported when reading them.
+        let channel_message_counts = message_counts
+            .iter()
+            .filter_map(|(conn, count)| Some((channels.get(conn)?.id, *count)))
+            .collect::<BTreeMap<_, _>>();
+        let (message_start_time, message_end_time) = time_range.unwrap_or_default();
+
+        let summary = ::mcap::Summary {
+            stats: Some(::mcap::records::Statistics {
+                message_count: channel_message_counts.values().sum(),
+                schema_count: schemas.len() as u16,
+                channel_count: channels.len() as u32,
+                attachment_count: 0,
+                metadata_count: 0,
+                chunk_count,
+                message_start_time,
+                message_end_time,
+                channel_message_counts,
+            }),
+            channels: channels
+                .values()
+                .map(|channel| (channel.id, Arc::clone(channel)))
+                .collect(),
+            schemas: schemas
+                .into_values()
+                .map(|schema| (schema.id, schema))
+                .collect(),
+            ..Default::default()
+        };
+
+        (channels, summary)
+    }
+}
+
+/// Decompresses the records of a chunk.
+fn decompress_chunk<'a>(record: &Record<'a>) -> Result<Cow<'a, [u8]>, Ros1BagError> {
+    let mut decoder: Box<dyn std::io::Read + 'a> = match record.header.string("compression")? {
+        "none" => return Ok(Cow::Borrowed(record.data)),
+        "bz2" => Box::new(bzip2::read::BzDecoder::new(record.data)),
+        "lz4" => Box::new(lz4_flex::frame::FrameDecoder::new(record.data)),
+        other => return Err(Ros1BagError::UnsupportedCompression(other.to_owned())),
+    };
+
+    let size = record.header.u32("size")? as usize;
+    let mut chunk = Vec::with_capacity(size.min(MAX_CHUNK_CAPACITY));
+    decoder.read_to_end(&mut chunk)?;
+
+    Ok(Cow::Owned(chunk))
+}
+
+/// A single record of a bag file, consisting of a header and the data that follows it.
+struct Record<'a> {
+    header: Fields<'a>,
+    data: &'a [u8],
+}
+
+impl Record<'_> {
+    fn op(&self) -> Result<u8, Ros1BagError> {
+        match self.header.get("op")? {
+            [op] => Ok(*op),
+            _ => Err(Ros1BagError::InvalidField("op")),
+        }
+    }
+}
+
+/// Iterates over consecutive records.
+struct Records<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Records<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Result<Record<'a>, Ros1BagError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let record = (|| {
+            let header = Fields::parse(take_length_prefixed(&mut self.bytes)?)?;
+            let data = take_length_prefixed(&mut self.bytes)?;
+            Ok(Record { header, data })
+        })();
+
+        if record.is_err() {
+            // We can't find the start of the next record after a malformed one.
+            self.bytes = &[];
+        }
+
+        Some(record)
+    }
+}
+
+/// A list of `name=value` fields, as used by record headers and connection headers.
+struct Fields<'a> {
+    fields: Vec<(&'a [u8], &'a [u8])>,
+}
+
+impl<'a> Fields<'a> {
+    fn parse(mut bytes: &'a [u8]) -> Result<Self, Ros1BagError> {
+        let mut fields = Vec::new();
+        while !bytes.is_empty() {
+            let field = take_length_prefixed(&mut bytes)?;
+            let separator = field
+                .iter()
+                .position(|&b| b == b'=')
+                .ok_or(Ros1BagError::UnexpectedEof)?;
+            fields.push((&field[..separator], &field[separator + 1..]));
+        }
+
+        Ok(Self { fields })
+    }
+
+    fn get(&self, name: &'static str) -> Result<&'a [u8], Ros1BagError> {
+        self.fields
+            .iter()
+            .find_map(|(key, value)| (*key == name.as_bytes()).then_some(*value))
+            .ok_or(Ros1BagError::MissingField(name))
+    }
+
+    fn string(&self, name: &'static str) -> Result<&'a str, Ros1BagError> {
+        std::str::from_utf8(self.get(name)?).map_err(|_err| Ros1BagError::InvalidField(name))
+    }
+
+    fn u32(&self, name: &'static str) -> Result<u32, Ros1BagError> {
+        let bytes = self.get(name)?;
+        Ok(u32::from_le_bytes(
+            bytes
+                .try_into()
+                .map_err(|_err| Ros1BagError::InvalidField(name))?,
+        ))
+    }
+
+    fn u64(&self, name: &'static str) -> Result<u64, Ros1BagError> {
+        let bytes = self.get(name)?;
+        Ok(u64::from_le_bytes(
+            bytes
+                .try_into()
+                .map_err(|_err| Ros1BagError::InvalidField(name))?,
+        ))
+    }
+
+    /// Reads a ROS 1 `time` field, and returns it as nanoseconds since the epoch.
+    fn time(&self, name: &'static str) -> Result<u64, Ros1BagError> {
+        let bytes = self.get(name)?;
+        let (secs, nanos) = match bytes {
+            [s0, s1, s2, s3, n0, n1, n2, n3] => (
+                u32::from_le_bytes([*s0, *s1, *s2, *s3]),
+                u32::from_le_bytes([*n0, *n1, *n2, *n3]),
+            ),
+            _ => return Err(Ros1BagError::InvalidField(name)),
+        };
+
+        Ok(secs as u64 * 1_000_000_000 + nanos as u64)
+    }
+}
+
+/// Takes a slice that is prefixed with its length as a little-endian `u32`.
+fn take_length_prefixed<'a>(bytes: &mut &'a [u8]) -> Result<&'a [u8], Ros1BagError> {
+    let (len, rest) = bytes
+        .split_first_chunk::<4>()
+        .ok_or(Ros1BagError::UnexpectedEof)?;
+    let len = u32::from_le_bytes(*len) as usize;
+    if rest.len() < len {
+        return Err(Ros1BagError::UnexpectedEof);
+    }
+
+    let (value, rest) = rest.split_at(len);
+    *bytes = rest;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, value: &[u8]) -> Vec<u8> {
+        let mut field = Vec::new();
+        field.extend((name.len() as u32 + 1 + value.len() as u32).to_le_bytes());
+        field.extend(name.as_bytes());
+        field.push(b'=');
+        field.extend(value);
+        field
+    }
+
+    fn record(header: &[Vec<u8>], data: &[u8]) -> Vec<u8> {
+        let header = header.concat();
+        let mut record = Vec::new();
+        record.extend((header.len() as u32).to_le_bytes());
+        record.extend(header);
+        record.extend((data.len() as u32).to_le_bytes());
+        record.extend(data);
+        record
+    }
+
+    fn connection(conn: u32) -> Vec<u8> {
+        record(
+            &[
+                field("op", &[op::CONNECTION]),
+                field("conn", &conn.to_le_bytes()),
+                field("topic", b"/chatter"),
+            ],
+            &[
+                field("topic", b"/chatter"),
+                field("type", b"std_msgs/String"),
+                field("md5sum", b"992ce8a1687cec8c8bd883ec73ca41d1"),
+                field("message_definition", b"string data\n"),
+                field("callerid", b"/talker"),
+            ]
+            .concat(),
+        )
+    }
+
+    fn message(conn: u32, secs: u32, nanos: u32, text: &str) -> Vec<u8> {
+        let mut time = secs.to_le_bytes().to_vec();
+        time.extend(nanos.to_le_bytes());
+
+        let mut data = (text.len() as u32).to_le_bytes().to_vec();
+        data.extend(text.as_bytes());
+
+        record(
+            &[
+                field("op", &[op::MESSAGE_DATA]),
+                field("conn", &conn.to_le_bytes()),
+                field("time", &time),
+            ],
+            &data,
+        )
+    }
+
+    fn chunk(compression: &str, size: u32, data: &[u8]) -> Vec<u8> {
+        record(
+            &[
+                field("op", &[op::CHUNK]),
+                field("compression", compression.as_bytes()),
+                field("size", &size.to_le_bytes()),
+            ],
+            data,
+        )
+    }
+
+    fn time(secs: u32) -> Vec<u8> {
+        [secs.to_le_bytes(), 0_u32.to_le_bytes()].concat()
+    }
+
+    /// The topic, log time and payload of the messages of every batch.
+    fn read_batches(bag: &Ros1Bag<'_>) -> Vec<Vec<(String, u64, Vec<u8>)>> {
+        let mut batches = Vec::new();
+        bag.for_each_batch(&mut |messages| {
+            batches.push(
+                messages
+                    .iter()
+                    .map(|msg| {
+                        (
+                            msg.channel.topic.clone(),
+                            msg.log_time,
+                            msg.data[4..].to_vec(),
+                        )
+                    })
+                    .collect(),
+            );
+        })
+        .unwrap();
+        batches
+    }
+
+    #[test]
+    fn read_unindexed_bag() {
+        let records = [connection(0), message(0, 1, 2, "hello")].concat();
+
+        let bag = [
+            MAGIC.to_vec(),
+            chunk("none", records.len() as u32, &records),
+            // Another chunk, repeating the connection.
+            chunk("none", records.len() as u32, &records),
+        ]
+        .concat();
+        let bag = Ros1Bag::new(&bag).unwrap();
+
+        let summary = bag.summary();
+        assert_eq!(summary.channels.len(), 1);
+        let channel = &summary.channels[&0];
+        assert_eq!(channel.topic, "/chatter");
+        assert_eq!(channel.message_encoding, "ros1");
+        assert_eq!(channel.metadata["callerid"], "/talker");
+
+        let schema = channel.schema.as_ref().unwrap();
+        assert_eq!(schema.name, "std_msgs/String");
+        assert_eq!(schema.encoding, "ros1msg");
+        assert_eq!(summary.schemas[&schema.id], *schema);
+
+        let stats = summary.stats.as_ref().unwrap();
+        assert_eq!(stats.message_count, 2);
+        assert_eq!(stats.chunk_count, 2);
+        assert_eq!(stats.message_start_time, 1_000_000_002);
+
+        let hello = ("/chatter".to_owned(), 1_000_000_002, b"hello".to_vec());
+        assert_eq!(read_batches(&bag), vec![vec![hello.clone()], vec![hello]]);
+    }
+
+    #[test]
+    fn read_indexed_bag() {
+        let records = [
+            connection(0),
+            message(0, 1, 0, "hello"),
+            message(0, 2, 0, "world"),
+        ]
+        .concat();
+
+        let mut compressed = lz4_flex::frame::FrameEncoder::new(Vec::new());
+        std::io::Write::write_all(&mut compressed, &records).unwrap();
+        let compressed = compressed.finish().unwrap();
+
+        // The size of the chunk is only a hint, and must not be trusted.
+        let chunk = chunk("lz4", u32::MAX, &compressed);
+
+        let index = [
+            connection(0),
+            record(
+                &[
+                    field("op", &[op::CHUNK_INFO]),
+                    field("ver", &1_u32.to_le_bytes()),
+                    field("chunk_pos", &0_u64.to_le_bytes()),
+                    field("start_time", &time(1)),
+                    field("end_time", &time(2)),
+                    field("count", &1_u32.to_le_bytes()),
+                ],
+                &[0_u32.to_le_bytes(), 2_u32.to_le_bytes()].concat(),
+            ),
+        ]
+        .concat();
+
+        let bag_header = |index_pos: u64| {
+            record(
+                &[
+                    field("op", &[op::BAG_HEADER]),
+                    field("index_pos", &index_pos.to_le_bytes()),
+                    field("conn_count", &1_u32.to_le_bytes()),
+                    field("chunk_count", &1_u32.to_le_bytes()),
+                ],
+                &[b' '; 16],
+            )
+        };
+        let index_pos = MAGIC.len() + bag_header(0).len() + chunk.len();
+
+        let bag = [MAGIC.to_vec(), bag_header(index_pos as u64), chunk, index].concat();
+        let bag = Ros1Bag::new(&bag).unwrap();
+
+        let stats = bag.summary().stats.as_ref().unwrap();
+        assert_eq!(stats.message_count, 2);
+        assert_eq!(stats.channel_message_counts, BTreeMap::from([(0, 2)]));
+        assert_eq!(stats.chunk_count, 1);
+        assert_eq!(stats.message_start_time, 1_000_000_000);
+        assert_eq!(stats.message_end_time, 2_000_000_000);
+
+        assert_eq!(
+            read_batches(&bag),
+            vec![vec![
+                ("/chatter".to_owned(), 1_000_000_000, b"hello".to_vec()),
+                ("/chatter".to_owned(), 2_000_000_000, b"world".to_vec()),
+            ]]
+        );
+    }
+
+    #[test]
+    fn truncated_bag() {
+        let records = [connection(0), message(0, 1, 0, "hello")].concat();
+        let mut bag = [
+            MAGIC.to_vec(),
+            chunk("none", records.len() as u32, &records),
+            chunk("none", records.len() as u32, &records),
+        ]
+        .concat();
+        bag.truncate(bag.len() - 3);
+
+        let bag = Ros1Bag::new(&bag).unwrap();
+        assert_eq!(read_batches(&bag).len(), 1);
+    }
+
+    #[test]
+    fn unknown_connection() {
+        let bag = [MAGIC.to_vec(), message(3, 0, 0, "hello")].concat();
+        let bag = Ros1Bag::new(&bag).unwrap();
+        assert!(bag.summary().channels.is_empty());
+        assert!(matches!(
+            bag.for_each_batch(&mut |_| {}),
+            Err(Ros1BagError::UnknownConnection(3))
+        ));
+    }
+
+    #[test]
+    fn not_a_bag() {
+        assert!(matches!(
+            Ros1Bag::new(b"#ROSBAG V1.2\n"),
+            Err(Ros1BagError::InvalidMagic)
+        ));
+    }
+}
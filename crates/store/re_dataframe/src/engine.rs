@@ -56,6 +56,22 @@ impl QueryEngine<StorageEngine> {
                 .collect(),
         )
     }
+
+    /// Like [`re_chunk_store::ChunkStore::from_rrd_filepath_lazy`], but automatically instantiates
+    /// [`QueryEngine`]s with new empty [`QueryCache`]s.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[inline]
+    pub fn from_rrd_filepath_lazy(
+        store_config: &re_chunk_store::ChunkStoreConfig,
+        path_to_rrd: impl AsRef<std::path::Path>,
+    ) -> anyhow::Result<std::collections::BTreeMap<re_log_types::StoreId, Self>> {
+        Ok(
+            re_chunk_store::ChunkStore::handle_from_rrd_filepath_lazy(store_config, path_to_rrd)?
+                .into_iter()
+                .map(|(store_id, store)| (store_id, Self::from_store(store)))
+                .collect(),
+        )
+    }
 }
 
 impl<E: StorageEngineLike + Clone> QueryEngine<E> {
@@ -0,0 +1,181 @@
+//! Evaluation of [`RowFilter`]s.
+//!
+//! See [`re_chunk_store::QueryExpression::filtered_by`].
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+
+use arrow::{
+    array::{
+        Array as _, ArrayRef as ArrowArrayRef, BooleanArray as ArrowBooleanArray,
+        Float64Array as ArrowFloat64Array, Int64Array as ArrowInt64Array, Scalar as ArrowScalar,
+        StringArray as ArrowStringArray, StructArray as ArrowStructArray,
+    },
+    compute::kernels::cmp,
+    datatypes::DataType as ArrowDataType,
+};
+
+use re_arrow_util::ArrowArrayDowncastRef as _;
+use re_chunk::{Chunk, RowId, TimeInt, TimelineName};
+use re_chunk_store::{ComparisonOperator, ComponentColumnDescriptor, FilterValue, RowFilter};
+use re_sorbet::ComponentColumnSelector;
+
+/// The data of a column that a [`RowFilter`] depends on.
+pub enum ColumnValues {
+    /// Static data always wins, no matter what.
+    Static(ArrowArrayRef),
+
+    /// The latest value (in `RowId` order) for each index value.
+    Temporal(BTreeMap<TimeInt, (RowId, ArrowArrayRef)>),
+}
+
+impl ColumnValues {
+    /// Gathers the values of a column from its (sorted and densified) view chunks.
+    pub fn new(
+        descr: &ComponentColumnDescriptor,
+        filtered_index: TimelineName,
+        chunks: &[(AtomicU64, Chunk)],
+        static_value: Option<ArrowArrayRef>,
+    ) -> Self {
+        if let Some(static_value) = static_value {
+            return Self::Static(static_value);
+        }
+
+        let mut values: BTreeMap<TimeInt, (RowId, ArrowArrayRef)> = BTreeMap::new();
+
+        for (_cursor, chunk) in chunks {
+            let (Some(time_column), Some(list_array)) = (
+                chunk.timelines().get(&filtered_index),
+                chunk.components().get_array(descr.component),
+            ) else {
+                continue;
+            };
+
+            for (row_idx, (time, row_id)) in time_column
+                .times()
+                .zip(chunk.row_ids_slice().iter().copied())
+                .enumerate()
+            {
+                if list_array.is_null(row_idx) {
+                    continue;
+                }
+
+                let is_latest = values
+                    .get(&time)
+                    .is_none_or(|(latest_row_id, _)| row_id > *latest_row_id);
+                if is_latest {
+                    values.insert(time, (row_id, list_array.value(row_idx)));
+                }
+            }
+        }
+
+        Self::Temporal(values)
+    }
+
+    /// The data of the column at the given index value, if any.
+    pub fn at(&self, index_value: TimeInt) -> Option<&ArrowArrayRef> {
+        match self {
+            Self::Static(values) => Some(values),
+            Self::Temporal(values) => values.get(&index_value).map(|(_row_id, values)| values),
+        }
+    }
+}
+
+/// Evaluates the predicate for a single row, given the data of the row for each column.
+pub fn evaluate(
+    filter: &RowFilter,
+    row_values: &dyn Fn(&ComponentColumnSelector) -> Option<ArrowArrayRef>,
+) -> bool {
+    match filter {
+        RowFilter::IsNotNull(column) => row_values(column).is_some(),
+
+        RowFilter::Compare {
+            column,
+            field,
+            op,
+            value,
+        } => row_values(column)
+            .is_some_and(|values| compare_any(&values, field.as_deref(), *op, value)),
+
+        RowFilter::And(filters) => filters.iter().all(|filter| evaluate(filter, row_values)),
+
+        RowFilter::Or(filters) => filters.iter().any(|filter| evaluate(filter, row_values)),
+    }
+}
+
+/// Returns whether at least one instance satisfies the comparison.
+///
+/// Values that cannot be compared to the literal never satisfy it.
+fn compare_any(
+    values: &ArrowArrayRef,
+    field: Option<&str>,
+    op: ComparisonOperator,
+    literal: &FilterValue,
+) -> bool {
+    let values = if let Some(field) = field {
+        let Some(values) = values
+            .downcast_array_ref::<ArrowStructArray>()
+            .and_then(|values| values.column_by_name(field))
+        else {
+            return false;
+        };
+        values.clone()
+    } else {
+        values.clone()
+    };
+
+    let datatype = values.data_type();
+    let (target_datatype, literal): (ArrowDataType, ArrowArrayRef) = match literal {
+        FilterValue::Bool(literal) if datatype == &ArrowDataType::Boolean => (
+            ArrowDataType::Boolean,
+            Arc::new(ArrowBooleanArray::from(vec![*literal])),
+        ),
+
+        FilterValue::Int(literal) if datatype.is_integer() => (
+            ArrowDataType::Int64,
+            Arc::new(ArrowInt64Array::from(vec![*literal])),
+        ),
+
+        FilterValue::Int(literal) if datatype.is_numeric() => (
+            ArrowDataType::Float64,
+            Arc::new(ArrowFloat64Array::from(vec![*literal as f64])),
+        ),
+
+        FilterValue::Float(literal) if datatype.is_numeric() => (
+            ArrowDataType::Float64,
+            Arc::new(ArrowFloat64Array::from(vec![*literal])),
+        ),
+
+        FilterValue::String(literal) if is_string(datatype) => (
+            ArrowDataType::Utf8,
+            Arc::new(ArrowStringArray::from(vec![literal.clone()])),
+        ),
+
+        _ => return false,
+    };
+
+    let Ok(values) = arrow::compute::cast(&values, &target_datatype) else {
+        return false;
+    };
+    let literal = ArrowScalar::new(literal);
+
+    let result = match op {
+        ComparisonOperator::Eq => cmp::eq(&values, &literal),
+        ComparisonOperator::NotEq => cmp::neq(&values, &literal),
+        ComparisonOperator::Lt => cmp::lt(&values, &literal),
+        ComparisonOperator::LtEq => cmp::lt_eq(&values, &literal),
+        ComparisonOperator::Gt => cmp::gt(&values, &literal),
+        ComparisonOperator::GtEq => cmp::gt_eq(&values, &literal),
+    };
+
+    result.is_ok_and(|result| result.true_count() > 0)
+}
+
+fn is_string(datatype: &ArrowDataType) -> bool {
+    match datatype {
+        ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 | ArrowDataType::Utf8View => true,
+        ArrowDataType::Dictionary(_, datatype) => is_string(datatype),
+        _ => false,
+    }
+}
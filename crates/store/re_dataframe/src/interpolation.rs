@@ -0,0 +1,295 @@
+//! Interpolation of component values, as used by the interpolating sparse-fill strategies.
+//!
+//! See [`re_chunk_store::SparseFillStrategy::InterpolateGlobal`].
+
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array as _, ArrayRef as ArrowArrayRef, FixedSizeListArray as ArrowFixedSizeListArray,
+        Float32Array as ArrowFloat32Array, Float64Array as ArrowFloat64Array,
+        ListArray as ArrowListArray,
+    },
+    buffer::OffsetBuffer as ArrowOffsetBuffer,
+    datatypes::DataType as ArrowDataType,
+};
+
+use re_arrow_util::ArrowArrayDowncastRef as _;
+use re_types_core::ComponentType;
+
+/// Components of this type are spherically interpolated, rather than linearly.
+const ROTATION_QUAT: &str = "rerun.components.RotationQuat";
+
+/// Interpolates between two single-row component columns.
+///
+/// `t` is the position of the interpolated value between `prev` (0.0) and `next` (1.0).
+///
+/// Floating-point values (scalars, translations, fixed-size float arrays, …) are linearly
+/// interpolated element-wise, while [`ROTATION_QUAT`] components are interpolated using slerp.
+///
+/// Returns `None` if the values cannot be interpolated, e.g. because of their datatype, or
+/// because `prev` and `next` don't have the same number of instances.
+pub fn interpolate(
+    component_type: Option<ComponentType>,
+    prev: &ArrowListArray,
+    next: &ArrowListArray,
+    t: f64,
+) -> Option<ArrowListArray> {
+    if prev.len() != 1 || next.len() != 1 || prev.is_null(0) || next.is_null(0) {
+        return None;
+    }
+
+    let (prev_values, next_values) = (prev.value(0), next.value(0));
+    if prev_values.data_type() != next_values.data_type() || prev_values.len() != next_values.len()
+    {
+        return None;
+    }
+
+    let values = if component_type.is_some_and(|typ| typ.full_name() == ROTATION_QUAT) {
+        slerp_quats(&prev_values, &next_values, t)?
+    } else {
+        lerp(&prev_values, &next_values, t)?
+    };
+
+    let ArrowDataType::List(field) = prev.data_type() else {
+        return None;
+    };
+
+    ArrowListArray::try_new(
+        field.clone(),
+        ArrowOffsetBuffer::from_lengths([values.len()]),
+        values,
+        None,
+    )
+    .ok()
+}
+
+/// Element-wise linear interpolation of floating-point arrays, possibly nested in fixed-size lists.
+///
+/// Elements which are null on either side are null in the result.
+fn lerp(prev: &ArrowArrayRef, next: &ArrowArrayRef, t: f64) -> Option<ArrowArrayRef> {
+    match prev.data_type() {
+        ArrowDataType::Float32 => {
+            let (prev, next) = (
+                prev.downcast_array_ref::<ArrowFloat32Array>()?,
+                next.downcast_array_ref::<ArrowFloat32Array>()?,
+            );
+            let values: ArrowFloat32Array = prev
+                .iter()
+                .zip(next.iter())
+                .map(|(prev, next)| {
+                    let (prev, next) = (f64::from(prev?), f64::from(next?));
+                    Some((prev + (next - prev) * t) as f32)
+                })
+                .collect();
+            Some(Arc::new(values))
+        }
+
+        ArrowDataType::Float64 => {
+            let (prev, next) = (
+                prev.downcast_array_ref::<ArrowFloat64Array>()?,
+                next.downcast_array_ref::<ArrowFloat64Array>()?,
+            );
+            let values: ArrowFloat64Array = prev
+                .iter()
+                .zip(next.iter())
+                .map(|(prev, next)| {
+                    let (prev, next) = (prev?, next?);
+                    Some(prev + (next - prev) * t)
+                })
+                .collect();
+            Some(Arc::new(values))
+        }
+
+        ArrowDataType::FixedSizeList(field, size) => {
+            let (prev, next) = (
+                prev.downcast_array_ref::<ArrowFixedSizeListArray>()?,
+                next.downcast_array_ref::<ArrowFixedSizeListArray>()?,
+            );
+            let values = lerp(prev.values(), next.values(), t)?;
+            let nulls = arrow::buffer::NullBuffer::union(prev.nulls(), next.nulls());
+            Some(Arc::new(
+                ArrowFixedSizeListArray::try_new(field.clone(), *size, values, nulls).ok()?,
+            ))
+        }
+
+        _ => None,
+    }
+}
+
+/// Spherical linear interpolation of an array of `[x, y, z, w]` quaternions.
+///
+/// Instances which are null on either side are null in the result.
+fn slerp_quats(prev: &ArrowArrayRef, next: &ArrowArrayRef, t: f64) -> Option<ArrowArrayRef> {
+    let ArrowDataType::FixedSizeList(field, 4) = prev.data_type() else {
+        return None;
+    };
+
+    let (prev, next) = (
+        prev.downcast_array_ref::<ArrowFixedSizeListArray>()?,
+        next.downcast_array_ref::<ArrowFixedSizeListArray>()?,
+    );
+    let (prev_values, next_values) = (
+        prev.values().downcast_array_ref::<ArrowFloat32Array>()?,
+        next.values().downcast_array_ref::<ArrowFloat32Array>()?,
+    );
+
+    let nulls = arrow::buffer::NullBuffer::union(prev.nulls(), next.nulls());
+
+    let mut values = Vec::with_capacity(prev_values.len());
+    for (prev_quat, next_quat) in prev_values
+        .values()
+        .chunks_exact(4)
+        .zip(next_values.values().chunks_exact(4))
+    {
+        let to_quat = |quat: &[f32]| std::array::from_fn(|i| f64::from(quat[i]));
+        let quat = slerp(to_quat(prev_quat), to_quat(next_quat), t);
+        values.extend(quat.map(|v| v as f32));
+    }
+
+    Some(Arc::new(
+        ArrowFixedSizeListArray::try_new(
+            field.clone(),
+            4,
+            Arc::new(ArrowFloat32Array::from(values)),
+            nulls,
+        )
+        .ok()?,
+    ))
+}
+
+/// Spherical linear interpolation between two unit quaternions, along the shortest path.
+fn slerp(prev: [f64; 4], mut next: [f64; 4], t: f64) -> [f64; 4] {
+    let mut dot: f64 = prev.iter().zip(&next).map(|(a, b)| a * b).sum();
+
+    // `q` and `-q` are the same rotation: go the short way around.
+    if dot < 0.0 {
+        next = next.map(|v| -v);
+        dot = -dot;
+    }
+
+    let (prev_weight, next_weight) = if dot > 0.9995 {
+        // Nearly identical rotations: linear interpolation is accurate and numerically stable.
+        (1.0 - t, t)
+    } else {
+        let theta = dot.acos();
+        let sin_theta = theta.sin();
+        (
+            ((1.0 - t) * theta).sin() / sin_theta,
+            (t * theta).sin() / sin_theta,
+        )
+    };
+
+    let quat: [f64; 4] = std::array::from_fn(|i| prev[i] * prev_weight + next[i] * next_weight);
+
+    let norm = quat.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        quat.map(|v| v / norm)
+    } else {
+        quat
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn single_row(values: ArrowArrayRef) -> ArrowListArray {
+        let field = Arc::new(arrow::datatypes::Field::new_list_field(
+            values.data_type().clone(),
+            true,
+        ));
+        ArrowListArray::new(
+            field,
+            ArrowOffsetBuffer::from_lengths([values.len()]),
+            values,
+            None,
+        )
+    }
+
+    fn fixed_size_list(values: Vec<f32>, size: i32) -> ArrowArrayRef {
+        let field = Arc::new(arrow::datatypes::Field::new_list_field(
+            ArrowDataType::Float32,
+            false,
+        ));
+        Arc::new(ArrowFixedSizeListArray::new(
+            field,
+            size,
+            Arc::new(ArrowFloat32Array::from(values)),
+            None,
+        ))
+    }
+
+    fn fixed_size_list_values(list: &ArrowListArray) -> Vec<f32> {
+        list.value(0)
+            .downcast_array_ref::<ArrowFixedSizeListArray>()
+            .unwrap()
+            .values()
+            .downcast_array_ref::<ArrowFloat32Array>()
+            .unwrap()
+            .values()
+            .to_vec()
+    }
+
+    #[test]
+    fn lerp_scalars() {
+        let prev = single_row(Arc::new(ArrowFloat64Array::from(vec![Some(0.0), None])));
+        let next = single_row(Arc::new(ArrowFloat64Array::from(vec![
+            Some(10.0),
+            Some(1.0),
+        ])));
+
+        let interpolated = interpolate(None, &prev, &next, 0.25).unwrap();
+        let values = interpolated.value(0);
+        let values = values.downcast_array_ref::<ArrowFloat64Array>().unwrap();
+        assert_eq!(values.iter().collect::<Vec<_>>(), vec![Some(2.5), None]);
+    }
+
+    #[test]
+    fn lerp_translations() {
+        let prev = single_row(fixed_size_list(vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0], 3));
+        let next = single_row(fixed_size_list(vec![2.0, 4.0, 6.0, 3.0, 2.0, 1.0], 3));
+
+        let interpolated = interpolate(None, &prev, &next, 0.5).unwrap();
+        assert_eq!(
+            fixed_size_list_values(&interpolated),
+            vec![1.0, 2.0, 3.0, 2.0, 2.0, 2.0]
+        );
+    }
+
+    #[test]
+    fn slerp_rotations() {
+        let component_type = Some(ComponentType::from(ROTATION_QUAT));
+
+        // Identity, and a half turn around Z.
+        let prev = single_row(fixed_size_list(vec![0.0, 0.0, 0.0, 1.0], 4));
+        let next = single_row(fixed_size_list(vec![0.0, 0.0, 1.0, 0.0], 4));
+
+        let interpolated = interpolate(component_type, &prev, &next, 0.5).unwrap();
+        let half_sqrt2 = std::f32::consts::FRAC_1_SQRT_2;
+        let expected = [0.0, 0.0, half_sqrt2, half_sqrt2];
+        for (value, expected) in fixed_size_list_values(&interpolated).iter().zip(expected) {
+            assert!((value - expected).abs() < 1e-6, "{value} != {expected}");
+        }
+
+        // Opposite quaternions are the same rotation: no interpolation needed.
+        let next = single_row(fixed_size_list(vec![0.0, 0.0, 0.0, -1.0], 4));
+        let interpolated = interpolate(component_type, &prev, &next, 0.5).unwrap();
+        assert_eq!(
+            fixed_size_list_values(&interpolated),
+            vec![0.0, 0.0, 0.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn not_interpolatable() {
+        let prev = single_row(Arc::new(arrow::array::UInt32Array::from(vec![0])));
+        let next = single_row(Arc::new(arrow::array::UInt32Array::from(vec![10])));
+        assert!(interpolate(None, &prev, &next, 0.5).is_none());
+
+        // Mismatched number of instances.
+        let prev = single_row(Arc::new(ArrowFloat64Array::from(vec![0.0])));
+        let next = single_row(Arc::new(ArrowFloat64Array::from(vec![1.0, 2.0])));
+        assert!(interpolate(None, &prev, &next, 0.5).is_none());
+    }
+}
@@ -1,6 +1,8 @@
 //! The Rerun public data APIs. Get dataframes back from your Rerun datastore.
 
 mod engine;
+mod filter;
+mod interpolation;
 mod query;
 
 pub use self::engine::QueryEngine;
@@ -8,8 +10,8 @@ pub use self::query::QueryHandle;
 
 #[doc(no_inline)]
 pub use self::external::re_chunk_store::{
-    ChunkStoreConfig, ChunkStoreHandle, Index, IndexRange, IndexValue, QueryExpression,
-    SparseFillStrategy, ViewContentsSelector,
+    ChunkStoreConfig, ChunkStoreHandle, ComparisonOperator, FilterValue, Index, IndexRange,
+    IndexValue, QueryExpression, RowFilter, SparseFillStrategy, ViewContentsSelector,
 };
 #[doc(no_inline)]
 pub use self::external::re_log_types::{
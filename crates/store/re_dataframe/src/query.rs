@@ -9,8 +9,9 @@ use std::{
 use arrow::array::RecordBatchOptions;
 use arrow::{
     array::{
-        ArrayRef as ArrowArrayRef, BooleanArray as ArrowBooleanArray,
-        PrimitiveArray as ArrowPrimitiveArray, RecordBatch as ArrowRecordBatch,
+        Array as _, ArrayRef as ArrowArrayRef, BooleanArray as ArrowBooleanArray,
+        ListArray as ArrowListArray, PrimitiveArray as ArrowPrimitiveArray,
+        RecordBatch as ArrowRecordBatch,
     },
     buffer::ScalarBuffer as ArrowScalarBuffer,
     datatypes::{
@@ -39,6 +40,8 @@ use re_types_core::{
     Loggable as _, SerializedComponentColumn, archetypes, arrow_helpers::as_array_ref,
 };
 
+use crate::filter::ColumnValues;
+
 // ---
 
 // TODO(cmc): (no specific order) (should we make issues for these?)
@@ -48,6 +51,7 @@ use re_types_core::{
 // * [x] pagination (any solution, even a slow one)
 // * [x] pov support
 // * [x] latestat sparse-filling
+// * [x] interpolated sparse-filling
 // * [x] sampling support
 // * [x] clears
 // * [x] pagination (fast)
@@ -55,7 +59,7 @@ use re_types_core::{
 // * [x] dedupe-latest without allocs/copies
 // * [ ] allocate null arrays once
 // * [ ] overlaps (less dumb)
-// * [ ] selector-based `filtered_index`
+// * [x] selector-based `filtered_index`
 // * [ ] configurable cache bypass
 
 /// A handle to a dataframe query, ready to be executed.
@@ -108,6 +112,14 @@ struct QueryHandleState {
     /// value is irrelevant since this means we are only concerned with static data anyway.
     filtered_index: Index,
 
+    /// The index actually used by each column of the view, as resolved from the `filtered_index`
+    /// and its fallbacks.
+    ///
+    /// This vector's entries correspond to those in [`QueryHandleState::view_contents`].
+    ///
+    /// See [`QueryExpression::filtered_index_fallbacks`].
+    view_indices: Vec<Index>,
+
     /// The Arrow schema that corresponds to the `selected_contents`.
     ///
     /// All returned rows will have this schema.
@@ -147,6 +159,25 @@ struct QueryHandleState {
     ///
     /// See also [`QueryHandleState::cur_row`].
     unique_index_values: Vec<IndexValue>,
+
+    /// The values used to interpolate the last null cell of each column, if any.
+    ///
+    /// This vector's entries correspond to those in [`QueryHandleState::view_contents`].
+    ///
+    /// See [`SparseFillStrategy::InterpolateGlobal`].
+    interpolation_cursors: Vec<parking_lot::Mutex<Option<InterpolationCursor>>>,
+}
+
+/// The non-null values surrounding the last interpolated cell of a column.
+///
+/// Consecutive null cells of a sparse column fall between the same two values, so this saves
+/// looking them up again for each of these cells.
+struct InterpolationCursor {
+    /// The index of the latest-at value that the interpolation starts from.
+    prev_index: (TimeInt, RowId),
+
+    /// The first value indexed after it, if any, along with its index value.
+    next: Option<(TimeInt, UnitChunkShared)>,
 }
 
 impl<E: StorageEngineLike> QueryHandle<E> {
@@ -183,6 +214,18 @@ impl<E: StorageEngineLike> QueryHandle<E> {
         let view_contents_schema = store.schema_for_query(&self.query);
         let view_contents = view_contents_schema.indices_and_components();
 
+        let entity_indices = self.resolve_entity_indices(store, filtered_index, &view_contents);
+        let view_indices = view_contents
+            .iter()
+            .map(|descr| {
+                descr
+                    .entity_path()
+                    .and_then(|entity_path| entity_indices.get(entity_path))
+                    .copied()
+                    .unwrap_or(filtered_index)
+            })
+            .collect_vec();
+
         // 2. Compute the schema of the selected contents.
         //
         // The caller might have selected columns that do not exist in the view: they should
@@ -227,7 +270,7 @@ impl<E: StorageEngineLike> QueryHandle<E> {
                 .keep_extra_components(false)
         };
         let (view_pov_chunks_idx, mut view_chunks) =
-            self.fetch_view_chunks(store, cache, &query, &view_contents);
+            self.fetch_view_chunks(store, cache, &query, &view_contents, &view_indices);
 
         // 5. Collect all relevant clear chunks and update the view accordingly.
         //
@@ -235,7 +278,8 @@ impl<E: StorageEngineLike> QueryHandle<E> {
         {
             re_tracing::profile_scope!("clear_chunks");
 
-            let clear_chunks = self.fetch_clear_chunks(store, cache, &query, &view_contents);
+            let clear_chunks =
+                self.fetch_clear_chunks(store, cache, &query, &view_contents, &entity_indices);
             for (view_idx, chunks) in view_chunks.iter_mut().enumerate() {
                 let Some(ColumnDescriptor::Component(descr)) = view_contents.get(view_idx) else {
                     continue;
@@ -283,7 +327,7 @@ impl<E: StorageEngineLike> QueryHandle<E> {
                         // the time range for the specific component of interest.
                         chunk
                             .timelines()
-                            .get(&filtered_index)
+                            .get(&view_indices[view_idx])
                             .map(|time_column| time_column.time_range())
                             .map_or(TimeInt::STATIC, |time_range| time_range.min())
                     });
@@ -294,7 +338,7 @@ impl<E: StorageEngineLike> QueryHandle<E> {
         // 6. Collect all unique index values.
         //
         // Used to achieve ~O(log(n)) pagination.
-        let unique_index_values = if self.query.filtered_index.is_none() {
+        let mut unique_index_values = if self.query.filtered_index.is_none() {
             vec![TimeInt::STATIC]
         } else if let Some(using_index_values) = self.query.using_index_values.as_ref() {
             using_index_values
@@ -305,7 +349,7 @@ impl<E: StorageEngineLike> QueryHandle<E> {
         } else {
             re_tracing::profile_scope!("index_values");
 
-            let mut view_chunks = view_chunks.iter();
+            let mut view_chunks = view_chunks.iter().zip(&view_indices);
             let view_chunks = if let Some(view_pov_chunks_idx) = view_pov_chunks_idx {
                 Either::Left(view_chunks.nth(view_pov_chunks_idx).into_iter())
             } else {
@@ -313,11 +357,11 @@ impl<E: StorageEngineLike> QueryHandle<E> {
             };
 
             let mut all_unique_index_values: BTreeSet<TimeInt> = view_chunks
-                .flat_map(|chunks| {
+                .flat_map(|(chunks, view_index)| {
                     chunks.iter().filter_map(|(_cursor, chunk)| {
                         chunk
                             .timelines()
-                            .get(&filtered_index)
+                            .get(view_index)
                             .map(|time_column| time_column.times())
                     })
                 })
@@ -334,6 +378,89 @@ impl<E: StorageEngineLike> QueryHandle<E> {
                 .collect_vec()
         };
 
+        // 7. Filter out the rows for which the user-provided predicate doesn't hold.
+        //
+        // The predicate is evaluated on the rows as they will be returned, i.e. after
+        // sparse-filling.
+        if let Some(filter) = self.query.filtered_by.as_ref()
+            && self.query.filtered_index.is_some()
+        {
+            re_tracing::profile_scope!("filtered_by");
+
+            let column_values = filter
+                .columns()
+                .into_iter()
+                .map(|column| {
+                    let values = view_contents
+                        .iter()
+                        .zip(&view_chunks)
+                        .zip(&view_indices)
+                        .find_map(|((descr, chunks), view_index)| match descr {
+                            ColumnDescriptor::Component(descr) if descr.matches(column) => {
+                                let query = re_chunk::LatestAtQuery::new(
+                                    TimelineName::new(""),
+                                    TimeInt::STATIC,
+                                );
+                                let static_value = cache
+                                    .latest_at(&query, &descr.entity_path, [descr.component])
+                                    .components
+                                    .into_values()
+                                    .next()
+                                    .and_then(|unit| {
+                                        unit.components()
+                                            .get_array(descr.component)
+                                            .filter(|list_array| list_array.is_valid(0))
+                                            .map(|list_array| list_array.value(0))
+                                    });
+
+                                let values =
+                                    ColumnValues::new(descr, *view_index, chunks, static_value);
+
+                                // Consecutive rows are filtered in order: they can share the
+                                // interpolation brackets, just like when they are returned.
+                                let cursor = parking_lot::Mutex::new(None);
+
+                                Some((descr, *view_index, values, cursor))
+                            }
+                            _ => None,
+                        });
+
+                    (column, values)
+                })
+                .collect_vec();
+
+            unique_index_values.retain(|index_value| {
+                crate::filter::evaluate(filter, &|column| {
+                    let (descr, view_index, values, cursor) = column_values
+                        .iter()
+                        .find(|(selector, _)| *selector == column)
+                        .and_then(|(_, values)| values.as_ref())?;
+
+                    if let Some(values) = values.at(*index_value) {
+                        return Some(values.clone());
+                    }
+
+                    match self.fill_null_cell(
+                        store,
+                        cache,
+                        descr,
+                        *view_index,
+                        *index_value,
+                        cursor,
+                    )? {
+                        Either::Left(unit) => unit
+                            .components()
+                            .get_array(descr.component)
+                            .filter(|list_array| list_array.is_valid(0))
+                            .map(|list_array| list_array.value(0)),
+                        Either::Right(list_array) => {
+                            list_array.is_valid(0).then(|| list_array.value(0))
+                        }
+                    }
+                })
+            });
+        }
+
         let selected_static_values = {
             re_tracing::profile_scope!("static_values");
 
@@ -365,7 +492,9 @@ impl<E: StorageEngineLike> QueryHandle<E> {
             selected_contents,
             selected_static_values,
             filtered_index,
+            view_indices,
             arrow_schema,
+            interpolation_cursors: view_chunks.iter().map(|_| Default::default()).collect(),
             view_chunks,
             cur_row: AtomicU64::new(0),
             unique_index_values,
@@ -461,12 +590,60 @@ impl<E: StorageEngineLike> QueryHandle<E> {
             .collect_vec()
     }
 
+    /// Resolves the index used by each entity of the view, for those that don't have any data on
+    /// the `filtered_index` itself.
+    ///
+    /// See [`QueryExpression::filtered_index_fallbacks`].
+    fn resolve_entity_indices(
+        &self,
+        store: &ChunkStore,
+        filtered_index: Index,
+        view_contents: &[ColumnDescriptor],
+    ) -> IntMap<EntityPath, Index> {
+        if self.query.filtered_index.is_none() || self.query.filtered_index_fallbacks.is_empty() {
+            return IntMap::default();
+        }
+
+        let timelines = store.timelines();
+
+        // All indices in the chain must be of the same type as the `filtered_index`, otherwise
+        // their values cannot be reported in its column.
+        // If the store doesn't know about the `filtered_index` at all, there is no such column to
+        // begin with.
+        let Some(index_type) = timelines
+            .get(&filtered_index)
+            .map(|timeline| timeline.typ())
+        else {
+            return IntMap::default();
+        };
+        let index_chain = std::iter::once(filtered_index)
+            .chain(self.query.filtered_index_fallbacks.iter().copied())
+            .filter(|index| {
+                timelines
+                    .get(index)
+                    .is_some_and(|timeline| timeline.typ() == index_type)
+            })
+            .collect_vec();
+
+        view_contents
+            .iter()
+            .filter_map(|descr| descr.entity_path())
+            .filter_map(|entity_path| {
+                let index = index_chain
+                    .iter()
+                    .find(|index| store.entity_has_temporal_data_on_timeline(index, entity_path))?;
+                Some((entity_path.clone(), *index))
+            })
+            .collect()
+    }
+
     fn fetch_view_chunks(
         &self,
         store: &ChunkStore,
         cache: &QueryCache,
         query: &RangeQuery,
         view_contents: &[ColumnDescriptor],
+        view_indices: &[Index],
     ) -> (Option<usize>, Vec<Vec<(AtomicU64, Chunk)>>) {
         let mut view_pov_chunks_idx = self.query.filtered_is_not_null.as_ref().map(|_| usize::MAX);
 
@@ -477,8 +654,18 @@ impl<E: StorageEngineLike> QueryHandle<E> {
                 ColumnDescriptor::RowId(_) | ColumnDescriptor::Time(_) => Vec::new(),
 
                 ColumnDescriptor::Component(column) => {
+                    let query = RangeQuery {
+                        timeline: view_indices[idx],
+                        ..query.clone()
+                    };
                     let chunks = self
-                        .fetch_chunks(store, cache, query, &column.entity_path, [column.component])
+                        .fetch_chunks(
+                            store,
+                            cache,
+                            &query,
+                            &column.entity_path,
+                            [column.component],
+                        )
                         .unwrap_or_default();
 
                     if let Some(pov) = self.query.filtered_is_not_null.as_ref()
@@ -505,6 +692,7 @@ impl<E: StorageEngineLike> QueryHandle<E> {
         cache: &QueryCache,
         query: &RangeQuery,
         view_contents: &[ColumnDescriptor],
+        entity_indices: &IntMap<EntityPath, Index>,
     ) -> IntMap<EntityPath, Vec<Chunk>> {
         /// Returns all the ancestors of an [`EntityPath`].
         ///
@@ -564,6 +752,17 @@ impl<E: StorageEngineLike> QueryHandle<E> {
         entity_paths
             .iter()
             .filter_map(|entity_path| {
+                // Clears are only relevant on the index used for the entity itself, whoever they
+                // come from.
+                let query = RangeQuery {
+                    timeline: entity_indices
+                        .get(entity_path)
+                        .copied()
+                        .unwrap_or(query.timeline),
+                    ..query.clone()
+                };
+                let query = &query;
+
                 // For the entity itself, any chunk that contains clear data is relevant, recursive or not.
                 // Just fetch everything we find.
                 let flat_chunks = self
@@ -635,8 +834,8 @@ impl<E: StorageEngineLike> QueryHandle<E> {
                         // remaining unique index values all while taking row-id ordering semantics
                         // into account.
                         debug_assert!(
-                            if let Some(index) = self.query.filtered_index.as_ref() {
-                                chunk.is_timeline_sorted(index)
+                            if self.query.filtered_index.is_some() {
+                                chunk.is_timeline_sorted(query.timeline())
                             } else {
                                 chunk.is_sorted()
                             },
@@ -747,11 +946,11 @@ impl<E: StorageEngineLike> QueryHandle<E> {
             return;
         }
 
-        for chunks in &state.view_chunks {
+        for (chunks, view_index) in state.view_chunks.iter().zip(&state.view_indices) {
             for (cursor, chunk) in chunks {
                 // NOTE: The chunk has been densified already: its global time range is the same as
                 // the time range for the specific component of interest.
-                let Some(time_column) = chunk.timelines().get(&state.filtered_index) else {
+                let Some(time_column) = chunk.timelines().get(view_index) else {
                     continue;
                 };
 
@@ -887,6 +1086,7 @@ impl<E: StorageEngineLike> QueryHandle<E> {
         ///
         /// Possibly retrofilled, see [`QueryExpression::sparse_fill_strategy`].
         #[derive(Debug)]
+        #[expect(clippy::enum_variant_names)] // `StreamingJoinState::StreamingJoinState` predates the other variants
         enum StreamingJoinState<'a> {
             /// Incoming data for the current iteration.
             StreamingJoinState(StreamingJoinStateEntry<'a>),
@@ -895,6 +1095,11 @@ impl<E: StorageEngineLike> QueryHandle<E> {
             ///
             /// See [`QueryExpression::sparse_fill_strategy`].
             Retrofilled(UnitChunkShared),
+
+            /// Data interpolated from the surrounding values.
+            ///
+            /// See [`SparseFillStrategy::InterpolateGlobal`].
+            Interpolated(ArrowListArray),
         }
 
         // Although that's a synchronous lock, we probably don't need to worry about it until
@@ -930,7 +1135,7 @@ impl<E: StorageEngineLike> QueryHandle<E> {
                 let cur_index_times_empty: &[i64] = &[];
                 let cur_index_times = cur_chunk
                     .timelines()
-                    .get(&state.filtered_index)
+                    .get(&state.view_indices[view_column_idx])
                     .map_or(cur_index_times_empty, |time_column| time_column.times_raw());
                 let cur_index_row_ids = cur_chunk.row_ids_slice();
 
@@ -1029,47 +1234,33 @@ impl<E: StorageEngineLike> QueryHandle<E> {
             }
         }
 
-        match self.query.sparse_fill_strategy {
-            SparseFillStrategy::None => {}
-
-            SparseFillStrategy::LatestAtGlobal => {
-                // Everything that yielded `null` for the current iteration.
-                let null_streaming_states = view_streaming_state
-                    .iter_mut()
-                    .enumerate()
-                    .filter(|(_view_idx, streaming_state)| streaming_state.is_none());
+        if self.query.sparse_fill_strategy != SparseFillStrategy::None {
+            // Everything that yielded `null` for the current iteration.
+            let null_streaming_states = view_streaming_state
+                .iter_mut()
+                .enumerate()
+                .filter(|(_view_idx, streaming_state)| streaming_state.is_none());
 
-                for (view_idx, streaming_state) in null_streaming_states {
-                    let Some(ColumnDescriptor::Component(descr)) =
-                        state.view_contents.get_index_or_component(view_idx)
-                    else {
-                        continue;
-                    };
-
-                    // NOTE: While it would be very tempting to resolve the latest-at state
-                    // of the entire view contents at `filtered_index_range.start - 1` once
-                    // during `QueryHandle` initialization, and then bootstrap off of that, that
-                    // would effectively close the door to efficient pagination forever, since
-                    // we'd have to iterate over all the pages to compute the right latest-at
-                    // value at t+n (i.e. no more random access).
-                    // Therefore, it is better to simply do this the "dumb" way.
-                    //
-                    // TODO(cmc): Still, as always, this can be made faster and smarter at
-                    // the cost of some extra complexity (e.g. caching the result across
-                    // consecutive nulls etc). Later.
-
-                    let query =
-                        re_chunk::LatestAtQuery::new(state.filtered_index, *cur_index_value);
-
-                    let results =
-                        cache.latest_at(&query, &descr.entity_path.clone(), [descr.component]);
+            for (view_idx, streaming_state) in null_streaming_states {
+                let Some(ColumnDescriptor::Component(descr)) =
+                    state.view_contents.get_index_or_component(view_idx)
+                else {
+                    continue;
+                };
 
-                    *streaming_state = results
-                        .components
-                        .into_values()
-                        .next()
-                        .map(|unit| StreamingJoinState::Retrofilled(unit.clone()));
-                }
+                *streaming_state = self
+                    .fill_null_cell(
+                        store,
+                        cache,
+                        &descr,
+                        state.view_indices[view_idx],
+                        *cur_index_value,
+                        &state.interpolation_cursors[view_idx],
+                    )
+                    .map(|filled| match filled {
+                        Either::Left(unit) => StreamingJoinState::Retrofilled(unit),
+                        Either::Right(list_array) => StreamingJoinState::Interpolated(list_array),
+                    });
             }
         }
 
@@ -1097,29 +1288,35 @@ impl<E: StorageEngineLike> QueryHandle<E> {
                 .iter()
                 .flatten()
                 .flat_map(|streaming_state| {
-                    match streaming_state {
-                        StreamingJoinState::StreamingJoinState(s) => s.chunk.timelines(),
-                        StreamingJoinState::Retrofilled(unit) => unit.timelines(),
-                    }
-                    .values()
-                    // NOTE: Cannot fail, just want to stay away from unwraps.
-                    .filter_map(move |time_column| {
-                        let cursor = match streaming_state {
-                            StreamingJoinState::StreamingJoinState(s) => s.cursor as usize,
-                            StreamingJoinState::Retrofilled(_) => 0,
-                        };
-                        time_column
-                            .times_raw()
-                            .get(cursor)
-                            .copied()
-                            .map(TimeInt::new_temporal)
-                            .map(|time| {
-                                (
-                                    *time_column.timeline(),
-                                    (time, time_column.times_buffer().slice(cursor, 1)),
-                                )
-                            })
-                    })
+                    // NOTE: Interpolated values are, by definition, not indexed anywhere.
+                    let timelines = match streaming_state {
+                        StreamingJoinState::StreamingJoinState(s) => Some(s.chunk.timelines()),
+                        StreamingJoinState::Retrofilled(unit) => Some(unit.timelines()),
+                        StreamingJoinState::Interpolated(_) => None,
+                    };
+
+                    timelines
+                        .into_iter()
+                        .flat_map(|timelines| timelines.values())
+                        // NOTE: Cannot fail, just want to stay away from unwraps.
+                        .filter_map(move |time_column| {
+                            let cursor = match streaming_state {
+                                StreamingJoinState::StreamingJoinState(s) => s.cursor as usize,
+                                StreamingJoinState::Retrofilled(_)
+                                | StreamingJoinState::Interpolated(_) => 0,
+                            };
+                            time_column
+                                .times_raw()
+                                .get(cursor)
+                                .copied()
+                                .map(TimeInt::new_temporal)
+                                .map(|time| {
+                                    (
+                                        *time_column.timeline(),
+                                        (time, time_column.times_buffer().slice(cursor, 1)),
+                                    )
+                                })
+                        })
                 })
                 .for_each(|(timeline, (time, time_sliced))| {
                     max_value_per_index
@@ -1133,6 +1330,25 @@ impl<E: StorageEngineLike> QueryHandle<E> {
                         .or_insert((time, time_sliced));
                 });
 
+            // Cells that were resolved through one of the `filtered_index_fallbacks` come from a
+            // different clock altogether: the values of the other indices cannot be meaningfully
+            // stitched together with theirs, so only the queried index is reported for such rows.
+            let has_fallback_cells = view_streaming_state.iter().zip(&state.view_indices).any(
+                |(streaming_state, view_index)| {
+                    *view_index != state.filtered_index
+                        && match streaming_state {
+                            Some(StreamingJoinState::StreamingJoinState(_)) => true,
+                            Some(StreamingJoinState::Retrofilled(unit)) => {
+                                !unit.timelines().is_empty()
+                            }
+                            Some(StreamingJoinState::Interpolated(_)) | None => false,
+                        }
+                },
+            );
+            if has_fallback_cells {
+                max_value_per_index.retain(|index, _| *index == state.filtered_index);
+            }
+
             if !cur_index_value.is_static() {
                 // The current index value (if temporal) should be the one returned for the
                 // queried index, no matter what.
@@ -1184,6 +1400,8 @@ impl<E: StorageEngineLike> QueryHandle<E> {
                         })?;
                         unit.components().get_array(component_desc.component).cloned()
                     }
+
+                    StreamingJoinState::Interpolated(list_array) => Some(list_array.clone()),
                 };
 
 
@@ -1240,6 +1458,197 @@ impl<E: StorageEngineLike> QueryHandle<E> {
         Some(selected_arrays)
     }
 
+    /// Fills a null cell of a component column, as per [`QueryExpression::sparse_fill_strategy`].
+    ///
+    /// Returns either the latest-at value that the cell is filled with, or the value interpolated
+    /// from it. `cursor` is the column's [`InterpolationCursor`].
+    fn fill_null_cell(
+        &self,
+        store: &ChunkStore,
+        cache: &QueryCache,
+        descr: &ComponentColumnDescriptor,
+        view_index: TimelineName,
+        index_value: TimeInt,
+        cursor: &parking_lot::Mutex<Option<InterpolationCursor>>,
+    ) -> Option<Either<UnitChunkShared, ArrowListArray>> {
+        let interpolate = match self.query.sparse_fill_strategy {
+            SparseFillStrategy::None => return None,
+            SparseFillStrategy::LatestAtGlobal | SparseFillStrategy::LatestAtView => false,
+            SparseFillStrategy::InterpolateGlobal | SparseFillStrategy::InterpolateView => true,
+        };
+
+        // Values indexed outside of this are outside the view, and cannot be used for filling.
+        let view_range = if matches!(
+            self.query.sparse_fill_strategy,
+            SparseFillStrategy::LatestAtView | SparseFillStrategy::InterpolateView
+        ) {
+            self.query.max_range().map(|query| query.range)
+        } else {
+            None
+        };
+        let min_index_value = view_range.map(|range| range.min());
+
+        // NOTE: While it would be very tempting to resolve the latest-at state
+        // of the entire view contents at `filtered_index_range.start - 1` once
+        // during `QueryHandle` initialization, and then bootstrap off of that, that
+        // would effectively close the door to efficient pagination forever, since
+        // we'd have to iterate over all the pages to compute the right latest-at
+        // value at t+n (i.e. no more random access).
+        // Therefore, it is better to simply do this the "dumb" way.
+        //
+        // TODO(cmc): Still, as always, this can be made faster and smarter at
+        // the cost of some extra complexity (e.g. caching the result across
+        // consecutive nulls etc). Later.
+
+        let query = re_chunk::LatestAtQuery::new(view_index, index_value);
+
+        let results = cache.latest_at(&query, &descr.entity_path, [descr.component]);
+
+        let unit = results.components.into_values().next().filter(|unit| {
+            min_index_value.is_none_or(|min_index_value| {
+                unit.index(&view_index).is_some_and(|(index_value, _)| {
+                    index_value.is_static() || index_value >= min_index_value
+                })
+            })
+        })?;
+
+        let interpolated = interpolate
+            .then(|| {
+                Self::interpolate_at(
+                    store,
+                    cache,
+                    view_index,
+                    descr,
+                    index_value,
+                    &unit,
+                    view_range.map(|range| range.max()),
+                    cursor,
+                )
+            })
+            .flatten();
+
+        Some(interpolated.map_or(Either::Left(unit), Either::Right))
+    }
+
+    /// Interpolates the value of a component at `index_value`, given its latest-at value `prev`.
+    ///
+    /// The value to interpolate towards is the first one indexed after `index_value`, up to
+    /// `max_index_value` (inclusive) if specified. It is looked up once per pair of surrounding
+    /// values, and kept in the column's `cursor` for the following cells.
+    ///
+    /// Returns `None` if there is no such value, or if the values cannot be interpolated.
+    /// See [`SparseFillStrategy::InterpolateGlobal`].
+    #[expect(clippy::too_many_arguments)]
+    fn interpolate_at(
+        store: &ChunkStore,
+        cache: &QueryCache,
+        filtered_index: TimelineName,
+        descr: &ComponentColumnDescriptor,
+        index_value: TimeInt,
+        prev: &UnitChunkShared,
+        max_index_value: Option<TimeInt>,
+        cursor: &parking_lot::Mutex<Option<InterpolationCursor>>,
+    ) -> Option<ArrowListArray> {
+        let prev_index = prev.index(&filtered_index)?;
+        let (prev_index_value, _) = prev_index;
+        if prev_index_value.is_static() || prev_index_value >= index_value {
+            return None;
+        }
+
+        let mut cursor = cursor.lock();
+
+        let is_cursor_valid = cursor.as_ref().is_some_and(|cursor| {
+            cursor.prev_index == prev_index
+                && cursor
+                    .next
+                    .as_ref()
+                    .is_none_or(|(next_index_value, _)| index_value < *next_index_value)
+        });
+        if !is_cursor_valid {
+            *cursor = Some(InterpolationCursor {
+                prev_index,
+                next: Self::next_value(
+                    store,
+                    cache,
+                    filtered_index,
+                    descr,
+                    index_value,
+                    max_index_value,
+                ),
+            });
+        }
+
+        let (next_index_value, next) = cursor.as_ref()?.next.as_ref()?;
+
+        let t = (index_value.as_i64() - prev_index_value.as_i64()) as f64
+            / (next_index_value.as_i64() - prev_index_value.as_i64()) as f64;
+
+        crate::interpolation::interpolate(
+            descr.component_type,
+            prev.components().get_array(descr.component)?,
+            next.components().get_array(descr.component)?,
+            t,
+        )
+    }
+
+    /// Returns the first value of a component indexed after `index_value`, up to
+    /// `max_index_value` (inclusive) if specified, along with its index value.
+    fn next_value(
+        store: &ChunkStore,
+        cache: &QueryCache,
+        filtered_index: TimelineName,
+        descr: &ComponentColumnDescriptor,
+        index_value: TimeInt,
+        max_index_value: Option<TimeInt>,
+    ) -> Option<(TimeInt, UnitChunkShared)> {
+        let max_index_value = max_index_value.unwrap_or(TimeInt::MAX);
+        let query = RangeQuery::new(
+            filtered_index,
+            AbsoluteTimeRange::new(index_value.inc(), max_index_value),
+        );
+
+        let chunks = store
+            .range_relevant_chunks(&query, &descr.entity_path, descr.component)
+            .into_iter()
+            .filter_map(|chunk| {
+                let time_range = chunk.timelines().get(&filtered_index)?.time_range();
+                Some((time_range.min(), chunk))
+            })
+            .sorted_by_key(|(min_index_value, _)| *min_index_value);
+
+        let mut next_index_value: Option<TimeInt> = None;
+        for (min_index_value, chunk) in chunks {
+            // Chunks are sorted by their first index value, none of the remaining ones can do
+            // better.
+            if next_index_value.is_some_and(|next| next <= min_index_value) {
+                break;
+            }
+
+            let mut indices = chunk
+                .iter_component_indices(filtered_index, descr.component)
+                .map(|(index_value, _)| index_value)
+                .filter(|next| index_value < *next && *next <= max_index_value);
+            let chunk_next = if chunk.is_timeline_sorted(&filtered_index) {
+                indices.next()
+            } else {
+                indices.min()
+            };
+
+            if let Some(chunk_next) = chunk_next {
+                next_index_value =
+                    Some(next_index_value.map_or(chunk_next, |next| next.min(chunk_next)));
+            }
+        }
+        let next_index_value = next_index_value?;
+
+        // Resolves the winning value at that index, if there are several.
+        let query = re_chunk::LatestAtQuery::new(filtered_index, next_index_value);
+        let results = cache.latest_at(&query, &descr.entity_path, [descr.component]);
+        let next = results.components.into_values().next()?;
+
+        (next.index(&filtered_index)?.0 == next_index_value).then_some((next_index_value, next))
+    }
+
     /// Calls [`Self::next_row`] and wraps the result in a [`ArrowRecordBatch`].
     ///
     /// Only use this if you absolutely need a [`ArrowRecordBatch`] as this adds a
@@ -1326,7 +1735,8 @@ mod tests {
     use re_arrow_util::format_record_batch;
     use re_chunk::{Chunk, ChunkId, ComponentIdentifier, RowId, TimePoint};
     use re_chunk_store::{
-        AbsoluteTimeRange, ChunkStore, ChunkStoreConfig, ChunkStoreHandle, QueryExpression, TimeInt,
+        AbsoluteTimeRange, ChunkStore, ChunkStoreConfig, ChunkStoreHandle, ComparisonOperator,
+        FilterValue, QueryExpression, RowFilter, TimeInt,
     };
     use re_log_types::{
         EntityPath, Timeline, build_frame_nr, build_log_time,
@@ -1367,6 +1777,7 @@ mod tests {
     // * [x] view_contents
     // * [x] selection
     // * [x] filtered_is_not_null
+    // * [x] filtered_by (multi-column predicates)
     // * [x] sparse_fill_strategy
     // * [x] using_index_values
     //
@@ -1469,6 +1880,187 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn sparse_fill_strategy_latestatview() -> anyhow::Result<()> {
+        re_log::setup_logging();
+
+        let store = ChunkStoreHandle::new(create_nasty_store()?);
+        eprintln!("{store}");
+        let query_cache = QueryCache::new_handle(store.clone());
+        let query_engine = QueryEngine::new(store.clone(), query_cache.clone());
+
+        let filtered_index = Some(TimelineName::new("frame_nr"));
+
+        // Values from before the view are used with global semantics, but not with view semantics.
+        for sparse_fill_strategy in [
+            SparseFillStrategy::LatestAtGlobal,
+            SparseFillStrategy::LatestAtView,
+        ] {
+            let query = QueryExpression {
+                filtered_index,
+                filtered_index_range: Some(AbsoluteTimeRange::new(55, 70)),
+                sparse_fill_strategy,
+                ..Default::default()
+            };
+            eprintln!("{query:#?}:");
+
+            let query_handle = query_engine.query(query.clone());
+            assert_eq!(
+                query_engine.query(query.clone()).into_iter().count() as u64,
+                query_handle.num_rows()
+            );
+            let dataframe = concat_batches(
+                query_handle.schema(),
+                &query_handle.batch_iter().collect_vec(),
+            )?;
+            eprintln!("{}", format_record_batch(&dataframe.clone()));
+
+            assert_snapshot!(DisplayRB(dataframe));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn sparse_fill_strategy_interpolate() -> anyhow::Result<()> {
+        re_log::setup_logging();
+
+        let store = ChunkStoreHandle::new(create_interpolation_store()?);
+        eprintln!("{store}");
+        let query_cache = QueryCache::new_handle(store.clone());
+        let query_engine = QueryEngine::new(store.clone(), query_cache.clone());
+
+        let filtered_index = Some(TimelineName::new("frame_nr"));
+
+        // With view semantics, the value at frame #0 is outside the view: the value at frame #5
+        // cannot be interpolated, nor filled.
+        for sparse_fill_strategy in [
+            SparseFillStrategy::InterpolateGlobal,
+            SparseFillStrategy::InterpolateView,
+        ] {
+            let query = QueryExpression {
+                filtered_index,
+                using_index_values: Some(
+                    [5, 10, 12].into_iter().map(TimeInt::new_temporal).collect(),
+                ),
+                sparse_fill_strategy,
+                ..Default::default()
+            };
+            eprintln!("{query:#?}:");
+
+            let query_handle = query_engine.query(query.clone());
+            assert_eq!(
+                query_engine.query(query.clone()).into_iter().count() as u64,
+                query_handle.num_rows()
+            );
+            let dataframe = concat_batches(
+                query_handle.schema(),
+                &query_handle.batch_iter().collect_vec(),
+            )?;
+            eprintln!("{}", format_record_batch(&dataframe.clone()));
+
+            assert_snapshot!(DisplayRB(dataframe));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn filtered_index_fallbacks() -> anyhow::Result<()> {
+        re_log::setup_logging();
+
+        let store = ChunkStoreHandle::new(create_fallback_store()?);
+        eprintln!("{store}");
+        let query_cache = QueryCache::new_handle(store.clone());
+        let query_engine = QueryEngine::new(store.clone(), query_cache.clone());
+
+        let filtered_index = Some(TimelineName::new("sensor_time"));
+
+        // Without fallbacks, `/logged` has no data on the filtered index; with them, it is indexed
+        // by its `log_time` instead.
+        for filtered_index_fallbacks in [vec![], vec![TimelineName::new("log_time")]] {
+            let query = QueryExpression {
+                filtered_index,
+                filtered_index_fallbacks,
+                ..Default::default()
+            };
+            eprintln!("{query:#?}:");
+
+            let query_handle = query_engine.query(query.clone());
+            assert_eq!(
+                query_engine.query(query.clone()).into_iter().count() as u64,
+                query_handle.num_rows()
+            );
+            let dataframe = concat_batches(
+                query_handle.schema(),
+                &query_handle.batch_iter().collect_vec(),
+            )?;
+            eprintln!("{}", format_record_batch(&dataframe.clone()));
+
+            assert_snapshot!(DisplayRB(dataframe));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn filtered_index_fallbacks_type_mismatch() -> anyhow::Result<()> {
+        re_log::setup_logging();
+
+        let mut store = create_fallback_store()?;
+        {
+            // Same values as `/logged`, but on a timestamp index.
+            let wall_time = Timeline::new_timestamp("wall_time");
+            let mut stamped = Chunk::builder("/stamped");
+            for t in 2..=4 {
+                stamped = stamped.with_archetype(
+                    RowId::new(),
+                    [(wall_time, t)],
+                    &re_types::archetypes::Scalars::new([t as f64 * 100.0]),
+                );
+            }
+            store.insert_chunk(&Arc::new(stamped.build()?))?;
+        }
+
+        let store = ChunkStoreHandle::new(store);
+        eprintln!("{store}");
+        let query_cache = QueryCache::new_handle(store.clone());
+        let query_engine = QueryEngine::new(store.clone(), query_cache.clone());
+
+        let run = |filtered_index: &str, filtered_index_fallbacks: &[&str]| {
+            let query = QueryExpression {
+                filtered_index: Some(TimelineName::new(filtered_index)),
+                filtered_index_fallbacks: filtered_index_fallbacks
+                    .iter()
+                    .map(|index| TimelineName::new(index))
+                    .collect(),
+                ..Default::default()
+            };
+            eprintln!("{query:#?}:");
+
+            let query_handle = query_engine.query(query);
+            concat_batches(
+                query_handle.schema(),
+                &query_handle.batch_iter().collect_vec(),
+            )
+        };
+
+        // `wall_time` isn't a sequence like `sensor_time`: it must be ignored.
+        assert_eq!(
+            run("sensor_time", &[])?,
+            run("sensor_time", &["wall_time"])?
+        );
+
+        // The type of `missing_time` is unknown: all fallbacks must be ignored, even though they
+        // share a type with one another.
+        let dataframe = run("missing_time", &["log_time"])?;
+        eprintln!("{}", format_record_batch(&dataframe.clone()));
+        assert_eq!(run("missing_time", &[])?, dataframe);
+        assert_eq!(0, dataframe.num_rows());
+
+        Ok(())
+    }
+
     #[test]
     fn filtered_index_range() -> anyhow::Result<()> {
         re_log::setup_logging();
@@ -1739,6 +2331,155 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn filtered_by() -> anyhow::Result<()> {
+        re_log::setup_logging();
+
+        let store = ChunkStoreHandle::new(create_nasty_store()?);
+        eprintln!("{store}");
+        let query_cache = QueryCache::new_handle(store.clone());
+        let query_engine = QueryEngine::new(store.clone(), query_cache.clone());
+
+        let filtered_index = Some(TimelineName::new("frame_nr"));
+        let entity_path: EntityPath = "this/that".into();
+
+        let column = |descr: ComponentDescriptor| ComponentColumnSelector {
+            entity_path: entity_path.clone(),
+            component: descr.component.to_string(),
+        };
+        let points = column(MyPoints::descriptor_points());
+        let colors = column(MyPoints::descriptor_colors());
+        let labels = column(MyPoints::descriptor_labels());
+
+        let filters = [
+            // multi-column is-not-null
+            RowFilter::And(vec![
+                RowFilter::IsNotNull(points.clone()),
+                RowFilter::IsNotNull(colors.clone()),
+            ]),
+            // primitive comparison, or'ed with a static comparison that never holds
+            RowFilter::Or(vec![
+                RowFilter::Compare {
+                    column: colors.clone(),
+                    field: None,
+                    op: ComparisonOperator::Gt,
+                    value: FilterValue::Int(2),
+                },
+                RowFilter::Compare {
+                    column: labels.clone(),
+                    field: None,
+                    op: ComparisonOperator::Eq,
+                    value: FilterValue::String("a".to_owned()),
+                },
+            ]),
+            // struct field comparison, and'ed with a static comparison that always holds
+            RowFilter::And(vec![
+                RowFilter::Compare {
+                    column: points.clone(),
+                    field: Some("x".to_owned()),
+                    op: ComparisonOperator::GtEq,
+                    value: FilterValue::Float(5.0),
+                },
+                RowFilter::Compare {
+                    column: labels.clone(),
+                    field: None,
+                    op: ComparisonOperator::Eq,
+                    value: FilterValue::String("c".to_owned()),
+                },
+            ]),
+        ];
+
+        for filter in filters {
+            let query = QueryExpression {
+                filtered_index,
+                filtered_by: Some(filter),
+                ..Default::default()
+            };
+            eprintln!("{query:#?}:");
+
+            let query_handle = query_engine.query(query.clone());
+            assert_eq!(
+                query_engine.query(query.clone()).into_iter().count() as u64,
+                query_handle.num_rows()
+            );
+            let dataframe = concat_batches(
+                query_handle.schema(),
+                &query_handle.batch_iter().collect_vec(),
+            )?;
+            eprintln!("{}", format_record_batch(&dataframe.clone()));
+
+            assert_snapshot!(DisplayRB(dataframe));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn filtered_by_sparse_fill() -> anyhow::Result<()> {
+        re_log::setup_logging();
+
+        let store = ChunkStoreHandle::new(create_nasty_store()?);
+        eprintln!("{store}");
+        let query_cache = QueryCache::new_handle(store.clone());
+        let query_engine = QueryEngine::new(store.clone(), query_cache.clone());
+
+        let filtered_index = TimelineName::new("frame_nr");
+        let colors = ComponentColumnSelector {
+            entity_path: "this/that".into(),
+            component: MyPoints::descriptor_colors().component.to_string(),
+        };
+
+        let frame_nrs = |query: QueryExpression| -> anyhow::Result<Vec<i64>> {
+            let query_handle = query_engine.query(query);
+            let dataframe = concat_batches(
+                query_handle.schema(),
+                &query_handle.batch_iter().collect_vec(),
+            )?;
+
+            let frame_nr = dataframe
+                .column_by_name("frame_nr")
+                .and_then(|column| column.downcast_array_ref::<arrow::array::Int64Array>())
+                .ok_or_else(|| anyhow::anyhow!("missing frame_nr column"))?;
+
+            Ok(frame_nr.values().to_vec())
+        };
+
+        // Colors are only logged at frames #30, #40, #50 and #70: the predicate must be evaluated
+        // on the rows as they are returned, i.e. after filling.
+        let query = QueryExpression {
+            filtered_index: Some(filtered_index),
+            filtered_by: Some(RowFilter::IsNotNull(colors.clone())),
+            ..Default::default()
+        };
+        assert_eq!(frame_nrs(query.clone())?, vec![30, 40, 50, 70]);
+
+        let query = QueryExpression {
+            sparse_fill_strategy: SparseFillStrategy::LatestAtGlobal,
+            ..query
+        };
+        assert_eq!(frame_nrs(query.clone())?, vec![30, 40, 50, 60, 70]);
+
+        // Sampled rows are filtered too.
+        let query = QueryExpression {
+            using_index_values: Some(
+                [15, 35, 65]
+                    .into_iter()
+                    .map(TimeInt::new_temporal)
+                    .collect(),
+            ),
+            ..query
+        };
+        assert_eq!(frame_nrs(query.clone())?, vec![35, 65]);
+
+        let query = QueryExpression {
+            sparse_fill_strategy: SparseFillStrategy::None,
+            ..query
+        };
+        assert_eq!(frame_nrs(query)?, Vec::<i64>::new());
+
+        Ok(())
+    }
+
     #[test]
     fn view_contents() -> anyhow::Result<()> {
         re_log::setup_logging();
@@ -2327,10 +3068,12 @@ mod tests {
             include_tombstone_columns: false,
             include_static_columns: re_chunk_store::StaticColumnSelection::Both,
             filtered_index: None,
+            filtered_index_fallbacks: Vec::new(),
             filtered_index_range: None,
             filtered_index_values: None,
             using_index_values: None,
             filtered_is_not_null: None,
+            filtered_by: None,
             sparse_fill_strategy: re_chunk_store::SparseFillStrategy::None,
             selection: None,
         };
@@ -2697,6 +3440,90 @@ mod tests {
         Ok(store)
     }
 
+    /// Scalars, transforms and texts (which cannot be interpolated) at frames #0 and #10.
+    fn create_interpolation_store() -> anyhow::Result<ChunkStore> {
+        use re_types::archetypes::{Scalars, TextDocument, Transform3D};
+        use re_types::datatypes::Quaternion;
+
+        let mut store = ChunkStore::new(
+            re_log_types::StoreId::random(re_log_types::StoreKind::Recording, "test_app"),
+            ChunkStoreConfig::COMPACTION_DISABLED,
+        );
+
+        let timepoint = |frame_nr: i64| [build_frame_nr(TimeInt::new_temporal(frame_nr))];
+
+        let scalars = Chunk::builder("/scalars")
+            .with_archetype(RowId::new(), timepoint(0), &Scalars::new([0.0, 10.0]))
+            .with_archetype(RowId::new(), timepoint(10), &Scalars::new([10.0, 0.0]))
+            .build()?;
+
+        // Identity, then a half turn around Z.
+        let transforms = Chunk::builder("/transforms")
+            .with_archetype(
+                RowId::new(),
+                timepoint(0),
+                &Transform3D::default()
+                    .with_translation([0.0, 0.0, 0.0])
+                    .with_quaternion(Quaternion::from_xyzw([0.0, 0.0, 0.0, 1.0])),
+            )
+            .with_archetype(
+                RowId::new(),
+                timepoint(10),
+                &Transform3D::default()
+                    .with_translation([10.0, 20.0, 30.0])
+                    .with_quaternion(Quaternion::from_xyzw([0.0, 0.0, 1.0, 0.0])),
+            )
+            .build()?;
+
+        let texts = Chunk::builder("/texts")
+            .with_archetype(RowId::new(), timepoint(0), &TextDocument::new("zero"))
+            .with_archetype(RowId::new(), timepoint(10), &TextDocument::new("ten"))
+            .build()?;
+
+        for chunk in [scalars, transforms, texts] {
+            store.insert_chunk(&Arc::new(chunk))?;
+        }
+
+        Ok(store)
+    }
+
+    /// `/sensor` is logged on both `sensor_time` and `log_time`, `/logged` only on `log_time`.
+    fn create_fallback_store() -> anyhow::Result<ChunkStore> {
+        use re_types::archetypes::Scalars;
+
+        let mut store = ChunkStore::new(
+            re_log_types::StoreId::random(re_log_types::StoreKind::Recording, "test_app"),
+            ChunkStoreConfig::COMPACTION_DISABLED,
+        );
+
+        let sensor_time = Timeline::new_sequence("sensor_time");
+        let log_time = Timeline::new_sequence("log_time");
+
+        let mut sensor = Chunk::builder("/sensor");
+        for t in 1..=3 {
+            sensor = sensor.with_archetype(
+                RowId::new(),
+                [(sensor_time, t), (log_time, t + 10)],
+                &Scalars::new([t as f64]),
+            );
+        }
+
+        let mut logged = Chunk::builder("/logged");
+        for t in 2..=4 {
+            logged = logged.with_archetype(
+                RowId::new(),
+                [(log_time, t)],
+                &Scalars::new([t as f64 * 10.0]),
+            );
+        }
+
+        for chunk in [sensor.build()?, logged.build()?] {
+            store.insert_chunk(&Arc::new(chunk))?;
+        }
+
+        Ok(store)
+    }
+
     fn extend_nasty_store_with_clears(store: &mut ChunkStore) -> anyhow::Result<()> {
         let entity_path = EntityPath::from("/this/that");
         let entity_path_parent = EntityPath::from("/this");
@@ -20,8 +20,8 @@ use datafusion::physical_plan::ExecutionPlan;
 use datafusion::physical_plan::coalesce_batches::CoalesceBatchesExec;
 
 use re_dataframe::external::re_chunk_store::ChunkStore;
-use re_dataframe::{Index, QueryExpression};
-use re_log_types::EntryId;
+use re_dataframe::{Index, QueryExpression, SparseFillStrategy};
+use re_log_types::{AbsoluteTimeRange, EntryId, TimeInt};
 use re_protos::cloud::v1alpha1::FetchChunksRequest;
 use re_protos::{
     cloud::v1alpha1::{
@@ -106,7 +106,7 @@ impl DataframeQueryTableProvider {
             .map(|ident| ident.to_string())
             .collect();
 
-        let query = query_from_query_expression(query_expression);
+        let query = query_from_query_expression(query_expression)?;
 
         let dataset_query = QueryDatasetRequest {
             partition_ids: partition_ids
@@ -486,23 +486,48 @@ pub(crate) fn time_array_ref_to_i64(time_array: &ArrayRef) -> Result<Int64Array,
     })
 }
 
-pub fn query_from_query_expression(query_expression: &QueryExpression) -> Query {
+/// Translates a [`QueryExpression`] into the [`Query`] used to select the relevant chunks on the server.
+///
+/// Fails if the filters of the expression cannot be forwarded as is.
+pub fn query_from_query_expression(
+    query_expression: &QueryExpression,
+) -> Result<Query, DataFusionError> {
+    let min_latest_at = query_expression.min_latest_at();
+    let max_range = query_expression.max_range();
+
+    // Entities may be indexed by any index of the fallback chain, but the server can only select
+    // chunks based on a single one of them.
+    if !query_expression.filtered_index_fallbacks.is_empty()
+        && (min_latest_at.is_some() || max_range.is_some())
+    {
+        return Err(exec_datafusion_err!(
+            "Index fallbacks cannot be combined with index value filters on remote datasets"
+        ));
+    }
+
     let latest_at = if query_expression.is_static() {
         Some(QueryLatestAt::new_static())
     } else {
-        query_expression
-            .min_latest_at()
-            .map(|latest_at| QueryLatestAt {
-                index: Some(latest_at.timeline().to_string()),
-                at: latest_at.at(),
-            })
+        min_latest_at.map(|latest_at| QueryLatestAt {
+            index: Some(latest_at.timeline().to_string()),
+            at: latest_at.at(),
+        })
     };
 
-    Query {
+    Ok(Query {
         latest_at,
-        range: query_expression.max_range().map(|range| QueryRange {
+        range: max_range.map(|range| QueryRange {
             index: range.timeline().to_string(),
-            index_range: range.range,
+            index_range: if query_expression.sparse_fill_strategy
+                == SparseFillStrategy::InterpolateGlobal
+            {
+                // Interpolating the last rows requires the values that come after the view.
+                // Only the chunks up to the next value of each column actually get fetched,
+                // see `InterpolationBound`.
+                AbsoluteTimeRange::new(range.range.min(), TimeInt::MAX)
+            } else {
+                range.range
+            },
         }),
         columns_always_include_everything: false,
         columns_always_include_chunk_ids: false,
@@ -511,7 +536,7 @@ pub fn query_from_query_expression(query_expression: &QueryExpression) -> Query
         columns_always_include_static_indexes: false,
         columns_always_include_global_indexes: false,
         columns_always_include_component_indexes: false,
-    }
+    })
 }
 
 #[cfg(test)]
@@ -639,4 +664,47 @@ mod tests {
         assert_eq!(chunk_ids_d.len(), 1);
         assert_eq!(chunk_ids_d.value(0), [6u8; 32]);
     }
+
+    #[test]
+    fn test_query_from_query_expression() {
+        let filtered_index_range = Some(AbsoluteTimeRange::new(
+            TimeInt::new_temporal(10),
+            TimeInt::new_temporal(20),
+        ));
+
+        // View-restricted sparse-filling still forwards both filters.
+        let query = query_from_query_expression(&QueryExpression {
+            filtered_index: Some(Index::new("frame")),
+            filtered_index_range,
+            sparse_fill_strategy: SparseFillStrategy::LatestAtView,
+            ..Default::default()
+        })
+        .unwrap();
+        let latest_at = query.latest_at.unwrap();
+        assert_eq!(Some("frame".to_owned()), latest_at.index);
+        assert_eq!(TimeInt::new_temporal(10), latest_at.at);
+        let range = query.range.unwrap();
+        assert_eq!("frame", range.index);
+        assert_eq!(filtered_index_range, Some(range.index_range));
+
+        // Index fallbacks cannot be pruned on a single index.
+        let query = query_from_query_expression(&QueryExpression {
+            filtered_index: Some(Index::new("sensor_time")),
+            filtered_index_fallbacks: vec![Index::new("log_time")],
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(query.latest_at.is_none());
+        assert!(query.range.is_none());
+
+        assert!(
+            query_from_query_expression(&QueryExpression {
+                filtered_index: Some(Index::new("sensor_time")),
+                filtered_index_fallbacks: vec![Index::new("log_time")],
+                filtered_index_range,
+                ..Default::default()
+            })
+            .is_err()
+        );
+    }
 }
@@ -5,7 +5,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
-use arrow::array::{Array, RecordBatch, RecordBatchOptions, StringArray};
+use arrow::array::{Array, Int64Array, RecordBatch, RecordBatchOptions, StringArray, UInt32Array};
 use arrow::compute::SortOptions;
 use arrow::datatypes::{Schema, SchemaRef};
 use datafusion::common::hash_utils::HashValue as _;
@@ -21,18 +21,21 @@ use datafusion::physical_plan::{DisplayAs, DisplayFormatType, ExecutionPlan, Pla
 use datafusion::{error::DataFusionError, execution::SendableRecordBatchStream};
 use futures_util::{Stream, StreamExt as _};
 use tokio::runtime::Handle;
-use tokio::sync::Notify;
 use tokio::sync::mpsc::{Receiver, Sender};
+use tokio::sync::{Notify, watch};
 use tokio::task::JoinHandle;
 use tracing::Instrument as _;
 
 use re_dataframe::external::re_chunk::Chunk;
-use re_dataframe::external::re_chunk_store::ChunkStore;
+use re_dataframe::external::re_chunk_store::{ChunkStore, RangeQuery};
 use re_dataframe::{
-    ChunkStoreHandle, Index, QueryCache, QueryEngine, QueryExpression, QueryHandle, StorageEngine,
+    AbsoluteTimeRange, ChunkStoreHandle, Index, QueryCache, QueryEngine, QueryExpression,
+    QueryHandle, SparseFillStrategy, StorageEngine, TimeInt, TimelineName,
 };
 use re_log_types::{ApplicationId, StoreId, StoreKind};
-use re_protos::cloud::v1alpha1::{FetchChunksRequest, ScanPartitionTableResponse};
+use re_protos::cloud::v1alpha1::{
+    FetchChunksRequest, QueryDatasetResponse, ScanPartitionTableResponse,
+};
 use re_redap_client::ConnectionClient;
 use re_sorbet::{ColumnDescriptor, ColumnSelector};
 
@@ -65,12 +68,145 @@ pub(crate) struct PartitionStreamExec {
 
 type ChunksWithPartition = Vec<(Chunk, Option<String>)>;
 
+/// The partition being queried, and the time by which every column of its view is known to have
+/// a value past the end of the view.
+type InterpolationHorizon = Option<(String, TimeInt)>;
+
+/// Interpolating the last rows of a view with [`SparseFillStrategy::InterpolateGlobal`] requires
+/// the first values that come after the view.
+///
+/// The chunks which only hold data past the end of the view are fetched last, in time order, and
+/// only until every column of the view has a value past its end.
+#[derive(Clone, Debug)]
+struct InterpolationBound {
+    index: TimelineName,
+    view_max: TimeInt,
+}
+
+impl InterpolationBound {
+    fn from_query_expression(query_expression: &QueryExpression) -> Option<Self> {
+        if query_expression.sparse_fill_strategy != SparseFillStrategy::InterpolateGlobal
+            || !query_expression.filtered_index_fallbacks.is_empty()
+        {
+            return None;
+        }
+
+        let range = query_expression.max_range()?;
+        let view_max = range.range.max();
+
+        (view_max < TimeInt::MAX).then(|| Self {
+            index: *range.timeline(),
+            view_max,
+        })
+    }
+
+    /// Whether the chunk only holds data past the end of the view.
+    fn is_past_view(&self, chunk: &Chunk) -> bool {
+        chunk
+            .timelines()
+            .get(&self.index)
+            .is_some_and(|time_column| time_column.time_range().min() > self.view_max)
+    }
+
+    /// Splits the chunk infos of a partition between the chunks needed by the view itself, and
+    /// those which only hold data past its end, sorted by start time.
+    ///
+    /// If the chunk infos don't say where the chunks start, they are all needed by the view.
+    fn split_chunk_infos(
+        &self,
+        chunk_info: RecordBatch,
+    ) -> Result<(RecordBatch, Vec<(TimeInt, RecordBatch)>), DataFusionError> {
+        let Some(starts) = chunk_info.column_by_name(
+            &QueryDatasetResponse::field_name_index_start(self.index.as_str()),
+        ) else {
+            return Ok((chunk_info, vec![]));
+        };
+        let starts = starts
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .ok_or_else(|| exec_datafusion_err!("Unexpected type for index start column"))?;
+
+        let mut view_rows = Vec::new();
+        let mut past_view_rows = Vec::new();
+        for (row, start) in starts.iter().enumerate() {
+            match start.map(TimeInt::new_temporal) {
+                Some(start) if start > self.view_max => past_view_rows.push((start, row)),
+                _ => view_rows.push(row as u32),
+            }
+        }
+        past_view_rows.sort_by_key(|(start, _)| *start);
+
+        let view_chunk_info =
+            arrow::compute::take_record_batch(&chunk_info, &UInt32Array::from(view_rows))?;
+        let past_view_chunk_infos = past_view_rows
+            .into_iter()
+            .map(|(start, row)| (start, chunk_info.slice(row, 1)))
+            .collect();
+
+        Ok((view_chunk_info, past_view_chunk_infos))
+    }
+
+    /// The time by which every column of the view has a value past the end of the view, if they
+    /// all have one in the store.
+    fn horizon(&self, store: &ChunkStore, query_expression: &QueryExpression) -> Option<TimeInt> {
+        let query = RangeQuery::new(
+            self.index,
+            AbsoluteTimeRange::new(self.view_max.inc(), TimeInt::MAX),
+        );
+
+        let mut horizon = self.view_max;
+        for entity_path in store.all_entities() {
+            let view_components = match &query_expression.view_contents {
+                Some(view_contents) => match view_contents.get(&entity_path) {
+                    Some(components) => components.as_ref(),
+                    None => continue,
+                },
+                None => None,
+            };
+
+            let Some(components) = store.all_components_on_timeline(&self.index, &entity_path)
+            else {
+                continue;
+            };
+
+            for component in components {
+                if view_components
+                    .is_some_and(|view_components| !view_components.contains(&component))
+                    || store.entity_has_static_component(&entity_path, component)
+                {
+                    continue;
+                }
+
+                let next_time = store
+                    .range_relevant_chunks(&query, &entity_path, component)
+                    .iter()
+                    .filter_map(|chunk| {
+                        chunk
+                            .iter_component_indices(self.index, component)
+                            .map(|(time, _)| time)
+                            .filter(|time| *time > self.view_max)
+                            .min()
+                    })
+                    .min()?;
+
+                horizon = horizon.max(next_time);
+            }
+        }
+
+        Some(horizon)
+    }
+}
+
 pub struct DataframePartitionStreamInner {
     projected_schema: SchemaRef,
     client: ConnectionClient,
     chunk_infos: Vec<RecordBatch>,
 
     chunk_tx: Option<Sender<Result<ChunksWithPartition, re_redap_client::ApiError>>>,
+
+    /// Handed over to the io worker, see [`InterpolationBound`].
+    interpolation: Option<(InterpolationBound, watch::Receiver<InterpolationHorizon>)>,
+
     store_output_channel: Receiver<RecordBatch>,
     io_join_handle: Option<JoinHandle<Result<(), DataFusionError>>>,
 
@@ -136,6 +272,7 @@ impl Stream for DataframePartitionStream {
                 this.client.clone(),
                 this.chunk_infos.clone(),
                 chunk_tx,
+                this.interpolation.take(),
             )));
         }
 
@@ -332,6 +469,7 @@ async fn chunk_store_cpu_worker_thread(
     output_channel: Sender<RecordBatch>,
     query_expression: QueryExpression,
     projected_schema: Arc<Schema>,
+    interpolation: Option<(InterpolationBound, watch::Sender<InterpolationHorizon>)>,
 ) -> Result<(), DataFusionError> {
     let mut current_stores: Option<(String, ChunkStoreHandle, QueryHandle<StorageEngine>)> = None;
     while let Some(chunks_and_partition_ids) = input_channel.recv().await {
@@ -375,10 +513,20 @@ async fn chunk_store_cpu_worker_thread(
 
             let (_, store, _) = current_stores;
 
+            let chunk = Arc::new(chunk);
             store
                 .write()
-                .insert_chunk(&Arc::new(chunk))
+                .insert_chunk(&chunk)
                 .map_err(|err| exec_datafusion_err!("{err}"))?;
+
+            // The chunks past the end of the view are always sent last: all the chunks of the view
+            // itself are already in the store.
+            if let Some((bound, horizon_tx)) = &interpolation
+                && bound.is_past_view(&chunk)
+                && let Some(horizon) = bound.horizon(&store.read(), &query_expression)
+            {
+                horizon_tx.send_replace(Some((partition_id.clone(), horizon)));
+            }
         }
     }
 
@@ -407,14 +555,15 @@ async fn chunk_store_cpu_worker_thread(
 /// a *single partition*. We also expect these to be previously sorted by partition id, otherwise
 /// our suggestion to the query planner that inputs are sorted by partition id will be incorrect.
 /// See `group_chunk_infos_by_partition_id` and `execute` for more details.
+/// When interpolating, the chunks past the end of the view are only fetched until the cpu worker
+/// thread reports that they can no longer change the result, see [`InterpolationBound`].
 #[tracing::instrument(level = "trace", skip_all)]
 async fn chunk_stream_io_loop(
     mut client: ConnectionClient,
     chunk_infos: Vec<RecordBatch>,
     output_channel: Sender<Result<ChunksWithPartition, re_redap_client::ApiError>>,
+    interpolation: Option<(InterpolationBound, watch::Receiver<InterpolationHorizon>)>,
 ) -> Result<(), DataFusionError> {
-    let chunk_infos: Vec<_> = chunk_infos.into_iter().map(Into::into).collect();
-
     // TODO(zehiko) same as previously with get_chunks, we keep sending 1 request per partition.
     // As these batches are sorted per partition (see docs above), this ensures that ordering by
     // partition id is preserved regardless of how server might order responses (in the case of having
@@ -422,28 +571,68 @@ async fn chunk_stream_io_loop(
     // is at least 2x slower than sending all partitions in one request. Consider providing ordering
     // guarantees server side in the future.
     for chunk_info in chunk_infos {
-        let fetch_chunks_request = FetchChunksRequest {
-            chunk_infos: vec![chunk_info],
+        let Some((bound, horizon_rx)) = &interpolation else {
+            fetch_chunks(&mut client, chunk_info, &output_channel).await?;
+            continue;
         };
 
-        let fetch_chunks_response_stream = client
-            .inner()
-            .fetch_chunks(fetch_chunks_request)
-            .instrument(tracing::trace_span!("chunk_stream_io_loop"))
-            .await
-            .map_err(|err| exec_datafusion_err!("{err}"))?
-            .into_inner();
-
-        // Then we need to fully decode these chunks, i.e. both the transport layer (Protobuf)
-        // and the app layer (Arrow).
-        let mut chunk_stream = re_redap_client::fetch_chunks_response_to_chunk_and_partition_id(
-            fetch_chunks_response_stream,
-        );
+        let partition_id = chunk_info
+            .column_by_name(QueryDatasetResponse::FIELD_CHUNK_PARTITION_ID)
+            .and_then(|column| column.as_any().downcast_ref::<StringArray>())
+            .filter(|column| !column.is_empty())
+            .map(|column| column.value(0).to_owned());
+
+        let (view_chunk_info, past_view_chunk_infos) = bound.split_chunk_infos(chunk_info)?;
+        if view_chunk_info.num_rows() > 0 {
+            fetch_chunks(&mut client, view_chunk_info, &output_channel).await?;
+        }
 
-        while let Some(chunk_and_partition_id) = chunk_stream.next().await {
-            if output_channel.send(chunk_and_partition_id).await.is_err() {
+        for (start, chunk_info) in past_view_chunk_infos {
+            let is_past_horizon =
+                horizon_rx
+                    .borrow()
+                    .as_ref()
+                    .is_some_and(|(horizon_partition_id, horizon)| {
+                        Some(horizon_partition_id) == partition_id.as_ref() && start > *horizon
+                    });
+            if is_past_horizon {
                 break;
             }
+
+            fetch_chunks(&mut client, chunk_info, &output_channel).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetches the chunks described by `chunk_info`, and forwards them to the cpu worker thread.
+async fn fetch_chunks(
+    client: &mut ConnectionClient,
+    chunk_info: RecordBatch,
+    output_channel: &Sender<Result<ChunksWithPartition, re_redap_client::ApiError>>,
+) -> Result<(), DataFusionError> {
+    let fetch_chunks_request = FetchChunksRequest {
+        chunk_infos: vec![chunk_info.into()],
+    };
+
+    let fetch_chunks_response_stream = client
+        .inner()
+        .fetch_chunks(fetch_chunks_request)
+        .instrument(tracing::trace_span!("chunk_stream_io_loop"))
+        .await
+        .map_err(|err| exec_datafusion_err!("{err}"))?
+        .into_inner();
+
+    // Then we need to fully decode these chunks, i.e. both the transport layer (Protobuf)
+    // and the app layer (Arrow).
+    let mut chunk_stream = re_redap_client::fetch_chunks_response_to_chunk_and_partition_id(
+        fetch_chunks_response_stream,
+    );
+
+    while let Some(chunk_and_partition_id) = chunk_stream.next().await {
+        if output_channel.send(chunk_and_partition_id).await.is_err() {
+            break;
         }
     }
 
@@ -515,8 +704,24 @@ impl ExecutionPlan for PartitionStreamExec {
         let (batches_tx, batches_rx) = tokio::sync::mpsc::channel(CPU_THREAD_IO_CHANNEL_SIZE);
         let query_expression = self.query_expression.clone();
         let projected_schema = self.projected_schema.clone();
+
+        let (worker_interpolation, io_interpolation) =
+            match InterpolationBound::from_query_expression(&query_expression) {
+                Some(bound) => {
+                    let (horizon_tx, horizon_rx) = watch::channel(None);
+                    (Some((bound.clone(), horizon_tx)), Some((bound, horizon_rx)))
+                }
+                None => (None, None),
+            };
+
         let cpu_join_handle = Some(self.worker_runtime.handle().spawn(
-            chunk_store_cpu_worker_thread(chunk_rx, batches_tx, query_expression, projected_schema),
+            chunk_store_cpu_worker_thread(
+                chunk_rx,
+                batches_tx,
+                query_expression,
+                projected_schema,
+                worker_interpolation,
+            ),
         ));
 
         let stream = DataframePartitionStreamInner {
@@ -525,6 +730,7 @@ impl ExecutionPlan for PartitionStreamExec {
             client,
             chunk_infos,
             chunk_tx: Some(chunk_tx),
+            interpolation: io_interpolation,
             io_join_handle: None,
             cpu_join_handle,
             cpu_runtime: Arc::clone(&self.worker_runtime),
@@ -638,3 +844,56 @@ impl CpuRuntime {
         &self.handle
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_chunk_infos_past_view() {
+        let start_column = QueryDatasetResponse::field_name_index_start("frame");
+        let chunk_info = RecordBatch::try_from_iter([
+            (
+                "chunk_partition_id",
+                Arc::new(StringArray::from(vec!["A"; 5])) as Arc<dyn Array>,
+            ),
+            (
+                start_column.as_str(),
+                Arc::new(Int64Array::from(vec![
+                    Some(30),
+                    Some(0),
+                    None,
+                    Some(20),
+                    Some(10),
+                ])),
+            ),
+        ])
+        .unwrap();
+
+        let bound = InterpolationBound {
+            index: "frame".into(),
+            view_max: TimeInt::new_temporal(10),
+        };
+
+        let (view_chunk_info, past_view_chunk_infos) = bound.split_chunk_infos(chunk_info).unwrap();
+
+        let view_starts = view_chunk_info
+            .column_by_name(&start_column)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(view_starts, vec![Some(0), None, Some(10)]);
+
+        let past_view_starts = past_view_chunk_infos
+            .iter()
+            .map(|(start, chunk_info)| {
+                assert_eq!(chunk_info.num_rows(), 1);
+                start.as_i64()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(past_view_starts, vec![20, 30]);
+    }
+}
@@ -806,7 +806,6 @@ impl EntityDb {
                             || time_range.contains(time_column.time_range().max())
                     })
                 })
-                .cloned() // refcount
                 .collect();
 
             // Try to roughly preserve the order of the chunks
@@ -872,7 +871,7 @@ impl EntityDb {
 
         let engine = self.storage_engine.read();
         for chunk in engine.store().iter_chunks() {
-            new_db.add_chunk(&Arc::clone(chunk))?;
+            new_db.add_chunk(&chunk)?;
         }
 
         Ok(new_db)
@@ -73,6 +73,9 @@ mod transport_to_app;
 
 pub mod external {
     pub use lz4_flex;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use zstd;
 }
 
 pub use self::rrd::*;
@@ -314,6 +314,10 @@ mod tests {
                 compression: Compression::LZ4,
                 serializer: Serializer::Protobuf,
             },
+            EncodingOptions {
+                compression: Compression::Zstd,
+                serializer: Serializer::Protobuf,
+            },
         ];
 
         // Low-level
@@ -453,6 +457,10 @@ mod tests {
                 compression: Compression::LZ4,
                 serializer: Serializer::Protobuf,
             },
+            EncodingOptions {
+                compression: Compression::Zstd,
+                serializer: Serializer::Protobuf,
+            },
         ];
 
         // make out-of-order messages
@@ -487,6 +495,10 @@ mod tests {
                 compression: Compression::LZ4,
                 serializer: Serializer::Protobuf,
             },
+            EncodingOptions {
+                compression: Compression::Zstd,
+                serializer: Serializer::Protobuf,
+            },
         ];
 
         for options in options {
@@ -3,10 +3,12 @@
 pub mod state_machine;
 
 mod iterator;
+mod seekable;
 mod stream;
 
 pub use self::{
     iterator::DecoderIterator,
+    seekable::DecoderSeekable,
     state_machine::{Decoder, DecoderApp, DecoderTransport},
     stream::DecoderStream,
 };
@@ -0,0 +1,464 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use re_build_info::CrateVersion;
+use re_chunk::TimelineName;
+use re_log_types::AbsoluteTimeRange;
+
+use crate::CachingApplicationIdInjector;
+use crate::rrd::{
+    CodecError, Decodable as _, DecodeError, Decoder, DecoderEntrypoint, MessageHeader,
+    RrdManifest, RrdManifestEntry, StreamFooter, StreamHeader,
+};
+
+// ---
+
+impl<T: DecoderEntrypoint> Decoder<T> {
+    /// Instantiates a new random-access decoder on top of the given seekable reader.
+    ///
+    /// This reads the [`StreamFooter`] at the very end of the reader, as well as the [`RrdManifest`]
+    /// that it points to, and nothing else.
+    /// Returns `None` if the stream doesn't have a footer, in which case it has to be decoded
+    /// sequentially using e.g. [`Self::decode_lazy`].
+    ///
+    /// If the reader contains several concatenated streams, only the last one is made accessible.
+    pub fn decode_seekable<R: Read + Seek>(
+        reader: R,
+    ) -> Result<Option<DecoderSeekable<T, R>>, DecodeError> {
+        DecoderSeekable::new(reader)
+    }
+}
+
+// ---
+
+/// Decodes arbitrary messages out of an RRD stream, using the [`RrdManifest`] in its [`StreamFooter`].
+///
+/// See [`Decoder::decode_seekable`].
+pub struct DecoderSeekable<T, R: Read + Seek> {
+    reader: R,
+
+    /// Where the stream starts in the reader, i.e. what all manifest offsets are relative to.
+    stream_start: u64,
+
+    /// The Rerun version used to encode the RRD data.
+    version: CrateVersion,
+
+    manifest: RrdManifest,
+
+    /// The application id cache used for migrating old data.
+    app_id_cache: CachingApplicationIdInjector,
+
+    _decodable: std::marker::PhantomData<T>,
+}
+
+impl<T: DecoderEntrypoint, R: Read + Seek> DecoderSeekable<T, R> {
+    fn new(mut reader: R) -> Result<Option<Self>, DecodeError> {
+        re_tracing::profile_function!();
+
+        let reader_len = reader.seek(SeekFrom::End(0))?;
+        let min_len = (StreamHeader::ENCODED_SIZE_BYTES
+            + MessageHeader::ENCODED_SIZE_BYTES
+            + StreamFooter::ENCODED_SIZE_BYTES) as u64;
+        if reader_len < min_len {
+            return Ok(None);
+        }
+
+        let mut footer = [0u8; StreamFooter::ENCODED_SIZE_BYTES];
+        reader.seek(SeekFrom::Start(reader_len - footer.len() as u64))?;
+        reader.read_exact(&mut footer)?;
+
+        // Streams without a footer are perfectly valid, they just cannot be seeked into.
+        let Ok(footer) = StreamFooter::from_rrd_bytes(&footer) else {
+            return Ok(None);
+        };
+
+        let Some(stream_start) = reader_len.checked_sub(footer.stream_len) else {
+            return Err(CodecError::InvalidFooter(format!(
+                "stream length ({}) exceeds reader length ({reader_len})",
+                footer.stream_len
+            ))
+            .into());
+        };
+
+        let mut header = [0u8; StreamHeader::ENCODED_SIZE_BYTES];
+        reader.seek(SeekFrom::Start(stream_start))?;
+        reader.read_exact(&mut header)?;
+        let (version, _options) =
+            StreamHeader::from_rrd_bytes(&header)?.to_version_and_options()?;
+
+        let mut manifest = vec![0u8; footer.manifest_byte_span.len as usize];
+        reader.seek(SeekFrom::Start(
+            stream_start + footer.manifest_byte_span.start,
+        ))?;
+        reader.read_exact(&mut manifest)?;
+        let manifest = RrdManifest::from_rrd_bytes(&manifest)?;
+
+        Ok(Some(Self {
+            reader,
+            stream_start,
+            version,
+            manifest,
+            app_id_cache: CachingApplicationIdInjector::default(),
+            _decodable: std::marker::PhantomData::<T>,
+        }))
+    }
+
+    /// The Rerun version used to encode the RRD data.
+    pub fn version(&self) -> CrateVersion {
+        self.version
+    }
+
+    /// The index of all the messages in the stream.
+    pub fn manifest(&self) -> &RrdManifest {
+        &self.manifest
+    }
+
+    /// Reads and decodes the message described by the given manifest entry.
+    ///
+    /// Returns `None` for end-of-stream markers.
+    pub fn read(&mut self, entry: &RrdManifestEntry) -> Result<Option<T>, DecodeError> {
+        re_tracing::profile_function!();
+
+        let mut data = vec![0u8; entry.byte_span.len as usize];
+        self.reader
+            .seek(SeekFrom::Start(self.stream_start + entry.byte_span.start))?;
+        self.reader.read_exact(&mut data)?;
+
+        if data.len() < MessageHeader::ENCODED_SIZE_BYTES {
+            return Err(CodecError::InvalidFooter(format!(
+                "manifest entry at {} is too short to contain a message",
+                entry.byte_span.start
+            ))
+            .into());
+        }
+
+        let header = MessageHeader::from_rrd_bytes(&data[..MessageHeader::ENCODED_SIZE_BYTES])?;
+        if header.kind != entry.kind
+            || header.len + MessageHeader::ENCODED_SIZE_BYTES as u64 != entry.byte_span.len
+        {
+            return Err(CodecError::InvalidFooter(format!(
+                "manifest entry at {} doesn't match the message found there ({header:?})",
+                entry.byte_span.start
+            ))
+            .into());
+        }
+
+        let byte_span = re_chunk::Span {
+            start: self.stream_start
+                + entry.byte_span.start
+                + MessageHeader::ENCODED_SIZE_BYTES as u64,
+            len: header.len,
+        };
+
+        let data = bytes::Bytes::from(data).slice(MessageHeader::ENCODED_SIZE_BYTES..);
+        let message = T::decode(
+            data,
+            byte_span,
+            header.kind,
+            &mut self.app_id_cache,
+            Some(self.version),
+        )?;
+
+        Ok(message)
+    }
+
+    /// Reads and decodes all the messages that are needed to answer a query for `range` on `timeline`,
+    /// in stream order.
+    ///
+    /// This includes all messages that don't carry chunks (e.g. `SetStoreInfo`), all static chunks,
+    /// and all temporal chunks on `timeline` that intersect with `range`.
+    /// Chunks that don't have any data on `timeline` are skipped.
+    pub fn read_range(
+        &mut self,
+        timeline: &TimelineName,
+        range: AbsoluteTimeRange,
+    ) -> impl Iterator<Item = Result<T, DecodeError>> + '_ {
+        let entries: Vec<_> = self
+            .manifest
+            .entries_for_range(timeline, range)
+            .cloned()
+            .collect();
+
+        entries
+            .into_iter()
+            .filter_map(move |entry| self.read(&entry).transpose())
+    }
+}
+
+// ---
+
+#[cfg(all(test, feature = "encoder"))]
+mod tests {
+    #![expect(unsafe_code, clippy::unwrap_used, clippy::undocumented_unsafe_blocks)] // tests
+
+    use re_chunk::{Chunk, RowId, TimePoint, Timeline};
+    use re_log_types::{LogMsg, SetStoreInfo, StoreId, StoreInfo, StoreKind, StoreSource};
+
+    use super::*;
+    use crate::rrd::{DecoderApp, DecoderTransport, EncodingOptions, MessageKind};
+    use crate::{Encoder, ToTransport as _};
+
+    /// A `SetStoreInfo`, three temporal chunks covering frames `[0, 9]`, `[10, 19]` & `[20, 29]`,
+    /// and a static chunk.
+    fn fake_log_messages() -> Vec<LogMsg> {
+        let store_id = StoreId::random(StoreKind::Recording, "test_app");
+        let timeline = Timeline::new_sequence("frame");
+
+        let mut messages = vec![LogMsg::SetStoreInfo(SetStoreInfo {
+            row_id: *RowId::new(),
+            info: StoreInfo::new(store_id.clone(), StoreSource::Unknown),
+        })];
+
+        for first_frame in [0, 10, 20] {
+            let mut builder = Chunk::builder("scalars");
+            for frame in first_frame..first_frame + 10 {
+                builder = builder.with_archetype(
+                    RowId::new(),
+                    TimePoint::default().with(timeline, frame),
+                    &re_types::archetypes::Scalars::new([frame as f64]),
+                );
+            }
+            let chunk = builder.build().unwrap();
+            messages.push(LogMsg::ArrowMsg(
+                store_id.clone(),
+                chunk.to_arrow_msg().unwrap(),
+            ));
+        }
+
+        let chunk = Chunk::builder("label")
+            .with_archetype(
+                RowId::new(),
+                TimePoint::default(),
+                &re_types::archetypes::TextDocument::new("hello"),
+            )
+            .build()
+            .unwrap();
+        messages.push(LogMsg::ArrowMsg(store_id, chunk.to_arrow_msg().unwrap()));
+
+        messages
+    }
+
+    fn encode_with_footer(options: EncodingOptions, messages: &[LogMsg]) -> Vec<u8> {
+        let mut file = vec![];
+        let mut encoder = Encoder::new_eager(CrateVersion::LOCAL, options, &mut file)
+            .unwrap()
+            .with_footer(true)
+            .unwrap();
+        for message in messages {
+            encoder.append(message).unwrap();
+        }
+        encoder.finish().unwrap();
+        drop(encoder);
+        file
+    }
+
+    #[test]
+    fn test_footer_roundtrip() {
+        let messages = fake_log_messages();
+
+        for options in [
+            EncodingOptions::PROTOBUF_UNCOMPRESSED,
+            EncodingOptions::PROTOBUF_COMPRESSED,
+        ] {
+            let file = encode_with_footer(options, &messages);
+
+            // The footer is invisible to sequential decoding.
+            let decoded_messages: Vec<_> = DecoderApp::decode_lazy(file.as_slice())
+                .map(Result::unwrap)
+                .collect();
+            similar_asserts::assert_eq!(decoded_messages, messages);
+
+            let mut decoder = DecoderApp::decode_seekable(std::io::Cursor::new(&file))
+                .unwrap()
+                .unwrap();
+            assert_eq!(decoder.version(), CrateVersion::LOCAL);
+
+            let manifest = decoder.manifest().clone();
+            assert_eq!(manifest.entries.len(), messages.len());
+            assert_eq!(manifest.entries[0].kind, MessageKind::SetStoreInfo);
+            assert_eq!(manifest.chunks().count(), 4);
+
+            let timeline = TimelineName::new("frame");
+            let time_ranges: Vec<_> = manifest
+                .chunks()
+                .map(|(_, chunk)| chunk.time_ranges.get(&timeline).copied())
+                .collect();
+            assert_eq!(
+                time_ranges,
+                vec![
+                    Some(AbsoluteTimeRange::new(0, 9)),
+                    Some(AbsoluteTimeRange::new(10, 19)),
+                    Some(AbsoluteTimeRange::new(20, 29)),
+                    None,
+                ]
+            );
+
+            for (entry, chunk) in manifest.chunks() {
+                let Some(LogMsg::ArrowMsg(_, msg)) = decoder.read(entry).unwrap() else {
+                    panic!("expected an ArrowMsg");
+                };
+                assert_eq!(Chunk::from_arrow_msg(&msg).unwrap().id(), chunk.chunk_id);
+            }
+
+            // Random access, all the way from raw bytes back to the original messages.
+            let decoded_messages: Vec<_> = manifest
+                .entries
+                .iter()
+                .map(|entry| decoder.read(entry).unwrap().unwrap())
+                .collect();
+            similar_asserts::assert_eq!(decoded_messages, messages);
+
+            let decoded_messages: Vec<_> = decoder
+                .read_range(&timeline, AbsoluteTimeRange::new(12, 15))
+                .map(Result::unwrap)
+                .collect();
+            similar_asserts::assert_eq!(
+                decoded_messages,
+                vec![
+                    messages[0].clone(),
+                    messages[2].clone(),
+                    messages[4].clone()
+                ]
+            );
+
+            // Unknown timelines only get the static data.
+            let decoded_messages: Vec<_> = decoder
+                .read_range(&TimelineName::log_time(), AbsoluteTimeRange::EVERYTHING)
+                .map(Result::unwrap)
+                .collect();
+            similar_asserts::assert_eq!(
+                decoded_messages,
+                vec![messages[0].clone(), messages[4].clone()]
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_footer() {
+        let messages = fake_log_messages();
+
+        let mut file = vec![];
+        Encoder::encode_into(
+            CrateVersion::LOCAL,
+            EncodingOptions::PROTOBUF_COMPRESSED,
+            messages.iter().map(Ok),
+            &mut file,
+        )
+        .unwrap();
+
+        assert!(
+            DecoderApp::decode_seekable(std::io::Cursor::new(&file))
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            DecoderApp::decode_seekable(std::io::Cursor::new(&[]))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_concatenated_streams() {
+        let messages = fake_log_messages();
+        let options = EncodingOptions::PROTOBUF_COMPRESSED;
+
+        let mut file = encode_with_footer(options, &messages[..2]);
+        file.extend(encode_with_footer(options, &messages[2..]));
+
+        let decoded_messages: Vec<_> = DecoderApp::decode_lazy(file.as_slice())
+            .map(Result::unwrap)
+            .collect();
+        similar_asserts::assert_eq!(decoded_messages, messages);
+
+        // Only the last stream is indexed.
+        let mut decoder = DecoderApp::decode_seekable(std::io::Cursor::new(&file))
+            .unwrap()
+            .unwrap();
+        let manifest = decoder.manifest().clone();
+        assert_eq!(manifest.entries.len(), messages.len() - 2);
+
+        let decoded_messages: Vec<_> = manifest
+            .entries
+            .iter()
+            .map(|entry| decoder.read(entry).unwrap().unwrap())
+            .collect();
+        similar_asserts::assert_eq!(decoded_messages, messages[2..]);
+    }
+
+    #[test]
+    fn test_footer_after_append() {
+        let messages = fake_log_messages();
+        let options = EncodingOptions::PROTOBUF_COMPRESSED;
+
+        let mut file = vec![];
+        let mut encoder = Encoder::new_eager(CrateVersion::LOCAL, options, &mut file).unwrap();
+        encoder.append(&messages[0]).unwrap();
+        assert!(matches!(
+            encoder.with_footer(true),
+            Err(crate::EncodeError::FooterAfterAppend)
+        ));
+    }
+
+    #[test]
+    fn test_zstd_levels_roundtrip() {
+        let messages = fake_log_messages();
+
+        for level in [1, crate::rrd::ZSTD_DEFAULT_LEVEL, 19] {
+            let mut file = vec![];
+            let mut encoder = Encoder::new_eager(
+                CrateVersion::LOCAL,
+                EncodingOptions::PROTOBUF_ZSTD,
+                &mut file,
+            )
+            .unwrap()
+            .with_zstd_level(level);
+            for message in &messages {
+                encoder.append(message).unwrap();
+            }
+            encoder.finish().unwrap();
+            drop(encoder);
+
+            let decoded_messages: Vec<_> = DecoderApp::decode_lazy(file.as_slice())
+                .map(Result::unwrap)
+                .collect();
+            similar_asserts::assert_eq!(decoded_messages, messages);
+        }
+    }
+
+    #[test]
+    fn test_footer_transport() {
+        let messages = fake_log_messages();
+        let options = EncodingOptions::PROTOBUF_COMPRESSED;
+
+        let mut file = vec![];
+        let mut encoder = Encoder::new_eager(CrateVersion::LOCAL, options, &mut file)
+            .unwrap()
+            .with_footer(true)
+            .unwrap();
+        for message in &messages {
+            let message = message.to_transport(options.compression).unwrap();
+            unsafe {
+                encoder.append_transport(&message).unwrap();
+            }
+        }
+        drop(encoder);
+
+        let mut decoder = DecoderTransport::decode_seekable(std::io::Cursor::new(&file))
+            .unwrap()
+            .unwrap();
+        let manifest = decoder.manifest().clone();
+        assert_eq!(manifest.entries.len(), messages.len());
+        assert_eq!(manifest.chunks().count(), 4);
+
+        let decoded_messages: Vec<_> = manifest
+            .entries
+            .iter()
+            .map(|entry| decoder.read(entry).unwrap().unwrap())
+            .collect();
+        let expected: Vec<_> = messages
+            .iter()
+            .map(|message| message.to_transport(options.compression).unwrap())
+            .collect();
+        similar_asserts::assert_eq!(decoded_messages, expected);
+    }
+}
@@ -554,6 +554,25 @@ mod tests {
         assert_eq!(input, decoded_messages);
     }
 
+    #[test]
+    fn stream_byte_chunks_zstd_protobuf() {
+        let (input, data) = test_data(EncodingOptions::PROTOBUF_ZSTD, 16);
+
+        let mut decoder = DecoderApp::new();
+
+        assert_message_incomplete!(decoder.try_read());
+
+        for byte_chunk in data.chunks(1) {
+            decoder.push_byte_chunk(byte_chunk.to_vec());
+        }
+
+        let decoded_messages: Vec<_> = (0..16)
+            .map(|_| assert_message_ok!(decoder.try_read()))
+            .collect();
+
+        assert_eq!(input, decoded_messages);
+    }
+
     #[test]
     fn stream_3x16_chunks_protobuf() {
         let (input, data) = test_data(EncodingOptions::PROTOBUF_COMPRESSED, 16);
@@ -251,6 +251,10 @@ mod tests {
                 compression: Compression::LZ4,
                 serializer: Serializer::Protobuf,
             },
+            EncodingOptions {
+                compression: Compression::Zstd,
+                serializer: Serializer::Protobuf,
+            },
         ];
 
         for options in options {
@@ -286,6 +290,10 @@ mod tests {
                 compression: Compression::LZ4,
                 serializer: Serializer::Protobuf,
             },
+            EncodingOptions {
+                compression: Compression::Zstd,
+                serializer: Serializer::Protobuf,
+            },
         ];
 
         for options in options {
@@ -6,11 +6,11 @@ use re_build_info::CrateVersion;
 use re_chunk::{ChunkError, ChunkResult};
 use re_log_types::LogMsg;
 
-use crate::ToTransport as _;
 use crate::rrd::{
     CodecError, Compression, Encodable as _, EncodingOptions, MessageHeader, MessageKind,
-    Serializer, StreamHeader,
+    RrdManifest, RrdManifestEntry, Serializer, StreamFooter, StreamHeader,
 };
+use crate::{CachingApplicationIdInjector, ToApplication as _};
 
 // ----------------------------------------------------------------------------
 
@@ -23,6 +23,9 @@ pub enum EncodeError {
     #[error("Called append on already unwrapped encoder")]
     AlreadyUnwrapped,
 
+    #[error("The footer must be enabled before appending any message")]
+    FooterAfterAppend,
+
     #[error("Failed to write: {0}")]
     Write(#[from] std::io::Error),
 
@@ -61,6 +64,11 @@ pub struct Encoder<W: std::io::Write> {
     serializer: Serializer,
     compression: Compression,
 
+    /// Only used with [`Compression::Zstd`].
+    ///
+    /// See [`Self::with_zstd_level`].
+    zstd_level: i32,
+
     /// Optional so that we can `take()` it in `into_inner`, while still being allowed to implement `Drop`.
     write: Option<W>,
 
@@ -69,6 +77,18 @@ pub struct Encoder<W: std::io::Write> {
 
     /// Tracks whether the end-of-stream marker has been written out already.
     is_finished: bool,
+
+    /// How many bytes have been written so far, [`StreamHeader`] included.
+    num_written: u64,
+
+    /// If set, a [`StreamFooter`] indexing all messages will be written as part of the
+    /// end-of-stream marker.
+    ///
+    /// See [`Self::with_footer`].
+    manifest: Option<RrdManifest>,
+
+    /// Only used to index messages given to [`Self::append_transport`].
+    app_id_cache: CachingApplicationIdInjector,
 }
 
 impl Encoder<Vec<u8>> {
@@ -114,7 +134,7 @@ impl<W: std::io::Write> Encoder<W> {
         // introduction of an InMemoryWriter trait or similar. In practice it makes no
         // difference and the cognitive overhead of this crate is already through the roof.
         let mut out = Vec::new();
-        StreamHeader {
+        let num_written = StreamHeader {
             fourcc: crate::rrd::RRD_FOURCC,
             version: version.to_bytes(),
             options,
@@ -125,12 +145,41 @@ impl<W: std::io::Write> Encoder<W> {
         Ok(Self {
             serializer: options.serializer,
             compression: options.compression,
+            zstd_level: crate::rrd::ZSTD_DEFAULT_LEVEL,
             write: Some(write),
             scratch: Vec::new(),
             is_finished: false,
+            num_written,
+            manifest: None,
+            app_id_cache: CachingApplicationIdInjector::default(),
         })
     }
 
+    /// Whether to index all appended messages in a [`StreamFooter`], written as part of the
+    /// end-of-stream marker.
+    ///
+    /// The footer is what allows [`crate::DecoderSeekable`] to fetch specific chunks without
+    /// having to decode the whole stream. Decoders that don't know about footers will just ignore it.
+    ///
+    /// This must be set before any message gets appended, otherwise the manifest would be
+    /// missing entries: returns [`EncodeError::FooterAfterAppend`] in that case.
+    pub fn with_footer(mut self, write_footer: bool) -> Result<Self, EncodeError> {
+        if self.num_written != StreamHeader::ENCODED_SIZE_BYTES as u64 {
+            return Err(EncodeError::FooterAfterAppend);
+        }
+        self.manifest = write_footer.then(RrdManifest::default);
+        Ok(self)
+    }
+
+    /// The Zstd level to compress messages with, from 1 (fastest) to 22 (smallest).
+    ///
+    /// Only used if the encoder was created with [`Compression::Zstd`]. The level only affects
+    /// encoding and is not recorded in the stream. Defaults to [`crate::rrd::ZSTD_DEFAULT_LEVEL`].
+    pub fn with_zstd_level(mut self, level: i32) -> Self {
+        self.zstd_level = level;
+        self
+    }
+
     /// Returns the size in bytes of the encoded data.
     pub fn append(&mut self, message: &re_log_types::LogMsg) -> Result<u64, EncodeError> {
         if self.is_finished {
@@ -143,12 +192,12 @@ impl<W: std::io::Write> Encoder<W> {
 
         re_tracing::profile_function!();
 
-        let message = message.to_transport(self.compression)?;
-        // Safety: the compression settings of this message are consistent with this stream.
-        #[expect(unsafe_code)]
-        unsafe {
-            self.append_transport(&message)
-        }
+        let transport =
+            crate::transport_to_app::log_msg_to_proto(message, self.compression, self.zstd_level)?;
+        let num_written = self.write_transport(&transport)?;
+        self.index_message(message, num_written)?;
+
+        Ok(num_written)
     }
 
     /// Returns the size in bytes of the encoded data.
@@ -166,21 +215,70 @@ impl<W: std::io::Write> Encoder<W> {
             return Err(EncodeError::AlreadyFinished);
         }
 
-        let Some(w) = self.write.as_mut() else {
+        if self.write.is_none() {
             return Err(EncodeError::AlreadyUnwrapped);
-        };
+        }
 
         re_tracing::profile_function!();
 
+        // Indexing requires application-level data: convert first so that we never end up with
+        // messages that are in the stream but missing from its manifest.
+        let app_message = if self.manifest.is_some() {
+            Some(message.to_application((&mut self.app_id_cache, None))?)
+        } else {
+            None
+        };
+
+        let num_written = self.write_transport(message)?;
+        if let Some(app_message) = app_message {
+            self.index_message(&app_message, num_written)?;
+        }
+
+        Ok(num_written)
+    }
+
+    fn write_transport(
+        &mut self,
+        message: &re_protos::log_msg::v1alpha1::log_msg::Msg,
+    ) -> Result<u64, EncodeError> {
+        let Some(w) = self.write.as_mut() else {
+            return Err(EncodeError::AlreadyUnwrapped);
+        };
+
         self.scratch.clear();
-        match self.serializer {
+        let num_written = match self.serializer {
             Serializer::Protobuf => {
                 message.to_rrd_bytes(&mut self.scratch)?;
                 w.write_all(&self.scratch)
-                    .map(|_| self.scratch.len() as _)
-                    .map_err(EncodeError::Write)
+                    .map(|_| self.scratch.len() as u64)
+                    .map_err(EncodeError::Write)?
             }
-        }
+        };
+
+        self.num_written += num_written;
+
+        Ok(num_written)
+    }
+
+    /// Adds a message that was just written out to the manifest, if any.
+    fn index_message(
+        &mut self,
+        message: &re_log_types::LogMsg,
+        num_written: u64,
+    ) -> Result<(), EncodeError> {
+        let Some(manifest) = self.manifest.as_mut() else {
+            return Ok(());
+        };
+
+        let byte_span = re_chunk::Span {
+            start: self.num_written - num_written,
+            len: num_written,
+        };
+        manifest
+            .entries
+            .push(RrdManifestEntry::from_log_msg(message, byte_span)?);
+
+        Ok(())
     }
 
     /// Appends an end-of-stream marker to the encoded bytes. Does not flush.
@@ -205,13 +303,43 @@ impl<W: std::io::Write> Encoder<W> {
                 // TODO(cmc): the extra heap-alloc and copy could be easily avoided with the
                 // introduction of an InMemoryWriter trait or similar. In practice it makes no
                 // difference and the cognitive overhead of this crate is already through the roof.
-                let mut header = Vec::new();
-                MessageHeader {
-                    kind: MessageKind::End,
-                    len: 0,
+                let mut out = Vec::new();
+
+                if let Some(manifest) = self.manifest.take() {
+                    // The manifest and footer are carried as the payload of the end-of-stream
+                    // marker, so that decoders that don't know about them skip right over.
+                    let mut payload = Vec::new();
+                    let manifest_len = manifest.to_rrd_bytes(&mut payload)?;
+                    let payload_len = manifest_len + StreamFooter::ENCODED_SIZE_BYTES as u64;
+
+                    let header_len = MessageHeader {
+                        kind: MessageKind::End,
+                        len: payload_len,
+                    }
+                    .to_rrd_bytes(&mut out)?;
+
+                    StreamFooter {
+                        fourcc: crate::rrd::RRD_FOURCC,
+                        identifier: crate::rrd::RRD_FOOTER_IDENTIFIER,
+                        manifest_byte_span: re_chunk::Span {
+                            start: self.num_written + header_len,
+                            len: manifest_len,
+                        },
+                        stream_len: self.num_written + header_len + payload_len,
+                    }
+                    .to_rrd_bytes(&mut payload)?;
+
+                    out.extend_from_slice(&payload);
+                } else {
+                    MessageHeader {
+                        kind: MessageKind::End,
+                        len: 0,
+                    }
+                    .to_rrd_bytes(&mut out)?;
                 }
-                .to_rrd_bytes(&mut header)?;
-                w.write_all(&header)?;
+
+                w.write_all(&out)?;
+                self.num_written += out.len() as u64;
             }
         }
 
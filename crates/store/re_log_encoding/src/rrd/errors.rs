@@ -28,6 +28,9 @@ pub enum CodecError {
     #[error("Failed to decode message header {0}")]
     HeaderDecoding(String),
 
+    #[error("Invalid stream footer: {0}")]
+    InvalidFooter(String),
+
     #[error("Arrow IPC deserialization error: {0}")]
     ArrowDeserialization(::arrow::error::ArrowError),
 
@@ -61,6 +64,9 @@ pub enum CodecError {
     #[error("lz4 error: {0}")]
     Lz4(#[from] lz4_flex::block::DecompressError),
 
+    #[error("zstd error: {0}")]
+    Zstd(std::io::Error),
+
     #[error("Sorbet error: {0}")]
     Sorbet(#[from] re_sorbet::SorbetError),
 
@@ -1,12 +1,15 @@
 use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
     fmt,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::mpsc::{Receiver, RecvTimeoutError, SendError, Sender, SyncSender},
+    time::Instant,
 };
 
 use parking_lot::Mutex;
 
-use re_log_types::LogMsg;
+use re_chunk::{ChunkId, ComponentIdentifier};
+use re_log_types::{EntityPath, LogMsg, StoreId};
 
 /// An error that can occur when flushing.
 #[derive(Debug, thiserror::Error)]
@@ -56,6 +59,51 @@ impl Command {
     }
 }
 
+/// Options for a [`FileSink`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileSinkOptions {
+    /// Whether to write a [`crate::rrd::StreamFooter`] indexing all chunks when the file is closed.
+    ///
+    /// This makes it possible to fetch chunks by time range without decoding the whole file,
+    /// see [`crate::DecoderSeekable`].
+    pub write_footer: bool,
+
+    /// If set, a new segment is started whenever the current one grows past this many bytes.
+    ///
+    /// See [`Self::is_segmented`].
+    pub max_segment_bytes: Option<u64>,
+
+    /// If set, a new segment is started whenever the current one has been open for longer than this.
+    ///
+    /// This is checked whenever a new message comes in, so idle segments might stay open longer.
+    /// See [`Self::is_segmented`].
+    pub max_segment_duration: Option<std::time::Duration>,
+
+    /// If set, only the given number of most recent segments are kept: older ones get deleted.
+    ///
+    /// Only applies to the segments written by the sink itself.
+    /// See [`Self::is_segmented`].
+    pub max_segments: Option<usize>,
+}
+
+impl FileSinkOptions {
+    /// Does the sink rotate through several segment files, rather than writing a single file?
+    ///
+    /// That is the case as soon as either [`Self::max_segment_bytes`] or
+    /// [`Self::max_segment_duration`] is set.
+    ///
+    /// The segments are written next to the requested path, and named after it: `recording.rrd` results in
+    /// `recording.000000.rrd`, `recording.000001.rrd`, etc.
+    ///
+    /// Every segment is a self-contained recording: it starts with the store infos, the latest static
+    /// data, the latest blueprint data and the latest blueprint activation commands of all the stores
+    /// seen so far.
+    #[inline]
+    pub fn is_segmented(&self) -> bool {
+        self.max_segment_bytes.is_some() || self.max_segment_duration.is_some()
+    }
+}
+
 /// Stream log messages to an `.rrd` file.
 pub struct FileSink {
     // None = quit
@@ -80,9 +128,14 @@ impl Drop for FileSink {
 impl FileSink {
     /// Start writing log messages to a file at the given path.
     pub fn new(path: impl Into<std::path::PathBuf>) -> Result<Self, FileSinkError> {
-        // We always compress on disk
-        let encoding_options = crate::rrd::EncodingOptions::PROTOBUF_COMPRESSED;
+        Self::new_with_options(path, FileSinkOptions::default())
+    }
 
+    /// Start writing log messages to a file at the given path, with the given options.
+    pub fn new_with_options(
+        path: impl Into<std::path::PathBuf>,
+        options: FileSinkOptions,
+    ) -> Result<Self, FileSinkError> {
         let (tx, rx) = std::sync::mpsc::channel();
 
         let path = path.into();
@@ -93,11 +146,13 @@ impl FileSink {
         // have multiple file sinks for the same file live?
         // This likely caused an instability in the past, see https://github.com/rerun-io/rerun/issues/3306
 
-        let file = std::fs::File::create(&path)
-            .map_err(|err| FileSinkError::CreateFile(path.clone(), err))?;
-        let encoder =
-            crate::Encoder::new_eager(re_build_info::CrateVersion::LOCAL, encoding_options, file)?;
-        let join_handle = spawn_and_stream(Some(&path), encoder, rx)?;
+        let join_handle = if options.is_segmented() {
+            let writer = SegmentedWriter::new(path.clone(), options)?;
+            spawn_and_stream(Some(&path), writer, rx)?
+        } else {
+            let encoder = create_file_encoder(&path, options)?;
+            spawn_and_stream(Some(&path), encoder, rx)?
+        };
 
         Ok(Self {
             tx: tx.into(),
@@ -152,10 +207,50 @@ impl FileSink {
     }
 }
 
+fn create_file_encoder(
+    path: &Path,
+    options: FileSinkOptions,
+) -> Result<crate::Encoder<std::fs::File>, FileSinkError> {
+    // We always compress on disk
+    let encoding_options = crate::rrd::EncodingOptions::PROTOBUF_COMPRESSED;
+
+    let file = std::fs::File::create(path)
+        .map_err(|err| FileSinkError::CreateFile(path.to_owned(), err))?;
+
+    Ok(
+        crate::Encoder::new_eager(re_build_info::CrateVersion::LOCAL, encoding_options, file)?
+            .with_footer(options.write_footer)?,
+    )
+}
+
+/// Whatever the writer thread of a [`FileSink`] writes to.
+trait StreamWriter: Send + 'static {
+    fn append(&mut self, log_msg: &LogMsg) -> Result<(), FileSinkError>;
+
+    fn flush_blocking(&mut self) -> Result<(), FileSinkError>;
+
+    fn finish(&mut self) -> Result<(), FileSinkError>;
+}
+
+impl<W: std::io::Write + Send + 'static> StreamWriter for crate::Encoder<W> {
+    fn append(&mut self, log_msg: &LogMsg) -> Result<(), FileSinkError> {
+        Self::append(self, log_msg)?;
+        Ok(())
+    }
+
+    fn flush_blocking(&mut self) -> Result<(), FileSinkError> {
+        Ok(Self::flush_blocking(self)?)
+    }
+
+    fn finish(&mut self) -> Result<(), FileSinkError> {
+        Ok(Self::finish(self)?)
+    }
+}
+
 /// Set `filepath` to `None` to stream to standard output.
-fn spawn_and_stream<W: std::io::Write + Send + 'static>(
+fn spawn_and_stream(
     filepath: Option<&std::path::Path>,
-    mut encoder: crate::Encoder<W>,
+    mut encoder: impl StreamWriter,
     rx: Receiver<Option<Command>>,
 ) -> Result<std::thread::JoinHandle<()>, FileSinkError> {
     let (name, target) = if let Some(filepath) = filepath {
@@ -202,6 +297,244 @@ fn spawn_and_stream<W: std::io::Write + Send + 'static>(
         .map_err(FileSinkError::SpawnThread)
 }
 
+// ---
+
+/// Writes to a sequence of self-contained segment files, see [`FileSinkOptions::is_segmented`].
+///
+/// Only ever used when segmentation is enabled: plain file sinks write straight to their
+/// [`crate::Encoder`] and don't track any state.
+struct SegmentedWriter {
+    path: PathBuf,
+    options: FileSinkOptions,
+
+    encoder: crate::Encoder<std::fs::File>,
+
+    /// The paths of all the segments written so far that haven't been deleted yet, oldest first.
+    segment_paths: VecDeque<PathBuf>,
+
+    /// The index of the current segment.
+    segment_index: u64,
+
+    /// When the current segment was started.
+    segment_started_at: Instant,
+
+    /// How many bytes were written to the current segment so far.
+    segment_num_bytes: u64,
+
+    /// Whether the current segment contains anything besides the replayed state.
+    ///
+    /// Segments are never rotated before that, otherwise a large enough state would lead to an
+    /// endless stream of empty segments.
+    segment_has_new_data: bool,
+
+    /// Everything that needs to be replayed at the start of each segment.
+    state: SegmentState,
+}
+
+impl SegmentedWriter {
+    fn new(path: PathBuf, options: FileSinkOptions) -> Result<Self, FileSinkError> {
+        debug_assert!(options.is_segmented());
+
+        let segment_path = segment_path(&path, 0);
+        let encoder = create_file_encoder(&segment_path, options)?;
+
+        Ok(Self {
+            path,
+            options,
+            encoder,
+            segment_paths: std::iter::once(segment_path).collect(),
+            segment_index: 0,
+            segment_started_at: Instant::now(),
+            segment_num_bytes: 0,
+            segment_has_new_data: false,
+            state: SegmentState::default(),
+        })
+    }
+
+    fn should_rotate(&self) -> bool {
+        let FileSinkOptions {
+            write_footer: _,
+            max_segment_bytes,
+            max_segment_duration,
+            max_segments: _,
+        } = self.options;
+
+        self.segment_has_new_data
+            && (max_segment_bytes.is_some_and(|max| self.segment_num_bytes >= max)
+                || max_segment_duration.is_some_and(|max| self.segment_started_at.elapsed() >= max))
+    }
+
+    /// Closes the current segment, and starts a new one pre-filled with the current state.
+    fn rotate(&mut self) -> Result<(), FileSinkError> {
+        re_tracing::profile_function!();
+
+        self.encoder.finish()?;
+
+        self.segment_index += 1;
+        let segment_path = segment_path(&self.path, self.segment_index);
+        re_log::debug!("Starting new segment {segment_path:?}…");
+
+        self.encoder = create_file_encoder(&segment_path, self.options)?;
+        self.segment_paths.push_back(segment_path);
+        self.segment_started_at = Instant::now();
+        self.segment_num_bytes = 0;
+        self.segment_has_new_data = false;
+
+        if let Some(max_segments) = self.options.max_segments {
+            while self.segment_paths.len() > max_segments.max(1) {
+                if let Some(segment_path) = self.segment_paths.pop_front()
+                    && let Err(err) = std::fs::remove_file(&segment_path)
+                {
+                    re_log::warn!("Failed to delete old segment {segment_path:?}: {err}");
+                }
+            }
+        }
+
+        for log_msg in self.state.replay() {
+            self.segment_num_bytes += self.encoder.append(log_msg)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StreamWriter for SegmentedWriter {
+    fn append(&mut self, log_msg: &LogMsg) -> Result<(), FileSinkError> {
+        if self.should_rotate() {
+            self.rotate()?;
+        }
+
+        self.state.on_log_msg(log_msg);
+
+        self.segment_num_bytes += self.encoder.append(log_msg)?;
+        self.segment_has_new_data = true;
+
+        Ok(())
+    }
+
+    fn flush_blocking(&mut self) -> Result<(), FileSinkError> {
+        Ok(self.encoder.flush_blocking()?)
+    }
+
+    fn finish(&mut self) -> Result<(), FileSinkError> {
+        Ok(self.encoder.finish()?)
+    }
+}
+
+/// `recording.rrd` -> `recording.000042.rrd`
+fn segment_path(path: &Path, segment_index: u64) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let file_name = if let Some(extension) = path.extension() {
+        format!("{stem}.{segment_index:06}.{}", extension.to_string_lossy())
+    } else {
+        format!("{stem}.{segment_index:06}")
+    };
+
+    path.with_file_name(file_name)
+}
+
+/// The state that makes a segment self-contained.
+#[derive(Default)]
+struct SegmentState {
+    /// The latest `SetStoreInfo` message of each store, in order of appearance.
+    store_infos: Vec<(StoreId, LogMsg)>,
+
+    /// The chunks of each store that still hold the latest data for at least one component.
+    ///
+    /// For recordings, only static chunks are tracked.
+    /// For blueprints, temporal chunks are tracked too, since that's where all of their data lives.
+    latest_chunks: HashMap<StoreId, LatestChunks>,
+
+    /// The latest `BlueprintActivationCommand` of each blueprint.
+    blueprint_activations: BTreeMap<StoreId, LogMsg>,
+}
+
+#[derive(Default)]
+struct LatestChunks {
+    /// Which chunk holds the latest data for each component, static and temporal data being
+    /// tracked separately.
+    per_component: HashMap<(EntityPath, ComponentIdentifier, bool), ChunkId>,
+
+    /// Ordered by [`ChunkId`], i.e. roughly in order of creation.
+    messages: BTreeMap<ChunkId, LogMsg>,
+}
+
+impl SegmentState {
+    fn on_log_msg(&mut self, log_msg: &LogMsg) {
+        match log_msg {
+            LogMsg::SetStoreInfo(info) => {
+                let store_id = &info.info.store_id;
+                if let Some((_, cur)) = self.store_infos.iter_mut().find(|(id, _)| id == store_id) {
+                    *cur = log_msg.clone();
+                } else {
+                    self.store_infos.push((store_id.clone(), log_msg.clone()));
+                }
+            }
+
+            LogMsg::ArrowMsg(store_id, arrow_msg) => {
+                // Only look at the schema: there's no need to decode the actual data.
+                let schema = match re_sorbet::SorbetSchema::try_from_raw_arrow_schema(
+                    arrow_msg.batch.schema(),
+                )
+                .and_then(re_sorbet::ChunkSchema::try_from)
+                {
+                    Ok(schema) => schema,
+                    Err(err) => {
+                        re_log::warn_once!(
+                            "Failed to parse chunk schema, it won't be replayed in later segments: {err}"
+                        );
+                        return;
+                    }
+                };
+
+                let is_static = schema.is_static();
+                if !is_static && !store_id.is_blueprint() {
+                    return;
+                }
+
+                let chunk_id = schema.chunk_id();
+                let LatestChunks {
+                    per_component,
+                    messages,
+                } = self.latest_chunks.entry(store_id.clone()).or_default();
+
+                let mut overwritten = Vec::new();
+                for column in schema.columns.component_columns() {
+                    let key = (schema.entity_path().clone(), column.component, is_static);
+                    if let Some(chunk_id) = per_component.insert(key, chunk_id) {
+                        overwritten.push(chunk_id);
+                    }
+                }
+                messages.insert(chunk_id, log_msg.clone());
+
+                for chunk_id in overwritten {
+                    if !per_component.values().any(|id| *id == chunk_id) {
+                        messages.remove(&chunk_id);
+                    }
+                }
+            }
+
+            LogMsg::BlueprintActivationCommand(cmd) => {
+                self.blueprint_activations
+                    .insert(cmd.blueprint_id.clone(), log_msg.clone());
+            }
+        }
+    }
+
+    fn replay(&self) -> impl Iterator<Item = &LogMsg> {
+        self.store_infos
+            .iter()
+            .map(|(_, log_msg)| log_msg)
+            .chain(
+                self.store_infos
+                    .iter()
+                    .filter_map(|(store_id, _)| self.latest_chunks.get(store_id))
+                    .flat_map(|latest_chunks| latest_chunks.messages.values()),
+            )
+            .chain(self.blueprint_activations.values())
+    }
+}
+
 impl fmt::Debug for FileSink {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("FileSink")
@@ -209,3 +542,231 @@ impl fmt::Debug for FileSink {
             .finish_non_exhaustive()
     }
 }
+
+#[cfg(all(test, feature = "decoder"))]
+mod tests {
+    #![expect(clippy::unwrap_used)] // tests
+
+    use re_chunk::{Chunk, RowId, TimePoint, Timeline};
+    use re_log_types::{SetStoreInfo, StoreInfo, StoreKind, StoreSource};
+
+    use super::*;
+
+    fn decode_file(path: &Path) -> Vec<LogMsg> {
+        let file = std::io::BufReader::new(std::fs::File::open(path).unwrap());
+        crate::Decoder::decode_eager(file)
+            .unwrap()
+            .collect::<Result<Vec<LogMsg>, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn segment_paths() {
+        assert_eq!(
+            PathBuf::from("/tmp/recording.000042.rrd"),
+            segment_path(Path::new("/tmp/recording.rrd"), 42)
+        );
+        assert_eq!(
+            PathBuf::from("recording.000000"),
+            segment_path(Path::new("recording"), 0)
+        );
+    }
+
+    #[test]
+    fn rotation_and_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording.rrd");
+
+        let store_id = StoreId::random(StoreKind::Recording, "test_app");
+        let timeline = Timeline::new_sequence("frame");
+
+        let store_info = LogMsg::SetStoreInfo(SetStoreInfo {
+            row_id: *RowId::new(),
+            info: StoreInfo::new(store_id.clone(), StoreSource::Unknown),
+        });
+        let static_chunk = Chunk::builder("label")
+            .with_archetype(
+                RowId::new(),
+                TimePoint::default(),
+                &re_types::archetypes::TextDocument::new("hello"),
+            )
+            .build()
+            .unwrap();
+        let temporal_chunks = (0..5)
+            .map(|frame| {
+                Chunk::builder("scalars")
+                    .with_archetype(
+                        RowId::new(),
+                        TimePoint::default().with(timeline, frame),
+                        &re_types::archetypes::Scalars::new([frame as f64]),
+                    )
+                    .build()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        {
+            let sink = FileSink::new_with_options(
+                &path,
+                FileSinkOptions {
+                    // Every single message ends up in its own segment.
+                    max_segment_bytes: Some(1),
+                    max_segments: Some(3),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            sink.send(store_info);
+            sink.send(LogMsg::ArrowMsg(
+                store_id.clone(),
+                static_chunk.to_arrow_msg().unwrap(),
+            ));
+            for chunk in &temporal_chunks {
+                sink.send(LogMsg::ArrowMsg(
+                    store_id.clone(),
+                    chunk.to_arrow_msg().unwrap(),
+                ));
+            }
+        }
+
+        // 7 messages -> 7 segments, of which only the last 3 are kept.
+        let mut file_names = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        file_names.sort();
+        assert_eq!(
+            vec![
+                "recording.000004.rrd",
+                "recording.000005.rrd",
+                "recording.000006.rrd"
+            ],
+            file_names
+        );
+
+        // Every segment is self-contained.
+        for (file_name, chunk) in file_names.iter().zip(&temporal_chunks[2..]) {
+            let messages = decode_file(&dir.path().join(file_name));
+            assert_eq!(3, messages.len(), "{file_name}");
+
+            assert!(
+                matches!(&messages[0], LogMsg::SetStoreInfo(info) if info.info.store_id == store_id)
+            );
+
+            let chunk_ids = messages[1..]
+                .iter()
+                .map(|msg| match msg {
+                    LogMsg::ArrowMsg(_, arrow_msg) => {
+                        Chunk::from_arrow_msg(arrow_msg).unwrap().id()
+                    }
+                    msg => panic!("unexpected message: {msg:?}"),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(vec![static_chunk.id(), chunk.id()], chunk_ids);
+        }
+    }
+
+    #[test]
+    fn blueprint_is_replayed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording.rrd");
+
+        let recording_id = StoreId::random(StoreKind::Recording, "test_app");
+        let blueprint_id = StoreId::random(StoreKind::Blueprint, "test_app");
+        let frame = Timeline::new_sequence("frame");
+        let blueprint_time = Timeline::new_sequence("blueprint");
+
+        let set_store_info = |store_id: &StoreId| {
+            LogMsg::SetStoreInfo(SetStoreInfo {
+                row_id: *RowId::new(),
+                info: StoreInfo::new(store_id.clone(), StoreSource::Unknown),
+            })
+        };
+        let text_chunk = |entity_path: &str, timepoint: TimePoint, text: &str| {
+            Chunk::builder(entity_path)
+                .with_archetype(
+                    RowId::new(),
+                    timepoint,
+                    &re_types::archetypes::TextDocument::new(text),
+                )
+                .build()
+                .unwrap()
+        };
+
+        // The second one overwrites the first one.
+        let blueprint_chunks = [
+            text_chunk("view", TimePoint::default().with(blueprint_time, 0), "a"),
+            text_chunk("view", TimePoint::default().with(blueprint_time, 1), "b"),
+            text_chunk("other", TimePoint::default().with(blueprint_time, 1), "c"),
+        ];
+        let recording_chunks = (0..2)
+            .map(|i| text_chunk("text", TimePoint::default().with(frame, i), "hello"))
+            .collect::<Vec<_>>();
+
+        {
+            let sink = FileSink::new_with_options(
+                &path,
+                FileSinkOptions {
+                    // Every single message ends up in its own segment.
+                    max_segment_bytes: Some(1),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            sink.send(set_store_info(&blueprint_id));
+            for chunk in &blueprint_chunks {
+                sink.send(LogMsg::ArrowMsg(
+                    blueprint_id.clone(),
+                    chunk.to_arrow_msg().unwrap(),
+                ));
+            }
+            sink.send(LogMsg::BlueprintActivationCommand(
+                re_log_types::BlueprintActivationCommand::make_active(blueprint_id.clone()),
+            ));
+
+            sink.send(set_store_info(&recording_id));
+            for chunk in &recording_chunks {
+                sink.send(LogMsg::ArrowMsg(
+                    recording_id.clone(),
+                    chunk.to_arrow_msg().unwrap(),
+                ));
+            }
+        }
+
+        // 8 messages -> 8 segments: open the last one on its own.
+        let messages = decode_file(&segment_path(&path, 7));
+
+        let store_ids = messages
+            .iter()
+            .filter_map(|msg| match msg {
+                LogMsg::SetStoreInfo(info) => Some(info.info.store_id.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec![blueprint_id.clone(), recording_id.clone()], store_ids);
+
+        let chunk_ids = |store_id: &StoreId| {
+            messages
+                .iter()
+                .filter_map(|msg| match msg {
+                    LogMsg::ArrowMsg(id, arrow_msg) if id == store_id => {
+                        Some(Chunk::from_arrow_msg(arrow_msg).unwrap().id())
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec![blueprint_chunks[1].id(), blueprint_chunks[2].id()],
+            chunk_ids(&blueprint_id)
+        );
+        assert_eq!(vec![recording_chunks[1].id()], chunk_ids(&recording_id));
+
+        assert!(messages.iter().any(|msg| matches!(
+            msg,
+            LogMsg::BlueprintActivationCommand(cmd) if cmd.blueprint_id == blueprint_id
+        )));
+    }
+}
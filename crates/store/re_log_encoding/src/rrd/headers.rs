@@ -5,6 +5,11 @@ use crate::rrd::{Decodable, Encodable, OptionsError};
 pub use re_build_info::CrateVersion; // convenience
 pub use re_protos::log_msg::v1alpha1::ext::Compression; // convenience
 
+/// The Zstd level used when none is specified. Same as the reference implementation.
+///
+/// The level only affects encoding, and is not recorded in the data.
+pub const ZSTD_DEFAULT_LEVEL: i32 = 3;
+
 /// How we serialize the data.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
@@ -31,6 +36,10 @@ impl EncodingOptions {
         compression: Compression::Off,
         serializer: Serializer::Protobuf,
     };
+    pub const PROTOBUF_ZSTD: Self = Self {
+        compression: Compression::Zstd,
+        serializer: Serializer::Protobuf,
+    };
 }
 
 impl Encodable for EncodingOptions {
@@ -63,6 +72,7 @@ impl Decodable for EncodingOptions {
                 let compression = match compression {
                     0 => Compression::Off,
                     1 => Compression::LZ4,
+                    2 => Compression::Zstd,
                     _ => return Err(OptionsError::UnknownCompression(compression).into()),
                 };
                 let serializer = match serializer {
@@ -207,6 +217,16 @@ impl MessageKind {
     const SET_STORE_INFO: u64 = 1;
     const ARROW_MSG: u64 = 2;
     const BLUEPRINT_ACTIVATION_COMMAND: u64 = 3;
+
+    pub fn from_u64(kind: u64) -> Option<Self> {
+        match kind {
+            Self::END => Some(Self::End),
+            Self::SET_STORE_INFO => Some(Self::SetStoreInfo),
+            Self::ARROW_MSG => Some(Self::ArrowMsg),
+            Self::BLUEPRINT_ACTIVATION_COMMAND => Some(Self::BlueprintActivationCommand),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -246,16 +266,10 @@ impl Decodable for MessageHeader {
         }
 
         let kind = u64::from_le_bytes(data[0..8].try_into().expect("cannot fail, checked above"));
-        let kind = match kind {
-            MessageKind::END => MessageKind::End,
-            MessageKind::SET_STORE_INFO => MessageKind::SetStoreInfo,
-            MessageKind::ARROW_MSG => MessageKind::ArrowMsg,
-            MessageKind::BLUEPRINT_ACTIVATION_COMMAND => MessageKind::BlueprintActivationCommand,
-            _ => {
-                return Err(crate::rrd::CodecError::HeaderDecoding(format!(
-                    "unknown MessageHeader kind: {kind:?}"
-                )));
-            }
+        let Some(kind) = MessageKind::from_u64(kind) else {
+            return Err(crate::rrd::CodecError::HeaderDecoding(format!(
+                "unknown MessageHeader kind: {kind:?}"
+            )));
         };
 
         let len = u64::from_le_bytes(data[8..16].try_into().expect("cannot fail, checked above"));
@@ -263,3 +277,92 @@ impl Decodable for MessageHeader {
         Ok(Self { kind, len })
     }
 }
+
+// --- StreamFooter ---
+
+/// The trailer of an RRD stream that was encoded with a [`crate::rrd::RrdManifest`].
+///
+/// The footer is always the very last bytes of the payload of the end-of-stream marker: decoders
+/// that don't know about footers just skip over it like they would any end-of-stream payload.
+///
+/// ```text,ignore
+/// StreamHeader | MessageHeader | Message | … | MessageHeader(End) | RrdManifest | StreamFooter
+/// ```
+///
+/// All offsets are relative to the start of the stream (i.e. the first byte of its [`StreamHeader`]),
+/// which makes it possible to locate a stream from the end of a file, even if it was concatenated
+/// with others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamFooter {
+    pub fourcc: [u8; 4],
+    pub identifier: [u8; 4],
+
+    /// Where the encoded [`crate::rrd::RrdManifest`] lives, relative to the start of the stream.
+    pub manifest_byte_span: re_chunk::Span<u64>,
+
+    /// The total length of the stream, from the first byte of its [`StreamHeader`] to the last
+    /// byte of this footer, inclusive.
+    pub stream_len: u64,
+}
+
+impl StreamFooter {
+    pub const ENCODED_SIZE_BYTES: usize = 32;
+}
+
+impl Encodable for StreamFooter {
+    fn to_rrd_bytes(&self, out: &mut Vec<u8>) -> Result<u64, crate::rrd::CodecError> {
+        let Self {
+            fourcc,
+            identifier,
+            manifest_byte_span,
+            stream_len,
+        } = *self;
+
+        let before = out.len() as u64;
+
+        out.extend_from_slice(&fourcc);
+        out.extend_from_slice(&identifier);
+        out.extend_from_slice(&manifest_byte_span.start.to_le_bytes());
+        out.extend_from_slice(&manifest_byte_span.len.to_le_bytes());
+        out.extend_from_slice(&stream_len.to_le_bytes());
+
+        let n = out.len() as u64 - before;
+        assert_eq!(Self::ENCODED_SIZE_BYTES as u64, n);
+
+        Ok(n)
+    }
+}
+
+impl Decodable for StreamFooter {
+    fn from_rrd_bytes(data: &[u8]) -> Result<Self, crate::rrd::CodecError> {
+        if data.len() != Self::ENCODED_SIZE_BYTES {
+            return Err(crate::rrd::CodecError::InvalidFooter(format!(
+                "invalid StreamFooter length (expected {} but got {})",
+                Self::ENCODED_SIZE_BYTES,
+                data.len()
+            )));
+        }
+
+        let fourcc: [u8; 4] = data[0..4].try_into().expect("cannot fail, checked above");
+        let identifier: [u8; 4] = data[4..8].try_into().expect("cannot fail, checked above");
+        if fourcc != crate::rrd::RRD_FOURCC || identifier != crate::rrd::RRD_FOOTER_IDENTIFIER {
+            return Err(crate::rrd::CodecError::InvalidFooter(format!(
+                "unexpected magic bytes: {fourcc:?}{identifier:?}"
+            )));
+        }
+
+        let read_u64 = |range: std::ops::Range<usize>| {
+            u64::from_le_bytes(data[range].try_into().expect("cannot fail, checked above"))
+        };
+
+        Ok(Self {
+            fourcc,
+            identifier,
+            manifest_byte_span: re_chunk::Span {
+                start: read_u64(8..16),
+                len: read_u64(16..24),
+            },
+            stream_len: read_u64(24..32),
+        })
+    }
+}
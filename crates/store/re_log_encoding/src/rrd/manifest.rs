@@ -0,0 +1,477 @@
+//! The per-message index that can optionally be appended to an RRD stream.
+//!
+//! See [`RrdManifest`] and [`crate::rrd::StreamFooter`].
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use arrow::array::{
+    Array as _, ArrayRef, AsArray as _, FixedSizeBinaryArray, Int64Array, ListArray, RecordBatch,
+    RecordBatchOptions, StringArray, StructArray, UInt64Array,
+};
+use arrow::buffer::{NullBuffer, OffsetBuffer};
+use arrow::datatypes::{DataType, Field, Fields, Int64Type, Schema, UInt64Type};
+
+use re_chunk::{Chunk, ChunkId, ComponentIdentifier, EntityPath, Span, TimelineName};
+use re_log_types::{AbsoluteTimeRange, LogMsg, StoreId, StoreKind};
+
+use crate::rrd::{CodecError, Decodable, Encodable, MessageKind};
+
+// ---
+
+/// An index of all the messages in an RRD stream, and of the chunks they carry.
+///
+/// This is what makes it possible to fetch specific chunks out of an RRD file without having to
+/// decode everything that comes before them, see [`crate::DecoderSeekable`].
+///
+/// It is serialized as a single Arrow record batch, with one row per entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RrdManifest {
+    /// All messages of the stream, in stream order.
+    pub entries: Vec<RrdManifestEntry>,
+}
+
+/// The location and description of a single message in an RRD stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RrdManifestEntry {
+    /// Where the message is, [`crate::rrd::MessageHeader`] included, relative to the start of the stream.
+    pub byte_span: Span<u64>,
+
+    pub kind: MessageKind,
+
+    pub store_id: StoreId,
+
+    /// Only set for [`MessageKind::ArrowMsg`].
+    pub chunk: Option<RrdManifestChunk>,
+}
+
+/// Describes the chunk carried by a [`RrdManifestEntry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RrdManifestChunk {
+    pub chunk_id: ChunkId,
+
+    pub entity_path: EntityPath,
+
+    pub num_rows: u64,
+
+    /// The size of the (uncompressed) Arrow data of the chunk.
+    pub size_bytes: u64,
+
+    /// Sorted.
+    pub components: Vec<ComponentIdentifier>,
+
+    /// The time range covered by the chunk on each of its timelines.
+    ///
+    /// Empty for static chunks.
+    pub time_ranges: BTreeMap<TimelineName, AbsoluteTimeRange>,
+}
+
+impl RrdManifestChunk {
+    pub fn from_chunk(chunk: &Chunk, size_bytes: u64) -> Self {
+        let mut components: Vec<_> = chunk.components_identifiers().collect();
+        components.sort();
+
+        Self {
+            chunk_id: chunk.id(),
+            entity_path: chunk.entity_path().clone(),
+            num_rows: chunk.num_rows() as u64,
+            size_bytes,
+            components,
+            time_ranges: chunk
+                .timelines()
+                .iter()
+                .map(|(name, column)| (*name, column.time_range()))
+                .collect(),
+        }
+    }
+
+    #[inline]
+    pub fn is_static(&self) -> bool {
+        self.time_ranges.is_empty()
+    }
+}
+
+impl RrdManifestEntry {
+    /// Describes an application-level message that was encoded at `byte_span`.
+    pub fn from_log_msg(msg: &LogMsg, byte_span: Span<u64>) -> Result<Self, CodecError> {
+        Ok(match msg {
+            LogMsg::SetStoreInfo(msg) => Self {
+                byte_span,
+                kind: MessageKind::SetStoreInfo,
+                store_id: msg.info.store_id.clone(),
+                chunk: None,
+            },
+
+            LogMsg::ArrowMsg(store_id, msg) => {
+                let chunk = Chunk::from_arrow_msg(msg)
+                    .map_err(|err| CodecError::InvalidFooter(err.to_string()))?;
+                Self {
+                    byte_span,
+                    kind: MessageKind::ArrowMsg,
+                    store_id: store_id.clone(),
+                    chunk: Some(RrdManifestChunk::from_chunk(
+                        &chunk,
+                        msg.batch.get_array_memory_size() as u64,
+                    )),
+                }
+            }
+
+            LogMsg::BlueprintActivationCommand(msg) => Self {
+                byte_span,
+                kind: MessageKind::BlueprintActivationCommand,
+                store_id: msg.blueprint_id.clone(),
+                chunk: None,
+            },
+        })
+    }
+
+    /// Whether this message is needed to answer a query for `range` on `timeline`.
+    ///
+    /// Messages that don't carry chunks, as well as static chunks, are always relevant.
+    pub fn is_relevant_for(&self, timeline: &TimelineName, range: AbsoluteTimeRange) -> bool {
+        let Some(chunk) = &self.chunk else {
+            return true;
+        };
+
+        chunk.is_static()
+            || chunk
+                .time_ranges
+                .get(timeline)
+                .is_some_and(|chunk_range| chunk_range.intersects(range))
+    }
+}
+
+impl RrdManifest {
+    /// All the entries that are needed to answer a query for `range` on `timeline`, in stream order.
+    ///
+    /// See [`RrdManifestEntry::is_relevant_for`].
+    pub fn entries_for_range<'a>(
+        &'a self,
+        timeline: &'a TimelineName,
+        range: AbsoluteTimeRange,
+    ) -> impl Iterator<Item = &'a RrdManifestEntry> + 'a {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.is_relevant_for(timeline, range))
+    }
+
+    /// All the chunks in this stream, in stream order.
+    pub fn chunks(&self) -> impl Iterator<Item = (&RrdManifestEntry, &RrdManifestChunk)> {
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.chunk.as_ref().map(|chunk| (entry, chunk)))
+    }
+}
+
+// --- Arrow ---
+
+mod fields {
+    pub const BYTE_OFFSET: &str = "byte_offset";
+    pub const BYTE_LEN: &str = "byte_len";
+    pub const KIND: &str = "kind";
+    pub const STORE_KIND: &str = "store_kind";
+    pub const APPLICATION_ID: &str = "application_id";
+    pub const RECORDING_ID: &str = "recording_id";
+    pub const CHUNK_ID: &str = "chunk_id";
+    pub const ENTITY_PATH: &str = "entity_path";
+    pub const NUM_ROWS: &str = "num_rows";
+    pub const SIZE_BYTES: &str = "size_bytes";
+    pub const COMPONENTS: &str = "components";
+    pub const TIME_RANGES: &str = "time_ranges";
+
+    pub const TIMELINE: &str = "timeline";
+    pub const MIN: &str = "min";
+    pub const MAX: &str = "max";
+}
+
+fn time_range_fields() -> Fields {
+    Fields::from(vec![
+        Field::new(fields::TIMELINE, DataType::Utf8, false),
+        Field::new(fields::MIN, DataType::Int64, false),
+        Field::new(fields::MAX, DataType::Int64, false),
+    ])
+}
+
+impl RrdManifest {
+    pub fn to_record_batch(&self) -> Result<RecordBatch, CodecError> {
+        re_tracing::profile_function!();
+
+        let entries = &self.entries;
+        let chunks = || entries.iter().map(|entry| entry.chunk.as_ref());
+
+        let byte_offsets = UInt64Array::from_iter_values(entries.iter().map(|e| e.byte_span.start));
+        let byte_lens = UInt64Array::from_iter_values(entries.iter().map(|e| e.byte_span.len));
+        let kinds = UInt64Array::from_iter_values(entries.iter().map(|e| e.kind as u64));
+        let store_kinds =
+            StringArray::from_iter_values(entries.iter().map(|e| e.store_id.kind().to_string()));
+        let application_ids = StringArray::from_iter_values(
+            entries.iter().map(|e| e.store_id.application_id().as_str()),
+        );
+        let recording_ids = StringArray::from_iter_values(
+            entries.iter().map(|e| e.store_id.recording_id().as_str()),
+        );
+
+        let chunk_ids = FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            chunks()
+                .map(|chunk| chunk.map(|chunk| chunk.chunk_id.as_tuid().as_u128().to_be_bytes())),
+            16,
+        )
+        .map_err(CodecError::ArrowSerialization)?;
+        let entity_paths: StringArray = chunks()
+            .map(|chunk| chunk.map(|chunk| chunk.entity_path.to_string()))
+            .collect();
+        let num_rows: UInt64Array = chunks().map(|chunk| chunk.map(|c| c.num_rows)).collect();
+        let size_bytes: UInt64Array = chunks().map(|chunk| chunk.map(|c| c.size_bytes)).collect();
+
+        let validity: NullBuffer = chunks().map(|chunk| chunk.is_some()).collect();
+
+        let components = {
+            let values = StringArray::from(
+                chunks()
+                    .flatten()
+                    .flat_map(|chunk| chunk.components.iter().map(|c| c.as_str()))
+                    .collect::<Vec<_>>(),
+            );
+            ListArray::try_new(
+                Arc::new(Field::new_list_field(DataType::Utf8, false)),
+                OffsetBuffer::from_lengths(
+                    chunks().map(|chunk| chunk.map_or(0, |chunk| chunk.components.len())),
+                ),
+                Arc::new(values),
+                Some(validity.clone()),
+            )
+            .map_err(CodecError::ArrowSerialization)?
+        };
+
+        let time_ranges = {
+            let ranges = || {
+                chunks()
+                    .flatten()
+                    .flat_map(|chunk| chunk.time_ranges.iter())
+            };
+            let values = StructArray::try_new(
+                time_range_fields(),
+                vec![
+                    Arc::new(StringArray::from(
+                        ranges()
+                            .map(|(timeline, _)| timeline.as_str())
+                            .collect::<Vec<_>>(),
+                    )) as ArrayRef,
+                    Arc::new(Int64Array::from_iter_values(
+                        ranges().map(|(_, range)| range.min().as_i64()),
+                    )),
+                    Arc::new(Int64Array::from_iter_values(
+                        ranges().map(|(_, range)| range.max().as_i64()),
+                    )),
+                ],
+                None,
+            )
+            .map_err(CodecError::ArrowSerialization)?;
+            ListArray::try_new(
+                Arc::new(Field::new_list_field(
+                    DataType::Struct(time_range_fields()),
+                    false,
+                )),
+                OffsetBuffer::from_lengths(
+                    chunks().map(|chunk| chunk.map_or(0, |chunk| chunk.time_ranges.len())),
+                ),
+                Arc::new(values),
+                Some(validity),
+            )
+            .map_err(CodecError::ArrowSerialization)?
+        };
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(byte_offsets),
+            Arc::new(byte_lens),
+            Arc::new(kinds),
+            Arc::new(store_kinds),
+            Arc::new(application_ids),
+            Arc::new(recording_ids),
+            Arc::new(chunk_ids),
+            Arc::new(entity_paths),
+            Arc::new(num_rows),
+            Arc::new(size_bytes),
+            Arc::new(components),
+            Arc::new(time_ranges),
+        ];
+
+        let schema = Schema::new_with_metadata(
+            vec![
+                Field::new(fields::BYTE_OFFSET, DataType::UInt64, false),
+                Field::new(fields::BYTE_LEN, DataType::UInt64, false),
+                Field::new(fields::KIND, DataType::UInt64, false),
+                Field::new(fields::STORE_KIND, DataType::Utf8, false),
+                Field::new(fields::APPLICATION_ID, DataType::Utf8, false),
+                Field::new(fields::RECORDING_ID, DataType::Utf8, false),
+                Field::new(fields::CHUNK_ID, DataType::FixedSizeBinary(16), true),
+                Field::new(fields::ENTITY_PATH, DataType::Utf8, true),
+                Field::new(fields::NUM_ROWS, DataType::UInt64, true),
+                Field::new(fields::SIZE_BYTES, DataType::UInt64, true),
+                Field::new(fields::COMPONENTS, columns[10].data_type().clone(), true),
+                Field::new(fields::TIME_RANGES, columns[11].data_type().clone(), true),
+            ],
+            Default::default(),
+        );
+
+        RecordBatch::try_new_with_options(
+            Arc::new(schema),
+            columns,
+            &RecordBatchOptions::new().with_row_count(Some(entries.len())),
+        )
+        .map_err(CodecError::ArrowSerialization)
+    }
+
+    pub fn from_record_batch(batch: &RecordBatch) -> Result<Self, CodecError> {
+        re_tracing::profile_function!();
+
+        fn invalid(what: impl std::fmt::Display) -> CodecError {
+            CodecError::InvalidFooter(format!("invalid manifest: {what}"))
+        }
+
+        let column = |name: &str| {
+            batch
+                .column_by_name(name)
+                .ok_or_else(|| invalid(format!("missing column {name:?}")))
+        };
+        let u64_column = |name: &str| {
+            column(name)?
+                .as_primitive_opt::<UInt64Type>()
+                .ok_or_else(|| invalid(format!("column {name:?} is not u64")))
+        };
+        let string_column = |name: &str| {
+            column(name)?
+                .as_string_opt::<i32>()
+                .ok_or_else(|| invalid(format!("column {name:?} is not utf8")))
+        };
+        let list_column = |name: &str| {
+            column(name)?
+                .as_list_opt::<i32>()
+                .ok_or_else(|| invalid(format!("column {name:?} is not a list")))
+        };
+
+        let byte_offsets = u64_column(fields::BYTE_OFFSET)?;
+        let byte_lens = u64_column(fields::BYTE_LEN)?;
+        let kinds = u64_column(fields::KIND)?;
+        let store_kinds = string_column(fields::STORE_KIND)?;
+        let application_ids = string_column(fields::APPLICATION_ID)?;
+        let recording_ids = string_column(fields::RECORDING_ID)?;
+        let chunk_ids = column(fields::CHUNK_ID)?
+            .as_fixed_size_binary_opt()
+            .ok_or_else(|| invalid("column \"chunk_id\" is not fixed-size binary"))?;
+        let entity_paths = string_column(fields::ENTITY_PATH)?;
+        let num_rows = u64_column(fields::NUM_ROWS)?;
+        let size_bytes = u64_column(fields::SIZE_BYTES)?;
+        let components = list_column(fields::COMPONENTS)?;
+        let time_ranges = list_column(fields::TIME_RANGES)?;
+
+        let entries = (0..batch.num_rows())
+            .map(|row| {
+                let kind = MessageKind::from_u64(kinds.value(row))
+                    .ok_or_else(|| invalid(format!("unknown message kind {}", kinds.value(row))))?;
+                let store_kind: StoreKind = store_kinds.value(row).parse().map_err(invalid)?;
+                let store_id = StoreId::new(
+                    store_kind,
+                    application_ids.value(row),
+                    recording_ids.value(row),
+                );
+
+                let chunk = if chunk_ids.is_valid(row) {
+                    let chunk_id = u128::from_be_bytes(
+                        chunk_ids
+                            .value(row)
+                            .try_into()
+                            .map_err(|_err| invalid("chunk ID must be 16 bytes"))?,
+                    );
+
+                    let components = components.value(row);
+                    let components = components
+                        .as_string_opt::<i32>()
+                        .ok_or_else(|| invalid("components must be utf8"))?
+                        .iter()
+                        .flatten()
+                        .map(ComponentIdentifier::from)
+                        .collect();
+
+                    let time_ranges = time_ranges.value(row);
+                    let time_ranges = time_ranges
+                        .as_struct_opt()
+                        .ok_or_else(|| invalid("time ranges must be structs"))?;
+                    let timelines = time_ranges
+                        .column_by_name(fields::TIMELINE)
+                        .and_then(|array| array.as_string_opt::<i32>())
+                        .ok_or_else(|| invalid("time ranges are missing their timeline"))?;
+                    let mins = time_ranges
+                        .column_by_name(fields::MIN)
+                        .and_then(|array| array.as_primitive_opt::<Int64Type>())
+                        .ok_or_else(|| invalid("time ranges are missing their min"))?;
+                    let maxs = time_ranges
+                        .column_by_name(fields::MAX)
+                        .and_then(|array| array.as_primitive_opt::<Int64Type>())
+                        .ok_or_else(|| invalid("time ranges are missing their max"))?;
+                    let time_ranges = (0..time_ranges.len())
+                        .map(|i| {
+                            (
+                                TimelineName::new(timelines.value(i)),
+                                AbsoluteTimeRange::new(mins.value(i), maxs.value(i)),
+                            )
+                        })
+                        .collect();
+
+                    Some(RrdManifestChunk {
+                        chunk_id: ChunkId::from_u128(chunk_id),
+                        entity_path: EntityPath::parse_forgiving(entity_paths.value(row)),
+                        num_rows: num_rows.value(row),
+                        size_bytes: size_bytes.value(row),
+                        components,
+                        time_ranges,
+                    })
+                } else {
+                    None
+                };
+
+                Ok(RrdManifestEntry {
+                    byte_span: Span {
+                        start: byte_offsets.value(row),
+                        len: byte_lens.value(row),
+                    },
+                    kind,
+                    store_id,
+                    chunk,
+                })
+            })
+            .collect::<Result<_, CodecError>>()?;
+
+        Ok(Self { entries })
+    }
+}
+
+impl Encodable for RrdManifest {
+    fn to_rrd_bytes(&self, out: &mut Vec<u8>) -> Result<u64, CodecError> {
+        let batch = self.to_record_batch()?;
+
+        let before = out.len() as u64;
+
+        let mut sw = ::arrow::ipc::writer::StreamWriter::try_new(&mut *out, batch.schema_ref())
+            .map_err(CodecError::ArrowSerialization)?;
+        sw.write(&batch).map_err(CodecError::ArrowSerialization)?;
+        sw.finish().map_err(CodecError::ArrowSerialization)?;
+        drop(sw);
+
+        Ok(out.len() as u64 - before)
+    }
+}
+
+impl Decodable for RrdManifest {
+    fn from_rrd_bytes(data: &[u8]) -> Result<Self, CodecError> {
+        let mut stream = ::arrow::ipc::reader::StreamReader::try_new(data, None)
+            .map_err(CodecError::ArrowDeserialization)?;
+
+        let batch = stream
+            .next()
+            .ok_or(CodecError::MissingRecordBatch)?
+            .map_err(CodecError::ArrowDeserialization)?;
+
+        Self::from_record_batch(&batch)
+    }
+}
@@ -18,6 +18,7 @@
 mod errors;
 mod headers;
 mod log_msg;
+mod manifest;
 
 #[cfg(feature = "decoder")]
 mod decoder;
@@ -35,13 +36,14 @@ pub mod stream_from_http;
 pub use self::errors::{CodecError, NotAnRrdError, OptionsError};
 pub use self::headers::{
     Compression, CrateVersion, EncodingOptions, MessageHeader, MessageKind, Serializer,
-    StreamHeader,
+    StreamFooter, StreamHeader, ZSTD_DEFAULT_LEVEL,
 };
+pub use self::manifest::{RrdManifest, RrdManifestChunk, RrdManifestEntry};
 
 #[cfg(feature = "decoder")]
 pub use self::decoder::{
-    DecodeError, Decoder, DecoderApp, DecoderEntrypoint, DecoderIterator, DecoderStream,
-    DecoderTransport,
+    DecodeError, Decoder, DecoderApp, DecoderEntrypoint, DecoderIterator, DecoderSeekable,
+    DecoderStream, DecoderTransport,
 };
 
 #[cfg(feature = "encoder")]
@@ -49,7 +51,7 @@ pub use self::encoder::{EncodeError, Encoder};
 
 #[cfg(feature = "encoder")]
 #[cfg(not(target_arch = "wasm32"))]
-pub use self::file_sink::{FileFlushError, FileSink, FileSinkError};
+pub use self::file_sink::{FileFlushError, FileSink, FileSinkError, FileSinkOptions};
 
 // ---
 
@@ -59,6 +61,9 @@ pub const RRD_FOURCC: [u8; 4] = *b"RRF2";
 /// Previously used `FourCC`s for Rerun RRD files.
 pub const OLD_RRD_FOURCC: &[[u8; 4]] = &[*b"RRF0", *b"RRF1"];
 
+/// Follows [`RRD_FOURCC`] in a [`StreamFooter`].
+pub const RRD_FOOTER_IDENTIFIER: [u8; 4] = *b"FOOT";
+
 // ---
 
 /// Encodes transport-level types (i.e. Protobuf objects) into RRD bytes.
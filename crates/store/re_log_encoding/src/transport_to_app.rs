@@ -35,7 +35,7 @@ impl ToTransport for re_log_types::LogMsg {
     type Context<'a> = crate::rrd::Compression;
 
     fn to_transport(&self, compression: Self::Context<'_>) -> Result<Self::Output, CodecError> {
-        log_msg_to_proto(self, compression)
+        log_msg_to_proto(self, compression, crate::rrd::ZSTD_DEFAULT_LEVEL)
     }
 }
 
@@ -47,7 +47,7 @@ impl ToTransport for re_log_types::ArrowMsg {
         &self,
         (store_id, compression): Self::Context<'_>,
     ) -> Result<Self::Output, CodecError> {
-        arrow_msg_to_proto(self, store_id, compression)
+        arrow_msg_to_proto(self, store_id, compression, crate::rrd::ZSTD_DEFAULT_LEVEL)
     }
 }
 
@@ -238,10 +238,13 @@ fn arrow_msg_to_app(
 }
 
 /// Converts an application-level `LogMsg` to its transport-level counterpart.
+///
+/// `zstd_level` is only used with [`crate::rrd::Compression::Zstd`].
 #[tracing::instrument(level = "trace", skip_all)]
-fn log_msg_to_proto(
+pub(crate) fn log_msg_to_proto(
     message: &re_log_types::LogMsg,
     compression: crate::rrd::Compression,
+    zstd_level: i32,
 ) -> Result<re_protos::log_msg::v1alpha1::log_msg::Msg, CodecError> {
     re_tracing::profile_function!();
 
@@ -251,7 +254,8 @@ fn log_msg_to_proto(
         }
 
         re_log_types::LogMsg::ArrowMsg(store_id, arrow_msg) => {
-            let arrow_msg = arrow_msg_to_proto(arrow_msg, store_id.clone(), compression)?;
+            let arrow_msg =
+                arrow_msg_to_proto(arrow_msg, store_id.clone(), compression, zstd_level)?;
             re_protos::log_msg::v1alpha1::log_msg::Msg::ArrowMsg(arrow_msg)
         }
 
@@ -271,6 +275,7 @@ fn arrow_msg_to_proto(
     arrow_msg: &re_log_types::ArrowMsg,
     store_id: re_log_types::StoreId,
     compression: crate::rrd::Compression,
+    zstd_level: i32,
 ) -> Result<re_protos::log_msg::v1alpha1::ArrowMsg, CodecError> {
     re_tracing::profile_function!();
 
@@ -280,7 +285,7 @@ fn arrow_msg_to_proto(
         on_release: _,
     } = arrow_msg;
 
-    let payload = encode_arrow(batch, compression)?;
+    let payload = encode_arrow(batch, compression, zstd_level)?;
 
     Ok(re_protos::log_msg::v1alpha1::ArrowMsg {
         store_id: Some(store_id.into()),
@@ -305,6 +310,7 @@ struct EncodedArrowRecordBatch {
 fn encode_arrow(
     batch: &arrow::array::RecordBatch,
     compression: crate::rrd::Compression,
+    zstd_level: i32,
 ) -> Result<EncodedArrowRecordBatch, CodecError> {
     re_tracing::profile_function!();
 
@@ -337,6 +343,11 @@ fn encode_arrow(
             let _span = tracing::trace_span!("lz4::compress").entered();
             lz4_flex::block::compress(&uncompressed)
         }
+        crate::rrd::Compression::Zstd => {
+            re_tracing::profile_scope!("zstd::compress");
+            let _span = tracing::trace_span!("zstd::compress").entered();
+            zstd_compress(&uncompressed, zstd_level)?
+        }
     };
 
     Ok(EncodedArrowRecordBatch {
@@ -366,6 +377,14 @@ fn decode_arrow(
             lz4_flex::block::decompress_into(data, &mut uncompressed)?;
             uncompressed.as_slice()
         }
+        crate::rrd::Compression::Zstd => {
+            re_tracing::profile_scope!("zstd-decompress");
+            let _span = tracing::trace_span!("zstd::decompress").entered();
+            uncompressed.resize(uncompressed_size, 0);
+            let num_decompressed = zstd_decompress_into(data, &mut uncompressed)?;
+            uncompressed.truncate(num_decompressed);
+            uncompressed.as_slice()
+        }
     };
 
     let mut stream = {
@@ -380,3 +399,33 @@ fn decode_arrow(
         .ok_or(CodecError::MissingRecordBatch)?
         .map_err(CodecError::ArrowDeserialization)
 }
+
+// The reference C implementation is only used natively: the web viewer only ever needs to decode
+// Zstd data, which the pure-Rust `ruzstd` does without pulling a C toolchain into wasm builds.
+
+#[cfg(not(target_arch = "wasm32"))]
+fn zstd_compress(data: &[u8], level: i32) -> Result<Vec<u8>, CodecError> {
+    zstd::bulk::compress(data, level).map_err(CodecError::Zstd)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn zstd_compress(_data: &[u8], _level: i32) -> Result<Vec<u8>, CodecError> {
+    Err(CodecError::Zstd(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "Zstd compression is not supported on the web",
+    )))
+}
+
+/// Returns the number of decompressed bytes written to `out`.
+#[cfg(not(target_arch = "wasm32"))]
+fn zstd_decompress_into(data: &[u8], out: &mut [u8]) -> Result<usize, CodecError> {
+    zstd::bulk::decompress_to_buffer(data, out).map_err(CodecError::Zstd)
+}
+
+/// Returns the number of decompressed bytes written to `out`.
+#[cfg(target_arch = "wasm32")]
+fn zstd_decompress_into(data: &[u8], out: &mut [u8]) -> Result<usize, CodecError> {
+    ruzstd::decoding::FrameDecoder::new()
+        .decode_all(data, out)
+        .map_err(|err| CodecError::Zstd(std::io::Error::other(err)))
+}
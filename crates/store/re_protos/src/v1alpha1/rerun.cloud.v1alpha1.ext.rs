@@ -2,7 +2,7 @@ use prost::Name as _;
 use std::sync::Arc;
 
 use arrow::array::{
-    BinaryArray, BooleanArray, FixedSizeBinaryBuilder, ListBuilder, RecordBatchOptions,
+    BinaryArray, BooleanArray, FixedSizeBinaryBuilder, Int64Array, ListBuilder, RecordBatchOptions,
     StringBuilder, UInt8Array, UInt64Array,
 };
 use arrow::datatypes::FieldRef;
@@ -20,7 +20,7 @@ use re_sorbet::ComponentColumnDescriptor;
 use crate::cloud::v1alpha1::{
     EntryKind, FetchChunksRequest, GetDatasetSchemaResponse, QueryDatasetResponse,
     QueryTasksResponse, RegisterWithDatasetResponse, ScanDatasetManifestResponse,
-    ScanPartitionTableResponse, VectorDistanceMetric,
+    ScanPartitionTableResponse, SearchDatasetResponse, VectorDistanceMetric,
 };
 use crate::common::v1alpha1::{
     ComponentDescriptor, DataframePart, TaskId,
@@ -250,6 +250,18 @@ impl QueryDatasetResponse {
         )
     }
 
+    /// Name of the optional column holding the start of the time range covered by each chunk on
+    /// the given index. Chunks which don't have that index are null.
+    pub fn field_name_index_start(index: &str) -> String {
+        format!("{index}:start")
+    }
+
+    /// Name of the optional column holding the end of the time range covered by each chunk on
+    /// the given index. Chunks which don't have that index are null.
+    pub fn field_name_index_end(index: &str) -> String {
+        format!("{index}:end")
+    }
+
     pub fn fields() -> Vec<FieldRef> {
         vec![
             Self::field_chunk_id(),
@@ -297,6 +309,43 @@ impl QueryDatasetResponse {
             &RecordBatchOptions::default().with_row_count(Some(chunk_ids.len())),
         )
     }
+
+    /// Appends the optional per-index time range columns to a dataframe created with
+    /// [`Self::create_dataframe`].
+    ///
+    /// See [`Self::field_name_index_start`] and [`Self::field_name_index_end`].
+    pub fn append_index_ranges(
+        batch: &RecordBatch,
+        index_ranges: impl IntoIterator<Item = (String, Vec<Option<i64>>, Vec<Option<i64>>)>,
+    ) -> arrow::error::Result<RecordBatch> {
+        let mut fields = batch.schema().fields().to_vec();
+        let mut columns = batch.columns().to_vec();
+
+        for (index, starts, ends) in index_ranges {
+            fields.push(Arc::new(Field::new(
+                Self::field_name_index_start(&index),
+                DataType::Int64,
+                true,
+            )));
+            columns.push(Arc::new(Int64Array::from(starts)));
+
+            fields.push(Arc::new(Field::new(
+                Self::field_name_index_end(&index),
+                DataType::Int64,
+                true,
+            )));
+            columns.push(Arc::new(Int64Array::from(ends)));
+        }
+
+        RecordBatch::try_new_with_options(
+            Arc::new(Schema::new_with_metadata(
+                fields,
+                batch.schema().metadata().clone(),
+            )),
+            columns,
+            &RecordBatchOptions::default().with_row_count(Some(batch.num_rows())),
+        )
+    }
 }
 
 impl FetchChunksRequest {
@@ -1652,6 +1701,20 @@ impl ScanPartitionTableResponse {
     }
 }
 
+// --- SearchDatasetResponse --
+
+impl SearchDatasetResponse {
+    // These columns are returned by `SearchDataset`, alongside the index column and the searched
+    // component column.
+    pub const FIELD_PARTITION_ID: &str = "rerun_partition_id";
+
+    /// Relevance of full-text search results. Higher is better.
+    pub const FIELD_SCORE: &str = "_score";
+
+    /// Distance of vector search results to the query. Lower is better.
+    pub const FIELD_DISTANCE: &str = "_distance";
+}
+
 // --- ScanDatasetManifestResponse --
 
 impl ScanDatasetManifestResponse {
@@ -1788,6 +1851,7 @@ impl ScanDatasetManifestResponse {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum DataSourceKind {
     Rrd = 1,
+    Mcap = 2,
 }
 
 impl TryFrom<crate::cloud::v1alpha1::DataSourceKind> for DataSourceKind {
@@ -1796,6 +1860,7 @@ impl TryFrom<crate::cloud::v1alpha1::DataSourceKind> for DataSourceKind {
     fn try_from(kind: crate::cloud::v1alpha1::DataSourceKind) -> Result<Self, Self::Error> {
         match kind {
             crate::cloud::v1alpha1::DataSourceKind::Rrd => Ok(Self::Rrd),
+            crate::cloud::v1alpha1::DataSourceKind::Mcap => Ok(Self::Mcap),
 
             crate::cloud::v1alpha1::DataSourceKind::Unspecified => {
                 return Err(TypeConversionError::InvalidField {
@@ -1822,66 +1887,71 @@ impl From<DataSourceKind> for crate::cloud::v1alpha1::DataSourceKind {
     fn from(value: DataSourceKind) -> Self {
         match value {
             DataSourceKind::Rrd => Self::Rrd,
+            DataSourceKind::Mcap => Self::Mcap,
         }
     }
 }
 
 impl DataSourceKind {
-    pub fn to_arrow(self) -> ArrayRef {
+    /// The name used for this kind in dataframes, which is also its usual file extension.
+    pub fn as_str(self) -> &'static str {
         match self {
-            Self::Rrd => {
-                let rec_type = StringArray::from_iter_values(["rrd".to_owned()]);
-                Arc::new(rec_type)
-            }
+            Self::Rrd => "rrd",
+            Self::Mcap => "mcap",
         }
     }
 
-    pub fn many_to_arrow(types: Vec<Self>) -> ArrayRef {
-        let data = types
+    /// Guesses the kind of a data source from its file extension (case insensitive).
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        [Self::Rrd, Self::Mcap]
             .into_iter()
-            .map(|typ| match typ {
-                Self::Rrd => "rrd",
-            })
-            .collect::<Vec<_>>();
-        Arc::new(StringArray::from(data))
+            .find(|kind| extension.eq_ignore_ascii_case(kind.as_str()))
     }
 
-    pub fn from_arrow(array: &dyn Array) -> Result<Self, TypeConversionError> {
-        let resource_type = array.try_downcast_array_ref::<StringArray>()?.value(0);
-
+    fn from_name(resource_type: &str) -> Result<Self, TypeConversionError> {
         match resource_type {
             "rrd" => Ok(Self::Rrd),
+            "mcap" => Ok(Self::Mcap),
             _ => Err(TypeConversionError::ArrowError(
                 ArrowError::InvalidArgumentError(format!("unknown resource type {resource_type}")),
             )),
         }
     }
 
+    pub fn to_arrow(self) -> ArrayRef {
+        let rec_type = StringArray::from_iter_values([self.as_str()]);
+        Arc::new(rec_type)
+    }
+
+    pub fn many_to_arrow(types: Vec<Self>) -> ArrayRef {
+        let data = types.into_iter().map(Self::as_str).collect::<Vec<_>>();
+        Arc::new(StringArray::from(data))
+    }
+
+    pub fn from_arrow(array: &dyn Array) -> Result<Self, TypeConversionError> {
+        let resource_type = array.try_downcast_array_ref::<StringArray>()?.value(0);
+        Self::from_name(resource_type)
+    }
+
     pub fn many_from_arrow(array: &dyn Array) -> Result<Vec<Self>, TypeConversionError> {
         let string_array = array.try_downcast_array_ref::<StringArray>()?;
 
         (0..string_array.len())
-            .map(|i| {
-                let resource_type = string_array.value(i);
-                match resource_type {
-                    "rrd" => Ok(Self::Rrd),
-                    _ => Err(TypeConversionError::ArrowError(
-                        ArrowError::InvalidArgumentError(format!(
-                            "unknown resource type {resource_type}"
-                        )),
-                    )),
-                }
-            })
+            .map(|i| Self::from_name(string_array.value(i)))
             .collect()
     }
 }
 
 #[test]
 fn datasourcekind_roundtrip() {
-    let kind = DataSourceKind::Rrd;
-    let kind: crate::cloud::v1alpha1::DataSourceKind = kind.into();
-    let kind = DataSourceKind::try_from(kind).unwrap();
-    assert_eq!(DataSourceKind::Rrd, kind);
+    for original in [DataSourceKind::Rrd, DataSourceKind::Mcap] {
+        let kind: crate::cloud::v1alpha1::DataSourceKind = original.into();
+        let kind = DataSourceKind::try_from(kind).unwrap();
+        assert_eq!(original, kind);
+
+        let kind = DataSourceKind::from_arrow(&original.to_arrow()).unwrap();
+        assert_eq!(original, kind);
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -1936,6 +2006,24 @@ impl DataSource {
             kind: DataSourceKind::Rrd,
         })
     }
+
+    pub fn new_mcap(storage_url: impl AsRef<str>) -> Result<Self, url::ParseError> {
+        Ok(Self {
+            storage_url: storage_url.as_ref().parse()?,
+            is_prefix: false,
+            layer: Self::DEFAULT_LAYER.to_owned(),
+            kind: DataSourceKind::Mcap,
+        })
+    }
+
+    pub fn new_mcap_prefix(storage_url: impl AsRef<str>) -> Result<Self, url::ParseError> {
+        Ok(Self {
+            storage_url: storage_url.as_ref().parse()?,
+            is_prefix: true,
+            layer: Self::DEFAULT_LAYER.to_owned(),
+            kind: DataSourceKind::Mcap,
+        })
+    }
 }
 
 impl From<DataSource> for crate::cloud::v1alpha1::DataSource {
@@ -2242,7 +2330,7 @@ impl From<IndexColumn> for crate::cloud::v1alpha1::IndexColumn {
 
 // ---
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct IndexConfig {
     pub time_index: re_log_types::TimelineName,
     pub column: IndexColumn,
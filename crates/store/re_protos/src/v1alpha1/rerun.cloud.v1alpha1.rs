@@ -1626,6 +1626,7 @@ impl ErrorCode {
 pub enum DataSourceKind {
     Unspecified = 0,
     Rrd = 1,
+    Mcap = 2,
 }
 impl DataSourceKind {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1636,6 +1637,7 @@ impl DataSourceKind {
         match self {
             Self::Unspecified => "DATA_SOURCE_KIND_UNSPECIFIED",
             Self::Rrd => "DATA_SOURCE_KIND_RRD",
+            Self::Mcap => "DATA_SOURCE_KIND_MCAP",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1643,6 +1645,7 @@ impl DataSourceKind {
         match value {
             "DATA_SOURCE_KIND_UNSPECIFIED" => Some(Self::Unspecified),
             "DATA_SOURCE_KIND_RRD" => Some(Self::Rrd),
+            "DATA_SOURCE_KIND_MCAP" => Some(Self::Mcap),
             _ => None,
         }
     }
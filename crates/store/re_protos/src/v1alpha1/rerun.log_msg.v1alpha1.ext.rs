@@ -10,6 +10,11 @@ pub enum Compression {
 
     /// Very fast compression and decompression, but not very good compression ratio.
     LZ4 = 1,
+
+    /// Much better compression ratio than LZ4, at the cost of slower compression.
+    ///
+    /// Decompression remains fast, which makes this a good fit for archival.
+    Zstd = 2,
 }
 
 impl From<crate::log_msg::v1alpha1::Compression> for Compression {
@@ -18,6 +23,7 @@ impl From<crate::log_msg::v1alpha1::Compression> for Compression {
             crate::log_msg::v1alpha1::Compression::Unspecified
             | crate::log_msg::v1alpha1::Compression::None => Self::Off,
             crate::log_msg::v1alpha1::Compression::Lz4 => Self::LZ4,
+            crate::log_msg::v1alpha1::Compression::Zstd => Self::Zstd,
         }
     }
 }
@@ -27,6 +33,7 @@ impl From<Compression> for crate::log_msg::v1alpha1::Compression {
         match value {
             Compression::Off => Self::None,
             Compression::LZ4 => Self::Lz4,
+            Compression::Zstd => Self::Zstd,
         }
     }
 }
@@ -278,6 +278,8 @@ pub enum Compression {
     None = 1,
     /// LZ4 block compression.
     Lz4 = 2,
+    /// Zstandard compression.
+    Zstd = 3,
 }
 impl Compression {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -289,6 +291,7 @@ impl Compression {
             Self::Unspecified => "COMPRESSION_UNSPECIFIED",
             Self::None => "COMPRESSION_NONE",
             Self::Lz4 => "COMPRESSION_LZ4",
+            Self::Zstd => "COMPRESSION_ZSTD",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -297,6 +300,7 @@ impl Compression {
             "COMPRESSION_UNSPECIFIED" => Some(Self::Unspecified),
             "COMPRESSION_NONE" => Some(Self::None),
             "COMPRESSION_LZ4" => Some(Self::Lz4),
+            "COMPRESSION_ZSTD" => Some(Self::Zstd),
             _ => None,
         }
     }
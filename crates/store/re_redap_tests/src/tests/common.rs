@@ -415,16 +415,18 @@ impl DataSourcesDefinition {
 ///
 /// This function implicitly tests the following properties:
 /// - There is always at least one record batch, even if it is empty.
-/// - All record batches have the same schema.
+/// - All record batches have compatible schemas.
+///
+/// Record batches may have additional columns of their own (e.g. the time range of each chunk on
+/// the indexes of its layer), which end up null in the rows of the other batches.
 pub fn concat_record_batches(record_batches: &[RecordBatch]) -> RecordBatch {
-    arrow::compute::concat_batches(
-        record_batches
-            .first()
-            .expect("at least one record batch must be passed")
-            .schema_ref(),
-        record_batches,
-    )
-    .expect("record batches should be concatenable")
-    .auto_sort_rows()
-    .expect("record batches should be sortable")
+    assert!(
+        !record_batches.is_empty(),
+        "at least one record batch must be passed"
+    );
+
+    re_arrow_util::concat_polymorphic_batches(record_batches)
+        .expect("record batches should be concatenable")
+        .auto_sort_rows()
+        .expect("record batches should be sortable")
 }
@@ -66,6 +66,18 @@ macro_rules! define_redap_tests {
                     }
                 )*
             };
+
+            // Same as above, for builders returning a `(service, fixture)` pair. The fixture (e.g. a
+            // temporary directory) is kept alive until the test completes.
+            ($builder:ident, with_fixture) => {
+                $(
+                    #[tokio::test]
+                    async fn $test() {
+                        let (service, _fixture) = $builder().await;
+                        $crate::$test(service).await
+                    }
+                )*
+            };
         }
     };
 }
@@ -1,8 +1,11 @@
 use futures::StreamExt as _;
 
+use re_chunk::ChunkId;
+use re_log_types::{AbsoluteTimeRange, TimeInt};
 use re_protos::{
     cloud::v1alpha1::{
-        QueryDatasetResponse, ext::QueryDatasetRequest,
+        QueryDatasetResponse,
+        ext::{Query, QueryDatasetRequest, QueryLatestAt, QueryRange},
         rerun_cloud_service_server::RerunCloudService,
     },
     headers::RerunHeadersInjectorExt as _,
@@ -62,7 +65,51 @@ pub async fn query_simple_dataset(service: impl RerunCloudService) {
             },
             "single_entity",
         ),
-        //TODO(RR-2613): add more test cases here when they are supported by OSS server
+        (
+            QueryDatasetRequest {
+                chunk_ids: vec![
+                    ChunkId::from_u128(0x0000_0000_0000_0001_0000_0000_0000_0002),
+                    ChunkId::from_u128(0x0000_0000_0000_0003_0000_0000_0000_0005),
+                ],
+                ..Default::default()
+            },
+            "chunk_ids",
+        ),
+        (
+            QueryDatasetRequest {
+                fuzzy_descriptors: vec!["labels".to_owned()],
+                ..Default::default()
+            },
+            "fuzzy_descriptors",
+        ),
+        (
+            QueryDatasetRequest {
+                exclude_static_data: true,
+                ..Default::default()
+            },
+            "exclude_static",
+        ),
+        (
+            QueryDatasetRequest {
+                exclude_temporal_data: true,
+                ..Default::default()
+            },
+            "exclude_temporal",
+        ),
+        (
+            QueryDatasetRequest {
+                query: Some(latest_at_query("frame_nr", 5)),
+                ..Default::default()
+            },
+            "latest_at_early",
+        ),
+        (
+            QueryDatasetRequest {
+                query: Some(range_query("frame_nr", 50, 60)),
+                ..Default::default()
+            },
+            "range_late",
+        ),
     ];
 
     for (request, snapshot_name) in requests {
@@ -143,6 +190,26 @@ pub async fn query_dataset_should_fail(service: impl RerunCloudService) {
 
 // ---
 
+fn latest_at_query(timeline: &str, at: i64) -> Query {
+    Query {
+        latest_at: Some(QueryLatestAt {
+            index: Some(timeline.to_owned()),
+            at: TimeInt::new_temporal(at),
+        }),
+        ..Default::default()
+    }
+}
+
+fn range_query(timeline: &str, min: i64, max: i64) -> Query {
+    Query {
+        range: Some(QueryRange {
+            index: timeline.to_owned(),
+            index_range: AbsoluteTimeRange::new(min, max),
+        }),
+        ..Default::default()
+    }
+}
+
 async fn query_dataset_snapshot(
     service: &impl RerunCloudService,
     query_dataset_request: QueryDatasetRequest,
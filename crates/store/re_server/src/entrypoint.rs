@@ -22,7 +22,7 @@ pub struct Args {
     #[clap(long, short = 'p', default_value_t = 51234)]
     pub port: u16,
 
-    /// Load a directory of RRD as dataset (can be specified multiple times).
+    /// Load a directory of RRD and MCAP files as dataset (can be specified multiple times).
     /// You can specify only a path or provide a name such as
     /// `-d my_dataset=./path/to/files`
     #[clap(long = "dataset", short = 'd')]
@@ -33,6 +33,13 @@ pub struct Args {
     /// `-t my_table=./path/to/table`
     #[clap(long = "table", short = 't')]
     pub tables: Vec<NamedPath>,
+
+    /// Persist datasets in this directory instead of only keeping them in memory.
+    ///
+    /// Datasets already stored there are loaded back on startup, and the data they contain is
+    /// only read from disk when needed.
+    #[clap(long = "storage-dir")]
+    pub storage_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -63,14 +70,20 @@ impl Args {
     pub async fn create_server_handle(self) -> anyhow::Result<ServerHandle> {
         let rerun_cloud_server = {
             use re_protos::cloud::v1alpha1::rerun_cloud_service_server::RerunCloudServiceServer;
+            use re_protos::common::v1alpha1::ext::IfDuplicateBehavior;
 
             let mut builder = crate::RerunCloudHandlerBuilder::new();
 
+            // When persisting, the datasets were most likely already registered by a previous run.
+            let on_duplicate_dataset = if let Some(storage_dir) = &self.storage_dir {
+                builder = builder.with_storage_directory(storage_dir)?;
+                IfDuplicateBehavior::Skip
+            } else {
+                IfDuplicateBehavior::Error
+            };
+
             for dataset in &self.datasets {
-                builder = builder.with_directory_as_dataset(
-                    dataset,
-                    re_protos::common::v1alpha1::ext::IfDuplicateBehavior::Error,
-                )?;
+                builder = builder.with_directory_as_dataset(dataset, on_duplicate_dataset)?;
             }
 
             #[cfg_attr(not(feature = "lance"), expect(clippy::never_loop))]
@@ -78,10 +91,7 @@ impl Args {
                 cfg_if::cfg_if! {
                     if #[cfg(feature = "lance")] {
                         builder = builder
-                            .with_directory_as_table(
-                                table,
-                                re_protos::common::v1alpha1::ext::IfDuplicateBehavior::Error,
-                            )
+                            .with_directory_as_table(table, IfDuplicateBehavior::Error)
                             .await?;
                     } else {
                         _ = table;
@@ -1,19 +1,19 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
-use ahash::HashMap;
+use ahash::{HashMap, HashSet};
 use arrow::array::BinaryArray;
 use arrow::record_batch::RecordBatch;
 use datafusion::logical_expr::dml::InsertOp;
 use datafusion::prelude::SessionContext;
-use nohash_hasher::IntSet;
+use itertools::Itertools as _;
 use tokio_stream::StreamExt as _;
 use tonic::{Code, Request, Response, Status};
 
 use re_arrow_util::RecordBatchExt as _;
-use re_chunk_store::{Chunk, ChunkStore, ChunkStoreHandle};
+use re_chunk_store::{Chunk, ChunkId, ChunkStore, ChunkStoreHandle};
 use re_log_encoding::ToTransport as _;
-use re_log_types::{EntityPath, EntryId, StoreId, StoreKind};
+use re_log_types::{EntryId, StoreId, StoreKind};
 use re_protos::{
     cloud::v1alpha1::{
         DeleteEntryResponse, EntryDetails, EntryKind, FetchChunksRequest,
@@ -22,7 +22,7 @@ use re_protos::{
         QueryTasksOnCompletionRequest, QueryTasksOnCompletionResponse, QueryTasksRequest,
         QueryTasksResponse, RegisterTableRequest, RegisterTableResponse,
         RegisterWithDatasetResponse, ScanDatasetManifestRequest, ScanDatasetManifestResponse,
-        ScanPartitionTableResponse, ScanTableResponse,
+        ScanPartitionTableResponse, ScanTableResponse, SearchDatasetResponse,
         ext::{
             self, CreateDatasetEntryRequest, CreateDatasetEntryResponse, CreateTableEntryRequest,
             CreateTableEntryResponse, DataSource, DatasetDetails, EntryDetailsUpdate,
@@ -34,13 +34,16 @@ use re_protos::{
     },
     common::v1alpha1::{
         TaskId,
-        ext::{IfDuplicateBehavior, PartitionId},
+        ext::{IfDuplicateBehavior, PartitionId, ScanParameters},
     },
     headers::RerunHeadersExtractorExt as _,
 };
 
 use crate::entrypoint::NamedPath;
-use crate::store::{ChunkKey, Dataset, InMemoryStore, Table};
+use crate::store::{
+    ChunkIndexEntry, ChunkIndexFilter, ChunkKey, Dataset, Error, InMemoryStore, MaintenanceReport,
+    Table, TaskRegistry,
+};
 
 #[derive(Debug, Default)]
 pub struct RerunCloudHandlerSettings {}
@@ -57,6 +60,18 @@ impl RerunCloudHandlerBuilder {
         Self::default()
     }
 
+    /// Persist all datasets in the given directory, loading back any dataset already stored there.
+    ///
+    /// This replaces the current store, so it must be called before registering any data.
+    pub fn with_storage_directory(
+        mut self,
+        directory: &std::path::Path,
+    ) -> Result<Self, crate::store::Error> {
+        self.store = InMemoryStore::open(directory)?;
+
+        Ok(self)
+    }
+
     pub fn with_directory_as_dataset(
         mut self,
         directory: &NamedPath,
@@ -88,13 +103,19 @@ impl RerunCloudHandlerBuilder {
 
 // ---
 
-const DUMMY_TASK_ID: &str = "task_00000000DEADBEEF";
+/// Default time limit for `QueryTasksOnCompletion`, when the request doesn't specify any.
+const DEFAULT_TASK_COMPLETION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How often `QueryTasksOnCompletion` checks whether its tasks are done.
+const TASK_COMPLETION_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
 
 pub struct RerunCloudHandler {
     #[expect(dead_code)]
     settings: RerunCloudHandlerSettings,
 
     store: tokio::sync::RwLock<InMemoryStore>,
+
+    tasks: tokio::sync::RwLock<TaskRegistry>,
 }
 
 impl RerunCloudHandler {
@@ -102,18 +123,64 @@ impl RerunCloudHandler {
         Self {
             settings,
             store: tokio::sync::RwLock::new(store),
+            tasks: tokio::sync::RwLock::new(TaskRegistry::default()),
         }
     }
 
-    /// Returns all the chunk stores of the specified dataset and partitions ids. If `partition_ids`
-    /// is empty, return stores of all partitions.
+    /// Runs the maintenance of a dataset, tracking it as a task.
+    ///
+    /// The store is only locked to snapshot the dataset and to apply the result: the maintenance
+    /// itself runs on a blocking thread. See [`Dataset::maintenance_snapshot`].
+    async fn run_maintenance_task(
+        &self,
+        entry_id: EntryId,
+        rebuild_indexes: bool,
+    ) -> Result<(TaskId, MaintenanceReport), tonic::Status> {
+        let snapshot = self
+            .store
+            .read()
+            .await
+            .dataset(entry_id)?
+            .maintenance_snapshot();
+
+        let task_id = self.tasks.write().await.start("maintenance");
+
+        let changes = tokio::task::spawn_blocking(move || snapshot.run())
+            .await
+            .unwrap_or_else(|err| Err(Error::TaskFailed(task_id.id.clone(), format!("{err:#}"))));
+        let result = match changes {
+            Ok(changes) => self
+                .store
+                .write()
+                .await
+                .dataset_mut(entry_id)
+                .and_then(|dataset| dataset.apply_maintenance(changes, rebuild_indexes)),
+            Err(err) => Err(err),
+        };
+
+        self.tasks.write().await.finish(
+            &task_id,
+            result
+                .as_ref()
+                .map(ToString::to_string)
+                .map_err(|err| format!("{err:#}")),
+        );
+
+        let report = result?;
+        re_log::info!(%entry_id, "Maintenance task {}: {report}", task_id.id);
+
+        Ok((task_id, report))
+    }
+
+    /// Returns the chunk indices of all layers of the specified dataset and partitions ids. If
+    /// `partition_ids` is empty, return the layers of all partitions.
     ///
-    /// Returns (partition id, layer name, store) tuples.
-    async fn get_chunk_stores(
+    /// Returns (partition id, layer name, chunk index) tuples.
+    async fn get_chunk_indices(
         &self,
         dataset_id: EntryId,
         partition_ids: &[PartitionId],
-    ) -> Result<Vec<(PartitionId, String, ChunkStoreHandle)>, tonic::Status> {
+    ) -> Result<Vec<(PartitionId, String, Vec<ChunkIndexEntry>)>, tonic::Status> {
         let store = self.store.read().await;
         let dataset = store.dataset(dataset_id)?;
 
@@ -124,7 +191,7 @@ impl RerunCloudHandler {
                     (
                         partition_id.clone(),
                         layer_name.to_owned(),
-                        layer.store_handle().clone(),
+                        layer.chunk_index(),
                     )
                 })
             })
@@ -155,7 +222,7 @@ impl RerunCloudHandler {
                     )));
                 }
 
-                // Recursively walk the directory and grab all '.rrd' files
+                // Recursively walk the directory and grab all the files of the requested kind
                 let mut dirs_to_visit = vec![path];
                 let mut files = Vec::new();
 
@@ -177,7 +244,8 @@ impl RerunCloudHandler {
                         if entry_path.is_dir() {
                             dirs_to_visit.push(entry_path);
                         } else if let Some(extension) = entry_path.extension()
-                            && extension == "rrd"
+                            && ext::DataSourceKind::from_extension(&extension.to_string_lossy())
+                                == Some(source.kind)
                         {
                             files.push(entry_path);
                         }
@@ -518,7 +586,7 @@ impl RerunCloudService for RerunCloudHandler {
         let mut store = self.store.write().await;
         let dataset = store.dataset_mut(request.id)?;
 
-        dataset.set_dataset_details(request.dataset_details);
+        dataset.set_dataset_details(request.dataset_details)?;
 
         Ok(tonic::Response::new(
             UpdateDatasetEntryResponse {
@@ -613,7 +681,6 @@ impl RerunCloudService for RerunCloudHandler {
         let mut partition_layers: Vec<String> = vec![];
         let mut partition_types: Vec<String> = vec![];
         let mut storage_urls: Vec<String> = vec![];
-        let mut task_ids: Vec<String> = vec![];
 
         let data_sources = Self::resolve_data_sources(&data_sources)?;
 
@@ -631,31 +698,45 @@ impl RerunCloudService for RerunCloudHandler {
                 ));
             }
 
-            if kind != ext::DataSourceKind::Rrd {
-                return Err(tonic::Status::unimplemented(
-                    "register_with_dataset: only RRD data sources are implemented",
-                ));
-            }
+            let Ok(path) = storage_url.to_file_path() else {
+                continue;
+            };
 
-            if let Ok(rrd_path) = storage_url.to_file_path() {
-                let new_partition_ids = dataset.load_rrd(
-                    &rrd_path,
-                    Some(&layer),
-                    on_duplicate,
-                    dataset.store_kind(),
-                )?;
-
-                for partition_id in new_partition_ids {
-                    partition_ids.push(partition_id.to_string());
-                    partition_layers.push(layer.clone());
-                    partition_types.push("rrd".to_owned());
-                    // TODO(RR-2289): this should probably be a memory address
-                    storage_urls.push(storage_url.to_string());
-                    task_ids.push(DUMMY_TASK_ID.to_owned());
+            let new_layers = match kind {
+                ext::DataSourceKind::Rrd => dataset
+                    .load_rrd(&path, Some(&layer), on_duplicate, dataset.store_kind())?
+                    .into_iter()
+                    .map(|partition_id| (partition_id, layer.clone()))
+                    .collect(),
+
+                // The layer of MCAP data sources selects the MCAP layers to extract, which
+                // become the partition layers.
+                ext::DataSourceKind::Mcap => {
+                    dataset.load_mcap(&path, Some(&layer), on_duplicate)?
                 }
+            };
+
+            for (partition_id, layer) in new_layers {
+                partition_ids.push(partition_id.to_string());
+                partition_layers.push(layer);
+                partition_types.push(kind.as_str().to_owned());
+                // TODO(RR-2289): this should probably be a memory address
+                storage_urls.push(storage_url.to_string());
             }
         }
 
+        // Registration happens synchronously, so the task is already done by now.
+        let task_id = {
+            let mut tasks = self.tasks.write().await;
+            let task_id = tasks.start("register_with_dataset");
+            tasks.finish(
+                &task_id,
+                Ok(format!("registered {} layer(s)", partition_ids.len())),
+            );
+            task_id
+        };
+        let task_ids = vec![task_id.id; partition_ids.len()];
+
         let record_batch = RegisterWithDatasetResponse::create_dataframe(
             partition_ids,
             partition_layers,
@@ -944,33 +1025,87 @@ impl RerunCloudService for RerunCloudHandler {
 
     async fn create_index(
         &self,
-        _request: tonic::Request<re_protos::cloud::v1alpha1::CreateIndexRequest>,
+        request: tonic::Request<re_protos::cloud::v1alpha1::CreateIndexRequest>,
     ) -> std::result::Result<
         tonic::Response<re_protos::cloud::v1alpha1::CreateIndexResponse>,
         tonic::Status,
     > {
-        Err(tonic::Status::unimplemented("create_index not implemented"))
+        let mut store = self.store.write().await;
+        let entry_id = get_entry_id_from_headers(&store, &request)?;
+        let dataset = store.dataset_mut(entry_id)?;
+
+        let config: ext::IndexConfig = request
+            .into_inner()
+            .config
+            .ok_or_else(|| tonic::Status::invalid_argument("missing index config"))?
+            .try_into()?;
+
+        dataset.create_index(config.clone())?;
+
+        Ok(tonic::Response::new(
+            re_protos::cloud::v1alpha1::CreateIndexResponse {
+                statistics_json: dataset.index_statistics_json(&config).into(),
+                index: Some(config.into()),
+                debug_info: None,
+            },
+        ))
     }
 
     async fn list_indexes(
         &self,
-        _request: tonic::Request<re_protos::cloud::v1alpha1::ListIndexesRequest>,
+        request: tonic::Request<re_protos::cloud::v1alpha1::ListIndexesRequest>,
     ) -> std::result::Result<
         tonic::Response<re_protos::cloud::v1alpha1::ListIndexesResponse>,
         tonic::Status,
     > {
-        Err(tonic::Status::unimplemented("list_indexes not implemented"))
+        let store = self.store.read().await;
+        let entry_id = get_entry_id_from_headers(&store, &request)?;
+        let dataset = store.dataset(entry_id)?;
+
+        let (indexes, statistics_json) = dataset
+            .indexes()
+            .iter()
+            .map(|config| {
+                (
+                    config.clone().into(),
+                    dataset.index_statistics_json(config).into(),
+                )
+            })
+            .unzip();
+
+        Ok(tonic::Response::new(
+            re_protos::cloud::v1alpha1::ListIndexesResponse {
+                indexes,
+                statistics_json,
+            },
+        ))
     }
 
     async fn delete_indexes(
         &self,
-        _request: tonic::Request<re_protos::cloud::v1alpha1::DeleteIndexesRequest>,
+        request: tonic::Request<re_protos::cloud::v1alpha1::DeleteIndexesRequest>,
     ) -> std::result::Result<
         tonic::Response<re_protos::cloud::v1alpha1::DeleteIndexesResponse>,
         tonic::Status,
     > {
-        Err(tonic::Status::unimplemented(
-            "delete_indexes not implemented",
+        let mut store = self.store.write().await;
+        let entry_id = get_entry_id_from_headers(&store, &request)?;
+        let dataset = store.dataset_mut(entry_id)?;
+
+        let column: ext::IndexColumn = request
+            .into_inner()
+            .column
+            .ok_or_else(|| tonic::Status::invalid_argument("missing index column"))?
+            .try_into()?;
+
+        let indexes = dataset
+            .delete_indexes(&column)?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(tonic::Response::new(
+            re_protos::cloud::v1alpha1::DeleteIndexesResponse { indexes },
         ))
     }
 
@@ -980,10 +1115,80 @@ impl RerunCloudService for RerunCloudHandler {
 
     async fn search_dataset(
         &self,
-        _request: tonic::Request<re_protos::cloud::v1alpha1::SearchDatasetRequest>,
+        request: tonic::Request<re_protos::cloud::v1alpha1::SearchDatasetRequest>,
     ) -> std::result::Result<tonic::Response<Self::SearchDatasetStream>, tonic::Status> {
-        Err(tonic::Status::unimplemented(
-            "search_dataset not implemented",
+        let store = self.store.read().await;
+        let entry_id = get_entry_id_from_headers(&store, &request)?;
+
+        let re_protos::cloud::v1alpha1::SearchDatasetRequest {
+            column,
+            query,
+            properties,
+            scan_parameters,
+        } = request.into_inner();
+
+        let column: ext::IndexColumn = column
+            .ok_or_else(|| tonic::Status::invalid_argument("missing index column"))?
+            .try_into()?;
+        let query: RecordBatch = query
+            .ok_or_else(|| tonic::Status::invalid_argument("missing query"))?
+            .try_into()?;
+        let properties: ext::IndexQueryProperties = properties
+            .ok_or_else(|| tonic::Status::invalid_argument("missing query properties"))?
+            .try_into()?;
+
+        // Only select the chunks while holding the lock, reading them may hit the disk.
+        let pending_search = store
+            .dataset(entry_id)?
+            .search(&column, &query, &properties)?;
+        drop(store);
+
+        let mut record_batch = tokio::task::spawn_blocking(move || pending_search.run())
+            .await
+            .map_err(|err| tonic::Status::internal(format!("Search task failed: {err:#}")))??;
+
+        if let Some(scan_parameters) = scan_parameters {
+            let ScanParameters {
+                columns,
+                on_missing_columns: _,
+                filter,
+                limit_offset,
+                limit_len,
+                order_by,
+                explain_plan: _,
+                explain_filter: _,
+            } = scan_parameters.try_into()?;
+
+            if filter.is_some() || !order_by.is_empty() {
+                return Err(tonic::Status::unimplemented(
+                    "search_dataset: filtering and ordering results is not implemented",
+                ));
+            }
+
+            if !columns.is_empty() {
+                record_batch = record_batch
+                    .project_columns(columns.iter().map(|s| s.as_str()))
+                    .map_err(|err| {
+                        tonic::Status::invalid_argument(format!(
+                            "Unable to project columns: {err:#}"
+                        ))
+                    })?;
+            }
+
+            let num_rows = record_batch.num_rows();
+            let offset = (limit_offset.unwrap_or(0).max(0) as usize).min(num_rows);
+            let len = limit_len.map_or(num_rows, |len| len.max(0) as usize);
+            record_batch = record_batch.slice(offset, len.min(num_rows - offset));
+        }
+
+        let stream = futures::stream::once(async move {
+            Ok(SearchDatasetResponse {
+                data: Some(record_batch.into()),
+            })
+        });
+
+        Ok(tonic::Response::new(
+            Box::pin(stream) as Self::SearchDatasetStream
         ))
     }
 
@@ -993,46 +1198,56 @@ impl RerunCloudService for RerunCloudHandler {
         &self,
         request: tonic::Request<re_protos::cloud::v1alpha1::QueryDatasetRequest>,
     ) -> std::result::Result<tonic::Response<Self::QueryDatasetStream>, tonic::Status> {
-        if !request.get_ref().chunk_ids.is_empty() {
-            return Err(tonic::Status::unimplemented(
-                "query_dataset: querying specific chunk ids is not implemented",
-            ));
-        }
-
         let entry_id = get_entry_id_from_headers(&*self.store.read().await, &request)?;
 
-        let re_protos::cloud::v1alpha1::QueryDatasetRequest {
+        let ext::QueryDatasetRequest {
             partition_ids,
+            chunk_ids,
             entity_paths,
             select_all_entity_paths,
+            fuzzy_descriptors,
+            exclude_static_data,
+            exclude_temporal_data,
+            query,
 
             //TODO(RR-2613): we must do a much better job at handling these
-            chunk_ids: _,
-            fuzzy_descriptors: _,
-            exclude_static_data: _,
-            exclude_temporal_data: _,
             scan_parameters: _,
-            query: _,
-        } = request.into_inner();
+        } = request.into_inner().try_into()?;
 
-        let entity_paths: IntSet<EntityPath> = entity_paths
-            .into_iter()
-            .map(EntityPath::try_from)
-            .collect::<Result<IntSet<EntityPath>, _>>()?;
         if select_all_entity_paths && !entity_paths.is_empty() {
             return Err(tonic::Status::invalid_argument(
                 "cannot specify entity paths if `select_all_entity_paths` is true",
             ));
         }
 
-        let partition_ids = partition_ids
-            .into_iter()
-            .map(PartitionId::try_from)
-            .collect::<Result<Vec<_>, _>>()?;
+        let (latest_at, range) = query.map_or((None, None), |query| (query.latest_at, query.range));
+
+        let filter = ChunkIndexFilter {
+            chunk_ids: chunk_ids.into_iter().collect(),
+            entity_paths: entity_paths.into_iter().collect(),
+            fuzzy_descriptors,
+            exclude_static_data,
+            exclude_temporal_data,
+            latest_at,
+            range,
+        };
 
-        let chunk_stores = self.get_chunk_stores(entry_id, &partition_ids).await?;
+        let chunk_indices = self.get_chunk_indices(entry_id, &partition_ids).await?;
 
-        if chunk_stores.is_empty() {
+        // Latest-at queries compare the chunks of a whole partition, across all its layers.
+        let relevant_chunk_ids: HashMap<PartitionId, HashSet<ChunkId>> = chunk_indices
+            .iter()
+            .into_group_map_by(|(partition_id, _, _)| partition_id.clone())
+            .into_iter()
+            .map(|(partition_id, layers)| {
+                let entries = layers
+                    .into_iter()
+                    .flat_map(|(_, _, chunk_index)| chunk_index);
+                (partition_id, filter.relevant_chunk_ids(entries))
+            })
+            .collect();
+
+        if chunk_indices.is_empty() {
             let stream = futures::stream::iter([{
                 let batch = QueryDatasetResponse::create_empty_dataframe();
                 let data = Some(batch.into());
@@ -1044,9 +1259,14 @@ impl RerunCloudService for RerunCloudHandler {
             ));
         }
 
-        let stream = futures::stream::iter(chunk_stores.into_iter().map(
-            move |(partition_id, layer_name, store_handle)| {
-                let num_chunks = store_handle.read().num_chunks();
+        let stream = futures::stream::iter(chunk_indices.into_iter().map(
+            move |(partition_id, layer_name, chunk_index)| {
+                let relevant_chunk_ids = &relevant_chunk_ids[&partition_id];
+                let chunk_index = chunk_index
+                    .into_iter()
+                    .filter(|chunk| relevant_chunk_ids.contains(&chunk.chunk_id))
+                    .collect_vec();
+                let num_chunks = chunk_index.len();
 
                 let mut chunk_ids = Vec::with_capacity(num_chunks);
                 let mut chunk_partition_ids = Vec::with_capacity(num_chunks);
@@ -1056,46 +1276,36 @@ impl RerunCloudService for RerunCloudHandler {
 
                 let mut timelines = BTreeMap::new();
 
-                for chunk in store_handle.read().iter_chunks() {
-                    if !entity_paths.is_empty() && !entity_paths.contains(chunk.entity_path()) {
-                        continue;
-                    }
-
-                    let mut missing_timelines: BTreeSet<_> = timelines.keys().copied().collect();
-                    for (timeline_name, timeline_col) in chunk.timelines() {
-                        let range = timeline_col.time_range();
-                        let time_min = range.min();
-                        let time_max = range.max();
+                for chunk in &chunk_index {
+                    let mut missing_timelines: BTreeSet<_> = timelines.keys().cloned().collect();
+                    for time_range in &chunk.time_ranges {
+                        missing_timelines.remove(&time_range.timeline);
 
-                        let timeline_name = timeline_name.as_str();
-                        missing_timelines.remove(timeline_name);
-                        let timeline_data_type = timeline_col.times_array().data_type().to_owned();
+                        let timeline_data =
+                            timelines.entry(time_range.timeline.clone()).or_insert((
+                                vec![None; chunk_partition_ids.len()],
+                                vec![None; chunk_partition_ids.len()],
+                            ));
 
-                        let timeline_data = timelines.entry(timeline_name).or_insert((
-                            timeline_data_type,
-                            vec![None; chunk_partition_ids.len()],
-                            vec![None; chunk_partition_ids.len()],
-                        ));
-
-                        timeline_data.1.push(Some(time_min.as_i64()));
-                        timeline_data.2.push(Some(time_max.as_i64()));
+                        timeline_data.0.push(Some(time_range.min));
+                        timeline_data.1.push(Some(time_range.max));
                     }
                     for timeline_name in missing_timelines {
                         let timeline_data = timelines
-                            .get_mut(timeline_name)
+                            .get_mut(&timeline_name)
                             .expect("timeline_names already checked"); // Already checked
 
+                        timeline_data.0.push(None);
                         timeline_data.1.push(None);
-                        timeline_data.2.push(None);
                     }
 
                     chunk_partition_ids.push(partition_id.id.clone());
-                    chunk_ids.push(chunk.id());
-                    chunk_entity_path.push(chunk.entity_path().to_string());
-                    chunk_is_static.push(chunk.is_static());
+                    chunk_ids.push(chunk.chunk_id);
+                    chunk_entity_path.push(chunk.entity_path.to_string());
+                    chunk_is_static.push(chunk.is_static);
                     chunk_keys.push(
                         ChunkKey {
-                            chunk_id: chunk.id(),
+                            chunk_id: chunk.chunk_id,
                             partition_id: partition_id.clone(),
                             layer_name: layer_name.clone(),
                             dataset_id: entry_id,
@@ -1114,6 +1324,14 @@ impl RerunCloudService for RerunCloudHandler {
                     chunk_entity_path,
                     chunk_is_static,
                 )
+                .and_then(|batch| {
+                    QueryDatasetResponse::append_index_ranges(
+                        &batch,
+                        timelines
+                            .into_iter()
+                            .map(|(timeline, (starts, ends))| (timeline, starts, ends)),
+                    )
+                })
                 .map_err(|err| {
                     tonic::Status::internal(format!("Failed to create dataframe: {err:#}"))
                 })?;
@@ -1338,37 +1556,10 @@ impl RerunCloudService for RerunCloudHandler {
         &self,
         request: tonic::Request<QueryTasksRequest>,
     ) -> Result<tonic::Response<QueryTasksResponse>, tonic::Status> {
-        let tasks_id = request.into_inner().ids;
-
-        let dummy_task_id = TaskId {
-            id: DUMMY_TASK_ID.to_owned(),
-        };
-
-        for task_id in &tasks_id {
-            if task_id != &dummy_task_id {
-                return Err(tonic::Status::not_found(format!(
-                    "task {} not found",
-                    task_id.id
-                )));
-            }
-        }
+        let task_ids = request.into_inner().ids;
 
-        let rb = QueryTasksResponse::create_dataframe(
-            vec![DUMMY_TASK_ID.to_owned()],
-            vec![None],
-            vec![None],
-            vec!["success".to_owned()],
-            vec![None],
-            vec![None],
-            vec![None],
-            vec![None],
-            vec![1],
-            vec![None],
-            vec![None],
-        )
-        .expect("constant content that should always succeed");
+        let rb = self.tasks.read().await.query(&task_ids)?;
 
-        // All tasks finish immediately in the OSS server
         Ok(tonic::Response::new(QueryTasksResponse {
             data: Some(rb.into()),
         }))
@@ -1380,9 +1571,24 @@ impl RerunCloudService for RerunCloudHandler {
         &self,
         request: tonic::Request<QueryTasksOnCompletionRequest>,
     ) -> Result<tonic::Response<Self::QueryTasksOnCompletionStream>, tonic::Status> {
-        let task_ids = request.into_inner().ids;
+        let QueryTasksOnCompletionRequest {
+            ids: task_ids,
+            timeout,
+        } = request.into_inner();
+
+        let timeout = timeout
+            .and_then(|timeout| std::time::Duration::try_from(timeout).ok())
+            .unwrap_or(DEFAULT_TASK_COMPLETION_TIMEOUT);
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        // Tasks run as part of the request which started them, so all we can do is wait for
+        // those requests to complete.
+        while !self.tasks.read().await.all_finished(&task_ids)?
+            && tokio::time::Instant::now() < deadline
+        {
+            tokio::time::sleep(TASK_COMPLETION_POLL_INTERVAL).await;
+        }
 
-        // All tasks finish immediately in the OSS server, so we can delegate to `query_tasks
         let response_data = self
             .query_tasks(tonic::Request::new(QueryTasksRequest { ids: task_ids }))
             .await?
@@ -1400,11 +1606,28 @@ impl RerunCloudService for RerunCloudHandler {
 
     async fn do_maintenance(
         &self,
-        _request: tonic::Request<re_protos::cloud::v1alpha1::DoMaintenanceRequest>,
+        request: tonic::Request<re_protos::cloud::v1alpha1::DoMaintenanceRequest>,
     ) -> Result<tonic::Response<re_protos::cloud::v1alpha1::DoMaintenanceResponse>, tonic::Status>
     {
-        Err(tonic::Status::unimplemented(
-            "do_maintenance not implemented",
+        let entry_id = get_entry_id_from_headers(&*self.store.read().await, &request)?;
+
+        // Lance fragments cleanup doesn't apply to our storage.
+        let ext::DoMaintenanceRequest {
+            optimize_indexes,
+            retrain_indexes,
+            compact_fragments: _,
+            cleanup_before: _,
+            unsafe_allow_recent_cleanup: _,
+        } = request.into_inner().try_into()?;
+
+        let (task_id, report) = self
+            .run_maintenance_task(entry_id, optimize_indexes || retrain_indexes)
+            .await?;
+
+        Ok(tonic::Response::new(
+            re_protos::cloud::v1alpha1::DoMaintenanceResponse {
+                report: format!("{}: {report}", task_id.id),
+            },
         ))
     }
 
@@ -1415,8 +1638,20 @@ impl RerunCloudService for RerunCloudHandler {
         tonic::Response<re_protos::cloud::v1alpha1::DoGlobalMaintenanceResponse>,
         tonic::Status,
     > {
-        Err(tonic::Status::unimplemented(
-            "do_global_maintenance not implemented",
+        let entry_ids = self
+            .store
+            .read()
+            .await
+            .iter_datasets()
+            .map(Dataset::id)
+            .collect_vec();
+
+        for entry_id in entry_ids {
+            self.run_maintenance_task(entry_id, true).await?;
+        }
+
+        Ok(tonic::Response::new(
+            re_protos::cloud::v1alpha1::DoGlobalMaintenanceResponse {},
         ))
     }
 
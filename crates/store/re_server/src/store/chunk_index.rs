@@ -0,0 +1,234 @@
+use ahash::{HashMap, HashSet};
+use nohash_hasher::IntSet;
+
+use re_byte_size::SizeBytes as _;
+use re_chunk_store::Chunk;
+use re_log_types::EntityPath;
+use re_protos::cloud::v1alpha1::ext::{QueryLatestAt, QueryRange};
+use re_types_core::{ChunkId, ComponentDescriptor, ComponentIdentifier};
+
+/// The time range covered by a chunk on a given timeline.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ChunkTimeRange {
+    pub timeline: String,
+    pub min: i64,
+    pub max: i64,
+
+    /// The time range of the non-null values of each component, sorted by component.
+    ///
+    /// Component columns are potentially sparse, so this can be narrower than the range of the
+    /// whole chunk. Components without any non-null value are missing.
+    pub per_component: Vec<(ComponentIdentifier, i64, i64)>,
+}
+
+impl ChunkTimeRange {
+    /// The time range of the non-null values of the given component, if it has any.
+    pub fn component_range(&self, component: ComponentIdentifier) -> Option<(i64, i64)> {
+        self.per_component
+            .iter()
+            .find(|(other, _, _)| *other == component)
+            .map(|&(_, min, max)| (min, max))
+    }
+}
+
+/// Everything we need to know about a chunk in order to answer manifest-level queries (e.g.
+/// `QueryDataset`), without having to load the chunk itself.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ChunkIndexEntry {
+    pub chunk_id: ChunkId,
+    pub entity_path: EntityPath,
+    pub is_static: bool,
+    pub time_ranges: Vec<ChunkTimeRange>,
+    pub components: Vec<ComponentDescriptor>,
+    pub num_rows: u64,
+    pub size_bytes: u64,
+}
+
+impl ChunkIndexEntry {
+    pub fn from_chunk(chunk: &Chunk) -> Self {
+        let mut time_range_per_component = chunk.time_range_per_component();
+
+        let mut time_ranges: Vec<_> = chunk
+            .timelines()
+            .iter()
+            .map(|(timeline_name, time_column)| {
+                let range = time_column.time_range();
+
+                let mut per_component: Vec<_> = time_range_per_component
+                    .remove(timeline_name)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(component, range)| {
+                        (component, range.min().as_i64(), range.max().as_i64())
+                    })
+                    .collect();
+                per_component.sort_by_key(|(component, _, _)| *component);
+
+                ChunkTimeRange {
+                    timeline: timeline_name.to_string(),
+                    min: range.min().as_i64(),
+                    max: range.max().as_i64(),
+                    per_component,
+                }
+            })
+            .collect();
+        time_ranges.sort_by(|a, b| a.timeline.cmp(&b.timeline));
+
+        Self {
+            chunk_id: chunk.id(),
+            entity_path: chunk.entity_path().clone(),
+            is_static: chunk.is_static(),
+            time_ranges,
+            components: chunk.component_descriptors().cloned().collect(),
+            num_rows: chunk.num_rows() as u64,
+            size_bytes: chunk.heap_size_bytes(),
+        }
+    }
+
+    /// The time range covered on the given timeline, if the chunk has it.
+    pub fn time_range(&self, timeline: &str) -> Option<&ChunkTimeRange> {
+        self.time_ranges
+            .iter()
+            .find(|time_range| time_range.timeline == timeline)
+    }
+}
+
+/// The chunk-level filters of a `QueryDataset` request, evaluated on [`ChunkIndexEntry`]s only.
+///
+/// Empty sets and `None`s don't filter anything.
+#[derive(Debug, Default)]
+pub struct ChunkIndexFilter {
+    pub chunk_ids: HashSet<ChunkId>,
+    pub entity_paths: IntSet<EntityPath>,
+
+    /// Keeps chunks with at least one component whose identifier or type contains one of these.
+    pub fuzzy_descriptors: Vec<String>,
+
+    pub exclude_static_data: bool,
+    pub exclude_temporal_data: bool,
+
+    /// Keeps the chunks relevant to either of these queries, when specified.
+    pub latest_at: Option<QueryLatestAt>,
+    pub range: Option<QueryRange>,
+}
+
+impl ChunkIndexFilter {
+    /// Returns the ids of the chunks of `entries` which pass the filter.
+    ///
+    /// `entries` must cover a whole partition (i.e. all its layers), since latest-at queries are
+    /// answered by comparing the chunks with each other.
+    pub fn relevant_chunk_ids<'a>(
+        &self,
+        entries: impl IntoIterator<Item = &'a ChunkIndexEntry>,
+    ) -> HashSet<ChunkId> {
+        let candidates = entries
+            .into_iter()
+            .filter(|entry| self.chunk_ids.is_empty() || self.chunk_ids.contains(&entry.chunk_id))
+            .filter(|entry| {
+                self.entity_paths.is_empty() || self.entity_paths.contains(&entry.entity_path)
+            })
+            .filter(|entry| {
+                if entry.is_static {
+                    !self.exclude_static_data
+                } else {
+                    !self.exclude_temporal_data
+                }
+            })
+            .filter(|entry| self.matches_fuzzy_descriptors(entry))
+            .collect::<Vec<_>>();
+
+        if self.latest_at.is_none() && self.range.is_none() {
+            return candidates.iter().map(|entry| entry.chunk_id).collect();
+        }
+
+        let mut relevant = HashSet::default();
+
+        if let Some(latest_at) = &self.latest_at {
+            relevant.extend(latest_at_relevant_chunks(latest_at, &candidates));
+        }
+
+        if let Some(range) = &self.range {
+            relevant.extend(
+                candidates
+                    .iter()
+                    .filter(|entry| {
+                        // Static data is relevant at all times.
+                        entry.is_static
+                            || entry.time_range(&range.index).is_some_and(|time_range| {
+                                time_range.min <= range.index_range.max().as_i64()
+                                    && range.index_range.min().as_i64() <= time_range.max
+                            })
+                    })
+                    .map(|entry| entry.chunk_id),
+            );
+        }
+
+        relevant
+    }
+
+    fn matches_fuzzy_descriptors(&self, entry: &ChunkIndexEntry) -> bool {
+        self.fuzzy_descriptors.is_empty()
+            || entry.components.iter().any(|descr| {
+                self.fuzzy_descriptors.iter().any(|fuzzy| {
+                    descr.display_name().contains(fuzzy.as_str())
+                        || descr.component_type.is_some_and(|component_type| {
+                            component_type.as_str().contains(fuzzy.as_str())
+                        })
+                })
+            })
+    }
+}
+
+/// Keeps the chunks which may contain the latest-at value of one of their components.
+///
+/// For each component, the latest non-null value at or before the query time is known to be no
+/// older than the most recent first non-null value of the chunks which have one before the query
+/// time. Chunks whose non-null values all come before that cannot contain it.
+fn latest_at_relevant_chunks<'a>(
+    latest_at: &'a QueryLatestAt,
+    candidates: &'a [&'a ChunkIndexEntry],
+) -> impl Iterator<Item = ChunkId> + 'a {
+    let at = latest_at.at.as_i64();
+    let timeline = latest_at.index.as_deref();
+
+    let mut lower_bounds: HashMap<(&EntityPath, &ComponentDescriptor), i64> = HashMap::default();
+    for &entry in candidates {
+        for (descr, min, _) in component_ranges_before(entry, timeline, at) {
+            let bound = lower_bounds
+                .entry((&entry.entity_path, descr))
+                .or_insert(min);
+            *bound = (*bound).max(min);
+        }
+    }
+
+    candidates.iter().filter_map(move |&entry| {
+        // Static data is relevant at all times.
+        let is_relevant = entry.is_static
+            || component_ranges_before(entry, timeline, at).any(|(descr, _, max)| {
+                lower_bounds
+                    .get(&(&entry.entity_path, descr))
+                    .is_some_and(|bound| *bound <= max)
+            });
+
+        is_relevant.then_some(entry.chunk_id)
+    })
+}
+
+/// The part of the time range of the non-null values of each component of a temporal chunk that
+/// is at or before the query time.
+///
+/// Components without any non-null value at or before the query time are skipped.
+fn component_ranges_before<'a>(
+    entry: &'a ChunkIndexEntry,
+    timeline: Option<&'a str>,
+    at: i64,
+) -> impl Iterator<Item = (&'a ComponentDescriptor, i64, i64)> + 'a {
+    let time_range = timeline
+        .filter(|_| !entry.is_static)
+        .and_then(|timeline| entry.time_range(timeline));
+
+    entry.components.iter().filter_map(move |descr| {
+        let (min, max) = time_range?.component_range(descr.component)?;
+        (min <= at).then_some((descr, min, max.min(at)))
+    })
+}
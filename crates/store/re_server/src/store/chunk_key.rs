@@ -4,6 +4,10 @@ use re_types_core::ChunkId;
 
 use crate::store::Error;
 
+/// Identifies a chunk of a dataset, as returned by `QueryDataset` and consumed by `FetchChunks`.
+///
+/// A key is only valid for as long as the layer it points to isn't replaced. In particular,
+/// maintenance compacts layers into new chunks, after which their former keys can't be fetched.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct ChunkKey {
     pub chunk_id: ChunkId,
@@ -1,10 +1,10 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use arrow::array::{RecordBatch, RecordBatchOptions};
 use arrow::datatypes::{Fields, Schema};
-use itertools::Either;
+use itertools::{Either, Itertools as _};
 
 use re_arrow_util::RecordBatchExt as _;
 use re_chunk_store::{ChunkStore, ChunkStoreHandle};
@@ -12,12 +12,191 @@ use re_log_types::{EntryId, StoreKind};
 use re_protos::{
     cloud::v1alpha1::{
         EntryKind, ScanDatasetManifestResponse, ScanPartitionTableResponse,
-        ext::{DataSource, DatasetDetails, DatasetEntry, EntryDetails},
+        ext::{
+            DataSource, DatasetDetails, DatasetEntry, EntryDetails, IndexColumn, IndexConfig,
+            IndexQueryProperties,
+        },
     },
     common::v1alpha1::ext::{DatasetHandle, IfDuplicateBehavior, PartitionId},
 };
 
-use crate::store::{Error, InMemoryStore, Layer, Partition};
+use crate::store::mcap;
+use crate::store::on_disk::{
+    DatasetManifest, LayerManifest, PartitionManifest, staging_dir, timestamp_from_ns,
+    timestamp_to_ns,
+};
+use crate::store::search::{PendingSearch, ResolvedIndexColumns, Search};
+use crate::store::{ChunkIndexEntry, Error, InMemoryStore, Layer, OnDiskLayer, Partition};
+
+/// What [`Dataset::apply_maintenance`] did.
+#[derive(Debug, Default, Clone)]
+pub struct MaintenanceReport {
+    pub num_duplicate_layers_removed: usize,
+    pub num_layers_compacted: usize,
+    pub num_chunks_before: u64,
+    pub num_chunks_after: u64,
+
+    /// Only set if indexes were rebuilt.
+    pub num_indexes_rebuilt: Option<usize>,
+}
+
+impl std::fmt::Display for MaintenanceReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            num_duplicate_layers_removed,
+            num_layers_compacted,
+            num_chunks_before,
+            num_chunks_after,
+            num_indexes_rebuilt,
+        } = self;
+
+        write!(
+            f,
+            "removed {num_duplicate_layers_removed} duplicate layer(s), compacted \
+            {num_layers_compacted} layer(s) ({num_chunks_before} -> {num_chunks_after} chunks)"
+        )?;
+        if let Some(num_indexes_rebuilt) = num_indexes_rebuilt {
+            write!(f, ", rebuilt {num_indexes_rebuilt} index(es)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The layers of a dataset at some point in time, see [`Dataset::maintenance_snapshot`].
+pub struct MaintenanceSnapshot {
+    storage_dir: Option<PathBuf>,
+
+    /// The layers of each partition, in registration order.
+    partitions: Vec<(PartitionId, Vec<(String, Layer)>)>,
+}
+
+impl MaintenanceSnapshot {
+    /// Computes how to reorganize the data of the snapshotted dataset for faster queries.
+    ///
+    /// This finds the layers which hold the exact same chunks as an older layer of the same
+    /// partition, then merges the small chunks of each other layer together. If the dataset is
+    /// persisted, compacted layers are staged on disk right away.
+    ///
+    /// This can be slow, and doesn't need the dataset: it should run on a blocking thread, without
+    /// holding any lock.
+    ///
+    /// Compaction gives new ids to the chunks it merges: once applied, the chunk keys previously
+    /// returned by `QueryDataset` for the compacted layers can no longer be fetched, and clients
+    /// must query the dataset again.
+    pub fn run(self) -> Result<MaintenanceChanges, Error> {
+        re_tracing::profile_function!();
+
+        let Self {
+            storage_dir,
+            partitions,
+        } = self;
+
+        let mut changes = MaintenanceChanges::default();
+
+        for (partition_id, layers) in partitions {
+            let mut seen_chunk_ids = BTreeSet::new();
+
+            for (layer_name, layer) in layers {
+                let chunk_ids = layer
+                    .chunk_index()
+                    .into_iter()
+                    .map(|entry| entry.chunk_id)
+                    .sorted()
+                    .collect_vec();
+
+                // Empty layers have nothing in common but being empty.
+                if !chunk_ids.is_empty() && !seen_chunk_ids.insert(chunk_ids) {
+                    changes
+                        .duplicate_layers
+                        .push((partition_id.clone(), layer_name, layer));
+                    continue;
+                }
+
+                let num_chunks = layer.num_chunks();
+                changes.num_chunks_before += num_chunks;
+
+                if let Some(store_handle) = layer.compacted_store_handle()? {
+                    changes.num_chunks_after += store_handle.read().num_chunks() as u64;
+                    let compacted_layer = if let Some(storage_dir) = &storage_dir {
+                        CompactedLayer::Staged(OnDiskLayer::write_staged(
+                            storage_dir,
+                            &store_handle,
+                        )?)
+                    } else {
+                        CompactedLayer::InMemory(store_handle)
+                    };
+                    changes.compacted_layers.push((
+                        partition_id.clone(),
+                        layer_name,
+                        layer,
+                        compacted_layer,
+                    ));
+                } else {
+                    changes.num_chunks_after += num_chunks;
+                }
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+/// The changes computed by [`MaintenanceSnapshot::run`], see [`Dataset::apply_maintenance`].
+#[derive(Default)]
+pub struct MaintenanceChanges {
+    /// (partition id, layer name, layer) of the layers to remove.
+    duplicate_layers: Vec<(PartitionId, String, Layer)>,
+
+    /// (partition id, layer name, layer, compacted layer) of the layers to replace.
+    compacted_layers: Vec<(PartitionId, String, Layer, CompactedLayer)>,
+
+    num_chunks_before: u64,
+    num_chunks_after: u64,
+}
+
+/// The result of compacting a layer, before it replaces the original one.
+enum CompactedLayer {
+    InMemory(ChunkStoreHandle),
+
+    /// Staged in the storage directory of the dataset, see [`OnDiskLayer::write_staged`].
+    Staged(OnDiskLayer),
+}
+
+impl CompactedLayer {
+    /// Turns this into a layer of the dataset persisted in `storage_dir`, if any.
+    fn commit(
+        self,
+        storage_dir: Option<&Path>,
+        registration_time: jiff::Timestamp,
+    ) -> Result<Layer, Error> {
+        match (self, storage_dir) {
+            (Self::InMemory(store_handle), None) => {
+                Ok(Layer::in_memory(store_handle, registration_time))
+            }
+            (Self::InMemory(store_handle), Some(storage_dir)) => Ok(Layer::on_disk(
+                OnDiskLayer::write(storage_dir, &store_handle)?,
+                registration_time,
+            )),
+            (Self::Staged(layer), Some(storage_dir)) => Ok(Layer::on_disk(
+                layer.commit(storage_dir)?,
+                registration_time,
+            )),
+            (Self::Staged(layer), None) => {
+                let store_handle = layer.load_store_handle();
+                layer.discard();
+                Ok(Layer::in_memory(store_handle?, registration_time))
+            }
+        }
+    }
+
+    fn discard(self) {
+        match self {
+            Self::InMemory(_) => {}
+            Self::Staged(layer) => layer.discard(),
+        }
+    }
+}
 
 pub struct Dataset {
     id: EntryId,
@@ -27,21 +206,161 @@ pub struct Dataset {
 
     partitions: HashMap<PartitionId, Partition>,
 
+    /// At most one index per column.
+    indexes: Vec<IndexConfig>,
+
     created_at: jiff::Timestamp,
     updated_at: jiff::Timestamp,
+
+    /// If set, this dataset is persisted in this directory, and its layers are stored on disk.
+    storage_dir: Option<PathBuf>,
 }
 
 impl Dataset {
-    pub fn new(id: EntryId, name: String, store_kind: StoreKind, details: DatasetDetails) -> Self {
-        Self {
+    pub fn new(
+        id: EntryId,
+        name: String,
+        store_kind: StoreKind,
+        details: DatasetDetails,
+        storage_dir: Option<PathBuf>,
+    ) -> Result<Self, Error> {
+        let dataset = Self {
             id,
             name,
             store_kind,
             details,
             partitions: HashMap::default(),
+            indexes: Vec::new(),
             created_at: jiff::Timestamp::now(),
             updated_at: jiff::Timestamp::now(),
+            storage_dir,
+        };
+
+        if let Some(storage_dir) = &dataset.storage_dir {
+            std::fs::create_dir_all(storage_dir)?;
+        }
+        dataset.persist()?;
+
+        Ok(dataset)
+    }
+
+    /// Loads a dataset previously persisted in `storage_dir`.
+    ///
+    /// Only the chunk indices are loaded, chunks are read from disk when needed.
+    pub fn load(storage_dir: PathBuf) -> Result<Self, Error> {
+        re_log::info!("Loading dataset from {}", storage_dir.display());
+
+        let DatasetManifest {
+            id,
+            name,
+            store_kind,
+            blueprint_dataset,
+            default_blueprint,
+            created_at_ns,
+            updated_at_ns,
+            partitions,
+            indexes,
+        } = DatasetManifest::read(&storage_dir)?;
+
+        // Layers staged by maintenance tasks which didn't complete.
+        let staging_dir = staging_dir(&storage_dir);
+        if staging_dir.is_dir() {
+            std::fs::remove_dir_all(&staging_dir)?;
+        }
+
+        let partitions = partitions
+            .into_iter()
+            .map(|(partition_id, partition)| {
+                let layers = partition
+                    .layers
+                    .into_iter()
+                    .map(|(layer_name, layer)| {
+                        let on_disk_layer = OnDiskLayer::open(&storage_dir, &layer.file_id)?;
+                        let registration_time = timestamp_from_ns(layer.registration_time_ns)?;
+                        Ok((layer_name, Layer::on_disk(on_disk_layer, registration_time)))
+                    })
+                    .collect::<Result<HashMap<_, _>, Error>>()?;
+
+                Ok((
+                    PartitionId::new(partition_id),
+                    Partition::from_layers(
+                        layers,
+                        timestamp_from_ns(partition.last_updated_at_ns)?,
+                    ),
+                ))
+            })
+            .collect::<Result<HashMap<_, _>, Error>>()?;
+
+        Ok(Self {
+            id,
+            name,
+            store_kind,
+            details: DatasetDetails {
+                blueprint_dataset,
+                default_blueprint,
+            },
+            partitions,
+            indexes,
+            created_at: timestamp_from_ns(created_at_ns)?,
+            updated_at: timestamp_from_ns(updated_at_ns)?,
+            storage_dir: Some(storage_dir),
+        })
+    }
+
+    /// Writes the dataset manifest to disk, if this dataset is persisted.
+    fn persist(&self) -> Result<(), Error> {
+        let Some(storage_dir) = &self.storage_dir else {
+            return Ok(());
+        };
+
+        let partitions = self
+            .partitions
+            .iter()
+            .map(|(partition_id, partition)| {
+                let layers = partition
+                    .iter_layers()
+                    .filter_map(|(layer_name, layer)| {
+                        Some((
+                            layer_name.to_owned(),
+                            LayerManifest {
+                                file_id: layer.file_id()?.to_owned(),
+                                registration_time_ns: timestamp_to_ns(layer.registration_time()),
+                            },
+                        ))
+                    })
+                    .collect();
+
+                (
+                    partition_id.to_string(),
+                    PartitionManifest {
+                        last_updated_at_ns: timestamp_to_ns(partition.last_updated_at()),
+                        layers,
+                    },
+                )
+            })
+            .collect();
+
+        DatasetManifest {
+            id: self.id,
+            name: self.name.clone(),
+            store_kind: self.store_kind,
+            blueprint_dataset: self.details.blueprint_dataset,
+            default_blueprint: self.details.default_blueprint.clone(),
+            created_at_ns: timestamp_to_ns(self.created_at),
+            updated_at_ns: timestamp_to_ns(self.updated_at),
+            partitions,
+            indexes: self.indexes.clone(),
         }
+        .write(storage_dir)
+    }
+
+    /// Deletes everything this dataset persisted on disk, if anything.
+    pub fn delete_storage(&self) -> Result<(), Error> {
+        if let Some(storage_dir) = &self.storage_dir {
+            std::fs::remove_dir_all(storage_dir)?;
+        }
+
+        Ok(())
     }
 
     #[inline]
@@ -54,9 +373,10 @@ impl Dataset {
         &self.name
     }
 
-    pub fn set_name(&mut self, name: String) {
+    pub fn set_name(&mut self, name: String) -> Result<(), Error> {
         self.name = name;
         self.updated_at = jiff::Timestamp::now();
+        self.persist()
     }
 
     #[inline]
@@ -101,9 +421,10 @@ impl Dataset {
         }
     }
 
-    pub fn set_dataset_details(&mut self, details: DatasetDetails) {
+    pub fn set_dataset_details(&mut self, details: DatasetDetails) -> Result<(), Error> {
         self.details = details;
         self.updated_at = jiff::Timestamp::now();
+        self.persist()
     }
 
     pub fn as_entry_details(&self) -> EntryDetails {
@@ -302,17 +623,6 @@ impl Dataset {
             .map_err(Error::failed_to_extract_properties)
     }
 
-    pub fn layer_store_handle(
-        &self,
-        partition_id: &PartitionId,
-        layer_name: &str,
-    ) -> Option<&ChunkStoreHandle> {
-        self.partitions
-            .get(partition_id)
-            .and_then(|partition| partition.layer(layer_name))
-            .map(|layer| layer.store_handle())
-    }
-
     pub fn add_layer(
         &mut self,
         partition_id: PartitionId,
@@ -322,13 +632,234 @@ impl Dataset {
     ) -> Result<(), Error> {
         re_log::debug!(?partition_id, ?layer_name, "add_layer");
 
+        // Check for duplicates before creating the layer, which is expensive for persisted
+        // datasets.
+        let is_duplicate = self
+            .partitions
+            .get(&partition_id)
+            .is_some_and(|partition| partition.layer(&layer_name).is_some());
+        if is_duplicate {
+            match on_duplicate {
+                IfDuplicateBehavior::Overwrite => {}
+                IfDuplicateBehavior::Skip => {
+                    re_log::info!("Ignoring layer '{layer_name}': already exists in partition");
+                    return Ok(());
+                }
+                IfDuplicateBehavior::Error => {
+                    return Err(Error::LayerAlreadyExists(layer_name));
+                }
+            }
+        }
+
+        let layer = self.new_layer(store_handle, jiff::Timestamp::now())?;
+
         self.partitions
             .entry(partition_id)
             .or_default()
-            .insert_layer(layer_name, Layer::new(store_handle), on_duplicate)?;
+            .insert_layer(layer_name, layer, on_duplicate)?;
 
         self.updated_at = jiff::Timestamp::now();
-        Ok(())
+        self.persist()
+    }
+
+    /// Creates a layer for this dataset, on disk if the dataset is persisted.
+    fn new_layer(
+        &self,
+        store_handle: ChunkStoreHandle,
+        registration_time: jiff::Timestamp,
+    ) -> Result<Layer, Error> {
+        new_layer(self.storage_dir.as_deref(), store_handle, registration_time)
+    }
+
+    /// Snapshots the layers of this dataset, so that its maintenance can run without holding on
+    /// to the dataset.
+    ///
+    /// See [`MaintenanceSnapshot::run`] and [`Self::apply_maintenance`].
+    pub fn maintenance_snapshot(&self) -> MaintenanceSnapshot {
+        MaintenanceSnapshot {
+            storage_dir: self.storage_dir.clone(),
+            partitions: self
+                .partitions
+                .iter()
+                .map(|(partition_id, partition)| {
+                    let layers = partition
+                        .iter_layers()
+                        .map(|(layer_name, layer)| (layer_name.to_owned(), layer.clone()))
+                        .collect();
+                    (partition_id.clone(), layers)
+                })
+                .collect(),
+        }
+    }
+
+    /// Applies the result of a [`MaintenanceSnapshot::run`] to this dataset.
+    ///
+    /// Layers which were replaced or removed since the snapshot was taken are left untouched.
+    ///
+    /// Our indexes are brute-force and have no state of their own, so rebuilding them amounts to
+    /// checking that they still match the dataset schema.
+    pub fn apply_maintenance(
+        &mut self,
+        changes: MaintenanceChanges,
+        rebuild_indexes: bool,
+    ) -> Result<MaintenanceReport, Error> {
+        re_tracing::profile_function!();
+
+        let MaintenanceChanges {
+            duplicate_layers,
+            compacted_layers,
+            num_chunks_before,
+            num_chunks_after,
+        } = changes;
+
+        let mut report = MaintenanceReport {
+            num_chunks_before,
+            num_chunks_after,
+            ..Default::default()
+        };
+
+        for (partition_id, layer_name, layer) in duplicate_layers {
+            if let Some(partition) = self.unchanged_partition(&partition_id, &layer_name, &layer) {
+                re_log::debug!(?layer_name, "removing duplicate layer");
+                partition.remove_layer(&layer_name);
+                report.num_duplicate_layers_removed += 1;
+            }
+        }
+
+        let storage_dir = self.storage_dir.clone();
+        for (partition_id, layer_name, layer, compacted_layer) in compacted_layers {
+            if let Some(partition) = self.unchanged_partition(&partition_id, &layer_name, &layer) {
+                let compacted_layer =
+                    compacted_layer.commit(storage_dir.as_deref(), layer.registration_time())?;
+                partition.insert_layer(
+                    layer_name,
+                    compacted_layer,
+                    IfDuplicateBehavior::Overwrite,
+                )?;
+                report.num_layers_compacted += 1;
+            } else {
+                compacted_layer.discard();
+            }
+        }
+
+        if rebuild_indexes {
+            let schema = self.schema()?;
+            for config in &self.indexes {
+                ResolvedIndexColumns::new(&schema, config)?;
+            }
+            report.num_indexes_rebuilt = Some(self.indexes.len());
+        }
+
+        if report.num_duplicate_layers_removed > 0 || report.num_layers_compacted > 0 {
+            self.updated_at = jiff::Timestamp::now();
+            self.persist()?;
+        }
+
+        Ok(report)
+    }
+
+    /// Returns the partition holding `layer` as `layer_name`, if it still does.
+    fn unchanged_partition(
+        &mut self,
+        partition_id: &PartitionId,
+        layer_name: &str,
+        layer: &Layer,
+    ) -> Option<&mut Partition> {
+        self.partitions.get_mut(partition_id).filter(|partition| {
+            partition
+                .layer(layer_name)
+                .is_some_and(|current| current.is_same_layer(layer))
+        })
+    }
+
+    pub fn indexes(&self) -> &[IndexConfig] {
+        &self.indexes
+    }
+
+    fn index(&self, column: &IndexColumn) -> Option<&IndexConfig> {
+        self.indexes
+            .iter()
+            .find(|index| is_same_column(&index.column, column))
+    }
+
+    pub fn create_index(&mut self, config: IndexConfig) -> Result<(), Error> {
+        if self.index(&config.column).is_some() {
+            return Err(Error::IndexAlreadyExists(config.column.to_string()));
+        }
+
+        let schema = self.schema()?;
+        ResolvedIndexColumns::new(&schema, &config)?;
+
+        self.indexes.push(config);
+        self.updated_at = jiff::Timestamp::now();
+        self.persist()
+    }
+
+    /// Deletes the index of the given column, if any, and returns it.
+    pub fn delete_indexes(&mut self, column: &IndexColumn) -> Result<Vec<IndexConfig>, Error> {
+        let (deleted, kept) = std::mem::take(&mut self.indexes)
+            .into_iter()
+            .partition(|index| is_same_column(&index.column, column));
+        self.indexes = kept;
+
+        if !deleted.is_empty() {
+            self.updated_at = jiff::Timestamp::now();
+            self.persist()?;
+        }
+
+        Ok(deleted)
+    }
+
+    /// Backend-specific statistics about an index, as JSON.
+    pub fn index_statistics_json(&self, config: &IndexConfig) -> Vec<u8> {
+        let num_indexed_rows: u64 = self
+            .iter_layers()
+            .flat_map(|layer| layer.chunk_index())
+            .filter(|entry| chunk_has_column(entry, &config.column))
+            .map(|entry| entry.num_rows)
+            .sum();
+
+        serde_json::json!({ "num_indexed_rows": num_indexed_rows })
+            .to_string()
+            .into_bytes()
+    }
+
+    /// Prepares a search of the indexed `column`, which will scan all of its chunks.
+    ///
+    /// No chunk is read until [`PendingSearch::run`] is called.
+    pub fn search(
+        &self,
+        column: &IndexColumn,
+        query: &RecordBatch,
+        properties: &IndexQueryProperties,
+    ) -> Result<PendingSearch, Error> {
+        re_tracing::profile_function!();
+
+        let config = self
+            .index(column)
+            .ok_or_else(|| Error::IndexNotFound(column.to_string()))?;
+
+        let schema = self.schema()?;
+        let columns = ResolvedIndexColumns::new(&schema, config)?;
+        let search = Search::new(config, columns, properties, query)?;
+
+        let mut chunks = Vec::new();
+        for (partition_id, partition) in self.partitions.iter().sorted_by_key(|(id, _)| *id) {
+            for (_, layer) in partition.iter_layers() {
+                let chunk_ids = layer
+                    .chunk_index()
+                    .into_iter()
+                    .filter(|entry| chunk_has_column(entry, column))
+                    .map(|entry| entry.chunk_id)
+                    .collect_vec();
+
+                if !chunk_ids.is_empty() {
+                    chunks.push((partition_id.clone(), layer.clone(), chunk_ids));
+                }
+            }
+        }
+
+        Ok(PendingSearch::new(search, chunks))
     }
 
     /// Load a RRD using its recording id as partition id.¨
@@ -369,4 +900,70 @@ impl Dataset {
 
         Ok(new_partition_ids)
     }
+
+    /// Load an MCAP using its file name as partition id.
+    ///
+    /// `layer_names` is a comma-separated list of the MCAP layers to extract, all of them by
+    /// default. Each of them which extracted some data is added as a partition layer of the same
+    /// name.
+    ///
+    /// Returns the (partition id, layer name) pairs that were added.
+    pub fn load_mcap(
+        &mut self,
+        path: &Path,
+        layer_names: Option<&str>,
+        on_duplicate: IfDuplicateBehavior,
+    ) -> Result<Vec<(PartitionId, String)>, Error> {
+        let selected_layers =
+            mcap::selected_layers(layer_names.unwrap_or(DataSource::DEFAULT_LAYER))?;
+
+        // MCAP files only ever contain recordings.
+        if self.store_kind != StoreKind::Recording {
+            return Err(Error::McapIntoNonRecordingDataset(self.id, self.store_kind));
+        }
+
+        let (partition_id, stores) = mcap::load_mcap(self.id, path, &selected_layers)?;
+
+        let mut new_layers = Vec::with_capacity(stores.len());
+        for (layer_name, chunk_store) in stores {
+            self.add_layer(
+                partition_id.clone(),
+                layer_name.clone(),
+                chunk_store,
+                on_duplicate,
+            )?;
+            new_layers.push((partition_id.clone(), layer_name));
+        }
+
+        Ok(new_layers)
+    }
+}
+
+/// Creates a layer, on disk if `storage_dir` is set.
+fn new_layer(
+    storage_dir: Option<&Path>,
+    store_handle: ChunkStoreHandle,
+    registration_time: jiff::Timestamp,
+) -> Result<Layer, Error> {
+    Ok(if let Some(storage_dir) = storage_dir {
+        Layer::on_disk(
+            OnDiskLayer::write(storage_dir, &store_handle)?,
+            registration_time,
+        )
+    } else {
+        Layer::in_memory(store_handle, registration_time)
+    })
+}
+
+/// Two index columns are the same if they have the same entity path and component identifier.
+fn is_same_column(a: &IndexColumn, b: &IndexColumn) -> bool {
+    a.entity_path == b.entity_path && a.descriptor.component == b.descriptor.component
+}
+
+fn chunk_has_column(entry: &ChunkIndexEntry, column: &IndexColumn) -> bool {
+    entry.entity_path == column.entity_path
+        && entry
+            .components
+            .iter()
+            .any(|descr| descr.component == column.descriptor.component)
 }
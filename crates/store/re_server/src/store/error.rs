@@ -1,4 +1,4 @@
-use re_log_types::EntryId;
+use re_log_types::{EntryId, StoreKind};
 use re_protos::common::v1alpha1::ext::PartitionId;
 
 use crate::store::ChunkKey;
@@ -39,6 +39,15 @@ pub enum Error {
     #[error("Error loading RRD: {0}")]
     RrdLoadingError(anyhow::Error),
 
+    #[error("Error loading MCAP: {0}")]
+    McapLoadingError(anyhow::Error),
+
+    #[error("Unknown MCAP layer '{0}', expected one of: {1}")]
+    UnknownMcapLayer(String, String),
+
+    #[error("Only recording datasets can hold MCAP files, '{0}' is a {1} dataset")]
+    McapIntoNonRecordingDataset(EntryId, StoreKind),
+
     #[error("Failed to encode chunk key: {0}")]
     FailedToEncodeChunkKey(String),
 
@@ -50,6 +59,39 @@ pub enum Error {
 
     #[error("Failed to extract properties: {0:#?}")]
     FailedToExtractProperties(String),
+
+    #[error("Failed to write to storage directory: {0}")]
+    StorageWriteError(String),
+
+    #[error("Failed to read from storage directory: {0}")]
+    StorageReadError(String),
+
+    #[error("An index already exists for column '{0}'")]
+    IndexAlreadyExists(String),
+
+    #[error("No index found for column '{0}'")]
+    IndexNotFound(String),
+
+    #[error("Invalid index configuration: {0}")]
+    InvalidIndexConfig(String),
+
+    #[error("Invalid search query: {0}")]
+    InvalidSearchQuery(String),
+
+    #[error("Task '{0}' not found")]
+    TaskNotFound(String),
+
+    #[error("Task '{0}' failed: {1}")]
+    TaskFailed(String, String),
+
+    #[error(transparent)]
+    ChunkStoreError(#[from] re_chunk_store::ChunkStoreError),
+
+    #[error(transparent)]
+    ArrowError(#[from] arrow::error::ArrowError),
+
+    #[error(transparent)]
+    SorbetError(#[from] re_sorbet::SorbetError),
 }
 
 impl Error {
@@ -69,16 +111,32 @@ impl From<Error> for tonic::Status {
             | Error::EntryNameNotFound(_)
             | Error::PartitionIdNotFound(_, _)
             | Error::LayerNameNotFound(_, _, _)
-            | Error::ChunkNotFound(_) => Self::not_found(format!("{err:#}")),
+            | Error::ChunkNotFound(_)
+            | Error::TaskNotFound(_) => Self::not_found(format!("{err:#}")),
 
             Error::DataFusionError(err) => Self::internal(format!("DataFusion error: {err:#}")),
-            Error::RrdLoadingError(err) => Self::internal(format!("{err:#}")),
-
-            Error::FailedToDecodeChunkKey(_) => Self::invalid_argument(format!("{err:#}")),
-            Error::FailedToEncodeChunkKey(_) | Error::FailedToExtractProperties(_) => {
+            Error::ChunkStoreError(err) => Self::internal(format!("Chunk store error: {err:#}")),
+            Error::ArrowError(err) => Self::internal(format!("Arrow error: {err:#}")),
+            Error::SorbetError(err) => Self::internal(format!("Sorbet error: {err:#}")),
+            Error::RrdLoadingError(err) | Error::McapLoadingError(err) => {
                 Self::internal(format!("{err:#}"))
             }
 
+            Error::FailedToDecodeChunkKey(_)
+            | Error::IndexAlreadyExists(_)
+            | Error::IndexNotFound(_)
+            | Error::InvalidIndexConfig(_)
+            | Error::InvalidSearchQuery(_)
+            | Error::UnknownMcapLayer(_, _)
+            | Error::McapIntoNonRecordingDataset(_, _) => {
+                Self::invalid_argument(format!("{err:#}"))
+            }
+            Error::FailedToEncodeChunkKey(_)
+            | Error::FailedToExtractProperties(_)
+            | Error::StorageWriteError(_)
+            | Error::StorageReadError(_)
+            | Error::TaskFailed(_, _) => Self::internal(format!("{err:#}")),
+
             Error::DuplicateEntryNameError(_)
             | Error::DuplicateEntryIdError(_)
             | Error::LayerAlreadyExists(_) => Self::already_exists(format!("{err:#}")),
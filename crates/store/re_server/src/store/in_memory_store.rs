@@ -1,3 +1,4 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use ahash::HashMap;
@@ -14,7 +15,7 @@ use re_log_types::{EntryId, StoreId, StoreKind};
 use re_protos::{
     cloud::v1alpha1::{
         EntryKind,
-        ext::{DatasetDetails, EntryDetails, ProviderDetails, TableEntry},
+        ext::{DataSourceKind, DatasetDetails, EntryDetails, ProviderDetails, TableEntry},
     },
     common::v1alpha1::ext::{IfDuplicateBehavior, PartitionId},
 };
@@ -23,7 +24,7 @@ use re_types_core::{ComponentBatch as _, Loggable as _};
 
 use crate::entrypoint::NamedPath;
 use crate::store::table::TableType;
-use crate::store::{ChunkKey, Dataset, Error, Table};
+use crate::store::{ChunkKey, Dataset, Error, Table, on_disk};
 
 const ENTRIES_TABLE_NAME: &str = "__entries";
 
@@ -31,6 +32,9 @@ pub struct InMemoryStore {
     datasets: HashMap<EntryId, Dataset>,
     tables: HashMap<EntryId, Table>,
     id_by_name: HashMap<String, EntryId>,
+
+    /// If set, datasets are persisted in this directory rather than only living in memory.
+    storage_dir: Option<PathBuf>,
 }
 
 impl Default for InMemoryStore {
@@ -39,6 +43,7 @@ impl Default for InMemoryStore {
             tables: HashMap::default(),
             datasets: HashMap::default(),
             id_by_name: HashMap::default(),
+            storage_dir: None,
         };
         ret.update_entries_table()
             .expect("update_entries_table should never fail on initialization.");
@@ -47,6 +52,39 @@ impl Default for InMemoryStore {
 }
 
 impl InMemoryStore {
+    /// Opens a store persisted in the given directory, creating it if needed.
+    ///
+    /// All datasets previously persisted there are loaded back, and all datasets created
+    /// afterward will be persisted there as well.
+    pub fn open(storage_dir: &Path) -> Result<Self, Error> {
+        std::fs::create_dir_all(storage_dir)?;
+        let storage_dir = storage_dir.canonicalize()?;
+
+        let mut store = Self {
+            storage_dir: Some(storage_dir.clone()),
+            ..Default::default()
+        };
+
+        for dataset_dir in on_disk::list_dataset_dirs(&storage_dir)? {
+            let dataset = Dataset::load(dataset_dir)?;
+
+            if store.id_by_name.contains_key(dataset.name()) {
+                return Err(Error::DuplicateEntryNameError(dataset.name().to_owned()));
+            }
+            if store.id_exists(&dataset.id()) {
+                return Err(Error::DuplicateEntryIdError(dataset.id()));
+            }
+
+            store
+                .id_by_name
+                .insert(dataset.name().to_owned(), dataset.id());
+            store.datasets.insert(dataset.id(), dataset);
+        }
+
+        store.update_entries_table()?;
+        Ok(store)
+    }
+
     pub fn chunk_store_config() -> re_chunk_store::ChunkStoreConfig {
         ChunkStoreConfig::CHANGELOG_DISABLED
             .apply_env()
@@ -92,24 +130,20 @@ impl InMemoryStore {
                 );
 
                 for (layer_name, chunk_keys) in layer_index {
-                    let store_handle = partition
-                        .layer(layer_name)
-                        .ok_or_else(|| {
-                            Error::LayerNameNotFound(
-                                layer_name.to_owned(),
-                                partition_id.clone(),
-                                *dataset_id,
-                            )
-                        })?
-                        .store_handle()
-                        .read();
+                    let layer = partition.layer(layer_name).ok_or_else(|| {
+                        Error::LayerNameNotFound(
+                            layer_name.to_owned(),
+                            partition_id.clone(),
+                            *dataset_id,
+                        )
+                    })?;
 
                     for chunk_key in chunk_keys {
-                        let chunk = store_handle
-                            .chunk(&chunk_key.chunk_id)
+                        let chunk = layer
+                            .chunk(&chunk_key.chunk_id)?
                             .ok_or_else(|| Error::ChunkNotFound(chunk_key.clone()))?;
 
-                        result.push((store_id.clone(), Arc::clone(chunk)));
+                        result.push((store_id.clone(), chunk));
                     }
                 }
             }
@@ -118,7 +152,7 @@ impl InMemoryStore {
         Ok(result)
     }
 
-    /// Load a directory of RRDs.
+    /// Load a directory of RRDs and MCAPs.
     //TODO(ab): maybe we could be smart with .rbl and auto-setup a blueprint dataset?
     pub fn load_directory_as_dataset(
         &mut self,
@@ -142,20 +176,30 @@ impl InMemoryStore {
                 .to_string_lossy(),
         };
 
-        let dataset = self
-            .create_dataset(&entry_name, None, StoreKind::Recording, None)
-            .expect("Name cannot yet exist");
+        // When persisting to disk, the dataset may already exist from a previous run.
+        let dataset = match self.id_by_name.get(entry_name.as_ref()).copied() {
+            Some(entry_id) if on_duplicate != IfDuplicateBehavior::Error => {
+                self.dataset_mut(entry_id)?
+            }
+            _ => self.create_dataset(&entry_name, None, StoreKind::Recording, None)?,
+        };
 
         for entry in std::fs::read_dir(&directory)? {
             let entry = entry?;
             if entry.file_type()?.is_file() {
-                let is_rrd = entry
-                    .file_name()
-                    .to_str()
-                    .is_some_and(|s| s.to_lowercase().ends_with(".rrd"));
-
-                if is_rrd {
-                    dataset.load_rrd(&entry.path(), None, on_duplicate, StoreKind::Recording)?;
+                let path = entry.path();
+                let kind = path
+                    .extension()
+                    .and_then(|ext| DataSourceKind::from_extension(&ext.to_string_lossy()));
+
+                match kind {
+                    Some(DataSourceKind::Rrd) => {
+                        dataset.load_rrd(&path, None, on_duplicate, StoreKind::Recording)?;
+                    }
+                    Some(DataSourceKind::Mcap) => {
+                        dataset.load_mcap(&path, None, on_duplicate)?;
+                    }
+                    None => {}
                 }
             }
         }
@@ -243,7 +287,7 @@ impl InMemoryStore {
         }
 
         if let Some(dataset) = self.datasets.get_mut(&entry_id) {
-            dataset.set_name(entry_name.clone());
+            dataset.set_name(entry_name.clone())?;
         } else if let Some(table) = self.tables.get_mut(&entry_id) {
             table.set_name(entry_name.clone());
         } else {
@@ -338,18 +382,28 @@ impl InMemoryStore {
             return Err(Error::DuplicateEntryIdError(entry_id));
         }
 
-        self.id_by_name.insert(name.clone(), entry_id);
+        let storage_dir = self
+            .storage_dir
+            .as_deref()
+            .map(|storage_dir| on_disk::dataset_dir(storage_dir, entry_id));
+        let dataset = Dataset::new(
+            entry_id,
+            name.clone(),
+            store_kind,
+            details.unwrap_or_default(),
+            storage_dir,
+        )?;
+
+        self.id_by_name.insert(name, entry_id);
 
-        Ok(self.datasets.entry(entry_id).or_insert_with(|| {
-            Dataset::new(entry_id, name, store_kind, details.unwrap_or_default())
-        }))
+        Ok(self.datasets.entry(entry_id).or_insert(dataset))
     }
 
     pub fn delete_dataset(&mut self, entry_id: EntryId) -> Result<(), Error> {
         re_log::debug!(?entry_id, "delete_dataset");
         if let Some(dataset) = self.datasets.remove(&entry_id) {
             self.id_by_name.remove(dataset.name());
-            Ok(())
+            dataset.delete_storage()
         } else {
             Err(Error::EntryIdNotFound(entry_id))
         }
@@ -3,13 +3,36 @@ use arrow::datatypes::Schema;
 use arrow::error::ArrowError;
 use sha2::Digest as _;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use re_byte_size::SizeBytes as _;
-use re_chunk_store::ChunkStoreHandle;
+use re_chunk_store::{Chunk, ChunkStore, ChunkStoreHandle};
+use re_types_core::ChunkId;
+
+use crate::store::{ChunkIndexEntry, Error, InMemoryStore, OnDiskLayer};
+
+/// Upper bound on the number of compaction passes run by [`Layer::compacted_store_handle`].
+///
+/// Each pass can only merge a chunk with its immediate neighbors, so it takes a few of them for
+/// the result to converge.
+const MAX_COMPACTION_PASSES: usize = 16;
+
+/// Where the data of a [`Layer`] is stored.
+#[derive(Clone)]
+enum LayerStorage {
+    /// All chunks are kept in memory.
+    InMemory(ChunkStoreHandle),
+
+    /// Chunks live on disk and are loaded on demand.
+    OnDisk(Arc<OnDiskLayer>),
+}
 
 #[derive(Clone)]
 pub struct Layer {
-    store_handle: ChunkStoreHandle,
+    /// Identifies this layer, and its clones, across replacements of the layer of the same name.
+    id: re_tuid::Tuid,
+
+    storage: LayerStorage,
     registration_time: jiff::Timestamp,
 }
 
@@ -18,8 +41,100 @@ impl Layer {
         store_handle.into()
     }
 
-    pub fn store_handle(&self) -> &ChunkStoreHandle {
-        &self.store_handle
+    pub fn in_memory(store_handle: ChunkStoreHandle, registration_time: jiff::Timestamp) -> Self {
+        Self {
+            id: re_tuid::Tuid::new(),
+            storage: LayerStorage::InMemory(store_handle),
+            registration_time,
+        }
+    }
+
+    pub fn on_disk(layer: OnDiskLayer, registration_time: jiff::Timestamp) -> Self {
+        Self {
+            id: re_tuid::Tuid::new(),
+            storage: LayerStorage::OnDisk(Arc::new(layer)),
+            registration_time,
+        }
+    }
+
+    /// Whether `other` is this very layer (or a clone of it), as opposed to one with the same
+    /// content.
+    pub fn is_same_layer(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+
+    /// Returns the file id of this layer, if it is stored on disk.
+    pub fn file_id(&self) -> Option<&str> {
+        match &self.storage {
+            LayerStorage::InMemory(_) => None,
+            LayerStorage::OnDisk(layer) => Some(layer.file_id()),
+        }
+    }
+
+    /// Returns a handle to a store containing all the chunks of this layer.
+    ///
+    /// For on-disk layers, this loads the entire layer in memory.
+    pub fn load_store_handle(&self) -> Result<ChunkStoreHandle, Error> {
+        match &self.storage {
+            LayerStorage::InMemory(store_handle) => Ok(store_handle.clone()),
+            LayerStorage::OnDisk(layer) => layer.load_store_handle(),
+        }
+    }
+
+    /// Returns a store with the chunks of this layer merged together as much as the store
+    /// configuration allows, or `None` if no chunks could be merged.
+    ///
+    /// This is the equivalent of `rerun rrd compact`.
+    pub fn compacted_store_handle(&self) -> Result<Option<ChunkStoreHandle>, Error> {
+        re_tracing::profile_function!();
+
+        let mut store_handle = self.load_store_handle()?;
+        let num_chunks_before = store_handle.read().num_chunks();
+        let mut num_chunks = num_chunks_before;
+
+        for _ in 0..MAX_COMPACTION_PASSES {
+            let compacted = {
+                let store = store_handle.read();
+                let mut compacted =
+                    ChunkStore::new(store.id(), InMemoryStore::chunk_store_config());
+                for chunk in store.iter_chunks() {
+                    compacted.insert_chunk(&chunk)?;
+                }
+                compacted
+            };
+
+            let num_chunks_after = compacted.num_chunks();
+            store_handle = ChunkStoreHandle::new(compacted);
+
+            if num_chunks_after == num_chunks {
+                break;
+            }
+            num_chunks = num_chunks_after;
+        }
+
+        Ok((num_chunks < num_chunks_before).then_some(store_handle))
+    }
+
+    /// Returns the index of all the chunks in this layer, without loading them.
+    pub fn chunk_index(&self) -> Vec<ChunkIndexEntry> {
+        match &self.storage {
+            LayerStorage::InMemory(store_handle) => store_handle
+                .read()
+                .iter_chunks()
+                .map(|chunk| ChunkIndexEntry::from_chunk(&chunk))
+                .collect(),
+            LayerStorage::OnDisk(layer) => layer.chunk_index().to_vec(),
+        }
+    }
+
+    /// Returns the specified chunk, or `None` if it is not part of this layer.
+    pub fn chunk(&self, chunk_id: &ChunkId) -> Result<Option<Arc<Chunk>>, Error> {
+        match &self.storage {
+            LayerStorage::InMemory(store_handle) => {
+                Ok(store_handle.read().chunk(chunk_id).map(Arc::clone))
+            }
+            LayerStorage::OnDisk(layer) => layer.read_chunk(chunk_id),
+        }
     }
 
     pub fn registration_time(&self) -> jiff::Timestamp {
@@ -38,20 +153,35 @@ impl Layer {
     }
 
     pub fn num_chunks(&self) -> u64 {
-        self.store_handle.read().num_chunks() as u64
+        match &self.storage {
+            LayerStorage::InMemory(store_handle) => store_handle.read().num_chunks() as u64,
+            LayerStorage::OnDisk(layer) => layer.chunk_index().len() as u64,
+        }
     }
 
     pub fn size_bytes(&self) -> u64 {
-        self.store_handle
-            .read()
-            .iter_chunks()
-            .map(|chunk| chunk.heap_size_bytes())
-            .sum()
+        match &self.storage {
+            LayerStorage::InMemory(store_handle) => store_handle
+                .read()
+                .iter_chunks()
+                .map(|chunk| chunk.heap_size_bytes())
+                .sum(),
+            LayerStorage::OnDisk(layer) => layer
+                .chunk_index()
+                .iter()
+                .map(|entry| entry.size_bytes)
+                .sum(),
+        }
     }
 
     pub fn schema(&self) -> Schema {
-        let fields = self.store_handle.read().schema().arrow_fields();
-        Schema::new_with_metadata(fields, HashMap::default())
+        match &self.storage {
+            LayerStorage::InMemory(store_handle) => {
+                let fields = store_handle.read().schema().arrow_fields();
+                Schema::new_with_metadata(fields, HashMap::default())
+            }
+            LayerStorage::OnDisk(layer) => layer.schema().clone(),
+        }
     }
 
     pub fn schema_sha256(&self) -> Result<[u8; 32], ArrowError> {
@@ -81,15 +211,15 @@ impl Layer {
     pub fn compute_properties(
         &self,
     ) -> Result<RecordBatch, re_chunk_store::ExtractPropertiesError> {
-        self.store_handle.read().extract_properties()
+        match &self.storage {
+            LayerStorage::InMemory(store_handle) => store_handle.read().extract_properties(),
+            LayerStorage::OnDisk(layer) => Ok(layer.properties().clone()),
+        }
     }
 }
 
 impl From<ChunkStoreHandle> for Layer {
     fn from(value: ChunkStoreHandle) -> Self {
-        Self {
-            store_handle: value,
-            registration_time: jiff::Timestamp::now(),
-        }
+        Self::in_memory(value, jiff::Timestamp::now())
     }
 }
@@ -0,0 +1,112 @@
+//! Conversion of MCAP files into partition layers.
+//!
+//! The data of an MCAP file is extracted by the [`re_mcap`] layers, and the chunks produced by
+//! each of these layers end up in their own partition layer, named after the MCAP layer.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use re_chunk_store::{Chunk, ChunkStore, ChunkStoreHandle};
+use re_log_types::{EntryId, StoreId, StoreKind};
+use re_mcap::{LayerIdentifier, LayerRegistry, SelectedLayers};
+use re_protos::{cloud::v1alpha1::ext::DataSource, common::v1alpha1::ext::PartitionId};
+
+use crate::store::{Error, InMemoryStore};
+
+/// Interprets the layer of an MCAP data source as a comma-separated list of MCAP layers.
+///
+/// The default layer selects all of them.
+pub fn selected_layers(layer: &str) -> Result<SelectedLayers, Error> {
+    if layer == DataSource::DEFAULT_LAYER {
+        return Ok(SelectedLayers::All);
+    }
+
+    let registry = LayerRegistry::all_builtin(true);
+    let known_layers = registry
+        .layer_identifiers()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    layer
+        .split(',')
+        .map(str::trim)
+        .map(|name| {
+            if known_layers.iter().any(|known| known == name) {
+                Ok(LayerIdentifier::from(name.to_owned()))
+            } else {
+                Err(Error::UnknownMcapLayer(
+                    name.to_owned(),
+                    known_layers.join(", "),
+                ))
+            }
+        })
+        .collect::<Result<_, _>>()
+        .map(SelectedLayers::Subset)
+}
+
+/// Load an MCAP file, using its file name (without extension) as partition id.
+///
+/// Returns one chunk store per MCAP layer which extracted some data, keyed by layer name.
+pub fn load_mcap(
+    dataset_id: EntryId,
+    path: &Path,
+    selected_layers: &SelectedLayers,
+) -> Result<(PartitionId, BTreeMap<String, ChunkStoreHandle>), Error> {
+    re_tracing::profile_function!();
+    re_log::info!("Loading MCAP: {}", path.display());
+
+    let partition_id = path
+        .file_stem()
+        .map(|stem| PartitionId::new(stem.to_string_lossy().into_owned()))
+        .ok_or_else(|| {
+            Error::McapLoadingError(anyhow::anyhow!("Invalid MCAP path: {}", path.display()))
+        })?;
+
+    let file = std::fs::File::open(path)?;
+
+    // SAFETY: file-backed memory maps are marked unsafe because of potential UB when using the map and the underlying file is modified.
+    #[expect(unsafe_code)]
+    let mcap = unsafe { memmap2::Mmap::map(&file)? };
+
+    let summary = re_mcap::read_summary(std::io::Cursor::new(&mcap))
+        .map_err(Error::McapLoadingError)?
+        .ok_or_else(|| {
+            Error::McapLoadingError(anyhow::anyhow!("MCAP file does not contain a summary"))
+        })?;
+
+    let mut chunks_per_layer: BTreeMap<String, Vec<Chunk>> = BTreeMap::new();
+    LayerRegistry::all_builtin(true)
+        .select(selected_layers)
+        .plan(&summary)
+        .and_then(|plan| {
+            plan.run_per_layer(&mcap, &summary, &mut |layer, chunk| {
+                chunks_per_layer
+                    .entry(layer.to_string())
+                    .or_default()
+                    .push(chunk);
+            })
+        })
+        .map_err(Error::McapLoadingError)?;
+
+    let store_id = StoreId::new(
+        StoreKind::Recording,
+        dataset_id.to_string(),
+        partition_id.id.clone(),
+    );
+
+    let stores = chunks_per_layer
+        .into_iter()
+        .map(|(layer_name, chunks)| {
+            let mut store = ChunkStore::new(store_id.clone(), InMemoryStore::chunk_store_config());
+            for chunk in chunks {
+                store
+                    .insert_chunk(&Arc::new(chunk))
+                    .map_err(|err| Error::McapLoadingError(err.into()))?;
+            }
+            Ok((layer_name, ChunkStoreHandle::new(store)))
+        })
+        .collect::<Result<_, Error>>()?;
+
+    Ok((partition_id, stores))
+}
@@ -1,12 +1,25 @@
+mod chunk_index;
 mod chunk_key;
 mod dataset;
 mod error;
 mod in_memory_store;
 mod layer;
+mod mcap;
+mod on_disk;
 mod partition;
+mod search;
 mod table;
+mod tasks;
 
 pub use self::{
-    chunk_key::ChunkKey, dataset::Dataset, error::Error, in_memory_store::InMemoryStore,
-    layer::Layer, partition::Partition, table::Table,
+    chunk_index::{ChunkIndexEntry, ChunkIndexFilter},
+    chunk_key::ChunkKey,
+    dataset::{Dataset, MaintenanceReport},
+    error::Error,
+    in_memory_store::InMemoryStore,
+    layer::Layer,
+    on_disk::OnDiskLayer,
+    partition::Partition,
+    table::Table,
+    tasks::TaskRegistry,
 };
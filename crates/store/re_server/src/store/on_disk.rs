@@ -0,0 +1,532 @@
+//! Filesystem persistence for datasets.
+//!
+//! Layout of a storage directory:
+//!
+//! ```text
+//! <root>/
+//!   <dataset_id>/
+//!     dataset.json              # catalog entry, partitions, layers and indexes
+//!     layers/
+//!       <layer_file_id>.rrd     # the chunks of a layer, as a regular RRD file
+//!       <layer_file_id>.index   # the chunk index of that layer (see `LayerIndex`)
+//!     staging/                  # layers being written in the background, same layout as `layers`
+//! ```
+//!
+//! Layer files are immutable once written. Overwriting a layer writes a new file and the old one
+//! gets garbage collected the next time the dataset manifest is persisted.
+//!
+//! Layers written without holding the dataset (e.g. by maintenance tasks) are first staged, so that
+//! they are not garbage collected before they get referenced by the manifest.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read as _, Seek as _};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow::array::RecordBatch;
+use arrow::datatypes::Schema;
+
+use re_chunk_store::{Chunk, ChunkStore, ChunkStoreHandle, WeakChunkStoreHandle};
+use re_log_encoding::{
+    CrateVersion, Decodable as _, Encoder, EncodingOptions, StreamHeader, ToApplication as _,
+};
+use re_log_types::{EntryId, LogMsg, SetStoreInfo, StoreId, StoreInfo, StoreKind, StoreSource};
+use re_protos::{cloud::v1alpha1::ext::IndexConfig, common::v1alpha1::ext::PartitionId};
+use re_types_core::ChunkId;
+
+use crate::store::{ChunkIndexEntry, Error, InMemoryStore};
+
+const DATASET_MANIFEST_FILE_NAME: &str = "dataset.json";
+const LAYERS_DIR_NAME: &str = "layers";
+const STAGING_DIR_NAME: &str = "staging";
+
+// ---
+
+/// The persisted catalog entry of a dataset.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct DatasetManifest {
+    pub id: EntryId,
+    pub name: String,
+    pub store_kind: StoreKind,
+    pub blueprint_dataset: Option<EntryId>,
+    pub default_blueprint: Option<PartitionId>,
+    pub created_at_ns: i64,
+    pub updated_at_ns: i64,
+
+    /// Keyed by partition id.
+    pub partitions: BTreeMap<String, PartitionManifest>,
+
+    #[serde(default)]
+    pub indexes: Vec<IndexConfig>,
+}
+
+/// The persisted state of a partition.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PartitionManifest {
+    pub last_updated_at_ns: i64,
+    pub layers: BTreeMap<String, LayerManifest>,
+}
+
+/// The persisted state of a layer.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct LayerManifest {
+    /// File stem of the layer's `.rrd`/`.index` files, in the dataset's `layers` directory.
+    pub file_id: String,
+    pub registration_time_ns: i64,
+}
+
+impl DatasetManifest {
+    pub fn path(dataset_dir: &Path) -> PathBuf {
+        dataset_dir.join(DATASET_MANIFEST_FILE_NAME)
+    }
+
+    pub fn read(dataset_dir: &Path) -> Result<Self, Error> {
+        let path = Self::path(dataset_dir);
+        let data = std::fs::read(&path)?;
+        serde_json::from_slice(&data)
+            .map_err(|err| Error::StorageReadError(format!("{}: {err:#}", path.display())))
+    }
+
+    /// Atomically replaces the manifest, then deletes the layer files it no longer references.
+    pub fn write(&self, dataset_dir: &Path) -> Result<(), Error> {
+        let data = serde_json::to_vec_pretty(self)
+            .map_err(|err| Error::StorageWriteError(format!("{err:#}")))?;
+
+        let path = Self::path(dataset_dir);
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, data)?;
+        std::fs::rename(&tmp_path, &path)?;
+
+        self.remove_unreferenced_layer_files(dataset_dir)
+    }
+
+    fn remove_unreferenced_layer_files(&self, dataset_dir: &Path) -> Result<(), Error> {
+        let layers_dir = layers_dir(dataset_dir);
+        if !layers_dir.is_dir() {
+            return Ok(());
+        }
+
+        let referenced: ahash::HashSet<&str> = self
+            .partitions
+            .values()
+            .flat_map(|partition| partition.layers.values())
+            .map(|layer| layer.file_id.as_str())
+            .collect();
+
+        for entry in std::fs::read_dir(&layers_dir)? {
+            let path = entry?.path();
+            let is_referenced = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| referenced.contains(stem));
+
+            if !is_referenced {
+                re_log::debug!("Removing unreferenced layer file {}", path.display());
+                std::fs::remove_file(&path)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn dataset_dir(storage_dir: &Path, dataset_id: EntryId) -> PathBuf {
+    storage_dir.join(dataset_id.to_string())
+}
+
+pub fn layers_dir(dataset_dir: &Path) -> PathBuf {
+    dataset_dir.join(LAYERS_DIR_NAME)
+}
+
+pub fn staging_dir(dataset_dir: &Path) -> PathBuf {
+    dataset_dir.join(STAGING_DIR_NAME)
+}
+
+/// Lists the directories of all persisted datasets.
+pub fn list_dataset_dirs(storage_dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut dataset_dirs = Vec::new();
+    for entry in std::fs::read_dir(storage_dir)? {
+        let path = entry?.path();
+        if path.is_dir() && DatasetManifest::path(&path).is_file() {
+            dataset_dirs.push(path);
+        }
+    }
+
+    dataset_dirs.sort();
+    Ok(dataset_dirs)
+}
+
+pub fn timestamp_to_ns(timestamp: jiff::Timestamp) -> i64 {
+    timestamp.as_nanosecond() as i64
+}
+
+pub fn timestamp_from_ns(ns: i64) -> Result<jiff::Timestamp, Error> {
+    jiff::Timestamp::from_nanosecond(ns as i128)
+        .map_err(|err| Error::StorageReadError(format!("invalid timestamp {ns}: {err:#}")))
+}
+
+// ---
+
+/// Where an encoded chunk lives within a layer's RRD file.
+///
+/// `byte_offset` points to the start of the message header.
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+struct ChunkLocation {
+    byte_offset: u64,
+    byte_len: u64,
+}
+
+/// The sidecar index written next to each layer's RRD file.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct LayerIndex {
+    store_id: StoreId,
+    chunks: Vec<(ChunkIndexEntry, ChunkLocation)>,
+
+    /// The layer's schema, as an Arrow IPC stream with no batches.
+    schema_ipc: Vec<u8>,
+}
+
+/// A layer whose chunks live on disk, and are only loaded when they are actually needed.
+///
+/// Only the chunk index, the schema, and the (small, static) property chunks are kept in memory.
+pub struct OnDiskLayer {
+    file_id: String,
+    rrd_path: PathBuf,
+    store_id: StoreId,
+    entries: Vec<ChunkIndexEntry>,
+    locations: HashMap<ChunkId, ChunkLocation>,
+    schema: Schema,
+    properties: RecordBatch,
+
+    /// The store last returned by [`Self::load_store_handle`], for as long as someone uses it.
+    loaded_store: parking_lot::Mutex<WeakChunkStoreHandle>,
+
+    /// The layer's RRD file, opened by the first [`Self::read_chunk`] and kept open from then on.
+    file: parking_lot::Mutex<Option<std::fs::File>>,
+}
+
+impl OnDiskLayer {
+    /// Writes the content of `store_handle` as a new layer in `dataset_dir`.
+    ///
+    /// Nothing is left behind on disk if this fails.
+    pub fn write(dataset_dir: &Path, store_handle: &ChunkStoreHandle) -> Result<Self, Error> {
+        Self::write_in(&layers_dir(dataset_dir), store_handle)
+    }
+
+    /// Same as [`Self::write`], but the layer is staged until it is [`Self::commit`]ted.
+    ///
+    /// Staged layers are not garbage collected when the dataset manifest is persisted.
+    pub fn write_staged(
+        dataset_dir: &Path,
+        store_handle: &ChunkStoreHandle,
+    ) -> Result<Self, Error> {
+        Self::write_in(&staging_dir(dataset_dir), store_handle)
+    }
+
+    /// Moves a staged layer to the layers of `dataset_dir`.
+    pub fn commit(mut self, dataset_dir: &Path) -> Result<Self, Error> {
+        let layers_dir = layers_dir(dataset_dir);
+        std::fs::create_dir_all(&layers_dir)?;
+
+        let rrd_path = layers_dir.join(format!("{}.rrd", self.file_id));
+        std::fs::rename(index_path(&self.rrd_path), index_path(&rrd_path))?;
+        std::fs::rename(&self.rrd_path, &rrd_path)?;
+        self.rrd_path = rrd_path;
+        *self.file.get_mut() = None;
+
+        Ok(self)
+    }
+
+    /// Deletes the files of a staged layer.
+    pub fn discard(self) {
+        remove_layer_files(&self.rrd_path);
+    }
+
+    fn write_in(dir: &Path, store_handle: &ChunkStoreHandle) -> Result<Self, Error> {
+        re_tracing::profile_function!();
+
+        std::fs::create_dir_all(dir)?;
+
+        let file_id = re_tuid::Tuid::new().to_string();
+        let rrd_path = dir.join(format!("{file_id}.rrd"));
+
+        let result = Self::write_files(file_id, rrd_path.clone(), store_handle);
+        if result.is_err() {
+            remove_layer_files(&rrd_path);
+        }
+
+        result
+    }
+
+    fn write_files(
+        file_id: String,
+        rrd_path: PathBuf,
+        store_handle: &ChunkStoreHandle,
+    ) -> Result<Self, Error> {
+        let store = store_handle.read();
+        let store_id = store.id();
+
+        let write_err = |err: &dyn std::fmt::Display| {
+            Error::StorageWriteError(format!("{}: {err:#}", rrd_path.display()))
+        };
+
+        let file = std::fs::File::create(&rrd_path)?;
+        let mut encoder = Encoder::new_eager(
+            CrateVersion::LOCAL,
+            EncodingOptions::PROTOBUF_COMPRESSED,
+            std::io::BufWriter::new(file),
+        )
+        .map_err(|err| write_err(&err))?;
+
+        let mut byte_offset = StreamHeader::ENCODED_SIZE_BYTES as u64;
+        byte_offset += encoder
+            .append(&LogMsg::SetStoreInfo(SetStoreInfo {
+                row_id: re_tuid::Tuid::new(),
+                info: StoreInfo::new(store_id.clone(), StoreSource::Other("re_server".to_owned())),
+            }))
+            .map_err(|err| write_err(&err))?;
+
+        let mut chunks = Vec::with_capacity(store.num_chunks());
+        for chunk in store.iter_chunks() {
+            let arrow_msg = chunk.to_arrow_msg().map_err(|err| write_err(&err))?;
+            let byte_len = encoder
+                .append(&LogMsg::ArrowMsg(store_id.clone(), arrow_msg))
+                .map_err(|err| write_err(&err))?;
+
+            chunks.push((
+                ChunkIndexEntry::from_chunk(&chunk),
+                ChunkLocation {
+                    byte_offset,
+                    byte_len,
+                },
+            ));
+            byte_offset += byte_len;
+        }
+
+        encoder.finish().map_err(|err| write_err(&err))?;
+        encoder.flush_blocking().map_err(|err| write_err(&err))?;
+        drop(encoder);
+
+        let schema = Schema::new_with_metadata(store.schema().arrow_fields(), Default::default());
+        let schema_ipc = {
+            let mut schema_ipc = Vec::new();
+            arrow::ipc::writer::StreamWriter::try_new(&mut schema_ipc, &schema)
+                .and_then(|mut writer| writer.finish())
+                .map_err(|err| write_err(&err))?;
+            schema_ipc
+        };
+
+        let index = LayerIndex {
+            store_id,
+            chunks,
+            schema_ipc,
+        };
+        let index_data = bincode::serialize(&index).map_err(|err| write_err(&err))?;
+        std::fs::write(index_path(&rrd_path), index_data)?;
+
+        let properties = store
+            .extract_properties()
+            .map_err(Error::failed_to_extract_properties)?;
+
+        Ok(Self::from_index(
+            file_id, rrd_path, index, schema, properties,
+        ))
+    }
+
+    /// Opens an existing layer from `dataset_dir`.
+    pub fn open(dataset_dir: &Path, file_id: &str) -> Result<Self, Error> {
+        re_tracing::profile_function!();
+
+        let rrd_path = layers_dir(dataset_dir).join(format!("{file_id}.rrd"));
+        let read_err = |err: &dyn std::fmt::Display| {
+            Error::StorageReadError(format!("{}: {err:#}", rrd_path.display()))
+        };
+
+        let index_data = std::fs::read(index_path(&rrd_path))?;
+        let index: LayerIndex = bincode::deserialize(&index_data).map_err(|err| read_err(&err))?;
+
+        let schema = arrow::ipc::reader::StreamReader::try_new(index.schema_ipc.as_slice(), None)
+            .map_err(|err| read_err(&err))?
+            .schema()
+            .as_ref()
+            .clone();
+
+        // Properties are stored as static chunks on a handful of entities, so they are cheap to
+        // load eagerly.
+        let properties = {
+            let mut store =
+                ChunkStore::new(index.store_id.clone(), InMemoryStore::chunk_store_config());
+            let mut file = std::fs::File::open(&rrd_path)?;
+            for (entry, location) in &index.chunks {
+                if entry.entity_path.is_property() {
+                    let chunk =
+                        read_chunk_at(&mut file, *location).map_err(|err| read_err(&err))?;
+                    store
+                        .insert_chunk(&Arc::new(chunk))
+                        .map_err(|err| read_err(&err))?;
+                }
+            }
+            store
+                .extract_properties()
+                .map_err(Error::failed_to_extract_properties)?
+        };
+
+        Ok(Self::from_index(
+            file_id.to_owned(),
+            rrd_path,
+            index,
+            schema,
+            properties,
+        ))
+    }
+
+    fn from_index(
+        file_id: String,
+        rrd_path: PathBuf,
+        index: LayerIndex,
+        schema: Schema,
+        properties: RecordBatch,
+    ) -> Self {
+        let LayerIndex {
+            store_id,
+            chunks,
+            schema_ipc: _,
+        } = index;
+
+        let locations = chunks
+            .iter()
+            .map(|(entry, location)| (entry.chunk_id, *location))
+            .collect();
+        let entries = chunks.into_iter().map(|(entry, _)| entry).collect();
+
+        Self {
+            file_id,
+            rrd_path,
+            store_id,
+            entries,
+            locations,
+            schema,
+            properties,
+            loaded_store: Default::default(),
+            file: Default::default(),
+        }
+    }
+
+    pub fn file_id(&self) -> &str {
+        &self.file_id
+    }
+
+    pub fn chunk_index(&self) -> &[ChunkIndexEntry] {
+        &self.entries
+    }
+
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    pub fn properties(&self) -> &RecordBatch {
+        &self.properties
+    }
+
+    /// Reads a single chunk from disk.
+    ///
+    /// Returns `None` if the chunk is not part of this layer.
+    pub fn read_chunk(&self, chunk_id: &ChunkId) -> Result<Option<Arc<Chunk>>, Error> {
+        let Some(location) = self.locations.get(chunk_id) else {
+            return Ok(None);
+        };
+
+        let data = {
+            let mut file = self.file.lock();
+            let file = match file.take() {
+                Some(opened) => file.insert(opened),
+                None => file.insert(std::fs::File::open(&self.rrd_path)?),
+            };
+            read_bytes_at(file, *location)?
+        };
+
+        // Decoding doesn't need the file anymore, leave it to the other readers in the meantime.
+        let chunk = decode_chunk(&data, *location).map_err(|err| {
+            Error::StorageReadError(format!("{}: {err:#}", self.rrd_path.display()))
+        })?;
+
+        Ok(Some(Arc::new(chunk)))
+    }
+
+    /// Loads the entire layer in memory.
+    ///
+    /// The file is only decoded again once all the handles previously returned have been dropped.
+    pub fn load_store_handle(&self) -> Result<ChunkStoreHandle, Error> {
+        re_tracing::profile_function!();
+
+        let mut loaded_store = self.loaded_store.lock();
+        if let Some(store_handle) = loaded_store.upgrade() {
+            return Ok(store_handle);
+        }
+
+        let store_handle = ChunkStore::handle_from_rrd_filepath(
+            &InMemoryStore::chunk_store_config(),
+            &self.rrd_path,
+        )
+        .map_err(Error::RrdLoadingError)?
+        .remove(&self.store_id)
+        .ok_or_else(|| {
+            Error::StorageReadError(format!(
+                "{}: missing store {:?}",
+                self.rrd_path.display(),
+                self.store_id
+            ))
+        })?;
+
+        *loaded_store = store_handle.downgrade();
+        Ok(store_handle)
+    }
+}
+
+fn index_path(rrd_path: &Path) -> PathBuf {
+    rrd_path.with_extension("index")
+}
+
+/// Removes the files of a layer, if they exist.
+fn remove_layer_files(rrd_path: &Path) {
+    for path in [index_path(rrd_path), rrd_path.to_owned()] {
+        if let Err(err) = std::fs::remove_file(&path)
+            && err.kind() != std::io::ErrorKind::NotFound
+        {
+            re_log::warn!("Failed to remove {}: {err}", path.display());
+        }
+    }
+}
+
+fn read_chunk_at(file: &mut std::fs::File, location: ChunkLocation) -> anyhow::Result<Chunk> {
+    let data = read_bytes_at(file, location)?;
+    decode_chunk(&data, location)
+}
+
+fn read_bytes_at(file: &mut std::fs::File, location: ChunkLocation) -> std::io::Result<Vec<u8>> {
+    let ChunkLocation {
+        byte_offset,
+        byte_len,
+    } = location;
+
+    let mut data = vec![0u8; byte_len as usize];
+    file.seek(std::io::SeekFrom::Start(byte_offset))?;
+    file.read_exact(&mut data)?;
+
+    Ok(data)
+}
+
+fn decode_chunk(data: &[u8], location: ChunkLocation) -> anyhow::Result<Chunk> {
+    let ChunkLocation {
+        byte_offset,
+        byte_len: _,
+    } = location;
+
+    let Some(re_protos::log_msg::v1alpha1::log_msg::Msg::ArrowMsg(arrow_msg)) =
+        Option::<re_protos::log_msg::v1alpha1::log_msg::Msg>::from_rrd_bytes(data)?
+    else {
+        anyhow::bail!("expected an ArrowMsg at offset {byte_offset}");
+    };
+
+    Ok(Chunk::from_arrow_msg(&arrow_msg.to_application(())?)?)
+}
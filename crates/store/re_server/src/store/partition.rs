@@ -34,6 +34,14 @@ impl Partition {
         }
     }
 
+    /// Restores a partition from its persisted layers.
+    pub fn from_layers(layers: HashMap<String, Layer>, last_updated_at: jiff::Timestamp) -> Self {
+        Self {
+            layers,
+            last_updated_at,
+        }
+    }
+
     pub fn layer_count(&self) -> usize {
         self.layers.len()
     }
@@ -88,6 +96,12 @@ impl Partition {
         Ok(())
     }
 
+    pub fn remove_layer(&mut self, layer_name: &str) -> Option<Layer> {
+        let layer = self.layers.remove(layer_name)?;
+        self.last_updated_at = jiff::Timestamp::now();
+        Some(layer)
+    }
+
     pub fn num_chunks(&self) -> u64 {
         self.layers.values().map(|layer| layer.num_chunks()).sum()
     }
@@ -0,0 +1,625 @@
+//! Brute-force search over the chunks of a dataset.
+//!
+//! Indexes are not materialized: an index only records which column can be searched and how.
+//! Every search scans all the chunks of the indexed column, which keeps the results in sync with
+//! the registered data at the cost of search performance on large datasets.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::{
+    Array as _, ArrayRef, AsArray as _, Float32Array, Int64Array, RecordBatch, RecordBatchOptions,
+    Scalar, StringArray,
+};
+use arrow::datatypes::{DataType, Field, Float32Type, Schema};
+
+use re_chunk_store::Chunk;
+use re_protos::cloud::v1alpha1::{
+    SearchDatasetResponse, VectorDistanceMetric,
+    ext::{IndexConfig, IndexProperties, IndexQueryProperties},
+};
+use re_protos::common::v1alpha1::ext::PartitionId;
+use re_sorbet::{
+    BatchType, ColumnDescriptor, ComponentColumnDescriptor, IndexColumnDescriptor,
+    SorbetColumnDescriptors,
+};
+use re_types_core::ChunkId;
+
+use crate::store::{Error, Layer};
+
+/// BM25 parameters, using the usual defaults.
+const BM25_K1: f32 = 1.2;
+const BM25_B: f32 = 0.75;
+
+/// The columns of the dataset schema that an index refers to.
+pub struct ResolvedIndexColumns {
+    pub component: ComponentColumnDescriptor,
+    pub time_index: IndexColumnDescriptor,
+}
+
+impl ResolvedIndexColumns {
+    /// Finds the indexed component and timeline in the dataset schema, and checks that the index
+    /// can be built on that component.
+    pub fn new(schema: &Schema, config: &IndexConfig) -> Result<Self, Error> {
+        let columns = SorbetColumnDescriptors::try_from_arrow_fields(None, schema.fields())?;
+
+        let mut component = None;
+        let mut time_index = None;
+        for column in columns {
+            match column {
+                ColumnDescriptor::Component(descr)
+                    if descr.entity_path == config.column.entity_path
+                        && descr.component == config.column.descriptor.component =>
+                {
+                    component = Some(descr);
+                }
+
+                ColumnDescriptor::Time(descr) if descr.timeline_name() == config.time_index => {
+                    time_index = Some(descr);
+                }
+
+                _ => {}
+            }
+        }
+
+        let component = component.ok_or_else(|| {
+            Error::InvalidIndexConfig(format!("column '{}' not found", config.column))
+        })?;
+        let time_index = time_index.ok_or_else(|| {
+            Error::InvalidIndexConfig(format!("timeline '{}' not found", config.time_index))
+        })?;
+
+        let datatype = instance_datatype(&component);
+        let is_supported = match &config.properties {
+            IndexProperties::Inverted { base_tokenizer, .. } => {
+                if !Tokenizer::SUPPORTED.contains(&base_tokenizer.as_str()) {
+                    return Err(Error::InvalidIndexConfig(format!(
+                        "unsupported tokenizer {base_tokenizer:?}, expected one of {:?}",
+                        Tokenizer::SUPPORTED
+                    )));
+                }
+
+                matches!(datatype, DataType::Utf8 | DataType::LargeUtf8)
+            }
+
+            IndexProperties::VectorIvfPq { metric, .. } => {
+                if *metric == VectorDistanceMetric::Hamming {
+                    return Err(Error::InvalidIndexConfig(
+                        "the hamming distance is not supported".to_owned(),
+                    ));
+                }
+
+                match datatype {
+                    DataType::FixedSizeList(field, _)
+                    | DataType::List(field)
+                    | DataType::LargeList(field) => field.data_type().is_numeric(),
+                    datatype => datatype.is_numeric(),
+                }
+            }
+
+            IndexProperties::Btree => {
+                datatype.is_primitive()
+                    || matches!(
+                        datatype,
+                        DataType::Boolean | DataType::Utf8 | DataType::LargeUtf8
+                    )
+            }
+        };
+
+        if !is_supported {
+            return Err(Error::InvalidIndexConfig(format!(
+                "cannot create a {} index on column '{}' of type {datatype}",
+                config.properties, config.column
+            )));
+        }
+
+        Ok(Self {
+            component,
+            time_index,
+        })
+    }
+}
+
+/// The datatype of a single instance of the component.
+fn instance_datatype(component: &ComponentColumnDescriptor) -> &DataType {
+    match &component.store_datatype {
+        DataType::List(field) | DataType::LargeList(field) => field.data_type(),
+        datatype => datatype,
+    }
+}
+
+// ---
+
+#[derive(Clone, Copy)]
+enum Tokenizer {
+    /// Splits on anything that isn't alphanumeric, and lowercases.
+    Simple,
+
+    /// Splits on whitespaces.
+    Whitespace,
+
+    /// The whole text is a single token.
+    Raw,
+}
+
+impl Tokenizer {
+    const SUPPORTED: &[&str] = &["simple", "whitespace", "raw"];
+
+    fn new(name: &str) -> Self {
+        match name {
+            "whitespace" => Self::Whitespace,
+            "raw" => Self::Raw,
+            _ => Self::Simple,
+        }
+    }
+
+    fn tokenize(self, text: &str) -> Vec<String> {
+        match self {
+            Self::Simple => text
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|token| !token.is_empty())
+                .map(str::to_lowercase)
+                .collect(),
+            Self::Whitespace => text.split_whitespace().map(ToOwned::to_owned).collect(),
+            Self::Raw => vec![text.to_owned()],
+        }
+    }
+}
+
+enum Query {
+    FullText {
+        tokenizer: Tokenizer,
+        terms: Vec<String>,
+
+        /// Number of documents containing each term, for all searched rows.
+        doc_freqs: Vec<u32>,
+        num_docs: u32,
+        total_doc_len: u64,
+    },
+
+    Vector {
+        vector: Vec<f32>,
+        metric: VectorDistanceMetric,
+        top_k: usize,
+    },
+
+    Exact {
+        value: ArrayRef,
+    },
+}
+
+/// A row that matched the query.
+struct Hit {
+    partition_id: String,
+    time: Option<i64>,
+
+    /// Unit slice of the component's list array.
+    value: ArrayRef,
+
+    score: HitScore,
+}
+
+enum HitScore {
+    /// Term frequencies and length of the document, used to compute its BM25 score.
+    FullText {
+        term_freqs: Vec<u32>,
+        doc_len: u32,
+    },
+    Distance(f32),
+    None,
+}
+
+/// A search whose chunks have been selected, but not read yet.
+///
+/// It owns everything it needs, so that it can run without holding on to the store.
+pub struct PendingSearch {
+    search: Search,
+
+    /// The chunks to scan, in order.
+    chunks: Vec<(PartitionId, Layer, Vec<ChunkId>)>,
+}
+
+impl PendingSearch {
+    pub fn new(search: Search, chunks: Vec<(PartitionId, Layer, Vec<ChunkId>)>) -> Self {
+        Self { search, chunks }
+    }
+
+    /// Reads the selected chunks and scans them.
+    ///
+    /// This may read from disk, so it should not be run on an async executor thread.
+    pub fn run(self) -> Result<RecordBatch, Error> {
+        re_tracing::profile_function!();
+
+        let Self { mut search, chunks } = self;
+
+        for (partition_id, layer, chunk_ids) in chunks {
+            for chunk_id in chunk_ids {
+                if let Some(chunk) = layer.chunk(&chunk_id)? {
+                    search.add_chunk(&partition_id, &chunk)?;
+                }
+            }
+        }
+
+        search.finish()
+    }
+}
+
+/// An ongoing search, which chunks are fed to one at a time.
+pub struct Search {
+    columns: ResolvedIndexColumns,
+    config: IndexConfig,
+    query: Query,
+    hits: Vec<Hit>,
+}
+
+impl Search {
+    pub fn new(
+        config: &IndexConfig,
+        columns: ResolvedIndexColumns,
+        properties: &IndexQueryProperties,
+        query: &RecordBatch,
+    ) -> Result<Self, Error> {
+        let query_column = match query.columns() {
+            [column] if !column.is_empty() => column,
+            _ => {
+                return Err(Error::InvalidSearchQuery(
+                    "the query must have exactly one non-empty column".to_owned(),
+                ));
+            }
+        };
+
+        let query = match (&config.properties, properties) {
+            (IndexProperties::Inverted { base_tokenizer, .. }, IndexQueryProperties::Inverted) => {
+                let tokenizer = Tokenizer::new(base_tokenizer);
+                let text = arrow::compute::cast(query_column, &DataType::Utf8).map_err(|err| {
+                    Error::InvalidSearchQuery(format!("expected a text query: {err:#}"))
+                })?;
+                let mut terms = tokenizer.tokenize(text.as_string::<i32>().value(0));
+                terms.sort();
+                terms.dedup();
+
+                Query::FullText {
+                    tokenizer,
+                    doc_freqs: vec![0; terms.len()],
+                    terms,
+                    num_docs: 0,
+                    total_doc_len: 0,
+                }
+            }
+
+            (
+                IndexProperties::VectorIvfPq { metric, .. },
+                IndexQueryProperties::Vector { top_k },
+            ) => {
+                // The query is either a single list, or a column of floats.
+                let values = match query_column.data_type() {
+                    DataType::FixedSizeList(_, _) => query_column.as_fixed_size_list().value(0),
+                    DataType::List(_) => query_column.as_list::<i32>().value(0),
+                    DataType::LargeList(_) => query_column.as_list::<i64>().value(0),
+                    _ => query_column.clone(),
+                };
+
+                let vector = to_f32_vec(&values)
+                    .map_err(|err| Error::InvalidSearchQuery(format!("{err:#}")))?
+                    .ok_or_else(|| {
+                        Error::InvalidSearchQuery("the query vector contains nulls".to_owned())
+                    })?;
+
+                Query::Vector {
+                    vector,
+                    metric: *metric,
+                    top_k: *top_k as usize,
+                }
+            }
+
+            (IndexProperties::Btree, IndexQueryProperties::Btree) => {
+                let datatype = instance_datatype(&columns.component);
+                let value =
+                    arrow::compute::cast(&query_column.slice(0, 1), datatype).map_err(|err| {
+                        Error::InvalidSearchQuery(format!(
+                            "query cannot be converted to {datatype}: {err:#}"
+                        ))
+                    })?;
+
+                Query::Exact { value }
+            }
+
+            (index_properties, query_properties) => {
+                return Err(Error::InvalidSearchQuery(format!(
+                    "cannot run a {query_properties:?} query against a {index_properties} index"
+                )));
+            }
+        };
+
+        Ok(Self {
+            columns,
+            config: config.clone(),
+            query,
+            hits: Vec::new(),
+        })
+    }
+
+    /// Searches the rows of a chunk of the indexed entity.
+    pub fn add_chunk(&mut self, partition_id: &PartitionId, chunk: &Chunk) -> Result<(), Error> {
+        let Some(list_array) = chunk
+            .components()
+            .get_array(self.columns.component.component)
+        else {
+            return Ok(());
+        };
+
+        let times = chunk
+            .timelines()
+            .get(&self.config.time_index)
+            .map(|time_column| time_column.times_raw());
+
+        for row in 0..list_array.len() {
+            if list_array.is_null(row) {
+                continue;
+            }
+
+            let instances = list_array.value(row);
+            let score = match &mut self.query {
+                Query::FullText {
+                    tokenizer,
+                    terms,
+                    doc_freqs,
+                    num_docs,
+                    total_doc_len,
+                } => {
+                    let instances = arrow::compute::cast(&instances, &DataType::Utf8)?;
+                    let tokens: Vec<String> = instances
+                        .as_string::<i32>()
+                        .iter()
+                        .flatten()
+                        .flat_map(|text| tokenizer.tokenize(text))
+                        .collect();
+
+                    let term_freqs: Vec<u32> = terms
+                        .iter()
+                        .map(|term| tokens.iter().filter(|token| *token == term).count() as u32)
+                        .collect();
+
+                    *num_docs += 1;
+                    *total_doc_len += tokens.len() as u64;
+                    for (doc_freq, term_freq) in doc_freqs.iter_mut().zip(&term_freqs) {
+                        *doc_freq += u32::from(*term_freq > 0);
+                    }
+
+                    if term_freqs.iter().all(|term_freq| *term_freq == 0) {
+                        continue;
+                    }
+
+                    HitScore::FullText {
+                        term_freqs,
+                        doc_len: tokens.len() as u32,
+                    }
+                }
+
+                Query::Vector { vector, metric, .. } => {
+                    let mut min_distance = None;
+                    for instance in row_vectors(&instances)? {
+                        let Some(instance) = instance else {
+                            continue;
+                        };
+
+                        if instance.len() != vector.len() {
+                            return Err(Error::InvalidSearchQuery(format!(
+                                "the query vector has {} dimensions, but column '{}' has {}",
+                                vector.len(),
+                                self.config.column,
+                                instance.len()
+                            )));
+                        }
+
+                        let distance = distance(*metric, vector, &instance);
+                        min_distance =
+                            Some(min_distance.map_or(distance, |d: f32| d.min(distance)));
+                    }
+
+                    let Some(distance) = min_distance else {
+                        continue;
+                    };
+
+                    HitScore::Distance(distance)
+                }
+
+                Query::Exact { value } => {
+                    let matches =
+                        arrow::compute::kernels::cmp::eq(&instances, &Scalar::new(value.clone()))?;
+                    if matches.true_count() == 0 {
+                        continue;
+                    }
+
+                    HitScore::None
+                }
+            };
+
+            self.hits.push(Hit {
+                partition_id: partition_id.to_string(),
+                time: times.map(|times| times[row]),
+                value: Arc::new(list_array.slice(row, 1)),
+                score,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Ranks the hits, and returns them as a dataframe.
+    ///
+    /// Full-text hits come with their BM25 score (in decreasing order), vector hits with their
+    /// distance to the query (in increasing order), and exact matches in dataset order.
+    pub fn finish(self) -> Result<RecordBatch, Error> {
+        let Self {
+            columns,
+            config: _,
+            query,
+            hits,
+        } = self;
+
+        let (score_field_name, hits) = match query {
+            Query::FullText {
+                tokenizer: _,
+                terms: _,
+                doc_freqs,
+                num_docs,
+                total_doc_len,
+            } => {
+                let avg_doc_len = total_doc_len as f32 / num_docs.max(1) as f32;
+                let idfs: Vec<f32> = doc_freqs
+                    .iter()
+                    .map(|&doc_freq| {
+                        let doc_freq = doc_freq as f32;
+                        ((num_docs as f32 - doc_freq + 0.5) / (doc_freq + 0.5)).ln_1p()
+                    })
+                    .collect();
+
+                let mut hits: Vec<_> = hits
+                    .into_iter()
+                    .map(|hit| {
+                        let score = match &hit.score {
+                            HitScore::FullText {
+                                term_freqs,
+                                doc_len,
+                            } => bm25(&idfs, term_freqs, *doc_len as f32, avg_doc_len),
+                            HitScore::Distance(_) | HitScore::None => 0.0,
+                        };
+                        (hit, score)
+                    })
+                    .collect();
+                hits.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+                (Some(SearchDatasetResponse::FIELD_SCORE), hits)
+            }
+
+            Query::Vector { top_k, .. } => {
+                let mut hits: Vec<_> = hits
+                    .into_iter()
+                    .map(|hit| {
+                        let distance = match hit.score {
+                            HitScore::Distance(distance) => distance,
+                            HitScore::FullText { .. } | HitScore::None => f32::INFINITY,
+                        };
+                        (hit, distance)
+                    })
+                    .collect();
+                hits.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+                hits.truncate(top_k);
+
+                (Some(SearchDatasetResponse::FIELD_DISTANCE), hits)
+            }
+
+            Query::Exact { .. } => (None, hits.into_iter().map(|hit| (hit, 0.0)).collect()),
+        };
+
+        let partition_ids =
+            StringArray::from_iter_values(hits.iter().map(|(hit, _)| hit.partition_id.as_str()));
+
+        let times = arrow::compute::cast(
+            &hits.iter().map(|(hit, _)| hit.time).collect::<Int64Array>(),
+            columns.time_index.datatype(),
+        )?;
+
+        let values = if hits.is_empty() {
+            arrow::array::new_empty_array(&columns.component.store_datatype)
+        } else {
+            let values: Vec<_> = hits.iter().map(|(hit, _)| hit.value.as_ref()).collect();
+            re_arrow_util::concat_arrays(&values)?
+        };
+
+        let component_field = columns.component.to_arrow_field(BatchType::Dataframe);
+        let mut fields = vec![
+            Field::new(
+                SearchDatasetResponse::FIELD_PARTITION_ID,
+                DataType::Utf8,
+                false,
+            ),
+            columns.time_index.to_arrow_field().with_nullable(true),
+            Field::new(component_field.name(), values.data_type().clone(), true)
+                .with_metadata(component_field.metadata().clone()),
+        ];
+        let mut arrays: Vec<ArrayRef> = vec![Arc::new(partition_ids), times, values];
+
+        if let Some(score_field_name) = score_field_name {
+            fields.push(Field::new(score_field_name, DataType::Float32, false));
+            arrays.push(Arc::new(Float32Array::from_iter_values(
+                hits.iter().map(|(_, score)| *score),
+            )));
+        }
+
+        Ok(RecordBatch::try_new_with_options(
+            Arc::new(Schema::new_with_metadata(fields, HashMap::default())),
+            arrays,
+            &RecordBatchOptions::default().with_row_count(Some(hits.len())),
+        )?)
+    }
+}
+
+fn bm25(idfs: &[f32], term_freqs: &[u32], doc_len: f32, avg_doc_len: f32) -> f32 {
+    let length_norm = 1.0 - BM25_B + BM25_B * doc_len / avg_doc_len.max(f32::EPSILON);
+
+    idfs.iter()
+        .zip(term_freqs)
+        .map(|(idf, &term_freq)| {
+            let term_freq = term_freq as f32;
+            idf * term_freq * (BM25_K1 + 1.0) / (term_freq + BM25_K1 * length_norm)
+        })
+        .sum()
+}
+
+/// Follows the conventions of Lance, so that results are comparable with the hosted service.
+fn distance(metric: VectorDistanceMetric, a: &[f32], b: &[f32]) -> f32 {
+    let dot = || a.iter().zip(b).map(|(a, b)| a * b).sum::<f32>();
+
+    match metric {
+        VectorDistanceMetric::Cosine => {
+            let norm_a = a.iter().map(|a| a * a).sum::<f32>().sqrt();
+            let norm_b = b.iter().map(|b| b * b).sum::<f32>().sqrt();
+            if norm_a == 0.0 || norm_b == 0.0 {
+                1.0
+            } else {
+                1.0 - dot() / (norm_a * norm_b)
+            }
+        }
+
+        VectorDistanceMetric::Dot => 1.0 - dot(),
+
+        // Squared euclidean distance. Also the default, as in Lance.
+        VectorDistanceMetric::L2
+        | VectorDistanceMetric::Unspecified
+        | VectorDistanceMetric::Hamming => a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum(),
+    }
+}
+
+/// Converts an array of numbers to floats, or returns `None` if it contains nulls.
+fn to_f32_vec(array: &ArrayRef) -> Result<Option<Vec<f32>>, Error> {
+    if array.null_count() > 0 {
+        return Ok(None);
+    }
+
+    let array = arrow::compute::cast(array, &DataType::Float32)?;
+    Ok(Some(array.as_primitive::<Float32Type>().values().to_vec()))
+}
+
+/// Returns the vectors stored in a single row of a component.
+///
+/// A row is either a list of vectors, or a single vector if its instances are plain numbers.
+fn row_vectors(instances: &ArrayRef) -> Result<Vec<Option<Vec<f32>>>, Error> {
+    let vectors: Vec<Option<ArrayRef>> = match instances.data_type() {
+        datatype if datatype.is_numeric() => return Ok(vec![to_f32_vec(instances)?]),
+        DataType::FixedSizeList(_, _) => instances.as_fixed_size_list().iter().collect(),
+        DataType::List(_) => instances.as_list::<i32>().iter().collect(),
+        DataType::LargeList(_) => instances.as_list::<i64>().iter().collect(),
+        datatype => {
+            return Err(Error::InvalidSearchQuery(format!(
+                "expected a list of vectors, got {datatype}"
+            )));
+        }
+    };
+
+    vectors
+        .into_iter()
+        .map(|vector| vector.map_or(Ok(None), |vector| to_f32_vec(&vector)))
+        .collect()
+}
@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+
+use arrow::array::RecordBatch;
+
+use re_protos::{cloud::v1alpha1::QueryTasksResponse, common::v1alpha1::TaskId};
+
+use crate::store::Error;
+use crate::store::on_disk::timestamp_to_ns;
+
+/// How long finished tasks can still be queried.
+const FINISHED_TASK_TTL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// How many finished tasks are kept at most, the oldest ones are forgotten first.
+const MAX_FINISHED_TASKS: usize = 1000;
+
+/// Execution status of a task, as reported by `QueryTasks`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStatus {
+    Running,
+    Success,
+    Failed,
+}
+
+impl TaskStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Success => "success",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+struct Task {
+    kind: &'static str,
+    status: TaskStatus,
+    msgs: Option<String>,
+    creation_time: jiff::Timestamp,
+    last_update_time: jiff::Timestamp,
+}
+
+/// Keeps track of the tasks run by the server.
+///
+/// Tasks are run as part of the request which starts them, so they are only ever observed as
+/// running by concurrent requests.
+///
+/// Finished tasks are forgotten after [`FINISHED_TASK_TTL`], or once there are more than
+/// [`MAX_FINISHED_TASKS`] of them.
+#[derive(Default)]
+pub struct TaskRegistry {
+    /// Task ids are time-ordered, so this is also sorted by creation time.
+    tasks: BTreeMap<String, Task>,
+}
+
+impl TaskRegistry {
+    /// Registers a new running task of the given kind.
+    pub fn start(&mut self, kind: &'static str) -> TaskId {
+        let id = format!("task_{}", re_tuid::Tuid::new());
+        let now = jiff::Timestamp::now();
+
+        self.tasks.insert(
+            id.clone(),
+            Task {
+                kind,
+                status: TaskStatus::Running,
+                msgs: None,
+                creation_time: now,
+                last_update_time: now,
+            },
+        );
+
+        TaskId { id }
+    }
+
+    /// Marks a task as finished, with either its report or its error message.
+    pub fn finish(&mut self, task_id: &TaskId, result: Result<String, String>) {
+        let Some(task) = self.tasks.get_mut(&task_id.id) else {
+            re_log::warn!("Finishing unknown task {}", task_id.id);
+            return;
+        };
+
+        let (status, msgs) = match result {
+            Ok(report) => (TaskStatus::Success, report),
+            Err(err) => (TaskStatus::Failed, err),
+        };
+
+        task.status = status;
+        task.msgs = Some(msgs);
+        task.last_update_time = jiff::Timestamp::now();
+
+        self.evict_finished_tasks();
+    }
+
+    fn evict_finished_tasks(&mut self) {
+        let now = jiff::Timestamp::now();
+        self.tasks.retain(|_, task| {
+            task.status == TaskStatus::Running
+                || now.duration_since(task.last_update_time).unsigned_abs() < FINISHED_TASK_TTL
+        });
+
+        let num_finished = self
+            .tasks
+            .values()
+            .filter(|task| task.status != TaskStatus::Running)
+            .count();
+        let mut num_to_evict = num_finished.saturating_sub(MAX_FINISHED_TASKS);
+
+        // Task ids are time-ordered, so this evicts the oldest tasks first.
+        self.tasks.retain(|_, task| {
+            if num_to_evict > 0 && task.status != TaskStatus::Running {
+                num_to_evict -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Returns whether all the given tasks are finished.
+    ///
+    /// As per our proto conventions, all tasks are considered if none is listed.
+    pub fn all_finished(&self, task_ids: &[TaskId]) -> Result<bool, Error> {
+        Ok(self
+            .tasks_from_ids(task_ids)?
+            .iter()
+            .all(|(_, task)| task.status != TaskStatus::Running))
+    }
+
+    /// Returns the status of the given tasks, as expected by `QueryTasks`.
+    ///
+    /// As per our proto conventions, all tasks are returned if none is listed.
+    pub fn query(&self, task_ids: &[TaskId]) -> Result<RecordBatch, Error> {
+        let tasks = self.tasks_from_ids(task_ids)?;
+
+        Ok(QueryTasksResponse::create_dataframe(
+            tasks.iter().map(|(id, _)| (*id).to_owned()).collect(),
+            tasks
+                .iter()
+                .map(|(_, task)| Some(task.kind.to_owned()))
+                .collect(),
+            vec![None; tasks.len()],
+            tasks
+                .iter()
+                .map(|(_, task)| task.status.as_str().to_owned())
+                .collect(),
+            tasks.iter().map(|(_, task)| task.msgs.clone()).collect(),
+            vec![None; tasks.len()],
+            vec![None; tasks.len()],
+            vec![None; tasks.len()],
+            vec![1; tasks.len()],
+            tasks
+                .iter()
+                .map(|(_, task)| Some(timestamp_to_ns(task.creation_time)))
+                .collect(),
+            tasks
+                .iter()
+                .map(|(_, task)| Some(timestamp_to_ns(task.last_update_time)))
+                .collect(),
+        )?)
+    }
+
+    fn tasks_from_ids<'a>(
+        &'a self,
+        task_ids: &'a [TaskId],
+    ) -> Result<Vec<(&'a str, &'a Task)>, Error> {
+        if task_ids.is_empty() {
+            return Ok(self
+                .tasks
+                .iter()
+                .map(|(id, task)| (id.as_str(), task))
+                .collect());
+        }
+
+        task_ids
+            .iter()
+            .map(|task_id| {
+                self.tasks
+                    .get(&task_id.id)
+                    .map(|task| (task_id.id.as_str(), task))
+                    .ok_or_else(|| Error::TaskNotFound(task_id.id.clone()))
+            })
+            .collect()
+    }
+}
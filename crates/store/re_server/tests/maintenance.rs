@@ -0,0 +1,253 @@
+//! Checks that dataset maintenance compacts and deduplicates layers, and is tracked as a task.
+
+#![expect(clippy::unwrap_used)]
+
+use arrow::array::{AsArray as _, RecordBatch};
+use futures::StreamExt as _;
+
+use re_protos::{
+    cloud::v1alpha1::{
+        CreateDatasetEntryRequest, DataSource, DataSourceKind, DoGlobalMaintenanceRequest,
+        DoMaintenanceRequest, QueryTasksOnCompletionRequest, QueryTasksRequest, QueryTasksResponse,
+        RegisterWithDatasetRequest, ext::QueryDatasetRequest,
+        rerun_cloud_service_server::RerunCloudService as _,
+    },
+    common::v1alpha1::{IfDuplicateBehavior, TaskId},
+    headers::RerunHeadersInjectorExt as _,
+};
+use re_server::{RerunCloudHandler, RerunCloudHandlerBuilder};
+
+const DATASET_NAME: &str = "my_dataset";
+
+/// Registers the same recording twice, as two layers of the same partition.
+async fn setup(handler: &RerunCloudHandler, rrd_path: &std::path::Path) {
+    handler
+        .create_dataset_entry(tonic::Request::new(CreateDatasetEntryRequest {
+            name: Some(DATASET_NAME.to_owned()),
+            id: None,
+        }))
+        .await
+        .unwrap();
+
+    handler
+        .register_with_dataset(
+            tonic::Request::new(RegisterWithDatasetRequest {
+                data_sources: ["base", "copy"]
+                    .into_iter()
+                    .map(|layer| DataSource {
+                        storage_url: Some(url::Url::from_file_path(rrd_path).unwrap().into()),
+                        layer: Some(layer.to_owned()),
+                        prefix: false,
+                        typ: DataSourceKind::Rrd as i32,
+                    })
+                    .collect(),
+                on_duplicate: IfDuplicateBehavior::Error as i32,
+            })
+            .with_entry_name(DATASET_NAME)
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn num_chunks(handler: &RerunCloudHandler) -> usize {
+    let batches: Vec<RecordBatch> = handler
+        .query_dataset(
+            tonic::Request::new(QueryDatasetRequest::default().into())
+                .with_entry_name(DATASET_NAME)
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .into_inner()
+        .map(|resp| resp.unwrap().data.unwrap().try_into().unwrap())
+        .collect()
+        .await;
+
+    batches.iter().map(|batch| batch.num_rows()).sum()
+}
+
+async fn do_maintenance(handler: &RerunCloudHandler) -> String {
+    handler
+        .do_maintenance(
+            tonic::Request::new(DoMaintenanceRequest {
+                optimize_indexes: true,
+                ..Default::default()
+            })
+            .with_entry_name(DATASET_NAME)
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+        .into_inner()
+        .report
+}
+
+/// Returns the (task id, kind, status) of the given tasks, or all of them.
+async fn query_tasks(
+    handler: &RerunCloudHandler,
+    ids: Vec<TaskId>,
+) -> Vec<(String, String, String)> {
+    let batch: RecordBatch = handler
+        .query_tasks(tonic::Request::new(QueryTasksRequest { ids }))
+        .await
+        .unwrap()
+        .into_inner()
+        .data
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    let column = |name: &str| {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_string::<i32>()
+            .iter()
+            .map(|value| value.unwrap().to_owned())
+            .collect::<Vec<_>>()
+    };
+
+    itertools::izip!(
+        column(QueryTasksResponse::FIELD_TASK_ID),
+        column(QueryTasksResponse::FIELD_KIND),
+        column(QueryTasksResponse::FIELD_EXEC_STATUS),
+    )
+    .collect()
+}
+
+#[tokio::test]
+async fn maintenance_removes_duplicate_layers() {
+    let rrd_path =
+        re_redap_tests::create_simple_recording(1, "my_partition_id", &["my/entity"]).unwrap();
+
+    let handler = RerunCloudHandlerBuilder::new().build();
+    setup(&handler, &rrd_path).await;
+
+    let num_chunks_before = num_chunks(&handler).await;
+    assert!(num_chunks_before > 0);
+
+    let report = do_maintenance(&handler).await;
+    assert!(
+        report.contains("removed 1 duplicate layer(s)"),
+        "unexpected report: {report}"
+    );
+    assert!(
+        report.contains("rebuilt 0 index(es)"),
+        "unexpected report: {report}"
+    );
+    assert_eq!(num_chunks(&handler).await, num_chunks_before / 2);
+
+    // Nothing left to do.
+    let report = do_maintenance(&handler).await;
+    assert!(
+        report.contains("removed 0 duplicate layer(s), compacted 0 layer(s)"),
+        "unexpected report: {report}"
+    );
+}
+
+#[tokio::test]
+async fn maintenance_is_tracked_as_a_task() {
+    let rrd_path =
+        re_redap_tests::create_simple_recording(1, "my_partition_id", &["my/entity"]).unwrap();
+
+    let handler = RerunCloudHandlerBuilder::new().build();
+    setup(&handler, &rrd_path).await;
+
+    let report = do_maintenance(&handler).await;
+    let (task_id, _) = report.split_once(':').unwrap();
+    let task_id = TaskId {
+        id: task_id.to_owned(),
+    };
+
+    let responses: Vec<RecordBatch> = handler
+        .query_tasks_on_completion(tonic::Request::new(QueryTasksOnCompletionRequest {
+            ids: vec![task_id.clone()],
+            timeout: None,
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .map(|resp| resp.unwrap().data.unwrap().try_into().unwrap())
+        .collect()
+        .await;
+    assert_eq!(responses.len(), 1);
+    assert_eq!(responses[0].num_rows(), 1);
+
+    assert_eq!(
+        query_tasks(&handler, vec![task_id.clone()]).await,
+        vec![(task_id.id, "maintenance".to_owned(), "success".to_owned())]
+    );
+
+    handler
+        .do_global_maintenance(tonic::Request::new(DoGlobalMaintenanceRequest {}))
+        .await
+        .unwrap();
+
+    // Registration, then one maintenance task for our dataset and one per dataset (including
+    // blueprint datasets) for the global maintenance.
+    let kinds = query_tasks(&handler, vec![])
+        .await
+        .into_iter()
+        .map(|(_, kind, status)| {
+            assert_eq!(status, "success");
+            kind
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(kinds[0], "register_with_dataset");
+    assert!(kinds.len() > 2, "{kinds:?}");
+    assert!(
+        kinds[1..].iter().all(|kind| kind == "maintenance"),
+        "{kinds:?}"
+    );
+
+    let status = handler
+        .query_tasks(tonic::Request::new(QueryTasksRequest {
+            ids: vec![TaskId {
+                id: "task_unknown".to_owned(),
+            }],
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::NotFound);
+}
+
+#[tokio::test]
+async fn maintenance_survives_restart() {
+    let storage_dir = tempfile::tempdir().unwrap();
+    let rrd_path =
+        re_redap_tests::create_simple_recording(1, "my_partition_id", &["my/entity"]).unwrap();
+
+    let num_chunks_after = {
+        let handler = RerunCloudHandlerBuilder::new()
+            .with_storage_directory(storage_dir.path())
+            .unwrap()
+            .build();
+        setup(&handler, &rrd_path).await;
+
+        do_maintenance(&handler).await;
+        num_chunks(&handler).await
+    };
+
+    let handler = RerunCloudHandlerBuilder::new()
+        .with_storage_directory(storage_dir.path())
+        .unwrap()
+        .build();
+    assert_eq!(num_chunks(&handler).await, num_chunks_after);
+
+    // Only the files of the remaining layer are left.
+    let num_layer_files = std::fs::read_dir(storage_dir.path())
+        .unwrap()
+        .flat_map(|dataset_dir| std::fs::read_dir(dataset_dir.unwrap().path().join("layers")))
+        .flatten()
+        .filter(|entry| {
+            entry
+                .as_ref()
+                .unwrap()
+                .path()
+                .extension()
+                .is_some_and(|ext| ext == "rrd")
+        })
+        .count();
+    assert_eq!(num_layer_files, 1);
+}
@@ -0,0 +1,203 @@
+//! Runs the redap test suite against a server persisting its datasets on disk, and checks that
+//! datasets survive a restart.
+
+#![expect(clippy::unwrap_used)]
+
+use arrow::array::RecordBatch;
+use futures::StreamExt as _;
+
+use re_protos::{
+    cloud::v1alpha1::{
+        CreateDatasetEntryRequest, DataSource, DataSourceKind, FetchChunksRequest,
+        RegisterWithDatasetRequest, ScanPartitionTableRequest, ScanPartitionTableResponse,
+        ext::QueryDatasetRequest, rerun_cloud_service_server::RerunCloudService as _,
+    },
+    common::v1alpha1::IfDuplicateBehavior,
+    headers::RerunHeadersInjectorExt as _,
+};
+use re_redap_tests::RecordBatchExt as _;
+use re_server::{RerunCloudHandler, RerunCloudHandlerBuilder};
+
+#[expect(clippy::unused_async)] // needed by the macro
+async fn build() -> (RerunCloudHandler, tempfile::TempDir) {
+    let storage_dir = tempfile::tempdir().unwrap();
+
+    let handler = RerunCloudHandlerBuilder::new()
+        .with_storage_directory(storage_dir.path())
+        .unwrap()
+        .build();
+
+    (handler, storage_dir)
+}
+
+re_redap_tests::generate_redap_tests!(build, with_fixture);
+
+// ---
+
+async fn scan_partition_table(handler: &RerunCloudHandler, dataset_name: &str) -> RecordBatch {
+    let batches: Vec<RecordBatch> = handler
+        .scan_partition_table(
+            tonic::Request::new(ScanPartitionTableRequest { columns: vec![] })
+                .with_entry_name(dataset_name)
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .into_inner()
+        .map(|resp| resp.unwrap().data.unwrap().try_into().unwrap())
+        .collect()
+        .await;
+
+    arrow::compute::concat_batches(&batches[0].schema(), &batches)
+        .unwrap()
+        .sort_rows_by(&[ScanPartitionTableResponse::FIELD_PARTITION_ID])
+        .unwrap()
+}
+
+async fn fetch_all_chunks(handler: &RerunCloudHandler, dataset_name: &str) -> usize {
+    let chunk_infos: Vec<RecordBatch> = handler
+        .query_dataset(
+            tonic::Request::new(QueryDatasetRequest::default().into())
+                .with_entry_name(dataset_name)
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .into_inner()
+        .map(|resp| resp.unwrap().data.unwrap().try_into().unwrap())
+        .collect()
+        .await;
+
+    let chunk_infos = chunk_infos.into_iter().map(Into::into).collect();
+
+    handler
+        .fetch_chunks(tonic::Request::new(FetchChunksRequest { chunk_infos }))
+        .await
+        .unwrap()
+        .into_inner()
+        .map(|resp| resp.unwrap().chunks.len())
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .sum()
+}
+
+async fn register_rrd(
+    handler: &RerunCloudHandler,
+    dataset_name: &str,
+    rrd_path: &std::path::Path,
+    on_duplicate: IfDuplicateBehavior,
+) {
+    handler
+        .register_with_dataset(
+            tonic::Request::new(RegisterWithDatasetRequest {
+                data_sources: vec![DataSource {
+                    storage_url: Some(url::Url::from_file_path(rrd_path).unwrap().into()),
+                    layer: None,
+                    prefix: false,
+                    typ: DataSourceKind::Rrd as i32,
+                }],
+                on_duplicate: on_duplicate as i32,
+            })
+            .with_entry_name(dataset_name)
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+}
+
+/// The names of all the files in the `layers` directories of `storage_dir`.
+fn layer_files(storage_dir: &std::path::Path) -> Vec<std::ffi::OsString> {
+    let mut files = Vec::new();
+    for dataset_dir in std::fs::read_dir(storage_dir).unwrap() {
+        let layers_dir = dataset_dir.unwrap().path().join("layers");
+        if layers_dir.is_dir() {
+            for entry in std::fs::read_dir(layers_dir).unwrap() {
+                files.push(entry.unwrap().file_name());
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+#[tokio::test]
+async fn skipped_duplicates_are_not_rewritten() {
+    let storage_dir = tempfile::tempdir().unwrap();
+    let dataset_name = "my_dataset";
+
+    let rrd_path =
+        re_redap_tests::create_simple_recording(1, "my_partition_id", &["my/entity"]).unwrap();
+
+    let handler = RerunCloudHandlerBuilder::new()
+        .with_storage_directory(storage_dir.path())
+        .unwrap()
+        .build();
+
+    handler
+        .create_dataset_entry(tonic::Request::new(CreateDatasetEntryRequest {
+            name: Some(dataset_name.to_owned()),
+            id: None,
+        }))
+        .await
+        .unwrap();
+
+    register_rrd(&handler, dataset_name, &rrd_path, IfDuplicateBehavior::Error).await;
+    let files = layer_files(storage_dir.path());
+    assert!(!files.is_empty());
+
+    register_rrd(&handler, dataset_name, &rrd_path, IfDuplicateBehavior::Skip).await;
+    assert_eq!(files, layer_files(storage_dir.path()));
+}
+
+#[tokio::test]
+async fn datasets_survive_restart() {
+    let storage_dir = tempfile::tempdir().unwrap();
+    let dataset_name = "my_dataset";
+
+    let rrd_path = re_redap_tests::create_simple_recording(
+        1,
+        "my_partition_id",
+        &["my/entity", "my/other/entity"],
+    )
+    .unwrap();
+
+    let (partition_table, num_chunks) = {
+        let handler = RerunCloudHandlerBuilder::new()
+            .with_storage_directory(storage_dir.path())
+            .unwrap()
+            .build();
+
+        handler
+            .create_dataset_entry(tonic::Request::new(CreateDatasetEntryRequest {
+                name: Some(dataset_name.to_owned()),
+                id: None,
+            }))
+            .await
+            .unwrap();
+
+        register_rrd(&handler, dataset_name, &rrd_path, IfDuplicateBehavior::Error).await;
+
+        (
+            scan_partition_table(&handler, dataset_name).await,
+            fetch_all_chunks(&handler, dataset_name).await,
+        )
+    };
+
+    assert!(num_chunks > 0);
+
+    // The original recording is gone: everything must now be served from the storage directory.
+    drop(rrd_path);
+
+    let handler = RerunCloudHandlerBuilder::new()
+        .with_storage_directory(storage_dir.path())
+        .unwrap()
+        .build();
+
+    assert_eq!(
+        partition_table,
+        scan_partition_table(&handler, dataset_name).await
+    );
+    assert_eq!(num_chunks, fetch_all_chunks(&handler, dataset_name).await);
+}
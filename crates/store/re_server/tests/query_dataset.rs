@@ -0,0 +1,289 @@
+//! Checks that `query_dataset` prunes chunks which cannot contribute to a latest-at or range
+//! query, using a recording whose chunks cannot be compacted together.
+
+#![expect(clippy::unwrap_used)]
+
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::array::{AsArray as _, Float32Array, RecordBatch};
+use futures::StreamExt as _;
+
+use re_chunk_store::{Chunk, ChunkId, RowId};
+use re_log_encoding::{CrateVersion, Encoder, EncodingOptions};
+use re_log_types::{
+    AbsoluteTimeRange, EntityPath, LogMsg, SetStoreInfo, StoreId, StoreInfo, StoreKind,
+    StoreSource, TimeInt, TimePoint, Timeline,
+};
+use re_protos::{
+    cloud::v1alpha1::{
+        CreateDatasetEntryRequest, DataSource, DataSourceKind, QueryDatasetResponse,
+        RegisterWithDatasetRequest,
+        ext::{Query, QueryDatasetRequest, QueryLatestAt, QueryRange},
+        rerun_cloud_service_server::RerunCloudService as _,
+    },
+    common::v1alpha1::IfDuplicateBehavior,
+    headers::RerunHeadersInjectorExt as _,
+};
+use re_server::{RerunCloudHandler, RerunCloudHandlerBuilder};
+use re_types_core::{ComponentDescriptor, SerializedComponentBatch};
+
+const DATASET_NAME: &str = "my_dataset";
+
+const CHUNK_A: u128 = 0xA;
+const CHUNK_B: u128 = 0xB;
+const CHUNK_C: u128 = 0xC;
+const CHUNK_STATIC: u128 = 0x5;
+const CHUNK_SPARSE: u128 = 0x6;
+
+fn scalar_chunk(chunk_id: u128, row_id: u128, timepoint: TimePoint) -> Chunk {
+    Chunk::builder_with_id(ChunkId::from_u128(chunk_id), EntityPath::from("/my/entity"))
+        .with_serialized_batch(
+            RowId::from_u128(row_id),
+            timepoint,
+            SerializedComponentBatch::new(
+                Arc::new(Float32Array::from(vec![1.0])),
+                ComponentDescriptor::partial("scalar"),
+            ),
+        )
+        .build()
+        .unwrap()
+}
+
+/// Writes a recording with a single component spread over several chunks.
+///
+/// Each temporal chunk has a different set of timelines, so that the store cannot compact them:
+/// * A: `frame_nr` = 10
+/// * B: `frame_nr` = 20, `other` = 20
+/// * C: `frame_nr` = 30, `another` = 30
+/// * a static chunk
+fn write_recording(path: &Path) {
+    let frame_nr = Timeline::new_sequence("frame_nr");
+    let other = Timeline::new_sequence("other");
+    let another = Timeline::new_sequence("another");
+
+    write_chunks(
+        path,
+        [
+            scalar_chunk(CHUNK_A, 1, TimePoint::from([(frame_nr, 10)])),
+            scalar_chunk(CHUNK_B, 2, TimePoint::from([(frame_nr, 20), (other, 20)])),
+            scalar_chunk(CHUNK_C, 3, TimePoint::from([(frame_nr, 30), (another, 30)])),
+            scalar_chunk(CHUNK_STATIC, 4, TimePoint::default()),
+        ],
+    );
+}
+
+/// Writes a recording where the `scalar` component of chunk S is sparse:
+/// * A: `scalar` at `frame_nr` = 10
+/// * S: `other_scalar` at `frame_nr` = 20 and 30, `scalar` at `frame_nr` = 40, on an extra timeline
+fn write_sparse_recording(path: &Path) {
+    let frame_nr = Timeline::new_sequence("frame_nr");
+    let other = Timeline::new_sequence("other");
+
+    let batch = |component: &str| {
+        SerializedComponentBatch::new(
+            Arc::new(Float32Array::from(vec![1.0])),
+            ComponentDescriptor::partial(component),
+        )
+    };
+    let timepoint = |time: i64| TimePoint::from([(frame_nr, time), (other, time)]);
+
+    let sparse_chunk = Chunk::builder_with_id(
+        ChunkId::from_u128(CHUNK_SPARSE),
+        EntityPath::from("/my/entity"),
+    )
+    .with_serialized_batch(RowId::from_u128(2), timepoint(20), batch("other_scalar"))
+    .with_serialized_batch(RowId::from_u128(3), timepoint(30), batch("other_scalar"))
+    .with_serialized_batch(RowId::from_u128(4), timepoint(40), batch("scalar"))
+    .build()
+    .unwrap();
+
+    write_chunks(
+        path,
+        [
+            scalar_chunk(CHUNK_A, 1, TimePoint::from([(frame_nr, 10)])),
+            sparse_chunk,
+        ],
+    );
+}
+
+fn write_chunks(path: &Path, chunks: impl IntoIterator<Item = Chunk>) {
+    let store_id = StoreId::new(StoreKind::Recording, "my_app", "my_partition_id");
+    let mut encoder = Encoder::new_eager(
+        CrateVersion::LOCAL,
+        EncodingOptions::PROTOBUF_COMPRESSED,
+        std::fs::File::create(path).unwrap(),
+    )
+    .unwrap();
+
+    encoder
+        .append(&LogMsg::SetStoreInfo(SetStoreInfo {
+            row_id: re_tuid::Tuid::new(),
+            info: StoreInfo::new(store_id.clone(), StoreSource::Other("test".to_owned())),
+        }))
+        .unwrap();
+    for chunk in chunks {
+        encoder
+            .append(&LogMsg::ArrowMsg(
+                store_id.clone(),
+                chunk.to_arrow_msg().unwrap(),
+            ))
+            .unwrap();
+    }
+
+    encoder.finish().unwrap();
+}
+
+async fn setup(path: &Path) -> RerunCloudHandler {
+    let handler = RerunCloudHandlerBuilder::new().build();
+
+    handler
+        .create_dataset_entry(tonic::Request::new(CreateDatasetEntryRequest {
+            name: Some(DATASET_NAME.to_owned()),
+            id: None,
+        }))
+        .await
+        .unwrap();
+
+    handler
+        .register_with_dataset(
+            tonic::Request::new(RegisterWithDatasetRequest {
+                data_sources: vec![DataSource {
+                    storage_url: Some(url::Url::from_file_path(path).unwrap().into()),
+                    layer: None,
+                    prefix: false,
+                    typ: DataSourceKind::Rrd as i32,
+                }],
+                on_duplicate: IfDuplicateBehavior::Error as i32,
+            })
+            .with_entry_name(DATASET_NAME)
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    handler
+}
+
+/// Returns the sorted ids of the chunks returned for the given query.
+async fn query_chunk_ids(handler: &RerunCloudHandler, query: Query) -> Vec<u128> {
+    let request = QueryDatasetRequest {
+        query: Some(query),
+        ..Default::default()
+    };
+
+    let batches: Vec<RecordBatch> = handler
+        .query_dataset(
+            tonic::Request::new(request.into())
+                .with_entry_name(DATASET_NAME)
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .into_inner()
+        .map(|resp| resp.unwrap().data.unwrap().try_into().unwrap())
+        .collect()
+        .await;
+
+    let mut chunk_ids = batches
+        .iter()
+        .flat_map(|batch| {
+            batch
+                .column_by_name(QueryDatasetResponse::FIELD_CHUNK_ID)
+                .unwrap()
+                .as_fixed_size_binary()
+                .iter()
+                .map(|bytes| u128::from_be_bytes(bytes.unwrap().try_into().unwrap()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    chunk_ids.sort();
+
+    chunk_ids
+}
+
+#[tokio::test]
+async fn latest_at_prunes_shadowed_chunks() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("my_partition_id.rrd");
+    write_recording(&path);
+    let handler = setup(&path).await;
+
+    let latest_at = |at: i64| Query {
+        latest_at: Some(QueryLatestAt {
+            index: Some("frame_nr".to_owned()),
+            at: TimeInt::new_temporal(at),
+        }),
+        ..Default::default()
+    };
+
+    // A is shadowed by B, and C starts after the query time.
+    assert_eq!(
+        query_chunk_ids(&handler, latest_at(25)).await,
+        vec![CHUNK_STATIC, CHUNK_B]
+    );
+
+    // Before any temporal data, only static data is relevant.
+    assert_eq!(
+        query_chunk_ids(&handler, latest_at(5)).await,
+        vec![CHUNK_STATIC]
+    );
+}
+
+#[tokio::test]
+async fn latest_at_ignores_null_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("my_partition_id.rrd");
+    write_sparse_recording(&path);
+    let handler = setup(&path).await;
+
+    let latest_at = |at: i64| Query {
+        latest_at: Some(QueryLatestAt {
+            index: Some("frame_nr".to_owned()),
+            at: TimeInt::new_temporal(at),
+        }),
+        ..Default::default()
+    };
+
+    // S starts before the query time, but its only `scalar` value comes after it: the latest
+    // `scalar` is still in A.
+    assert_eq!(
+        query_chunk_ids(&handler, latest_at(35)).await,
+        vec![CHUNK_SPARSE, CHUNK_A]
+    );
+
+    // S now holds the latest value of both components.
+    assert_eq!(
+        query_chunk_ids(&handler, latest_at(45)).await,
+        vec![CHUNK_SPARSE]
+    );
+}
+
+#[tokio::test]
+async fn range_keeps_overlapping_chunks() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("my_partition_id.rrd");
+    write_recording(&path);
+    let handler = setup(&path).await;
+
+    let range = |min: i64, max: i64| Query {
+        range: Some(QueryRange {
+            index: "frame_nr".to_owned(),
+            index_range: AbsoluteTimeRange::new(min, max),
+        }),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        query_chunk_ids(&handler, range(15, 35)).await,
+        vec![CHUNK_STATIC, CHUNK_B, CHUNK_C]
+    );
+
+    // Chunks which are not on the queried timeline never overlap.
+    let mut other = range(15, 25);
+    other.range.as_mut().unwrap().index = "other".to_owned();
+    assert_eq!(
+        query_chunk_ids(&handler, other).await,
+        vec![CHUNK_STATIC, CHUNK_B]
+    );
+}
@@ -0,0 +1,189 @@
+//! Checks that MCAP files can be registered, with their MCAP layers exposed as partition layers.
+
+#![expect(clippy::unwrap_used)]
+
+use std::path::Path;
+
+use arrow::array::{AsArray as _, RecordBatch};
+use itertools::Itertools as _;
+
+use re_protos::{
+    cloud::v1alpha1::{
+        CreateDatasetEntryRequest, RegisterWithDatasetRequest, RegisterWithDatasetResponse,
+        ext::DataSource, rerun_cloud_service_server::RerunCloudService as _,
+    },
+    common::v1alpha1::IfDuplicateBehavior,
+    headers::RerunHeadersInjectorExt as _,
+};
+use re_server::{RerunCloudHandler, RerunCloudHandlerBuilder};
+
+const DATASET_NAME: &str = "my_dataset";
+
+/// Writes an MCAP file with a single JSON channel, which only the raw layer can handle.
+fn write_mcap(path: &Path) {
+    let mut writer = mcap::Writer::new(std::io::BufWriter::new(
+        std::fs::File::create(path).unwrap(),
+    ))
+    .unwrap();
+
+    let schema_id = writer
+        .add_schema("my_schema", "jsonschema", br#"{"type": "object"}"#)
+        .unwrap();
+    let channel_id = writer
+        .add_channel(schema_id, "/my/topic", "json", &Default::default())
+        .unwrap();
+
+    for i in 0..3 {
+        writer
+            .write_to_known_channel(
+                &mcap::records::MessageHeader {
+                    channel_id,
+                    sequence: i,
+                    log_time: u64::from(i) * 1_000,
+                    publish_time: u64::from(i) * 1_000,
+                },
+                format!(r#"{{"value": {i}}}"#).as_bytes(),
+            )
+            .unwrap();
+    }
+
+    writer.finish().unwrap();
+}
+
+async fn setup() -> RerunCloudHandler {
+    let handler = RerunCloudHandlerBuilder::new().build();
+
+    handler
+        .create_dataset_entry(tonic::Request::new(CreateDatasetEntryRequest {
+            name: Some(DATASET_NAME.to_owned()),
+            id: None,
+        }))
+        .await
+        .unwrap();
+
+    handler
+}
+
+async fn register(
+    handler: &RerunCloudHandler,
+    data_source: DataSource,
+) -> Result<RecordBatch, tonic::Status> {
+    let response = handler
+        .register_with_dataset(
+            tonic::Request::new(RegisterWithDatasetRequest {
+                data_sources: vec![data_source.into()],
+                on_duplicate: IfDuplicateBehavior::Error as i32,
+            })
+            .with_entry_name(DATASET_NAME)
+            .unwrap(),
+        )
+        .await?;
+
+    Ok(response.into_inner().data.unwrap().try_into().unwrap())
+}
+
+/// Returns the sorted (partition id, layer, type) triplets of a registration response.
+fn registered_layers(batch: &RecordBatch) -> Vec<(String, String, String)> {
+    let column = |name: &str| {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_string::<i32>()
+            .iter()
+            .map(|value| value.unwrap().to_owned())
+            .collect_vec()
+    };
+
+    itertools::izip!(
+        column(RegisterWithDatasetResponse::PARTITION_ID),
+        column(RegisterWithDatasetResponse::PARTITION_LAYER),
+        column(RegisterWithDatasetResponse::PARTITION_TYPE),
+    )
+    .sorted()
+    .collect()
+}
+
+fn file_url(path: &Path) -> String {
+    url::Url::from_file_path(path).unwrap().to_string()
+}
+
+#[tokio::test]
+async fn register_mcap_all_layers() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("my_bag.mcap");
+    write_mcap(&path);
+
+    let handler = setup().await;
+    let layers = registered_layers(
+        &register(&handler, DataSource::new_mcap(file_url(&path)).unwrap())
+            .await
+            .unwrap(),
+    );
+
+    assert!(
+        layers
+            .iter()
+            .all(|(partition_id, _, typ)| partition_id == "my_bag" && typ == "mcap")
+    );
+
+    let layer_names = layers
+        .iter()
+        .map(|(_, layer, _)| layer.as_str())
+        .collect_vec();
+    assert!(layer_names.contains(&"raw"), "{layer_names:?}");
+    assert!(layer_names.contains(&"stats"), "{layer_names:?}");
+}
+
+#[tokio::test]
+async fn register_mcap_selected_layers() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("my_bag.mcap");
+    write_mcap(&path);
+
+    let handler = setup().await;
+    let data_source = DataSource {
+        layer: "raw, stats".to_owned(),
+        ..DataSource::new_mcap(file_url(&path)).unwrap()
+    };
+
+    assert_eq!(
+        registered_layers(&register(&handler, data_source.clone()).await.unwrap()),
+        vec![
+            ("my_bag".to_owned(), "raw".to_owned(), "mcap".to_owned()),
+            ("my_bag".to_owned(), "stats".to_owned(), "mcap".to_owned()),
+        ]
+    );
+
+    let status = register(
+        &handler,
+        DataSource {
+            layer: "not_a_layer".to_owned(),
+            ..data_source
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn register_mcap_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    write_mcap(&dir.path().join("first.mcap"));
+    write_mcap(&dir.path().join("second.MCAP"));
+    std::fs::write(dir.path().join("not_an.rrd"), b"").unwrap();
+
+    let handler = setup().await;
+    let data_source = DataSource {
+        layer: "raw".to_owned(),
+        ..DataSource::new_mcap_prefix(file_url(dir.path())).unwrap()
+    };
+
+    assert_eq!(
+        registered_layers(&register(&handler, data_source).await.unwrap()),
+        vec![
+            ("first".to_owned(), "raw".to_owned(), "mcap".to_owned()),
+            ("second".to_owned(), "raw".to_owned(), "mcap".to_owned()),
+        ]
+    );
+}
@@ -0,0 +1,242 @@
+//! Checks the results of full-text and vector searches, beyond the index lifecycle covered by the
+//! redap test suite.
+
+#![expect(clippy::unwrap_used, clippy::disallowed_methods)]
+
+use std::sync::Arc;
+
+use arrow::array::{AsArray as _, Float32Array, RecordBatch, StringArray};
+use arrow::datatypes::{DataType, Field, Float32Type, Schema};
+use futures::StreamExt as _;
+
+use re_protos::{
+    cloud::v1alpha1::{
+        CreateDatasetEntryRequest, CreateIndexRequest, DataSource, DataSourceKind, IndexColumn,
+        IndexConfig, IndexProperties, IndexQueryProperties, InvertedIndex, InvertedIndexQuery,
+        RegisterWithDatasetRequest, SearchDatasetRequest, SearchDatasetResponse, VectorIndexQuery,
+        VectorIvfPqIndex, index_properties, index_query_properties,
+        rerun_cloud_service_server::RerunCloudService as _,
+    },
+    common::v1alpha1::{
+        ComponentDescriptor, EntityPath, IfDuplicateBehavior, IndexColumnSelector, Timeline,
+    },
+    headers::RerunHeadersInjectorExt as _,
+};
+use re_server::{RerunCloudHandler, RerunCloudHandlerBuilder};
+
+const DATASET_NAME: &str = "my_dataset";
+
+async fn setup() -> RerunCloudHandler {
+    let handler = RerunCloudHandlerBuilder::new().build();
+
+    handler
+        .create_dataset_entry(tonic::Request::new(CreateDatasetEntryRequest {
+            name: Some(DATASET_NAME.to_owned()),
+            id: None,
+        }))
+        .await
+        .unwrap();
+
+    let text = re_redap_tests::create_recording_with_text(1, "my_partition_id1").unwrap();
+    let embeddings =
+        re_redap_tests::create_recording_with_embeddings(2, "my_partition_id2", 3, 1).unwrap();
+
+    handler
+        .register_with_dataset(
+            tonic::Request::new(RegisterWithDatasetRequest {
+                data_sources: [&text, &embeddings]
+                    .into_iter()
+                    .map(|path| DataSource {
+                        storage_url: Some(url::Url::from_file_path(&**path).unwrap().into()),
+                        layer: None,
+                        prefix: false,
+                        typ: DataSourceKind::Rrd as i32,
+                    })
+                    .collect(),
+                on_duplicate: IfDuplicateBehavior::Error as i32,
+            })
+            .with_entry_name(DATASET_NAME)
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    handler
+}
+
+fn index_column(entity_path: &str, component: &str) -> IndexColumn {
+    IndexColumn {
+        entity_path: Some(EntityPath {
+            path: entity_path.to_owned(),
+        }),
+        component: Some(ComponentDescriptor {
+            component: Some(component.to_owned()),
+            ..Default::default()
+        }),
+    }
+}
+
+async fn create_index(handler: &RerunCloudHandler, column: IndexColumn, props: IndexProperties) {
+    handler
+        .create_index(
+            tonic::Request::new(CreateIndexRequest {
+                config: Some(IndexConfig {
+                    properties: Some(props),
+                    column: Some(column),
+                    time_index: Some(IndexColumnSelector {
+                        timeline: Some(Timeline {
+                            name: "log_time".to_owned(),
+                        }),
+                    }),
+                }),
+            })
+            .with_entry_name(DATASET_NAME)
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn search(
+    handler: &RerunCloudHandler,
+    column: IndexColumn,
+    query: RecordBatch,
+    properties: index_query_properties::Props,
+) -> RecordBatch {
+    let batches: Vec<RecordBatch> = handler
+        .search_dataset(
+            tonic::Request::new(SearchDatasetRequest {
+                column: Some(column),
+                query: Some(query.into()),
+                properties: Some(IndexQueryProperties {
+                    props: Some(properties),
+                }),
+                scan_parameters: None,
+            })
+            .with_entry_name(DATASET_NAME)
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+        .into_inner()
+        .map(|resp| resp.unwrap().data.unwrap().try_into().unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(batches.len(), 1);
+    batches.into_iter().next().unwrap()
+}
+
+fn query_batch(array: arrow::array::ArrayRef) -> RecordBatch {
+    RecordBatch::try_new(
+        Arc::new(Schema::new(vec![Field::new(
+            "query",
+            array.data_type().clone(),
+            false,
+        )])),
+        vec![array],
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn full_text_search() {
+    let handler = setup().await;
+    let column = index_column("/my_text", "TextLog:text");
+
+    create_index(
+        &handler,
+        column.clone(),
+        IndexProperties {
+            props: Some(index_properties::Props::Inverted(InvertedIndex {
+                store_position: Some(false),
+                base_tokenizer: Some("simple".to_owned()),
+            })),
+        },
+    )
+    .await;
+
+    let results = search(
+        &handler,
+        column,
+        query_batch(Arc::new(StringArray::from(vec!["Weathered WIND"]))),
+        index_query_properties::Props::Inverted(InvertedIndexQuery {}),
+    )
+    .await;
+
+    // Two sentences mention "weathered", one mentions "wind".
+    assert_eq!(results.num_rows(), 3);
+
+    let texts = results.column_by_name("/my_text:TextLog:text").unwrap();
+    let texts = texts.as_list::<i32>().values().as_string::<i32>();
+    assert!(texts.iter().flatten().all(|text| {
+        let text = text.to_lowercase();
+        text.contains("weathered") || text.contains("wind")
+    }));
+
+    let scores = results
+        .column_by_name(SearchDatasetResponse::FIELD_SCORE)
+        .unwrap()
+        .as_primitive::<Float32Type>();
+    assert!(scores.values().windows(2).all(|w| w[0] >= w[1]));
+    assert!(scores.values().iter().all(|score| *score > 0.0));
+}
+
+#[tokio::test]
+async fn vector_search() {
+    let handler = setup().await;
+    let column = index_column("/my_embeddings", "embedding");
+
+    create_index(
+        &handler,
+        column.clone(),
+        IndexProperties {
+            props: Some(index_properties::Props::Vector(VectorIvfPqIndex {
+                num_partitions: None,
+                target_partition_num_rows: None,
+                num_sub_vectors: Some(16),
+                distance_metrics: re_protos::cloud::v1alpha1::VectorDistanceMetric::L2 as i32,
+            })),
+        },
+    )
+    .await;
+
+    // The embedding logged at frame `10 * i` is filled with `0.1 * i * i`.
+    let results = search(
+        &handler,
+        column,
+        query_batch(Arc::new(Float32Array::from(vec![0.4; 256]))),
+        index_query_properties::Props::Vector(VectorIndexQuery { top_k: Some(2) }),
+    )
+    .await;
+
+    assert_eq!(results.num_rows(), 2);
+    assert_eq!(
+        results
+            .column_by_name(SearchDatasetResponse::FIELD_PARTITION_ID)
+            .unwrap()
+            .as_string::<i32>()
+            .iter()
+            .collect::<Vec<_>>(),
+        vec![Some("my_partition_id2"); 2],
+    );
+
+    let log_times = arrow::compute::cast(
+        results.column_by_name("log_time").unwrap(),
+        &DataType::Int64,
+    )
+    .unwrap();
+    assert_eq!(
+        log_times
+            .as_primitive::<arrow::datatypes::Int64Type>()
+            .values(),
+        &[20, 10]
+    );
+
+    let distances = results
+        .column_by_name(SearchDatasetResponse::FIELD_DISTANCE)
+        .unwrap()
+        .as_primitive::<Float32Type>();
+    assert!(distances.value(0) < 1e-3);
+    assert!(distances.value(0) < distances.value(1));
+}
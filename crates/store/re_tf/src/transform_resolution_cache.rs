@@ -779,13 +779,15 @@ impl TransformResolutionCache {
     /// * create empty entries for where transforms may change over time (may happen conservatively - creating more entries than needed)
     ///
     /// See also [`Self::process_store_events`].
-    pub fn add_chunks<'a>(&mut self, chunks: impl Iterator<Item = &'a std::sync::Arc<Chunk>>) {
+    pub fn add_chunks(&mut self, chunks: impl Iterator<Item = std::sync::Arc<Chunk>>) {
         re_tracing::profile_function!();
 
         // TODO(andreas): We eagerly index for all timelines even if they're never used.
         // Instead, we should do so lazily when results for a timeline are queried.
 
         for chunk in chunks {
+            let chunk = &chunk;
+
             // Since entity paths lead to implicit frames, we have to prime our lookup table with them even if this chunk doesn't have transform data.
             self.frame_id_registry.register_all_frames_in_chunk(chunk);
 
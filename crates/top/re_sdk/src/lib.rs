@@ -91,7 +91,7 @@ pub mod sink {
     pub use crate::log_sink::{GrpcSink, GrpcSinkConnectionFailure, GrpcSinkConnectionState};
 
     #[cfg(not(target_arch = "wasm32"))]
-    pub use re_log_encoding::{FileSink, FileSinkError};
+    pub use re_log_encoding::{FileSink, FileSinkError, FileSinkOptions};
 }
 
 /// Things directly related to logging.
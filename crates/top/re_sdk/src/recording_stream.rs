@@ -535,6 +535,32 @@ impl RecordingStreamBuilder {
     pub fn save(
         self,
         path: impl Into<std::path::PathBuf>,
+    ) -> RecordingStreamResult<RecordingStream> {
+        self.save_opts(path, crate::sink::FileSinkOptions::default())
+    }
+
+    /// Creates a new [`RecordingStream`] that is pre-configured to stream the data through to an
+    /// RRD file on disk, configured with the given [`crate::sink::FileSinkOptions`].
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// // Rotate through hourly segments, keeping only the last day's worth of data.
+    /// let rec = re_sdk::RecordingStreamBuilder::new("rerun_example_app").save_opts(
+    ///     "my_recording.rrd",
+    ///     re_sdk::sink::FileSinkOptions {
+    ///         max_segment_duration: Some(std::time::Duration::from_secs(60 * 60)),
+    ///         max_segments: Some(24),
+    ///         ..Default::default()
+    ///     },
+    /// )?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_opts(
+        self,
+        path: impl Into<std::path::PathBuf>,
+        options: crate::sink::FileSinkOptions,
     ) -> RecordingStreamResult<RecordingStream> {
         let (enabled, store_info, properties, batcher_config, batcher_hooks) = self.into_args();
 
@@ -544,7 +570,7 @@ impl RecordingStreamBuilder {
                 properties,
                 batcher_config,
                 batcher_hooks,
-                Box::new(crate::sink::FileSink::new(path)?),
+                Box::new(crate::sink::FileSink::new_with_options(path, options)?),
             )
         } else {
             re_log::debug!("Rerun disabled - call to save() ignored");
@@ -2213,13 +2239,30 @@ impl RecordingStream {
     pub fn save_opts(
         &self,
         path: impl Into<std::path::PathBuf>,
+    ) -> Result<(), crate::sink::FileSinkError> {
+        self.save_with_options(path, crate::sink::FileSinkOptions::default())
+    }
+
+    /// Swaps the underlying sink for a [`crate::sink::FileSink`] at the specified `path`, configured
+    /// with the given [`crate::sink::FileSinkOptions`].
+    ///
+    /// Use this to e.g. rotate through several size- or duration-capped segment files rather than
+    /// writing a single ever-growing one, see [`crate::sink::FileSinkOptions::is_segmented`].
+    ///
+    /// This is a convenience wrapper for [`Self::set_sink`] that upholds the same guarantees in
+    /// terms of data durability and ordering.
+    /// See [`Self::set_sink`] for more information.
+    pub fn save_with_options(
+        &self,
+        path: impl Into<std::path::PathBuf>,
+        options: crate::sink::FileSinkOptions,
     ) -> Result<(), crate::sink::FileSinkError> {
         if forced_sink_path().is_some() {
             re_log::debug!("Ignored setting new file since {ENV_FORCE_SAVE} is set");
             return Ok(());
         }
 
-        let sink = crate::sink::FileSink::new(path)?;
+        let sink = crate::sink::FileSink::new_with_options(path, options)?;
 
         self.set_sink(Box::new(sink));
 
@@ -0,0 +1,635 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use arrow::{
+    array::Array as _,
+    util::display::{ArrayFormatter, FormatOptions},
+};
+use itertools::Itertools as _;
+
+use re_chunk::{Chunk, ClockCorrection, ComponentIdentifier, EntityPath, TimelineName};
+use re_log_types::{LogMsg, StoreKind};
+
+use crate::commands::{read_rrd_streams_from_file_or_stdin, stdio::InputSource};
+
+// ---
+
+/// A single `--time-offset`/`--clock-drift` argument: `[INPUT:]TIMELINE=VALUE`.
+#[derive(Debug, Clone)]
+pub struct ClockCorrectionArg<T> {
+    /// Which input this applies to, all of them if `None`.
+    input: Option<String>,
+    timeline: TimelineName,
+    value: T,
+}
+
+impl<T: ClockCorrectionValue> std::str::FromStr for ClockCorrectionArg<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((target, value)) = s.rsplit_once('=') else {
+            return Err(format!(
+                "expected an argument of the form `[INPUT:]TIMELINE=VALUE`, got {s:?}"
+            ));
+        };
+
+        let (input, timeline) = match target.rsplit_once(':') {
+            Some((input, timeline)) => (Some(input.to_owned()), timeline),
+            None => (None, target),
+        };
+
+        if timeline.is_empty() {
+            return Err(format!("missing timeline name in {s:?}"));
+        }
+
+        Ok(Self {
+            input,
+            timeline: TimelineName::new(timeline),
+            value: T::parse(value.trim())?,
+        })
+    }
+}
+
+/// The value of a [`ClockCorrectionArg`].
+pub trait ClockCorrectionValue: Sized {
+    fn parse(s: &str) -> Result<Self, String>;
+
+    fn to_correction(&self) -> ClockCorrection;
+}
+
+/// A constant offset: either a plain integer in the unit of the timeline, or a duration (e.g. `-1.5s`).
+#[derive(Debug, Clone, Copy)]
+pub struct TimeOffset(i64);
+
+impl ClockCorrectionValue for TimeOffset {
+    fn parse(s: &str) -> Result<Self, String> {
+        let s = s.strip_prefix('+').unwrap_or(s);
+        if let Ok(offset) = s.parse::<i64>() {
+            Ok(Self(offset))
+        } else if let Ok(duration) = s.parse::<re_log_types::Duration>() {
+            Ok(Self(duration.as_nanos()))
+        } else {
+            Err(format!(
+                "expected an integer or a duration (e.g. `-1.5s`) as time offset, got {s:?}"
+            ))
+        }
+    }
+
+    fn to_correction(&self) -> ClockCorrection {
+        ClockCorrection::from_offset(self.0)
+    }
+}
+
+/// A clock drift in parts per million, and the time around which it applies: `PPM@PIVOT`
+/// (e.g. `-12.5@2025-01-01T12:00:00Z` or `3@1000`).
+#[derive(Debug, Clone, Copy)]
+pub struct ClockDrift {
+    ppm: f64,
+    pivot: i64,
+}
+
+impl ClockCorrectionValue for ClockDrift {
+    fn parse(s: &str) -> Result<Self, String> {
+        let error = || {
+            format!(
+                "expected a clock drift in parts per million and the time around which it applies (e.g. `-12.5@2025-01-01T12:00:00Z`), got {s:?}"
+            )
+        };
+
+        let (ppm, pivot) = s.split_once('@').ok_or_else(error)?;
+        let ppm = ppm
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|ppm| ppm.is_finite() && *ppm > -1e6)
+            .ok_or_else(error)?;
+        let pivot = pivot
+            .trim()
+            .parse::<re_log_types::TimeCell>()
+            .map_err(|err| format!("{}: {err}", error()))?;
+
+        Ok(Self {
+            ppm,
+            pivot: pivot.value.into(),
+        })
+    }
+
+    fn to_correction(&self) -> ClockCorrection {
+        ClockCorrection::from_drift(self.ppm * 1e-6, self.pivot)
+    }
+}
+
+/// Does the `input` part of a [`ClockCorrectionArg`] designate the input at `path`?
+///
+/// Inputs can be designated by their path as specified on the command line, their file name, or
+/// their file name without extension.
+fn matches_input(input: &str, path: &str) -> bool {
+    let path = Path::new(path);
+    input == path.as_os_str()
+        || path.file_name().is_some_and(|name| input == name)
+        || path.file_stem().is_some_and(|stem| input == stem)
+}
+
+// ---
+
+/// The [`ClockCorrection`]s to apply to each timeline of each input.
+#[derive(Debug, Clone, Default)]
+pub struct ClockCorrections {
+    per_input: HashMap<InputSource, BTreeMap<TimelineName, ClockCorrection>>,
+}
+
+impl ClockCorrections {
+    /// Resolves the command line arguments against the list of inputs.
+    ///
+    /// Corrections for the same timeline are applied in order: estimated ones first, then offsets,
+    /// and finally drifts.
+    pub fn new(
+        path_to_input_rrds: &[String],
+        estimated: HashMap<InputSource, BTreeMap<TimelineName, ClockCorrection>>,
+        offsets: &[ClockCorrectionArg<TimeOffset>],
+        drifts: &[ClockCorrectionArg<ClockDrift>],
+    ) -> anyhow::Result<Self> {
+        let inputs = if path_to_input_rrds.is_empty() {
+            vec![(None, InputSource::Stdin)]
+        } else {
+            path_to_input_rrds
+                .iter()
+                .map(|path| (Some(path.as_str()), InputSource::File(path.into())))
+                .collect_vec()
+        };
+
+        let mut per_input = estimated;
+
+        let args = offsets
+            .iter()
+            .map(|arg| (&arg.input, &arg.timeline, arg.value.to_correction()))
+            .chain(
+                drifts
+                    .iter()
+                    .map(|arg| (&arg.input, &arg.timeline, arg.value.to_correction())),
+            );
+
+        for (input, timeline, correction) in args {
+            let mut num_matches = 0;
+            for (path, source) in &inputs {
+                let is_match = match (input, path) {
+                    (None, _) => true,
+                    (Some(input), Some(path)) => matches_input(input, path),
+                    (Some(_), None) => false,
+                };
+
+                if is_match {
+                    num_matches += 1;
+                    let current = per_input
+                        .entry(source.clone())
+                        .or_default()
+                        .entry(*timeline)
+                        .or_default();
+                    *current = current.then(&correction);
+                }
+            }
+
+            if let Some(input) = input {
+                anyhow::ensure!(num_matches > 0, "{input:?} does not match any input");
+            }
+        }
+
+        per_input.retain(|_, corrections| {
+            corrections.retain(|_, correction| !correction.is_identity());
+            !corrections.is_empty()
+        });
+
+        Ok(Self { per_input })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.per_input.is_empty()
+    }
+
+    /// Returns the corrected chunk, or `None` if no correction applies to it.
+    ///
+    /// Blueprints are never corrected.
+    pub fn correct_chunk(
+        &self,
+        input: &InputSource,
+        chunk: &Chunk,
+        store_kind: StoreKind,
+    ) -> Option<Chunk> {
+        if store_kind != StoreKind::Recording {
+            return None;
+        }
+
+        let corrections = self.per_input.get(input)?;
+        if !corrections
+            .keys()
+            .any(|timeline| chunk.timelines().contains_key(timeline))
+        {
+            return None;
+        }
+
+        Some(
+            corrections
+                .iter()
+                .fold(chunk.clone(), |chunk, (timeline, correction)| {
+                    chunk.with_clock_correction(timeline, correction)
+                }),
+        )
+    }
+}
+
+// ---
+
+/// Estimates, for every input, the [`ClockCorrection`] that aligns its `timeline` onto the one
+/// of the reference input, i.e. the first input that logged the synchronization component.
+///
+/// Inputs are aligned on the times at which they logged identical values of the synchronization
+/// component `sync` (formatted as `ENTITY:COMPONENT`, e.g. `/sync:Scalars:scalars`).
+/// Values that were logged more than once by a given input are ambiguous, and therefore ignored.
+pub fn estimate_clock_corrections(
+    path_to_input_rrds: &[String],
+    sync: &str,
+    timeline: TimelineName,
+    continue_on_error: bool,
+) -> anyhow::Result<HashMap<InputSource, BTreeMap<TimelineName, ClockCorrection>>> {
+    anyhow::ensure!(
+        !path_to_input_rrds.is_empty(),
+        "estimating clock corrections requires reading the inputs twice, which isn't possible with standard input"
+    );
+
+    let inputs = path_to_input_rrds
+        .iter()
+        .map(|path| InputSource::File(path.into()))
+        .collect_vec();
+
+    let (rx, _) = read_rrd_streams_from_file_or_stdin(path_to_input_rrds);
+    estimate_clock_corrections_from_msgs(&inputs, rx, sync, timeline, continue_on_error)
+}
+
+/// See [`estimate_clock_corrections`].
+fn estimate_clock_corrections_from_msgs(
+    inputs: &[InputSource],
+    msgs: impl IntoIterator<Item = (InputSource, anyhow::Result<LogMsg>)>,
+    sync: &str,
+    timeline: TimelineName,
+    continue_on_error: bool,
+) -> anyhow::Result<HashMap<InputSource, BTreeMap<TimelineName, ClockCorrection>>> {
+    re_tracing::profile_function!();
+
+    let Some((entity_path, component)) = sync.split_once(':') else {
+        anyhow::bail!("expected the synchronization component as `ENTITY:COMPONENT`, got {sync:?}");
+    };
+    let entity_path = EntityPath::parse_forgiving(entity_path);
+    let component = ComponentIdentifier::from(component);
+
+    // For every input, the times at which each synchronization value was logged.
+    let mut sync_times: HashMap<InputSource, HashMap<String, Vec<i64>>> = HashMap::default();
+
+    for (source, res) in msgs {
+        let res = res.and_then(|msg| {
+            collect_sync_times(
+                &msg,
+                &entity_path,
+                component,
+                timeline,
+                sync_times.entry(source).or_default(),
+            )
+        });
+
+        if let Err(err) = res {
+            re_log::error!(err = re_error::format(err));
+            if !continue_on_error {
+                anyhow::bail!(
+                    "one or more IO and/or decoding failures in the input stream (check logs)"
+                );
+            }
+        }
+    }
+
+    let unique_sync_times = |source: &InputSource| -> HashMap<&str, i64> {
+        sync_times
+            .get(source)
+            .into_iter()
+            .flatten()
+            .filter_map(|(value, times)| match times.as_slice() {
+                [time] => Some((value.as_str(), *time)),
+                _ => None,
+            })
+            .collect()
+    };
+
+    let Some(reference) = inputs
+        .iter()
+        .find(|source| !unique_sync_times(source).is_empty())
+    else {
+        anyhow::bail!("no input contains any data for {sync:?} on timeline {timeline:?}");
+    };
+    let reference_times = unique_sync_times(reference);
+
+    let mut estimated = HashMap::default();
+    for source in inputs {
+        if source == reference {
+            continue;
+        }
+
+        let samples = unique_sync_times(source)
+            .into_iter()
+            .filter_map(|(value, time)| Some((time, *reference_times.get(value)?)))
+            .sorted()
+            .collect_vec();
+
+        let Some(correction) = ClockCorrection::estimate(&samples) else {
+            re_log::warn!(
+                input = %source,
+                "no synchronization values in common with {reference}, cannot align clocks"
+            );
+            continue;
+        };
+
+        re_log::info!(
+            input = %source,
+            %timeline,
+            offset = correction.offset,
+            drift_ppm = correction.drift * 1e6,
+            num_samples = samples.len(),
+            "estimated clock correction"
+        );
+
+        estimated.insert(
+            source.clone(),
+            std::iter::once((timeline, correction)).collect(),
+        );
+    }
+
+    Ok(estimated)
+}
+
+/// Records the times at which the synchronization component was logged in `msg`, if any.
+fn collect_sync_times(
+    msg: &LogMsg,
+    entity_path: &EntityPath,
+    component: ComponentIdentifier,
+    timeline: TimelineName,
+    sync_times: &mut HashMap<String, Vec<i64>>,
+) -> anyhow::Result<()> {
+    let LogMsg::ArrowMsg(store_id, arrow_msg) = msg else {
+        return Ok(());
+    };
+    if !store_id.is_recording() {
+        return Ok(());
+    }
+
+    let chunk = Chunk::from_arrow_msg(arrow_msg)?;
+    if chunk.entity_path() != entity_path {
+        return Ok(());
+    }
+    let (Some(time_column), Some(list_array)) = (
+        chunk.timelines().get(&timeline),
+        chunk.components().get_array(component),
+    ) else {
+        return Ok(());
+    };
+
+    for (row, time) in time_column.times_raw().iter().enumerate() {
+        if list_array.is_valid(row) {
+            let value = format_sync_value(&list_array.value(row))?;
+            sync_times.entry(value).or_default().push(*time);
+        }
+    }
+
+    Ok(())
+}
+
+fn format_sync_value(array: &dyn arrow::array::Array) -> anyhow::Result<String> {
+    let formatter = ArrayFormatter::try_new(array, &FormatOptions::default().with_null("null"))?;
+    Ok((0..array.len())
+        .map(|i| formatter.value(i).to_string())
+        .join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use re_chunk::{RowId, TimePoint, Timeline};
+    use re_log_types::example_components::{MyPoint, MyPoints};
+    use re_log_types::{StoreId, StoreKind};
+
+    use super::*;
+
+    #[test]
+    fn parse_time_offsets() {
+        let arg: ClockCorrectionArg<TimeOffset> = "log_time=-1.5s".parse().unwrap();
+        assert_eq!(arg.input, None);
+        assert_eq!(arg.timeline, TimelineName::log_time());
+        assert_eq!(arg.value.0, -1_500_000_000);
+
+        let arg: ClockCorrectionArg<TimeOffset> = "robot_b.rrd:frame=+10".parse().unwrap();
+        assert_eq!(arg.input.as_deref(), Some("robot_b.rrd"));
+        assert_eq!(arg.timeline, TimelineName::new("frame"));
+        assert_eq!(arg.value.0, 10);
+
+        let arg: ClockCorrectionArg<TimeOffset> = "frame=+250ms".parse().unwrap();
+        assert_eq!(arg.value.0, 250_000_000);
+
+        // The input is everything up to the last colon, so that paths can contain some.
+        let arg: ClockCorrectionArg<TimeOffset> = "C:/data/robot.rrd:frame=-3".parse().unwrap();
+        assert_eq!(arg.input.as_deref(), Some("C:/data/robot.rrd"));
+        assert_eq!(arg.value.0, -3);
+
+        assert!("frame".parse::<ClockCorrectionArg<TimeOffset>>().is_err());
+        assert!("=10".parse::<ClockCorrectionArg<TimeOffset>>().is_err());
+        assert!(
+            "robot_b:=10"
+                .parse::<ClockCorrectionArg<TimeOffset>>()
+                .is_err()
+        );
+        assert!(
+            "frame=ten"
+                .parse::<ClockCorrectionArg<TimeOffset>>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_clock_drifts() {
+        let arg: ClockCorrectionArg<ClockDrift> = "robot_b:log_time=-12.5@2025-01-01T12:00:00Z"
+            .parse()
+            .unwrap();
+        assert_eq!(arg.input.as_deref(), Some("robot_b"));
+        assert_eq!(arg.timeline, TimelineName::log_time());
+        assert_eq!(arg.value.ppm, -12.5);
+        assert_eq!(arg.value.pivot, 1_735_732_800_000_000_000);
+        assert_eq!(
+            arg.value.to_correction(),
+            ClockCorrection::from_drift(-12.5 * 1e-6, 1_735_732_800_000_000_000)
+        );
+
+        let arg: ClockCorrectionArg<ClockDrift> = "frame=3 @ 1000".parse().unwrap();
+        assert_eq!(arg.value.ppm, 3.0);
+        assert_eq!(arg.value.pivot, 1000);
+
+        assert!("frame=3".parse::<ClockCorrectionArg<ClockDrift>>().is_err());
+        assert!(
+            "frame=3@"
+                .parse::<ClockCorrectionArg<ClockDrift>>()
+                .is_err()
+        );
+        assert!(
+            "frame=NaN@0"
+                .parse::<ClockCorrectionArg<ClockDrift>>()
+                .is_err()
+        );
+        assert!(
+            "frame=-1000000@0"
+                .parse::<ClockCorrectionArg<ClockDrift>>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn input_matching() {
+        let path = "data/robot_b.rrd";
+        assert!(matches_input("data/robot_b.rrd", path));
+        assert!(matches_input("robot_b.rrd", path));
+        assert!(matches_input("robot_b", path));
+
+        assert!(!matches_input("robot", path));
+        assert!(!matches_input("data", path));
+        assert!(!matches_input("robot_a", path));
+    }
+
+    /// An input that logged the synchronization values `0..10` on the `frame` timeline, at
+    /// `time(value)`.
+    fn sync_msgs(
+        input: &str,
+        time: impl Fn(u32) -> i64,
+    ) -> Vec<(InputSource, anyhow::Result<LogMsg>)> {
+        let store_id = StoreId::random(StoreKind::Recording, "test");
+
+        let mut builder = Chunk::builder("sync");
+        for value in 0..10 {
+            builder = builder.with_component_batches(
+                RowId::new(),
+                TimePoint::default().with(Timeline::new_sequence("frame"), time(value)),
+                [(
+                    MyPoints::descriptor_points(),
+                    &[MyPoint::new(value as f32, 0.0)] as _,
+                )],
+            );
+        }
+        let chunk = builder.build().unwrap();
+
+        vec![(
+            InputSource::File(input.into()),
+            Ok(LogMsg::ArrowMsg(store_id, chunk.to_arrow_msg().unwrap())),
+        )]
+    }
+
+    fn sync_component() -> String {
+        format!("/sync:{}", MyPoints::descriptor_points().component)
+    }
+
+    #[test]
+    fn estimate_corrections() {
+        let inputs = ["empty.rrd", "a.rrd", "b.rrd"]
+            .map(|path| InputSource::File(path.into()))
+            .to_vec();
+
+        let msgs = itertools::chain!(
+            sync_msgs("a.rrd", |value| 100 * i64::from(value)),
+            sync_msgs("b.rrd", |value| 100 * i64::from(value) + 50),
+        );
+
+        let estimated = estimate_clock_corrections_from_msgs(
+            &inputs,
+            msgs,
+            &sync_component(),
+            TimelineName::new("frame"),
+            false,
+        )
+        .unwrap();
+
+        // The reference is the first input that logged synchronization data, not the first input.
+        assert_eq!(estimated.len(), 1);
+        let correction = estimated[&inputs[2]][&TimelineName::new("frame")];
+        assert_eq!(correction, ClockCorrection::from_offset(-50));
+        assert_eq!(correction.apply(150), 100);
+    }
+
+    #[test]
+    fn estimate_corrections_with_drift() {
+        let inputs = ["a.rrd", "b.rrd"]
+            .map(|path| InputSource::File(path.into()))
+            .to_vec();
+
+        let msgs = itertools::chain!(
+            sync_msgs("a.rrd", |value| 1_000 * i64::from(value)),
+            sync_msgs("b.rrd", |value| 2_000 * i64::from(value) + 7),
+        );
+
+        let estimated = estimate_clock_corrections_from_msgs(
+            &inputs,
+            msgs,
+            &sync_component(),
+            TimelineName::new("frame"),
+            false,
+        )
+        .unwrap();
+
+        let correction = estimated[&inputs[1]][&TimelineName::new("frame")];
+        for value in 0..10 {
+            assert_eq!(correction.apply(2_000 * value + 7), 1_000 * value);
+        }
+    }
+
+    #[test]
+    fn estimate_corrections_errors() {
+        let inputs = ["a.rrd", "b.rrd"]
+            .map(|path| InputSource::File(path.into()))
+            .to_vec();
+
+        let msgs = || {
+            let mut msgs = sync_msgs("a.rrd", |value| 100 * i64::from(value));
+            msgs.push((
+                InputSource::File("b.rrd".into()),
+                Err(anyhow::anyhow!("oops")),
+            ));
+            msgs.extend(sync_msgs("b.rrd", |value| 100 * i64::from(value) + 50));
+            msgs
+        };
+
+        assert!(
+            estimate_clock_corrections_from_msgs(
+                &inputs,
+                msgs(),
+                &sync_component(),
+                TimelineName::new("frame"),
+                false,
+            )
+            .is_err()
+        );
+
+        let estimated = estimate_clock_corrections_from_msgs(
+            &inputs,
+            msgs(),
+            &sync_component(),
+            TimelineName::new("frame"),
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            estimated[&inputs[1]][&TimelineName::new("frame")],
+            ClockCorrection::from_offset(-50)
+        );
+
+        // No synchronization data at all.
+        assert!(
+            estimate_clock_corrections_from_msgs(
+                &inputs,
+                msgs(),
+                "/nope:MyPoints:points",
+                TimelineName::new("frame"),
+                true,
+            )
+            .is_err()
+        );
+    }
+}
@@ -1,15 +1,30 @@
 use std::{
+    collections::BTreeMap,
     path::{Path, PathBuf},
     sync::Arc,
 };
 
 use anyhow::Context as _;
+use arrow::{
+    array::{Array as _, ArrayRef, AsArray as _},
+    datatypes::{DataType, Float32Type, Float64Type},
+    util::display::{ArrayFormatter, FormatOptions},
+};
 use itertools::{Itertools as _, izip};
 
-use re_chunk::Chunk;
+use re_chunk::{Chunk, EntityPath, RowId, TimePoint, TimelineName};
 
 // ---
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum DiffFormat {
+    /// Human-readable report.
+    Text,
+
+    /// Machine-readable JSON report.
+    Json,
+}
+
 #[derive(Debug, Clone, clap::Parser)]
 pub struct CompareCommand {
     path_to_rrd1: String,
@@ -31,6 +46,18 @@ pub struct CompareCommand {
     /// If specified, the comparison will ignore chunks without components.
     #[clap(long, default_value_t = false)]
     ignore_chunks_without_components: bool,
+
+    /// If specified, reports which rows were added, removed or changed, per entity and component,
+    /// instead of stopping at the first mismatching chunk.
+    ///
+    /// Rows are matched by their time points (ignoring `log_time`), regardless of how the data is
+    /// chunked.
+    #[clap(long, default_value_t = false)]
+    diff: bool,
+
+    /// The format of the `--diff` report, written to standard output.
+    #[clap(long, value_enum, default_value_t = DiffFormat::Text, requires = "diff")]
+    format: DiffFormat,
 }
 
 impl CompareCommand {
@@ -45,6 +72,8 @@ impl CompareCommand {
             unordered,
             full_dump,
             ignore_chunks_without_components,
+            diff,
+            format,
         } = self;
 
         re_log::debug!("Comparing {path_to_rrd1:?} to {path_to_rrd2:?}…");
@@ -69,6 +98,28 @@ impl CompareCommand {
             }
         }
 
+        if *diff {
+            let report = DiffReport::new(&app_id1, &chunks1, &app_id2, &chunks2);
+
+            match format {
+                DiffFormat::Text => print!("{report}"),
+                DiffFormat::Json => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&report).context("couldn't serialize report")?
+                ),
+            }
+
+            anyhow::ensure!(
+                report.is_empty(),
+                "{path_to_rrd1:?} and {path_to_rrd2:?} differ: {} rows added, {} removed, {} changed",
+                re_format::format_uint(report.num_rows_added),
+                re_format::format_uint(report.num_rows_removed),
+                re_format::format_uint(report.num_rows_changed),
+            );
+
+            return Ok(());
+        }
+
         anyhow::ensure!(
             app_id1 == app_id2,
             "Application IDs do not match: '{app_id1}' vs. '{app_id2}'"
@@ -137,6 +188,388 @@ impl CompareCommand {
     }
 }
 
+// --- Structural diff ---
+
+/// How many rows are listed per component in the text report.
+const MAX_TEXT_ROWS_PER_COMPONENT: usize = 10;
+
+/// How many instances are shown per value preview.
+const MAX_PREVIEW_INSTANCES: usize = 8;
+
+/// A row-level diff of two recordings, independent of how their data is chunked.
+#[derive(Debug, Default, serde::Serialize)]
+struct DiffReport {
+    /// Set if the application IDs differ.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    application_ids: Option<(String, String)>,
+
+    num_rows_added: usize,
+    num_rows_removed: usize,
+    num_rows_changed: usize,
+
+    entities: Vec<EntityDiff>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct EntityDiff {
+    entity_path: String,
+    components: Vec<ComponentDiff>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ComponentDiff {
+    component: String,
+    added: Vec<RowDiff>,
+    removed: Vec<RowDiff>,
+    changed: Vec<RowChange>,
+}
+
+/// A row that only exists on one side.
+#[derive(Debug, serde::Serialize)]
+struct RowDiff {
+    is_static: bool,
+
+    /// Raw index values, per timeline, excluding `log_time`. Empty for static data.
+    time_point: BTreeMap<String, i64>,
+
+    #[serde(skip)]
+    time_point_str: String,
+
+    value: String,
+}
+
+/// A row that exists on both sides, with different values.
+#[derive(Debug, serde::Serialize)]
+struct RowChange {
+    is_static: bool,
+
+    /// Raw index values, per timeline, excluding `log_time`. Empty for static data.
+    time_point: BTreeMap<String, i64>,
+
+    #[serde(skip)]
+    time_point_str: String,
+
+    before: String,
+    after: String,
+}
+
+/// All the cells of a recording, per entity and component.
+///
+/// Cells are keyed by their time point, plus their rank (by [`RowId`]) among the cells that share
+/// the same time point, so that repeated logging at the same time is matched in order.
+type Cells = BTreeMap<ComponentKey, BTreeMap<(RowTime, usize), ArrayRef>>;
+
+/// The time point of a row, ignoring `log_time`.
+///
+/// Rows that were only logged on `log_time` end up with an empty time point, and must not be
+/// mistaken for static data.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum RowTime {
+    Static,
+    Temporal(TimePoint),
+}
+
+impl RowTime {
+    fn new(time_point: &TimePoint) -> Self {
+        if time_point.is_static() {
+            Self::Static
+        } else {
+            let mut time_point = time_point.clone();
+            time_point.remove(&TimelineName::log_time());
+            Self::Temporal(time_point)
+        }
+    }
+
+    fn is_static(&self) -> bool {
+        matches!(self, Self::Static)
+    }
+
+    /// Raw index values, per timeline.
+    fn to_raw(&self) -> BTreeMap<String, i64> {
+        match self {
+            Self::Static => BTreeMap::default(),
+            Self::Temporal(time_point) => time_point
+                .iter()
+                .map(|(timeline, cell)| (timeline.to_string(), cell.as_i64()))
+                .collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for RowTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Static => f.write_str("static"),
+            Self::Temporal(time_point) if time_point.is_static() => f.write_str("log_time"),
+            Self::Temporal(time_point) => f.write_str(
+                &time_point
+                    .iter()
+                    .map(|(timeline, cell)| format!("{timeline}={cell}"))
+                    .join(" "),
+            ),
+        }
+    }
+}
+
+/// An entity path and a component name.
+type ComponentKey = (EntityPath, String);
+
+impl DiffReport {
+    fn new(
+        app_id1: &re_log_types::ApplicationId,
+        chunks1: &[Arc<Chunk>],
+        app_id2: &re_log_types::ApplicationId,
+        chunks2: &[Arc<Chunk>],
+    ) -> Self {
+        re_tracing::profile_function!();
+
+        let mut report = Self {
+            application_ids: (app_id1 != app_id2)
+                .then(|| (app_id1.to_string(), app_id2.to_string())),
+            ..Default::default()
+        };
+
+        let mut cells1 = collect_cells(chunks1);
+        let mut cells2 = collect_cells(chunks2);
+
+        let keys: std::collections::BTreeSet<_> =
+            cells1.keys().chain(cells2.keys()).cloned().collect();
+
+        for key in keys {
+            let rows1 = cells1.remove(&key).unwrap_or_default();
+            let mut rows2 = cells2.remove(&key).unwrap_or_default();
+            let (entity_path, component) = key;
+
+            let mut component_diff = ComponentDiff {
+                component,
+                added: Vec::new(),
+                removed: Vec::new(),
+                changed: Vec::new(),
+            };
+
+            for (row_key, before) in rows1 {
+                let (row_time, _rank) = &row_key;
+                match rows2.remove(&row_key) {
+                    Some(after) => {
+                        if before.as_ref() != after.as_ref() {
+                            component_diff.changed.push(RowChange {
+                                is_static: row_time.is_static(),
+                                time_point: row_time.to_raw(),
+                                time_point_str: row_time.to_string(),
+                                before: format_preview(before.as_ref()),
+                                after: format_preview(after.as_ref()),
+                            });
+                        }
+                    }
+
+                    None => component_diff.removed.push(RowDiff {
+                        is_static: row_time.is_static(),
+                        time_point: row_time.to_raw(),
+                        time_point_str: row_time.to_string(),
+                        value: format_preview(before.as_ref()),
+                    }),
+                }
+            }
+
+            for ((row_time, _rank), after) in rows2 {
+                component_diff.added.push(RowDiff {
+                    is_static: row_time.is_static(),
+                    time_point: row_time.to_raw(),
+                    time_point_str: row_time.to_string(),
+                    value: format_preview(after.as_ref()),
+                });
+            }
+
+            if component_diff.added.is_empty()
+                && component_diff.removed.is_empty()
+                && component_diff.changed.is_empty()
+            {
+                continue;
+            }
+
+            report.num_rows_added += component_diff.added.len();
+            report.num_rows_removed += component_diff.removed.len();
+            report.num_rows_changed += component_diff.changed.len();
+
+            let entity_path = entity_path.to_string();
+            match report.entities.last_mut() {
+                Some(entity_diff) if entity_diff.entity_path == entity_path => {
+                    entity_diff.components.push(component_diff);
+                }
+                _ => report.entities.push(EntityDiff {
+                    entity_path,
+                    components: vec![component_diff],
+                }),
+            }
+        }
+
+        report
+    }
+
+    fn is_empty(&self) -> bool {
+        self.application_ids.is_none() && self.entities.is_empty()
+    }
+}
+
+impl std::fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn write_truncated<T>(
+            f: &mut std::fmt::Formatter<'_>,
+            rows: &[T],
+            mut write_row: impl FnMut(&mut std::fmt::Formatter<'_>, &T) -> std::fmt::Result,
+        ) -> std::fmt::Result {
+            for row in rows.iter().take(MAX_TEXT_ROWS_PER_COMPONENT) {
+                write_row(f, row)?;
+            }
+            if rows.len() > MAX_TEXT_ROWS_PER_COMPONENT {
+                writeln!(
+                    f,
+                    "      … and {} more",
+                    re_format::format_uint(rows.len() - MAX_TEXT_ROWS_PER_COMPONENT)
+                )?;
+            }
+            Ok(())
+        }
+
+        if let Some((app_id1, app_id2)) = &self.application_ids {
+            writeln!(f, "Application IDs differ: '{app_id1}' vs. '{app_id2}'")?;
+        }
+
+        for EntityDiff {
+            entity_path,
+            components,
+        } in &self.entities
+        {
+            writeln!(f, "{entity_path}")?;
+
+            for ComponentDiff {
+                component,
+                added,
+                removed,
+                changed,
+            } in components
+            {
+                writeln!(f, "  {component}")?;
+                write_truncated(f, removed, |f, row| {
+                    writeln!(f, "    - {}: {}", row.time_point_str, row.value)
+                })?;
+                write_truncated(f, added, |f, row| {
+                    writeln!(f, "    + {}: {}", row.time_point_str, row.value)
+                })?;
+                write_truncated(f, changed, |f, row| {
+                    writeln!(
+                        f,
+                        "    ~ {}: {} → {}",
+                        row.time_point_str, row.before, row.after
+                    )
+                })?;
+            }
+        }
+
+        writeln!(
+            f,
+            "{} rows added, {} removed, {} changed across {} entities",
+            re_format::format_uint(self.num_rows_added),
+            re_format::format_uint(self.num_rows_removed),
+            re_format::format_uint(self.num_rows_changed),
+            re_format::format_uint(self.entities.len()),
+        )
+    }
+}
+
+fn collect_cells(chunks: &[Arc<Chunk>]) -> Cells {
+    re_tracing::profile_function!();
+
+    let mut rows: BTreeMap<ComponentKey, Vec<(RowTime, RowId, ArrayRef)>> = BTreeMap::default();
+
+    for chunk in chunks {
+        // NOTE: `iter_timepoints` yields empty time points forever for static chunks.
+        let row_times = chunk
+            .iter_timepoints()
+            .take(chunk.num_rows())
+            .map(|time_point| RowTime::new(&time_point))
+            .collect_vec();
+
+        for column in chunk.components().values() {
+            let rows = rows
+                .entry((
+                    chunk.entity_path().clone(),
+                    column.descriptor.display_name().to_owned(),
+                ))
+                .or_default();
+
+            for (row_index, (row_time, row_id)) in izip!(&row_times, chunk.row_ids()).enumerate() {
+                if !column.list_array.is_valid(row_index) {
+                    continue;
+                }
+
+                rows.push((row_time.clone(), row_id, column.list_array.value(row_index)));
+            }
+        }
+    }
+
+    rows.into_iter()
+        .map(|(key, mut rows)| {
+            rows.sort_by(|(row_time1, row_id1, _), (row_time2, row_id2, _)| {
+                (row_time1, row_id1).cmp(&(row_time2, row_id2))
+            });
+
+            let mut cells = BTreeMap::default();
+            let mut rank = 0;
+            let mut previous_row_time = None;
+            for (row_time, _row_id, cell) in rows {
+                if previous_row_time.as_ref() == Some(&row_time) {
+                    rank += 1;
+                } else {
+                    rank = 0;
+                    previous_row_time = Some(row_time.clone());
+                }
+                cells.insert((row_time, rank), cell);
+            }
+
+            (key, cells)
+        })
+        .collect()
+}
+
+/// Formats the first few instances of a cell.
+fn format_preview(array: &dyn arrow::array::Array) -> String {
+    let num_shown = array.len().min(MAX_PREVIEW_INSTANCES);
+
+    let values = match array.data_type() {
+        DataType::Float32 => array
+            .as_primitive::<Float32Type>()
+            .iter()
+            .take(num_shown)
+            .map(|value| value.map_or_else(|| "null".to_owned(), re_format::format_f32))
+            .collect_vec(),
+
+        DataType::Float64 => array
+            .as_primitive::<Float64Type>()
+            .iter()
+            .take(num_shown)
+            .map(|value| value.map_or_else(|| "null".to_owned(), re_format::format_f64))
+            .collect_vec(),
+
+        _ => match ArrayFormatter::try_new(array, &FormatOptions::default().with_null("null")) {
+            Ok(formatter) => (0..num_shown)
+                .map(|i| formatter.value(i).to_string())
+                .collect_vec(),
+            Err(err) => return format!("<{err}>"),
+        },
+    };
+
+    if array.len() > num_shown {
+        format!(
+            "[{}, … ({} instances)]",
+            values.join(", "),
+            re_format::format_uint(array.len())
+        )
+    } else {
+        format!("[{}]", values.join(", "))
+    }
+}
+
 /// Given a path to an rrd file, builds up a `ChunkStore` and returns its contents a stream of
 /// `Chunk`s.
 ///
@@ -195,13 +628,197 @@ fn load_chunks(
         engine
             .store()
             .iter_chunks()
-            .filter_map(|c| {
-                if ignore_chunks_without_components {
-                    (c.num_components() > 0).then_some(c.clone())
-                } else {
-                    Some(c.clone())
-                }
-            })
+            .filter(|c| !ignore_chunks_without_components || c.num_components() > 0)
             .collect_vec(),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::Float32Array;
+    use re_chunk::Timeline;
+    use re_log_types::ApplicationId;
+    use re_log_types::example_components::{MyPoint, MyPoints};
+
+    use super::*;
+
+    fn points_chunk(entity_path: &str, rows: &[(TimePoint, f32)]) -> Arc<Chunk> {
+        let mut builder = Chunk::builder(entity_path);
+        for (time_point, value) in rows {
+            builder = builder.with_component_batches(
+                RowId::new(),
+                time_point.clone(),
+                [(
+                    MyPoints::descriptor_points(),
+                    &[MyPoint::new(*value, *value)] as _,
+                )],
+            );
+        }
+        Arc::new(builder.build().unwrap())
+    }
+
+    fn frame(frame_nr: i64) -> TimePoint {
+        TimePoint::default().with(Timeline::new_sequence("frame"), frame_nr)
+    }
+
+    fn diff(chunks1: &[Arc<Chunk>], chunks2: &[Arc<Chunk>]) -> DiffReport {
+        let app_id = ApplicationId::from("test");
+        DiffReport::new(&app_id, chunks1, &app_id, chunks2)
+    }
+
+    #[test]
+    fn diff_identical() {
+        let chunks1 = [points_chunk(
+            "points",
+            &[(frame(0), 0.0), (frame(1), 1.0), (frame(1), 2.0)],
+        )];
+        // Chunked differently, and out of order.
+        let chunks2 = [
+            points_chunk("points", &[(frame(1), 1.0)]),
+            points_chunk("points", &[(frame(0), 0.0), (frame(1), 2.0)]),
+        ];
+
+        let report = diff(&chunks1, &chunks2);
+        assert!(report.is_empty(), "{report}");
+    }
+
+    #[test]
+    fn diff_application_ids() {
+        let chunks = [points_chunk("points", &[(frame(0), 0.0)])];
+        let report = DiffReport::new(
+            &ApplicationId::from("app1"),
+            &chunks,
+            &ApplicationId::from("app2"),
+            &chunks,
+        );
+
+        assert!(!report.is_empty());
+        assert_eq!(
+            report.application_ids,
+            Some(("app1".to_owned(), "app2".to_owned()))
+        );
+        assert!(report.entities.is_empty());
+    }
+
+    #[test]
+    fn diff_added() {
+        let chunks1 = [points_chunk("points", &[(frame(0), 0.0)])];
+        let chunks2 = [
+            points_chunk("points", &[(frame(0), 0.0), (frame(1), 1.0)]),
+            points_chunk("new", &[(TimePoint::default(), 2.0)]),
+        ];
+
+        let report = diff(&chunks1, &chunks2);
+        assert_eq!(
+            (
+                report.num_rows_added,
+                report.num_rows_removed,
+                report.num_rows_changed
+            ),
+            (2, 0, 0)
+        );
+
+        let entity_paths = report
+            .entities
+            .iter()
+            .map(|entity| entity.entity_path.as_str())
+            .collect_vec();
+        assert_eq!(entity_paths, vec!["/new", "/points"]);
+
+        let added = &report.entities[0].components[0].added;
+        assert!(added[0].is_static);
+        assert_eq!(added[0].time_point_str, "static");
+
+        let added = &report.entities[1].components[0].added;
+        assert!(!added[0].is_static);
+        assert_eq!(
+            added[0].time_point,
+            BTreeMap::from([("frame".to_owned(), 1)])
+        );
+    }
+
+    #[test]
+    fn diff_removed() {
+        let chunks1 = [points_chunk(
+            "points",
+            &[(frame(0), 0.0), (frame(1), 1.0), (frame(1), 2.0)],
+        )];
+        let chunks2 = [points_chunk("points", &[(frame(0), 0.0), (frame(1), 1.0)])];
+
+        let report = diff(&chunks1, &chunks2);
+        assert_eq!(
+            (
+                report.num_rows_added,
+                report.num_rows_removed,
+                report.num_rows_changed
+            ),
+            (0, 1, 0)
+        );
+
+        let removed = &report.entities[0].components[0].removed;
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].time_point_str, "frame=1");
+    }
+
+    #[test]
+    fn diff_changed() {
+        let chunks1 = [points_chunk("points", &[(frame(0), 0.0), (frame(1), 1.0)])];
+        let chunks2 = [points_chunk("points", &[(frame(0), 0.0), (frame(1), 42.0)])];
+
+        let report = diff(&chunks1, &chunks2);
+        assert_eq!(
+            (
+                report.num_rows_added,
+                report.num_rows_removed,
+                report.num_rows_changed
+            ),
+            (0, 0, 1)
+        );
+
+        let changed = &report.entities[0].components[0].changed;
+        assert_eq!(
+            changed[0].time_point,
+            BTreeMap::from([("frame".to_owned(), 1)])
+        );
+        assert_ne!(changed[0].before, changed[0].after);
+    }
+
+    #[test]
+    fn diff_log_time_only_is_not_static() {
+        let log_time = TimePoint::default().with(Timeline::log_time(), 1_000_000_000_i64);
+
+        let chunks1 = [points_chunk("points", &[(TimePoint::default(), 0.0)])];
+        let chunks2 = [
+            points_chunk("points", &[(TimePoint::default(), 0.0)]),
+            points_chunk("points", &[(log_time, 0.0)]),
+        ];
+
+        let report = diff(&chunks1, &chunks2);
+        assert_eq!(
+            (
+                report.num_rows_added,
+                report.num_rows_removed,
+                report.num_rows_changed
+            ),
+            (1, 0, 0)
+        );
+
+        let added = &report.entities[0].components[0].added;
+        assert!(!added[0].is_static);
+        assert!(added[0].time_point.is_empty());
+        assert_eq!(added[0].time_point_str, "log_time");
+    }
+
+    #[test]
+    fn preview_null_floats() {
+        let array = Float32Array::from(vec![Some(1.5), None, Some(2.5)]);
+        assert_eq!(
+            format_preview(&array),
+            format!(
+                "[{}, null, {}]",
+                re_format::format_f32(1.5),
+                re_format::format_f32(2.5)
+            )
+        );
+    }
+}
@@ -1,4 +1,8 @@
-use std::{collections::HashSet, io::IsTerminal as _};
+use std::{
+    collections::{HashMap, HashSet},
+    io::IsTerminal as _,
+    str::FromStr as _,
+};
 
 use anyhow::Context as _;
 use arrow::array::RecordBatchOptions;
@@ -9,7 +13,11 @@ use arrow::{
 use itertools::Either;
 
 use re_build_info::CrateVersion;
-use re_chunk::external::crossbeam;
+use re_chunk::{
+    Chunk, ComponentIdentifier, LatestAtQuery, RowId, TimeColumn, TimeInt, TimelineName,
+    external::crossbeam,
+};
+use re_log_types::{AbsoluteTimeRange, ArrowMsg, StoreId, TimeCell};
 use re_sdk::{EntityPath, external::arrow};
 
 use crate::commands::read_rrd_streams_from_file_or_stdin;
@@ -33,6 +41,28 @@ pub struct FilterCommand {
     #[clap(long = "drop-entity")]
     dropped_entity_paths: Vec<String>,
 
+    /// Name of the timeline used to cut a time window out of the data, see `--range`.
+    #[clap(long, requires = "range")]
+    timeline: Option<String>,
+
+    /// Only keep the data within this inclusive range of the `--timeline` (e.g. `100..200`,
+    /// `10s..20s`, `2025-01-01T00:00:00Z..`).
+    ///
+    /// Chunks are trimmed at the boundaries. Data that isn't on that timeline is dropped.
+    #[clap(long, requires = "timeline")]
+    range: Option<String>,
+
+    /// If set, static data is kept when cutting a time window.
+    #[clap(long, default_value_t = false, requires = "range")]
+    keep_static: bool,
+
+    /// If set, the latest-at state at the start of the time window (i.e. the latest value of
+    /// every component before the range) is kept, re-timestamped to the start of the range.
+    ///
+    /// This makes sure that the cut data renders the same at the start of the range as the original.
+    #[clap(long, default_value_t = false, requires = "range")]
+    keep_latest_at: bool,
+
     /// If set, will try to proceed even in the face of IO and/or decoding errors in the input data.
     #[clap(long = "continue-on-error", default_value_t = false)]
     continue_on_error: bool,
@@ -45,6 +75,10 @@ impl FilterCommand {
             path_to_output_rrd,
             dropped_timelines,
             dropped_entity_paths,
+            timeline,
+            range,
+            keep_static,
+            keep_latest_at,
             continue_on_error,
         } = self;
 
@@ -65,6 +99,17 @@ impl FilterCommand {
             .map(|s| EntityPath::parse_forgiving(s))
             .collect();
 
+        let mut time_slicer = if let (Some(timeline), Some(range)) = (timeline, range) {
+            Some(TimeSlicer::new(
+                timeline.as_str().into(),
+                parse_time_range(range)?,
+                *keep_static,
+                *keep_latest_at,
+            ))
+        } else {
+            None
+        };
+
         let (rx_decoder, rx_size_bytes) = read_rrd_streams_from_file_or_stdin(path_to_input_rrds);
 
         // TODO(cmc): might want to make this configurable at some point.
@@ -108,45 +153,16 @@ impl FilterCommand {
             match res {
                 Ok(msg) => {
                     let msg = match msg {
-                        re_log_types::LogMsg::ArrowMsg(store_id, mut msg) => {
-                            match re_sorbet::ChunkBatch::try_from(&msg.batch) {
-                                Ok(batch) => {
-                                    if !dropped_entity_paths.contains(batch.entity_path()) {
-                                        None
-                                    } else {
-                                        let (fields, columns): (Vec<_>, Vec<_>) = itertools::izip!(
-                                            &batch.schema().fields,
-                                            batch.columns()
-                                        )
-                                        .filter(|(field, _col)| {
-                                            !is_field_timeline_of(field, &dropped_timelines)
-                                        })
-                                        .map(|(field, col)| (field.clone(), col.clone()))
-                                        .unzip();
-
-                                        if let Ok(new_batch) =
-                                            ArrowRecordBatch::try_new_with_options(
-                                                ArrowSchema::new_with_metadata(
-                                                    fields,
-                                                    batch.schema().metadata().clone(),
-                                                )
-                                                .into(),
-                                                columns,
-                                                &RecordBatchOptions::default(),
-                                            )
-                                        {
-                                            msg.batch = new_batch;
-                                            Some(re_log_types::LogMsg::ArrowMsg(store_id, msg))
-                                        } else {
-                                            None // Probably failed because we filtered out everything
-                                        }
-                                    }
-                                }
-                                Err(err) => {
-                                    re_log::warn_once!("Failed to parse chunk schema: {err}");
-                                    None
-                                }
-                            }
+                        re_log_types::LogMsg::ArrowMsg(store_id, msg) => {
+                            let msg = match time_slicer.as_mut() {
+                                Some(time_slicer) => time_slicer.slice(&store_id, msg),
+                                None => Some(msg),
+                            };
+
+                            msg.and_then(|msg| {
+                                filter_arrow_msg(msg, &dropped_entity_paths, &dropped_timelines)
+                            })
+                            .map(|msg| re_log_types::LogMsg::ArrowMsg(store_id, msg))
                         }
 
                         msg => Some(msg),
@@ -170,6 +186,17 @@ impl FilterCommand {
             }
         }
 
+        if let Some(time_slicer) = time_slicer {
+            for (store_id, msg) in time_slicer.into_latest_at_msgs() {
+                if let Some(msg) = filter_arrow_msg(msg, &dropped_entity_paths, &dropped_timelines)
+                {
+                    tx_encoder
+                        .send(re_log_types::LogMsg::ArrowMsg(store_id, msg))
+                        .ok();
+                }
+            }
+        }
+
         std::mem::drop(tx_encoder);
         let rrd_out_size = encoding_handle
             .context("couldn't spawn IO thread")?
@@ -209,9 +236,491 @@ impl FilterCommand {
 
 // ---
 
+/// Drops the given entities and timelines from an [`ArrowMsg`].
+///
+/// Returns `None` if nothing is left.
+fn filter_arrow_msg(
+    mut msg: ArrowMsg,
+    dropped_entity_paths: &HashSet<EntityPath>,
+    dropped_timelines: &HashSet<String>,
+) -> Option<ArrowMsg> {
+    let batch = match re_sorbet::ChunkBatch::try_from(&msg.batch) {
+        Ok(batch) => batch,
+        Err(err) => {
+            re_log::warn_once!("Failed to parse chunk schema: {err}");
+            return None;
+        }
+    };
+
+    if dropped_entity_paths.contains(batch.entity_path()) {
+        return None;
+    }
+
+    let (fields, columns): (Vec<_>, Vec<_>) =
+        itertools::izip!(&batch.schema().fields, batch.columns())
+            .filter(|(field, _col)| !is_field_timeline_of(field, dropped_timelines))
+            .map(|(field, col)| (field.clone(), col.clone()))
+            .unzip();
+
+    // Probably fails because we filtered out everything.
+    let new_batch = ArrowRecordBatch::try_new_with_options(
+        ArrowSchema::new_with_metadata(fields, batch.schema().metadata().clone()).into(),
+        columns,
+        &RecordBatchOptions::default(),
+    )
+    .ok()?;
+
+    msg.batch = new_batch;
+    Some(msg)
+}
+
+/// Parses an inclusive `start..end` range of time values, where either bound may be omitted.
+fn parse_time_range(range: &str) -> anyhow::Result<AbsoluteTimeRange> {
+    let (start, end) = range
+        .split_once("..")
+        .with_context(|| format!("invalid range {range:?}, expected `start..end`"))?;
+
+    let parse_time = |value: &str, default: TimeInt| {
+        let value = value.trim();
+        if value.is_empty() {
+            Ok(default)
+        } else {
+            TimeCell::from_str(value)
+                .map(|cell| TimeInt::new_temporal(cell.value.get()))
+                .with_context(|| format!("invalid time value: {value:?}"))
+        }
+    };
+
+    let range = AbsoluteTimeRange::new(
+        parse_time(start, TimeInt::MIN)?,
+        parse_time(end, TimeInt::MAX)?,
+    );
+    anyhow::ensure!(
+        range.min() <= range.max(),
+        "invalid range {range:?}: start is after end"
+    );
+
+    Ok(range)
+}
+
+/// The latest row of a component before the start of the range, along with its time and [`RowId`].
+type LatestAtState = HashMap<(StoreId, EntityPath, ComponentIdentifier), (TimeInt, RowId, Chunk)>;
+
+/// Cuts a time window out of a stream of chunks.
+struct TimeSlicer {
+    timeline: TimelineName,
+    range: AbsoluteTimeRange,
+    keep_static: bool,
+
+    /// The latest row of every component before the start of the range, if requested.
+    ///
+    /// Sliced down to that single component and timeline.
+    latest_at_state: Option<LatestAtState>,
+}
+
+impl TimeSlicer {
+    fn new(
+        timeline: TimelineName,
+        range: AbsoluteTimeRange,
+        keep_static: bool,
+        keep_latest_at: bool,
+    ) -> Self {
+        Self {
+            timeline,
+            range,
+            keep_static,
+            latest_at_state: keep_latest_at.then(HashMap::default),
+        }
+    }
+
+    /// Trims the chunk in the given message down to the time range.
+    ///
+    /// Returns `None` if nothing is left.
+    fn slice(&mut self, store_id: &StoreId, msg: ArrowMsg) -> Option<ArrowMsg> {
+        let chunk = match Chunk::from_arrow_msg(&msg) {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                re_log::warn_once!("Failed to parse chunk: {err}");
+                return None;
+            }
+        };
+
+        if chunk.is_static() {
+            return self.keep_static.then_some(msg);
+        }
+
+        if !chunk.timelines().contains_key(&self.timeline) {
+            return None;
+        }
+        let chunk = chunk.sorted_by_timeline_if_unsorted(&self.timeline);
+        let times = chunk.timelines().get(&self.timeline)?.times_raw();
+
+        let start = times.partition_point(|&time| time < self.range.min().as_i64());
+        let end = times.partition_point(|&time| time <= self.range.max().as_i64());
+
+        if start > 0 {
+            self.update_latest_at_state(store_id, &chunk.row_sliced(0, start));
+        }
+
+        if start >= end {
+            None
+        } else if end - start == chunk.num_rows() {
+            Some(msg) // Untouched
+        } else {
+            let chunk = chunk
+                .row_sliced(start, end - start)
+                .with_id(re_chunk::ChunkId::new());
+            match chunk.to_arrow_msg() {
+                Ok(msg) => Some(msg),
+                Err(err) => {
+                    re_log::warn_once!("Failed to encode sliced chunk: {err}");
+                    None
+                }
+            }
+        }
+    }
+
+    /// Keeps track of the latest row of every component in `chunk`, which must only contain rows
+    /// that are before the start of the range.
+    fn update_latest_at_state(&mut self, store_id: &StoreId, chunk: &Chunk) {
+        let Some(latest_at_state) = self.latest_at_state.as_mut() else {
+            return;
+        };
+
+        let query = LatestAtQuery::new(self.timeline, self.range.min());
+        for component in chunk.components_identifiers() {
+            let latest = chunk
+                .latest_at(&query, component)
+                .component_sliced(component)
+                .timeline_sliced(self.timeline);
+
+            let Some(index) = latest
+                .timelines()
+                .get(&self.timeline)
+                .and_then(|time_column| time_column.times().next())
+            else {
+                continue;
+            };
+            let Some(row_id) = latest.row_ids().next() else {
+                continue;
+            };
+
+            let key = (store_id.clone(), chunk.entity_path().clone(), component);
+            let is_newer = latest_at_state
+                .get(&key)
+                .is_none_or(|(cur_index, cur_row_id, _)| {
+                    (index, row_id) > (*cur_index, *cur_row_id)
+                });
+            if is_newer {
+                latest_at_state.insert(key, (index, row_id, latest));
+            }
+        }
+    }
+
+    /// Returns the latest-at state at the start of the range, re-timestamped to that start.
+    fn into_latest_at_msgs(self) -> impl Iterator<Item = (StoreId, ArrowMsg)> {
+        let Self {
+            timeline,
+            range,
+            keep_static: _,
+            latest_at_state,
+        } = self;
+
+        latest_at_state.unwrap_or_default().into_iter().filter_map(
+            move |((store_id, _entity_path, _component), (_, _, chunk))| {
+                let time_column = chunk.timelines().get(&timeline)?;
+                let time_column = TimeColumn::new(
+                    Some(true),
+                    *time_column.timeline(),
+                    vec![range.min().as_i64()].into(),
+                );
+
+                let mut chunk = chunk.with_id(re_chunk::ChunkId::new());
+                let msg = chunk
+                    .add_timeline(time_column)
+                    .and_then(|()| chunk.to_arrow_msg());
+                match msg {
+                    Ok(msg) => Some((store_id, msg)),
+                    Err(err) => {
+                        re_log::warn_once!("Failed to encode latest-at chunk: {err}");
+                        None
+                    }
+                }
+            },
+        )
+    }
+}
+
 // Does the given field represent a timeline that is in the given set?
 fn is_field_timeline_of(field: &ArrowField, dropped_timelines: &HashSet<String>) -> bool {
     re_sorbet::IndexColumnDescriptor::try_from(field)
         .ok()
         .is_some_and(|schema| dropped_timelines.contains(schema.column_name()))
 }
+
+#[cfg(test)]
+mod tests {
+    use re_chunk::{TimePoint, Timeline};
+    use re_log_types::StoreKind;
+    use re_log_types::example_components::{MyColor, MyPoint, MyPoints};
+
+    use super::*;
+
+    fn frame() -> Timeline {
+        Timeline::new_sequence("frame")
+    }
+
+    /// One row of points per frame.
+    fn points_chunk(frames: impl IntoIterator<Item = i64>) -> (Chunk, Vec<RowId>) {
+        let mut row_ids = Vec::new();
+        let mut builder = Chunk::builder("points");
+        for frame_nr in frames {
+            let row_id = RowId::new();
+            row_ids.push(row_id);
+            builder = builder.with_component_batches(
+                row_id,
+                TimePoint::default().with(frame(), frame_nr),
+                [(
+                    MyPoints::descriptor_points(),
+                    &MyPoint::from_iter(0..3) as _,
+                )],
+            );
+        }
+        (builder.build().unwrap(), row_ids)
+    }
+
+    fn frame_times(msg: &ArrowMsg) -> Vec<i64> {
+        let chunk = Chunk::from_arrow_msg(msg).unwrap();
+        chunk.timelines()[frame().name()].times_raw().to_vec()
+    }
+
+    #[test]
+    fn parse_time_ranges() {
+        let range = |min: i64, max: i64| {
+            AbsoluteTimeRange::new(TimeInt::new_temporal(min), TimeInt::new_temporal(max))
+        };
+
+        assert_eq!(parse_time_range("100..200").unwrap(), range(100, 200));
+        assert_eq!(parse_time_range(" 100 .. 200 ").unwrap(), range(100, 200));
+        assert_eq!(parse_time_range("100..100").unwrap(), range(100, 100));
+        assert_eq!(
+            parse_time_range("10s..20s").unwrap(),
+            range(10_000_000_000, 20_000_000_000)
+        );
+
+        assert_eq!(
+            parse_time_range("..200").unwrap(),
+            AbsoluteTimeRange::new(TimeInt::MIN, TimeInt::new_temporal(200))
+        );
+        assert_eq!(
+            parse_time_range("100..").unwrap(),
+            AbsoluteTimeRange::new(TimeInt::new_temporal(100), TimeInt::MAX)
+        );
+        assert_eq!(
+            parse_time_range("..").unwrap(),
+            AbsoluteTimeRange::new(TimeInt::MIN, TimeInt::MAX)
+        );
+
+        assert!(parse_time_range("200..100").is_err());
+        assert!(parse_time_range("100").is_err());
+        assert!(parse_time_range("foo..200").is_err());
+    }
+
+    #[test]
+    fn drop_entities_and_timelines() {
+        let (chunk, _row_ids) = points_chunk(0..3);
+        let msg = chunk.to_arrow_msg().unwrap();
+
+        let no_timelines = HashSet::default();
+
+        let dropped: HashSet<_> = [EntityPath::from("points")].into_iter().collect();
+        assert_eq!(filter_arrow_msg(msg.clone(), &dropped, &no_timelines), None);
+
+        let dropped: HashSet<_> = [EntityPath::from("other")].into_iter().collect();
+        assert_eq!(
+            filter_arrow_msg(msg.clone(), &dropped, &no_timelines),
+            Some(msg.clone())
+        );
+        assert_eq!(
+            filter_arrow_msg(msg.clone(), &HashSet::default(), &no_timelines),
+            Some(msg.clone())
+        );
+
+        let dropped_timelines: HashSet<_> = ["frame".to_owned()].into_iter().collect();
+        let filtered = filter_arrow_msg(msg, &HashSet::default(), &dropped_timelines).unwrap();
+        let filtered = Chunk::from_arrow_msg(&filtered).unwrap();
+        assert!(filtered.is_static());
+        assert_eq!(filtered.num_rows(), 3);
+    }
+
+    #[test]
+    fn slice_inclusive_bounds() {
+        let store_id = StoreId::random(StoreKind::Recording, "test");
+        let (chunk, row_ids) = points_chunk(0..10);
+        let msg = chunk.to_arrow_msg().unwrap();
+
+        let mut slicer = TimeSlicer::new(
+            *frame().name(),
+            parse_time_range("3..6").unwrap(),
+            false,
+            false,
+        );
+        let sliced = slicer.slice(&store_id, msg.clone()).unwrap();
+        assert_eq!(frame_times(&sliced), vec![3, 4, 5, 6]);
+
+        let sliced_chunk = Chunk::from_arrow_msg(&sliced).unwrap();
+        assert_ne!(sliced_chunk.id(), chunk.id());
+        assert_eq!(sliced_chunk.row_ids().collect::<Vec<_>>(), row_ids[3..=6]);
+
+        // Open ranges.
+        let mut slicer = TimeSlicer::new(
+            *frame().name(),
+            parse_time_range("..1").unwrap(),
+            false,
+            false,
+        );
+        assert_eq!(
+            frame_times(&slicer.slice(&store_id, msg.clone()).unwrap()),
+            vec![0, 1]
+        );
+
+        let mut slicer = TimeSlicer::new(
+            *frame().name(),
+            parse_time_range("8..").unwrap(),
+            false,
+            false,
+        );
+        assert_eq!(
+            frame_times(&slicer.slice(&store_id, msg.clone()).unwrap()),
+            vec![8, 9]
+        );
+
+        // Fully within the range: untouched.
+        let mut slicer = TimeSlicer::new(
+            *frame().name(),
+            parse_time_range("..").unwrap(),
+            false,
+            false,
+        );
+        assert_eq!(slicer.slice(&store_id, msg.clone()), Some(msg.clone()));
+
+        // Fully outside of the range.
+        let mut slicer = TimeSlicer::new(
+            *frame().name(),
+            parse_time_range("10..20").unwrap(),
+            false,
+            false,
+        );
+        assert_eq!(slicer.slice(&store_id, msg.clone()), None);
+
+        // Not on the timeline.
+        let mut slicer = TimeSlicer::new(
+            "other".into(),
+            parse_time_range("..").unwrap(),
+            false,
+            false,
+        );
+        assert_eq!(slicer.slice(&store_id, msg), None);
+    }
+
+    #[test]
+    fn slice_static() {
+        let store_id = StoreId::random(StoreKind::Recording, "test");
+        let chunk = Chunk::builder("points")
+            .with_component_batches(
+                RowId::new(),
+                TimePoint::default(),
+                [(
+                    MyPoints::descriptor_points(),
+                    &MyPoint::from_iter(0..3) as _,
+                )],
+            )
+            .build()
+            .unwrap();
+        let msg = chunk.to_arrow_msg().unwrap();
+
+        let range = parse_time_range("3..6").unwrap();
+
+        let mut slicer = TimeSlicer::new(*frame().name(), range, false, false);
+        assert_eq!(slicer.slice(&store_id, msg.clone()), None);
+
+        let mut slicer = TimeSlicer::new(*frame().name(), range, true, false);
+        assert_eq!(slicer.slice(&store_id, msg.clone()), Some(msg));
+    }
+
+    #[test]
+    fn slice_latest_at() {
+        let store_id = StoreId::random(StoreKind::Recording, "test");
+
+        let (points, points_row_ids) = points_chunk([0, 2, 1, 7]);
+
+        let colors_row_id = RowId::new();
+        let colors = Chunk::builder("points")
+            .with_component_batches(
+                colors_row_id,
+                TimePoint::default().with(frame(), 1),
+                [(MyPoints::descriptor_colors(), &[MyColor(0xFF00_00FF)] as _)],
+            )
+            .build()
+            .unwrap();
+
+        // Only after the start of the range: not part of the latest-at state.
+        let later = Chunk::builder("later")
+            .with_component_batches(
+                RowId::new(),
+                TimePoint::default().with(frame(), 6),
+                [(
+                    MyPoints::descriptor_points(),
+                    &MyPoint::from_iter(0..3) as _,
+                )],
+            )
+            .build()
+            .unwrap();
+
+        let mut slicer = TimeSlicer::new(
+            *frame().name(),
+            parse_time_range("5..8").unwrap(),
+            false,
+            true,
+        );
+
+        let sliced = slicer
+            .slice(&store_id, points.to_arrow_msg().unwrap())
+            .unwrap();
+        assert_eq!(frame_times(&sliced), vec![7]);
+        assert_eq!(
+            slicer.slice(&store_id, colors.to_arrow_msg().unwrap()),
+            None
+        );
+        assert!(
+            slicer
+                .slice(&store_id, later.to_arrow_msg().unwrap())
+                .is_some()
+        );
+
+        let mut latest_at: Vec<_> = slicer
+            .into_latest_at_msgs()
+            .map(|(msg_store_id, msg)| {
+                assert_eq!(msg_store_id, store_id);
+                Chunk::from_arrow_msg(&msg).unwrap()
+            })
+            .collect();
+        latest_at.sort_by_key(|chunk| chunk.row_ids().next());
+        assert_eq!(latest_at.len(), 2);
+
+        let mut expected_row_ids = vec![points_row_ids[1], colors_row_id];
+        expected_row_ids.sort();
+
+        for (chunk, expected_row_id) in latest_at.iter().zip(expected_row_ids) {
+            assert_eq!(chunk.entity_path(), &EntityPath::from("points"));
+            assert_eq!(chunk.num_rows(), 1);
+            assert_eq!(chunk.row_ids().collect::<Vec<_>>(), vec![expected_row_id]);
+            assert_eq!(
+                chunk.timelines()[frame().name()].times_raw().to_vec(),
+                vec![5]
+            );
+            assert_ne!(chunk.id(), points.id());
+            assert_ne!(chunk.id(), colors.id());
+        }
+    }
+}
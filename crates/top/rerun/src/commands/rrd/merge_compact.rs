@@ -3,12 +3,18 @@ use std::io::{IsTerminal as _, Write as _};
 use anyhow::Context as _;
 use itertools::Either;
 
+use re_chunk::{Chunk, ChunkId, TimelineName};
 use re_chunk_store::{ChunkStore, ChunkStoreConfig, ChunkStoreError};
 use re_entity_db::EntityDb;
-use re_log_types::StoreId;
+use re_log_encoding::rrd::Compression;
+use re_log_types::{LogMsg, StoreId};
 use re_sdk::StoreKind;
 
-use crate::commands::read_rrd_streams_from_file_or_stdin;
+use super::align::{
+    ClockCorrectionArg, ClockCorrections, ClockDrift, TimeOffset, estimate_clock_corrections,
+};
+use super::remap::{EntityPathRemap, EntityPathRemaps};
+use crate::commands::{read_rrd_streams_from_file_or_stdin, stdio::InputSource};
 
 // ---
 
@@ -24,6 +30,52 @@ pub struct MergeCommand {
     /// If set, will try to proceed even in the face of IO and/or decoding errors in the input data.
     #[clap(long = "continue-on-error", default_value_t = false)]
     continue_on_error: bool,
+
+    /// Rewrites entity paths, e.g. `--remap-entity "/camera/** -> /robot_a/camera/**"`.
+    ///
+    /// Can be specified multiple times, the first matching rule wins.
+    /// `/** -> /robot_a/**` prefixes every entity, `/** -> /{input}/**` prefixes every entity with
+    /// the name of the file it comes from.
+    /// References to entities in blueprints (view origins, view contents, overrides) are rewritten too.
+    #[clap(long = "remap-entity", value_name = "FROM -> TO")]
+    remap_entity: Vec<EntityPathRemap>,
+
+    /// Shifts the times of a timeline, e.g. `--time-offset robot_b.rrd:log_time=-1.5s`.
+    ///
+    /// Applies to all inputs if no input is specified. Inputs can be designated by path, file
+    /// name, or file name without extension.
+    /// Offsets are either integers in the unit of the timeline, or durations (e.g. `250ms`).
+    /// Can be specified multiple times.
+    #[clap(long = "time-offset", value_name = "[INPUT:]TIMELINE=OFFSET")]
+    time_offset: Vec<ClockCorrectionArg<TimeOffset>>,
+
+    /// Corrects the drift of a clock running too fast (or too slow), e.g.
+    /// `--clock-drift robot_b:log_time=-12.5@2025-01-01T12:00:00Z`.
+    ///
+    /// The drift is expressed in parts per million, around a pivot time that is left untouched
+    /// (typically the start of the recording).
+    /// Applied after `--time-offset`. Can be specified multiple times.
+    #[clap(long = "clock-drift", value_name = "[INPUT:]TIMELINE=PPM@PIVOT")]
+    clock_drift: Vec<ClockCorrectionArg<ClockDrift>>,
+
+    /// Automatically aligns the clocks of all inputs onto the clock of the first input that logged
+    /// a synchronization component, e.g. `--align-on /sync:Scalars:scalars`.
+    ///
+    /// Identical values of that component are assumed to have been logged at the same instant by
+    /// every input: the resulting offset (and drift, given enough samples) is estimated and applied
+    /// to `--align-timeline`, before any `--time-offset` or `--clock-drift`.
+    ///
+    /// Requires reading the inputs twice, and thus doesn't work with standard input.
+    #[clap(
+        long = "align-on",
+        value_name = "ENTITY:COMPONENT",
+        requires = "align_timeline"
+    )]
+    align_on: Option<String>,
+
+    /// The timeline to align with `--align-on`.
+    #[clap(long = "align-timeline", requires = "align_on")]
+    align_timeline: Option<String>,
 }
 
 impl MergeCommand {
@@ -32,6 +84,11 @@ impl MergeCommand {
             path_to_input_rrds,
             path_to_output_rrd,
             continue_on_error,
+            remap_entity,
+            time_offset,
+            clock_drift,
+            align_on,
+            align_timeline,
         } = self;
 
         if path_to_output_rrd.is_none() {
@@ -47,11 +104,36 @@ impl MergeCommand {
         // (e.g. by recompacting it differently), so make sure to disable all these features.
         let store_config = ChunkStoreConfig::ALL_DISABLED;
 
+        let estimated_clock_corrections =
+            if let (Some(align_on), Some(align_timeline)) = (align_on, align_timeline) {
+                estimate_clock_corrections(
+                    path_to_input_rrds,
+                    align_on,
+                    TimelineName::new(align_timeline),
+                    *continue_on_error,
+                )?
+            } else {
+                Default::default()
+            };
+
+        let rewrites = Rewrites {
+            entity_paths: EntityPathRemaps::new(remap_entity.clone()),
+            clocks: ClockCorrections::new(
+                path_to_input_rrds,
+                estimated_clock_corrections,
+                time_offset,
+                clock_drift,
+            )?,
+        };
+
         let num_passes = 0;
         merge_and_compact(
             num_passes,
             *continue_on_error,
             &store_config,
+            &rewrites,
+            Compression::LZ4,
+            re_log_encoding::rrd::ZSTD_DEFAULT_LEVEL,
             path_to_input_rrds,
             path_to_output_rrd.as_ref(),
         )
@@ -60,6 +142,18 @@ impl MergeCommand {
 
 // ---
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum CompressionKind {
+    /// No compression.
+    Off,
+
+    /// Fast compression and decompression, but larger files.
+    Lz4,
+
+    /// Much smaller files, at the cost of slower compression. Best suited for archival.
+    Zstd,
+}
+
 #[derive(Debug, Clone, clap::Parser)]
 pub struct CompactCommand {
     /// Paths to read from. Reads from standard input if none are specified.
@@ -107,6 +201,16 @@ pub struct CompactCommand {
     #[arg(long = "num-pass", default_value_t = 50)]
     num_extra_passes: u32,
 
+    /// How to compress the output data.
+    #[clap(long, value_enum, default_value_t = CompressionKind::Lz4)]
+    compression: CompressionKind,
+
+    /// The Zstd compression level, from 1 (fastest) to 22 (smallest). Defaults to 3.
+    ///
+    /// Only valid with `--compression zstd`.
+    #[clap(long, value_parser = clap::value_parser!(i32).range(1..=22))]
+    compression_level: Option<i32>,
+
     /// If set, will try to proceed even in the face of IO and/or decoding errors in the input data.
     #[clap(long = "continue-on-error", default_value_t = false)]
     continue_on_error: bool,
@@ -121,6 +225,8 @@ impl CompactCommand {
             max_rows,
             max_rows_if_unsorted,
             num_extra_passes,
+            compression,
+            compression_level,
             continue_on_error,
         } = self;
 
@@ -146,20 +252,99 @@ impl CompactCommand {
             store_config.chunk_max_rows_if_unsorted = *max_rows_if_unsorted;
         }
 
+        anyhow::ensure!(
+            compression_level.is_none() || *compression == CompressionKind::Zstd,
+            "--compression-level is only valid with --compression zstd"
+        );
+
+        let compression = match compression {
+            CompressionKind::Off => Compression::Off,
+            CompressionKind::Lz4 => Compression::LZ4,
+            CompressionKind::Zstd => Compression::Zstd,
+        };
+        let zstd_level = compression_level.unwrap_or(re_log_encoding::rrd::ZSTD_DEFAULT_LEVEL);
+
         merge_and_compact(
             *num_extra_passes,
             *continue_on_error,
             &store_config,
+            &Rewrites::default(),
+            compression,
+            zstd_level,
             path_to_input_rrds,
             path_to_output_rrd.as_ref(),
         )
     }
 }
 
+/// Rewrites applied to every message of `rerun rrd merge`, before the data gets merged.
+#[derive(Default)]
+struct Rewrites {
+    entity_paths: EntityPathRemaps,
+    clocks: ClockCorrections,
+}
+
+impl Rewrites {
+    fn is_empty(&self) -> bool {
+        self.entity_paths.is_empty() && self.clocks.is_empty()
+    }
+
+    fn apply(&self, input: &InputSource, msg: LogMsg) -> anyhow::Result<LogMsg> {
+        let LogMsg::ArrowMsg(store_id, arrow_msg) = &msg else {
+            return Ok(msg);
+        };
+
+        let chunk = Chunk::from_arrow_msg(arrow_msg)?;
+        let remapped = self
+            .entity_paths
+            .for_input(input)
+            .remap_chunk(&chunk, store_id.kind())?;
+        // Corrected chunks must not be mistaken for the originals by the store.
+        let corrected = self
+            .clocks
+            .correct_chunk(input, remapped.as_ref().unwrap_or(&chunk), store_id.kind())
+            .map(|chunk| chunk.with_id(ChunkId::new()));
+
+        Ok(match corrected.or(remapped) {
+            Some(chunk) => LogMsg::ArrowMsg(store_id.clone(), chunk.to_arrow_msg()?),
+            None => msg,
+        })
+    }
+}
+
+/// Applies the `rewrites` to a message read from `source`, and adds it to the store it belongs to.
+///
+/// Returns whether the message was a chunk.
+fn ingest(
+    entity_dbs: &mut std::collections::HashMap<StoreId, EntityDb>,
+    store_config: &ChunkStoreConfig,
+    rewrites: &Rewrites,
+    source: &InputSource,
+    msg: anyhow::Result<LogMsg>,
+) -> anyhow::Result<bool> {
+    let msg = match msg {
+        Ok(msg) if !rewrites.is_empty() => rewrites.apply(source, msg)?,
+        msg => msg?,
+    };
+
+    entity_dbs
+        .entry(msg.store_id().clone())
+        .or_insert_with(|| {
+            EntityDb::with_store_config(msg.store_id().clone(), store_config.clone())
+        })
+        .add(&msg)
+        .context("couldn't index corrupt chunk")?;
+
+    Ok(matches!(msg, LogMsg::ArrowMsg(_, _)))
+}
+
 fn merge_and_compact(
     num_passes: u32,
     continue_on_error: bool,
     store_config: &ChunkStoreConfig,
+    rewrites: &Rewrites,
+    compression: Compression,
+    zstd_level: i32,
     path_to_input_rrds: &[String],
     path_to_output_rrd: Option<&String>,
 ) -> anyhow::Result<()> {
@@ -186,32 +371,17 @@ fn merge_and_compact(
     re_log::info!("processing input…");
     let mut num_chunks_before = 0u64;
     let mut last_checkpoint = std::time::Instant::now();
-    for (msg_nr, (_source, res)) in rx.iter().enumerate() {
-        let mut is_success = true;
-
-        match res {
-            Ok(msg) => {
-                num_chunks_before += matches!(msg, re_log_types::LogMsg::ArrowMsg(_, _)) as u64;
-                if let Err(err) = entity_dbs
-                    .entry(msg.store_id().clone())
-                    .or_insert_with(|| {
-                        re_entity_db::EntityDb::with_store_config(
-                            msg.store_id().clone(),
-                            store_config.clone(),
-                        )
-                    })
-                    .add(&msg)
-                {
-                    re_log::error!(%err, "couldn't index corrupt chunk");
-                    is_success = false;
-                }
+    for (msg_nr, (source, res)) in rx.iter().enumerate() {
+        let is_success = match ingest(&mut entity_dbs, store_config, rewrites, &source, res) {
+            Ok(is_chunk) => {
+                num_chunks_before += is_chunk as u64;
+                true
             }
-
             Err(err) => {
                 re_log::error!(err = re_error::format(err));
-                is_success = false;
+                false
             }
-        }
+        };
 
         if !continue_on_error && !is_success {
             anyhow::bail!(
@@ -250,7 +420,7 @@ fn merge_and_compact(
 
                 let mut store = ChunkStore::new(store_id.clone(), store_config.clone());
                 for chunk in engine.read().store().iter_chunks() {
-                    store.insert_chunk(chunk)?;
+                    store.insert_chunk(&chunk)?;
                 }
 
                 num_chunks_after += store.num_chunks() as u64;
@@ -300,7 +470,10 @@ fn merge_and_compact(
         });
 
     // TODO(cmc): encoding options should match the original.
-    let encoding_options = re_log_encoding::rrd::EncodingOptions::PROTOBUF_COMPRESSED;
+    let encoding_options = re_log_encoding::rrd::EncodingOptions {
+        compression,
+        ..re_log_encoding::rrd::EncodingOptions::PROTOBUF_COMPRESSED
+    };
     let version = entity_dbs
         .values()
         .next()
@@ -309,15 +482,20 @@ fn merge_and_compact(
         .unwrap_or(re_build_info::CrateVersion::LOCAL);
 
     re_log::info!("encoding…");
-    let rrd_out_size = re_log_encoding::Encoder::encode_into(
-        version,
-        encoding_options,
+    let rrd_out_size = {
+        let mut encoder =
+            re_log_encoding::Encoder::new_eager(version, encoding_options, &mut rrd_out)
+                .context("couldn't encode messages")?
+                .with_zstd_level(zstd_level);
+        let mut size_bytes = 0;
         // NOTE: We want to make sure all blueprints come first, so that the viewer can immediately
         // set up the viewport correctly.
-        messages_rbl.chain(messages_rrd),
-        &mut rrd_out,
-    )
-    .context("couldn't encode messages")?;
+        for msg in messages_rbl.chain(messages_rrd) {
+            size_bytes += encoder.append(&msg?).context("couldn't encode messages")?;
+        }
+        encoder.finish().context("couldn't encode messages")?;
+        size_bytes
+    };
 
     rrd_out.flush().context("couldn't flush output")?;
 
@@ -349,3 +527,68 @@ fn merge_and_compact(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use re_chunk::{RowId, TimePoint, Timeline};
+    use re_log_types::example_components::{MyPoint, MyPoints};
+    use re_log_types::{EntityPath, SetStoreInfo, StoreInfo};
+
+    use super::*;
+
+    #[test]
+    fn merge_copies_under_different_prefixes() {
+        let info = StoreInfo::testing();
+        let store_id = info.store_id.clone();
+
+        let chunk = Chunk::builder("points")
+            .with_component_batches(
+                RowId::new(),
+                TimePoint::default().with(Timeline::new_sequence("frame"), 1),
+                [(
+                    MyPoints::descriptor_points(),
+                    &MyPoint::from_iter(0..10) as _,
+                )],
+            )
+            .build()
+            .unwrap();
+        let messages = [
+            LogMsg::SetStoreInfo(SetStoreInfo {
+                row_id: *RowId::new(),
+                info,
+            }),
+            LogMsg::ArrowMsg(store_id.clone(), chunk.to_arrow_msg().unwrap()),
+        ];
+
+        let rewrites = Rewrites {
+            entity_paths: EntityPathRemaps::new(vec!["/** -> /{input}/**".parse().unwrap()]),
+            ..Default::default()
+        };
+
+        // The very same recording, twice.
+        let mut entity_dbs = Default::default();
+        for input in ["robot_a.rrd", "robot_b.rrd"] {
+            let source = InputSource::File(PathBuf::from(input));
+            for msg in &messages {
+                ingest(
+                    &mut entity_dbs,
+                    &ChunkStoreConfig::DEFAULT,
+                    &rewrites,
+                    &source,
+                    Ok(msg.clone()),
+                )
+                .unwrap();
+            }
+        }
+
+        assert_eq!(entity_dbs.len(), 1);
+        let entity_db = &entity_dbs[&store_id];
+        assert_eq!(entity_db.num_rows(), 2);
+
+        let entities = entity_db.storage_engine().store().all_entities_sorted();
+        assert!(entities.contains(&EntityPath::from("/robot_a/points")));
+        assert!(entities.contains(&EntityPath::from("/robot_b/points")));
+    }
+}
@@ -1,8 +1,12 @@
+mod align;
 mod compare;
 mod filter;
 mod merge_compact;
 mod migrate;
 mod print;
+mod query;
+mod remap;
+mod repair;
 mod route;
 mod stats;
 mod verify;
@@ -13,6 +17,8 @@ use self::{
     merge_compact::{CompactCommand, MergeCommand},
     migrate::MigrateCommand,
     print::PrintCommand,
+    query::QueryCommand,
+    repair::RepairCommand,
     route::RouteCommand,
     stats::StatsCommand,
     verify::VerifyCommand,
@@ -44,21 +50,30 @@ pub enum RrdCommands {
     /// * `RERUN_CHUNK_MAX_ROWS=4096 RERUN_CHUNK_MAX_BYTES=1048576 rerun rrd compact /my/recordings/*.rrd -o output.rrd`
     ///
     /// * `rerun rrd compact --max-rows 4096 --max-bytes=1048576 /my/recordings/*.rrd > output.rrd`
+    ///
+    /// * `rerun rrd compact --compression zstd --compression-level 19 /my/recordings/*.rrd -o archive.rrd`
     Compact(CompactCommand),
 
     /// Compares the data between 2 .rrd files, returning a successful shell exit code if they
     /// match.
     ///
     /// This ignores the `log_time` timeline.
+    ///
+    /// Example: `rerun rrd compare --diff --format json before.rrd after.rrd > diff.json`
     Compare(CompareCommand),
 
     /// Filters out data from .rrd/.rbl files/streams, and writes the result to standard output.
     ///
     /// Reads from standard input if no paths are specified.
     ///
-    /// This will not affect the chunking of the data in any way.
+    /// This will not affect the chunking of the data in any way, except for chunks that are trimmed
+    /// at the boundaries of a time window (`--range`).
+    ///
+    /// Examples:
+    ///
+    /// * `rerun rrd filter --drop-timeline log_tick /my/recordings/*.rrd > output.rrd`
     ///
-    /// Example: `rerun rrd filter --drop-timeline log_tick /my/recordings/*.rrd > output.rrd`
+    /// * `rerun rrd filter --timeline frame_nr --range 100..200 --keep-static --keep-latest-at recording.rrd -o excerpt.rrd`
     Filter(FilterCommand),
 
     /// Merges the contents of multiple .rrd/.rbl files/streams, and writes the result to standard output.
@@ -67,7 +82,13 @@ pub enum RrdCommands {
     ///
     /// ⚠️ This will automatically migrate the data to the latest version of the RRD protocol, if needed. ⚠️
     ///
-    /// Example: `rerun rrd merge /my/recordings/*.rrd > output.rrd`
+    /// Examples:
+    ///
+    /// * `rerun rrd merge /my/recordings/*.rrd > output.rrd`
+    ///
+    /// * `rerun rrd merge robot_a.rrd robot_b.rrd --remap-entity "/** -> /{input}/**" -o fleet.rrd`
+    ///
+    /// * `rerun rrd merge robot_a.rrd robot_b.rrd --align-on /sync:Scalars:scalars --align-timeline log_time -o fleet.rrd`
     Merge(MergeCommand),
 
     /// Migrate one or more .rrd files to the newest Rerun version.
@@ -83,19 +104,44 @@ pub enum RrdCommands {
     /// Example: `rerun rrd print /my/recordings/*.rrd`
     Print(PrintCommand),
 
+    /// Runs a dataframe query against an .rrd file, and writes the resulting table as Parquet,
+    /// Arrow IPC or CSV.
+    ///
+    /// Writes to standard output if no output path is specified.
+    ///
+    /// Examples:
+    ///
+    /// * `rerun rrd query recording.rrd --index frame_nr --contents "/world/**" -o table.parquet`
+    ///
+    /// * `rerun rrd query recording.rrd --index log_time --from 2025-01-01T00:00:00Z --fill latest-at --format csv`
+    Query(QueryCommand),
+
+    /// Salvages every fully decodable message from a truncated or partially corrupted .rrd/.rbl file,
+    /// and writes them to a new, valid file.
+    ///
+    /// Reports every range of bytes that had to be skipped on standard error.
+    ///
+    /// Example: `rerun rrd repair crashed.rrd -o repaired.rrd`
+    Repair(RepairCommand),
+
     /// Manipulates the metadata of log message streams without decoding the payloads.
     ///
     /// This can be used to combine multiple .rrd files into a single recording.
     /// Example: `rerun rrd route --recording-id my_recording /my/recordings/*.rrd > output.rrd`
     ///
-    /// Note: Because the payload of the messages is never decoded, no migration or verification will performed.
+    /// Note: Because the payload of the messages is never decoded (except when remapping entity paths
+    /// with `--remap-entity`), no migration or verification will performed.
     Route(RouteCommand),
 
     /// Compute important statistics for one or more .rrd/.rbl files/streams.
     ///
     /// Reads from standard input if no paths are specified.
     ///
-    /// Example: `rerun rrd stats /my/recordings/*.rrd`
+    /// Examples:
+    ///
+    /// * `rerun rrd stats /my/recordings/*.rrd`
+    ///
+    /// * `rerun rrd stats --format parquet /my/recordings/*.rrd -o stats.parquet`
     Stats(StatsCommand),
 
     /// Verify the that the .rrd file can be loaded and correctly interpreted.
@@ -117,6 +163,8 @@ impl RrdCommands {
             Self::Merge(cmd) => cmd.run(),
             Self::Migrate(cmd) => cmd.run(),
             Self::Print(cmd) => cmd.run(),
+            Self::Query(cmd) => cmd.run(),
+            Self::Repair(cmd) => cmd.run(),
             Self::Route(cmd) => cmd.run(),
             Self::Stats(cmd) => cmd.run(),
             Self::Verify(cmd) => cmd.run(),
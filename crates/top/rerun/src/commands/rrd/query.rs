@@ -0,0 +1,513 @@
+use std::io::{IsTerminal as _, Write as _};
+use std::str::FromStr as _;
+
+use anyhow::Context as _;
+use arrow::array::{ArrayRef, RecordBatch, RecordBatchOptions, StringArray};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use itertools::Itertools as _;
+
+use re_dataframe::{
+    AbsoluteTimeRange, ChunkStoreConfig, EntityPathFilter, QueryEngine, QueryExpression,
+    SparseFillStrategy, StoreKind, TimeCell, TimeInt, ViewContentsSelector,
+};
+use re_sorbet::{ColumnSelector, ComponentColumnSelector, TimeColumnSelector};
+
+// ---
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(super) enum OutputFormat {
+    /// Apache Parquet.
+    Parquet,
+
+    /// Arrow IPC file format.
+    Ipc,
+
+    /// Comma-separated values.
+    ///
+    /// Nested columns (e.g. lists of instances) are written in their textual representation.
+    Csv,
+}
+
+impl OutputFormat {
+    /// The format explicitly asked for, else the one matching the output path, else Parquet.
+    fn resolve(format: Option<Self>, path_to_output: Option<&str>) -> Self {
+        format
+            .or_else(|| path_to_output.and_then(Self::from_path))
+            .unwrap_or(Self::Parquet)
+    }
+
+    fn from_path(path: &str) -> Option<Self> {
+        let extension = std::path::Path::new(path).extension()?.to_str()?;
+        match extension.to_lowercase().as_str() {
+            "parquet" => Some(Self::Parquet),
+            "arrow" | "arrows" | "ipc" | "feather" => Some(Self::Ipc),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum FillStrategy {
+    /// No sparse filling: missing values are null.
+    None,
+
+    /// Fill missing values with the latest known value.
+    LatestAt,
+
+    /// Fill missing values with the latest known value within the queried range.
+    LatestAtView,
+
+    /// Interpolate missing values between their neighbours, falling back to `latest-at`.
+    Interpolate,
+
+    /// Like `interpolate`, but only using values within the queried range.
+    InterpolateView,
+}
+
+impl From<FillStrategy> for SparseFillStrategy {
+    fn from(strategy: FillStrategy) -> Self {
+        match strategy {
+            FillStrategy::None => Self::None,
+            FillStrategy::LatestAt => Self::LatestAtGlobal,
+            FillStrategy::LatestAtView => Self::LatestAtView,
+            FillStrategy::Interpolate => Self::InterpolateGlobal,
+            FillStrategy::InterpolateView => Self::InterpolateView,
+        }
+    }
+}
+
+#[derive(Debug, Clone, clap::Parser)]
+pub struct QueryCommand {
+    /// Path to the .rrd file to query.
+    path_to_input_rrd: String,
+
+    /// The index (i.e. timeline) to query, e.g. `frame_nr`.
+    ///
+    /// Several indices can be chained with `else` (e.g. `"sensor_time else log_time"`), in which
+    /// case each entity is indexed by the first of them it has data on.
+    ///
+    /// If unspecified, only static data is queried.
+    #[clap(long)]
+    index: Option<String>,
+
+    /// Only keep rows at or after this index value (e.g. `42`, `10s`, `2025-01-01T00:00:00Z`).
+    #[clap(long, requires = "index")]
+    from: Option<String>,
+
+    /// Only keep rows at or before this index value (e.g. `42`, `10s`, `2025-01-01T00:00:00Z`).
+    #[clap(long, requires = "index")]
+    to: Option<String>,
+
+    /// Entity path filter expressions selecting the entities to query, e.g. `/world/**`.
+    ///
+    /// Can be specified more than once. Defaults to all entities.
+    #[clap(long)]
+    contents: Vec<String>,
+
+    /// Columns to return, either index names (`frame_nr`) or `entity_path:component` selectors
+    /// (`/world/points:Points3D:positions`).
+    ///
+    /// Can be specified more than once. Defaults to all the columns of the queried contents.
+    #[clap(long)]
+    select: Vec<String>,
+
+    /// How to fill the missing values of sparse columns.
+    #[clap(long, value_enum, default_value_t = FillStrategy::None)]
+    fill: FillStrategy,
+
+    /// The recording to query, if the file contains more than one.
+    #[clap(long)]
+    recording_id: Option<String>,
+
+    /// Output format. Inferred from the extension of the output path if unspecified, otherwise
+    /// defaults to Parquet.
+    #[clap(long, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// Path to write to. Writes to standard output if unspecified.
+    #[clap(short = 'o', long = "output", value_name = "dst")]
+    path_to_output: Option<String>,
+}
+
+impl QueryCommand {
+    pub fn run(&self) -> anyhow::Result<()> {
+        let Self {
+            path_to_input_rrd,
+            index,
+            from,
+            to,
+            contents,
+            select,
+            fill,
+            recording_id,
+            format,
+            path_to_output,
+        } = self;
+
+        let format = OutputFormat::resolve(*format, path_to_output.as_deref());
+
+        if path_to_output.is_none()
+            && format != OutputFormat::Csv
+            && std::io::stdout().is_terminal()
+        {
+            anyhow::bail!(
+                "refusing to write binary {format:?} data to a terminal, use `-o` or redirect standard output"
+            );
+        }
+
+        let engine = load_recording(path_to_input_rrd, recording_id.as_deref())?;
+
+        let mut indices = index
+            .as_deref()
+            .map(QueryExpression::parse_index_selector)
+            .unwrap_or_default()
+            .into_iter();
+        let filtered_index = indices.next();
+        let filtered_index_fallbacks = indices.collect();
+
+        let parse_time = |value: &String| {
+            TimeCell::from_str(value)
+                .map(|cell| TimeInt::new_temporal(cell.value.get()))
+                .with_context(|| format!("invalid index value: {value:?}"))
+        };
+        let filtered_index_range = if from.is_some() || to.is_some() {
+            Some(AbsoluteTimeRange::new(
+                from.as_ref()
+                    .map(parse_time)
+                    .transpose()?
+                    .unwrap_or(TimeInt::MIN),
+                to.as_ref()
+                    .map(parse_time)
+                    .transpose()?
+                    .unwrap_or(TimeInt::MAX),
+            ))
+        } else {
+            None
+        };
+
+        let view_contents = if contents.is_empty() {
+            None
+        } else {
+            let filter = EntityPathFilter::parse_forgiving(contents.join("\n"));
+            let view_contents: ViewContentsSelector = engine
+                .iter_entity_paths_sorted(&filter)
+                .map(|entity_path| (entity_path, None))
+                .collect();
+            Some(view_contents)
+        };
+
+        let selection = if select.is_empty() {
+            None
+        } else {
+            Some(
+                select
+                    .iter()
+                    .map(|column| parse_column_selector(column))
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+            )
+        };
+
+        let mut query = QueryExpression {
+            view_contents,
+            include_static_columns: if filtered_index.is_none() {
+                re_chunk_store::StaticColumnSelection::StaticOnly
+            } else {
+                re_chunk_store::StaticColumnSelection::Both
+            },
+            filtered_index,
+            filtered_index_fallbacks,
+            filtered_index_range,
+            sparse_fill_strategy: (*fill).into(),
+            selection,
+            ..Default::default()
+        };
+
+        // Static data isn't indexed: don't return empty index columns.
+        if query.is_static() && query.selection.is_none() {
+            query.selection = Some(
+                engine
+                    .schema_for_query(&query)
+                    .components
+                    .into_iter()
+                    .map(|column| ColumnSelector::Component(column.into()))
+                    .collect(),
+            );
+        }
+
+        re_log::debug!("{query:#?}");
+
+        let query_handle = engine.query(query);
+
+        let output: Box<dyn std::io::Write + Send> = if let Some(path) = path_to_output {
+            let file = std::fs::File::create(path)
+                .with_context(|| format!("couldn't create output file {path:?}"))?;
+            Box::new(std::io::BufWriter::new(file))
+        } else {
+            Box::new(std::io::BufWriter::new(std::io::stdout()))
+        };
+
+        let schema = query_handle.schema().clone();
+        let mut writer = BatchWriter::new(format, output, &schema)?;
+
+        let mut num_rows = 0;
+        while let Some(batch) = query_handle.next_row_batch() {
+            num_rows += batch.num_rows();
+            writer.write(&batch)?;
+        }
+        writer.finish()?;
+
+        re_log::info!(
+            "wrote {} rows ({} columns) as {format:?}",
+            re_format::format_uint(num_rows),
+            re_format::format_uint(schema.fields().len()),
+        );
+
+        Ok(())
+    }
+}
+
+/// Loads the single recording to query from the given .rrd file.
+fn load_recording(
+    path_to_input_rrd: &str,
+    recording_id: Option<&str>,
+) -> anyhow::Result<QueryEngine<re_dataframe::StorageEngine>> {
+    let engines = QueryEngine::from_rrd_filepath(&ChunkStoreConfig::DEFAULT, path_to_input_rrd)
+        .with_context(|| format!("couldn't load {path_to_input_rrd:?}"))?;
+
+    let mut recordings = engines
+        .into_iter()
+        .filter(|(store_id, _engine)| store_id.kind() == StoreKind::Recording)
+        .filter(|(store_id, _engine)| {
+            recording_id.is_none_or(|recording_id| store_id.recording_id().as_str() == recording_id)
+        })
+        .collect_vec();
+
+    match recordings.len() {
+        0 => anyhow::bail!("no matching recording found in {path_to_input_rrd:?}"),
+        1 => Ok(recordings.remove(0).1),
+        _ => anyhow::bail!(
+            "{path_to_input_rrd:?} contains several recordings, pick one with `--recording-id`: {}",
+            recordings
+                .iter()
+                .map(|(store_id, _engine)| store_id.recording_id().as_str())
+                .join(", ")
+        ),
+    }
+}
+
+/// Parses either an index name or an `entity_path:component` selector.
+fn parse_column_selector(column: &str) -> anyhow::Result<ColumnSelector> {
+    if column.contains(':') {
+        let selector = ComponentColumnSelector::from_str(column)
+            .with_context(|| format!("invalid column selector: {column:?}"))?;
+        Ok(ColumnSelector::Component(selector))
+    } else {
+        Ok(TimeColumnSelector::from(column).into())
+    }
+}
+
+// ---
+
+pub(super) enum BatchWriter {
+    Parquet(parquet::arrow::ArrowWriter<Box<dyn std::io::Write + Send>>),
+    Ipc(arrow::ipc::writer::FileWriter<Box<dyn std::io::Write + Send>>),
+    Csv(arrow::csv::Writer<Box<dyn std::io::Write + Send>>),
+}
+
+impl BatchWriter {
+    pub(super) fn new(
+        format: OutputFormat,
+        output: Box<dyn std::io::Write + Send>,
+        schema: &SchemaRef,
+    ) -> anyhow::Result<Self> {
+        Ok(match format {
+            OutputFormat::Parquet => Self::Parquet(parquet::arrow::ArrowWriter::try_new(
+                output,
+                schema.clone(),
+                None,
+            )?),
+            OutputFormat::Ipc => {
+                Self::Ipc(arrow::ipc::writer::FileWriter::try_new(output, schema)?)
+            }
+            OutputFormat::Csv => Self::Csv(arrow::csv::Writer::new(output)),
+        })
+    }
+
+    pub(super) fn write(&mut self, batch: &RecordBatch) -> anyhow::Result<()> {
+        match self {
+            Self::Parquet(writer) => writer.write(batch)?,
+            Self::Ipc(writer) => writer.write(batch)?,
+            Self::Csv(writer) => writer.write(&stringify_nested_columns(batch)?)?,
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn finish(self) -> anyhow::Result<()> {
+        match self {
+            Self::Parquet(writer) => {
+                writer.into_inner()?.flush()?;
+            }
+            Self::Ipc(mut writer) => {
+                writer.finish()?;
+                writer.into_inner()?.flush()?;
+            }
+            Self::Csv(writer) => {
+                writer.into_inner().flush()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// CSV cannot represent nested data: replace these columns with their textual representation.
+fn stringify_nested_columns(batch: &RecordBatch) -> anyhow::Result<RecordBatch> {
+    let options = arrow::util::display::FormatOptions::default().with_null("");
+
+    let (fields, columns): (Vec<Field>, Vec<ArrayRef>) = batch
+        .schema()
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(|(field, column)| {
+            if !field.data_type().is_nested() {
+                return Ok((field.as_ref().clone(), column.clone()));
+            }
+
+            let formatter = arrow::util::display::ArrayFormatter::try_new(column, &options)?;
+            let values: StringArray = (0..column.len())
+                .map(|idx| {
+                    use arrow::array::Array as _;
+                    column
+                        .is_valid(idx)
+                        .then(|| formatter.value(idx).to_string())
+                })
+                .collect();
+
+            Ok((
+                Field::new(field.name(), DataType::Utf8, true),
+                std::sync::Arc::new(values) as ArrayRef,
+            ))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+
+    Ok(RecordBatch::try_new_with_options(
+        std::sync::Arc::new(Schema::new_with_metadata(fields, Default::default())),
+        columns,
+        &RecordBatchOptions::new().with_row_count(Some(batch.num_rows())),
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Array as _, Int64Array, ListArray};
+    use arrow::datatypes::Int32Type;
+
+    use super::*;
+
+    #[test]
+    fn output_format_inference() {
+        assert_eq!(
+            OutputFormat::resolve(None, Some("out.parquet")),
+            OutputFormat::Parquet
+        );
+        assert_eq!(
+            OutputFormat::resolve(None, Some("dir.v2/out.CSV")),
+            OutputFormat::Csv
+        );
+        for path in ["out.arrow", "out.arrows", "out.ipc", "out.feather"] {
+            assert_eq!(OutputFormat::resolve(None, Some(path)), OutputFormat::Ipc);
+        }
+
+        // Unknown extensions and standard output default to Parquet.
+        assert_eq!(
+            OutputFormat::resolve(None, Some("out.txt")),
+            OutputFormat::Parquet
+        );
+        assert_eq!(
+            OutputFormat::resolve(None, Some("out")),
+            OutputFormat::Parquet
+        );
+        assert_eq!(OutputFormat::resolve(None, None), OutputFormat::Parquet);
+
+        // An explicit format always wins.
+        assert_eq!(
+            OutputFormat::resolve(Some(OutputFormat::Csv), Some("out.parquet")),
+            OutputFormat::Csv
+        );
+        assert_eq!(
+            OutputFormat::resolve(Some(OutputFormat::Ipc), None),
+            OutputFormat::Ipc
+        );
+    }
+
+    #[test]
+    fn column_selectors() {
+        assert_eq!(
+            parse_column_selector("frame_nr").unwrap(),
+            ColumnSelector::Time(TimeColumnSelector::from("frame_nr"))
+        );
+
+        let ColumnSelector::Component(selector) =
+            parse_column_selector("/world/points:Points3D:positions").unwrap()
+        else {
+            panic!("expected a component column selector");
+        };
+        assert_eq!(selector.entity_path, "/world/points".into());
+        assert_eq!(selector.component, "Points3D:positions");
+    }
+
+    #[test]
+    fn csv_stringifies_nested_columns() {
+        let batch = RecordBatch::try_from_iter([
+            (
+                "frame_nr",
+                Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+            ),
+            (
+                "/points:Points3D:positions",
+                Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>([
+                    Some(vec![Some(1), Some(2)]),
+                    None,
+                    Some(vec![]),
+                ])),
+            ),
+        ])
+        .unwrap();
+
+        let stringified = stringify_nested_columns(&batch).unwrap();
+
+        // Flat columns are left untouched.
+        assert_eq!(stringified.schema().field(0), batch.schema().field(0));
+        assert_eq!(stringified.column(0), batch.column(0));
+
+        let field = stringified.schema().field(1).clone();
+        assert_eq!(field.name(), "/points:Points3D:positions");
+        assert_eq!(field.data_type(), &DataType::Utf8);
+
+        let values = stringified
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(values.value(0), "[1, 2]");
+        assert!(values.is_null(1));
+        assert_eq!(values.value(2), "[]");
+
+        let mut csv = Vec::new();
+        {
+            let mut writer = arrow::csv::Writer::new(&mut csv);
+            writer.write(&stringified).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "frame_nr,/points:Points3D:positions\n1,\"[1, 2]\"\n,\n3,[]\n"
+        );
+    }
+}
@@ -0,0 +1,490 @@
+use std::sync::Arc;
+
+use arrow::array::{Array as _, ListArray, StringArray};
+
+use re_chunk::{Chunk, ChunkId, ChunkResult, RowId};
+use re_log_encoding::{ToApplication as _, ToTransport as _};
+use re_log_types::{EntityPath, EntityPathPart, StoreId, StoreKind};
+use re_types::{
+    Archetype as _, Component as _, SerializedComponentColumn,
+    blueprint::{
+        archetypes::ViewContents,
+        components::{QueryExpression, ViewOrigin},
+    },
+};
+
+use crate::commands::stdio::InputSource;
+
+// ---
+
+/// A single entity path remapping rule, as passed to `--remap-entity`.
+///
+/// Either an exact mapping (`/camera -> /robot_a/camera`), or a recursive prefix mapping
+/// (`/camera/** -> /robot_a/camera/**`) that applies to the path and all of its descendants.
+///
+/// `/** -> /robot_a/**` prefixes every entity.
+///
+/// The target may contain an `{input}` placeholder, which is replaced by the name of the input
+/// file the data comes from (without extension), e.g. `/** -> /{input}/**`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityPathRemap {
+    from: EntityPath,
+    to: EntityPath,
+    recursive: bool,
+}
+
+impl std::str::FromStr for EntityPathRemap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((from, to)) = s.split_once("->") else {
+            return Err(format!(
+                "expected a rule of the form `<from> -> <to>` (e.g. `/camera/** -> /robot_a/camera/**`), got {s:?}"
+            ));
+        };
+
+        let (from, from_recursive) = split_recursive_suffix(from.trim());
+        let (to, to_recursive) = split_recursive_suffix(to.trim());
+
+        if from_recursive != to_recursive {
+            return Err(format!(
+                "either both or neither sides of {s:?} must end with `/**`"
+            ));
+        }
+
+        let parse = |path: &str| {
+            EntityPath::parse_strict(path)
+                .map_err(|err| format!("invalid entity path {path:?}: {err}"))
+        };
+
+        // The placeholder is kept around as a path part of its own, see `EntityPathRemaps::for_input`.
+        let to = to.replace(INPUT_PLACEHOLDER, r"\{input\}");
+
+        Ok(Self {
+            from: parse(from)?,
+            to: parse(&to)?,
+            recursive: from_recursive,
+        })
+    }
+}
+
+impl EntityPathRemap {
+    fn apply(&self, entity_path: &EntityPath) -> Option<EntityPath> {
+        if self.recursive {
+            entity_path
+                .strip_prefix(&self.from)
+                .map(|rest| self.to.join(&rest))
+        } else {
+            (entity_path == &self.from).then(|| self.to.clone())
+        }
+    }
+}
+
+/// Replaced by the name of the input in the target of a rule.
+const INPUT_PLACEHOLDER: &str = "{input}";
+
+/// Splits `/foo/**` into `("/foo", true)`, and `/**` into `("/", true)`.
+fn split_recursive_suffix(path: &str) -> (&str, bool) {
+    if path == "/**" || path == "**" {
+        ("/", true)
+    } else if let Some(path) = path.strip_suffix("/**") {
+        (path, true)
+    } else {
+        (path, false)
+    }
+}
+
+// ---
+
+/// An ordered set of [`EntityPathRemap`] rules: the first rule that matches a path wins.
+///
+/// Reserved entity paths (e.g. `/__properties`) are never remapped.
+///
+/// In blueprint stores, remapping applies to every reference to a recording entity instead:
+/// view origins, view contents query expressions and per-entity overrides.
+#[derive(Debug, Clone, Default)]
+pub struct EntityPathRemaps {
+    rules: Vec<EntityPathRemap>,
+}
+
+impl EntityPathRemaps {
+    pub fn new(rules: Vec<EntityPathRemap>) -> Self {
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Returns the rules to apply to data coming from `input`, i.e. with all `{input}`
+    /// placeholders resolved.
+    pub fn for_input(&self, input: &InputSource) -> Self {
+        let name = match input {
+            InputSource::Stdin => "stdin".to_owned(),
+            InputSource::File(path) => path
+                .file_stem()
+                .map_or_else(|| path.to_string_lossy(), |stem| stem.to_string_lossy())
+                .into_owned(),
+        };
+
+        let rules = self
+            .rules
+            .iter()
+            .map(|rule| EntityPathRemap {
+                to: rule
+                    .to
+                    .iter()
+                    .map(|part| {
+                        if part.unescaped_str() == INPUT_PLACEHOLDER {
+                            EntityPathPart::from(name.as_str())
+                        } else {
+                            part.clone()
+                        }
+                    })
+                    .collect(),
+                ..rule.clone()
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Returns the remapped path, or `None` if no rule applies.
+    fn remap_entity_path(&self, entity_path: &EntityPath) -> Option<EntityPath> {
+        if entity_path.is_reserved() {
+            return None;
+        }
+
+        self.rules.iter().find_map(|rule| rule.apply(entity_path))
+    }
+
+    /// Remaps the paths of a (possibly multi-line) entity path filter expression, e.g. `+ /camera/**`.
+    ///
+    /// Expressions that rely on variable substitution (e.g. `$origin/**`) are left untouched.
+    fn remap_query_expression(&self, expression: &str) -> String {
+        expression
+            .lines()
+            .map(|line| {
+                let trimmed = line.trim();
+                let (effect, pattern) = match trimmed.chars().next() {
+                    Some(c @ ('+' | '-')) => (Some(c), trimmed[1..].trim()),
+                    _ => (None, trimmed),
+                };
+
+                let (path, recursive) = split_recursive_suffix(pattern);
+                if !path.starts_with('/') {
+                    return line.to_owned();
+                }
+
+                let Some(remapped) = self.remap_entity_path(&EntityPath::parse_forgiving(path))
+                else {
+                    return line.to_owned();
+                };
+
+                let pattern = match (recursive, remapped.is_root()) {
+                    (true, true) => "/**".to_owned(),
+                    (true, false) => format!("{remapped}/**"),
+                    (false, _) => remapped.to_string(),
+                };
+
+                match effect {
+                    Some(effect) => format!("{effect} {pattern}"),
+                    None => pattern,
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Remaps a blueprint entity path, which only ever refers to a recording entity when it
+    /// holds per-entity overrides, i.e. `/view/<id>/ViewContents/overrides/<entity>`.
+    fn remap_blueprint_entity_path(&self, entity_path: &EntityPath) -> Option<EntityPath> {
+        let parts = entity_path.as_slice();
+        let is_override_path = parts.len() >= 4
+            && parts[0].unescaped_str() == "view"
+            && parts[2].unescaped_str() == ViewContents::name().short_name()
+            && parts[3].unescaped_str() == "overrides";
+        if !is_override_path {
+            return None;
+        }
+
+        let prefix = EntityPath::new(parts[..4].to_vec());
+        let entity = EntityPath::new(parts[4..].to_vec());
+        self.remap_entity_path(&entity)
+            .map(|entity| prefix.join(&entity))
+    }
+
+    /// Returns the remapped chunk, or `None` if no rule applies to it.
+    ///
+    /// The remapped chunk always gets a new [`ChunkId`], and new [`RowId`]s if its entity path
+    /// changed: otherwise, merging the same data under different prefixes would result in duplicate
+    /// ids, and all but the first copy would be dropped by the store.
+    pub fn remap_chunk(&self, chunk: &Chunk, store_kind: StoreKind) -> ChunkResult<Option<Chunk>> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+
+        match store_kind {
+            StoreKind::Recording => {
+                Ok(self
+                    .remap_entity_path(chunk.entity_path())
+                    .map(|entity_path| {
+                        chunk
+                            .clone_as(ChunkId::new(), RowId::new())
+                            .with_entity_path(entity_path)
+                    }))
+            }
+
+            StoreKind::Blueprint => {
+                let entity_path = self.remap_blueprint_entity_path(chunk.entity_path());
+
+                let mut components = chunk.components().clone();
+                let mut any_component_remapped = false;
+                for column in chunk.components().values() {
+                    let Some(component_type) = column.descriptor.component_type else {
+                        continue;
+                    };
+
+                    let list_array = if component_type == QueryExpression::name() {
+                        map_strings(&column.list_array, |s| self.remap_query_expression(s))
+                    } else if component_type == ViewOrigin::name() {
+                        map_strings(&column.list_array, |s| {
+                            self.remap_entity_path(&EntityPath::parse_forgiving(s))
+                                .map_or_else(|| s.to_owned(), |path| path.to_string())
+                        })
+                    } else {
+                        continue;
+                    };
+
+                    if let Some(list_array) = list_array?
+                        && list_array != column.list_array
+                    {
+                        components.insert(SerializedComponentColumn::new(
+                            list_array,
+                            column.descriptor.clone(),
+                        ));
+                        any_component_remapped = true;
+                    }
+                }
+
+                if entity_path.is_none() && !any_component_remapped {
+                    return Ok(None);
+                }
+
+                let is_moved = entity_path.is_some();
+                let remapped = Chunk::new(
+                    ChunkId::new(),
+                    entity_path.unwrap_or_else(|| chunk.entity_path().clone()),
+                    Some(chunk.is_sorted()),
+                    chunk.row_ids_array().clone(),
+                    chunk.timelines().clone(),
+                    components,
+                )?;
+
+                Ok(Some(if is_moved {
+                    remapped.clone_as(remapped.id(), RowId::new())
+                } else {
+                    remapped
+                }))
+            }
+        }
+    }
+
+    /// Remaps the chunk carried by a transport-level message in place.
+    ///
+    /// Only the payload of the message is re-encoded, and only if a rule applies to it.
+    pub fn remap_transport_arrow_msg(
+        &self,
+        msg: &mut re_protos::log_msg::v1alpha1::ArrowMsg,
+    ) -> anyhow::Result<()> {
+        use re_protos::common::v1alpha1::StoreKind as ProtoStoreKind;
+
+        let store_kind = match msg.store_id.as_ref().map(|store_id| store_id.kind()) {
+            Some(ProtoStoreKind::Blueprint) => StoreKind::Blueprint,
+            Some(ProtoStoreKind::Recording | ProtoStoreKind::Unspecified) | None => {
+                StoreKind::Recording
+            }
+        };
+
+        let chunk = Chunk::from_arrow_msg(&msg.to_application(())?)?;
+        let Some(chunk) = self.remap_chunk(&chunk, store_kind)? else {
+            return Ok(());
+        };
+
+        // The store ID is only used to fill in the header of the transport message, which we
+        // then discard in favor of the original one: it is never decoded from the payload.
+        let remapped = chunk
+            .to_arrow_msg()?
+            .to_transport((StoreId::empty_recording(), msg.compression().into()))?;
+
+        *msg = re_protos::log_msg::v1alpha1::ArrowMsg {
+            store_id: msg.store_id.take(),
+            ..remapped
+        };
+
+        Ok(())
+    }
+}
+
+/// Applies `f` to every string of a list of UTF-8 strings.
+///
+/// Returns `None` if the data is not a list of UTF-8 strings.
+fn map_strings(
+    list_array: &ListArray,
+    mut f: impl FnMut(&str) -> String,
+) -> ChunkResult<Option<ListArray>> {
+    let Some(strings) = list_array.values().as_any().downcast_ref::<StringArray>() else {
+        return Ok(None);
+    };
+
+    let strings: StringArray = strings.iter().map(|s| s.map(&mut f)).collect();
+
+    let (field, offsets, _values, nulls) = list_array.clone().into_parts();
+    Ok(Some(ListArray::try_new(
+        field,
+        offsets,
+        Arc::new(strings),
+        nulls,
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn remaps(rules: &[&str]) -> EntityPathRemaps {
+        EntityPathRemaps::new(rules.iter().map(|rule| rule.parse().unwrap()).collect())
+    }
+
+    fn remap(remaps: &EntityPathRemaps, entity_path: &str) -> Option<String> {
+        remaps
+            .remap_entity_path(&EntityPath::from(entity_path))
+            .map(|entity_path| entity_path.to_string())
+    }
+
+    #[test]
+    fn parse_rules() {
+        assert_eq!(
+            "/camera -> /robot_a/camera".parse::<EntityPathRemap>(),
+            Ok(EntityPathRemap {
+                from: EntityPath::from("/camera"),
+                to: EntityPath::from("/robot_a/camera"),
+                recursive: false,
+            })
+        );
+        assert_eq!(
+            "  /camera/**->/robot_a/camera/**  ".parse::<EntityPathRemap>(),
+            Ok(EntityPathRemap {
+                from: EntityPath::from("/camera"),
+                to: EntityPath::from("/robot_a/camera"),
+                recursive: true,
+            })
+        );
+        assert_eq!(
+            "/** -> /robot_a/**".parse::<EntityPathRemap>(),
+            Ok(EntityPathRemap {
+                from: EntityPath::root(),
+                to: EntityPath::from("/robot_a"),
+                recursive: true,
+            })
+        );
+
+        assert!("/camera".parse::<EntityPathRemap>().is_err());
+        assert!("/camera/** -> /robot_a".parse::<EntityPathRemap>().is_err());
+        assert!("/camera -> /robot_a/**".parse::<EntityPathRemap>().is_err());
+        assert!("/camera -> /robot a".parse::<EntityPathRemap>().is_err());
+    }
+
+    #[test]
+    fn input_placeholder() {
+        let rule: EntityPathRemap = "/** -> /robots/{input}/**".parse().unwrap();
+
+        // The placeholder survives parsing as a path part of its own…
+        let parts = rule.to.as_slice();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[1].unescaped_str(), INPUT_PLACEHOLDER);
+
+        // …and gets resolved per input.
+        let remaps = EntityPathRemaps::new(vec![rule]);
+
+        let from_file = remaps.for_input(&InputSource::File(PathBuf::from("data/robot_a.rrd")));
+        assert_eq!(
+            remap(&from_file, "/camera/image").as_deref(),
+            Some("/robots/robot_a/camera/image")
+        );
+
+        let from_stdin = remaps.for_input(&InputSource::Stdin);
+        assert_eq!(
+            remap(&from_stdin, "/camera").as_deref(),
+            Some("/robots/stdin/camera")
+        );
+    }
+
+    #[test]
+    fn remap_entity_paths() {
+        let remaps = remaps(&[
+            "/camera -> /robot_a/cam",
+            "/camera/** -> /robot_a/camera/**",
+            "/** -> /other/**",
+        ]);
+
+        // The first matching rule wins.
+        assert_eq!(remap(&remaps, "/camera").as_deref(), Some("/robot_a/cam"));
+        assert_eq!(
+            remap(&remaps, "/camera/image").as_deref(),
+            Some("/robot_a/camera/image")
+        );
+        assert_eq!(remap(&remaps, "/lidar").as_deref(), Some("/other/lidar"));
+
+        // Reserved paths are left alone.
+        assert_eq!(remap(&remaps, "/__properties"), None);
+
+        let remaps = self::remaps(&["/camera -> /robot_a/camera"]);
+        assert_eq!(remap(&remaps, "/camera/image"), None);
+    }
+
+    #[test]
+    fn remap_query_expressions() {
+        let remaps = remaps(&["/camera/** -> /robot_a/camera/**", "/robot_b/** -> /**"]);
+
+        assert_eq!(
+            remaps.remap_query_expression("+ /camera/**\n-/camera/depth\n+ /lidar/**"),
+            "+ /robot_a/camera/**\n- /robot_a/camera/depth\n+ /lidar/**"
+        );
+        assert_eq!(remaps.remap_query_expression("/camera"), "/robot_a/camera");
+        assert_eq!(remaps.remap_query_expression("+ /robot_b/**"), "+ /**");
+        assert_eq!(remaps.remap_query_expression("+ /robot_b/arm"), "+ /arm");
+
+        // Variable substitutions are left untouched.
+        assert_eq!(
+            remaps.remap_query_expression("+ $origin/**"),
+            "+ $origin/**"
+        );
+    }
+
+    #[test]
+    fn remap_blueprint_entity_paths() {
+        let remaps = remaps(&["/camera/** -> /robot_a/camera/**"]);
+
+        let remap_blueprint = |entity_path: &str| {
+            remaps
+                .remap_blueprint_entity_path(&EntityPath::from(entity_path))
+                .map(|entity_path| entity_path.to_string())
+        };
+
+        assert_eq!(
+            remap_blueprint("/view/1234/ViewContents/overrides/camera/image").as_deref(),
+            Some("/view/1234/ViewContents/overrides/robot_a/camera/image")
+        );
+        assert_eq!(
+            remap_blueprint("/view/1234/ViewContents/overrides/lidar"),
+            None
+        );
+
+        // Not an override: the path is part of the blueprint itself.
+        assert_eq!(remap_blueprint("/camera/image"), None);
+        assert_eq!(remap_blueprint("/view/1234/ViewContents"), None);
+    }
+}
@@ -0,0 +1,519 @@
+use std::io::{IsTerminal as _, Read as _, Write as _};
+
+use anyhow::Context as _;
+use itertools::Either;
+
+use re_build_info::CrateVersion;
+use re_log_encoding::rrd::{Decodable as _, MessageHeader, MessageKind, StreamHeader};
+use re_log_encoding::{CachingApplicationIdInjector, DecoderEntrypoint as _};
+use re_log_types::LogMsg;
+
+// ---
+
+#[derive(Debug, Clone, clap::Parser)]
+pub struct RepairCommand {
+    /// Path to the damaged .rrd/.rbl file.
+    path_to_input_rrd: String,
+
+    /// Path to write to. Writes to standard output if unspecified.
+    #[arg(short = 'o', long = "output", value_name = "dst.(rrd|rbl)")]
+    path_to_output_rrd: Option<String>,
+}
+
+impl RepairCommand {
+    pub fn run(&self) -> anyhow::Result<()> {
+        let Self {
+            path_to_input_rrd,
+            path_to_output_rrd,
+        } = self;
+
+        if path_to_output_rrd.is_none() {
+            anyhow::ensure!(
+                !std::io::stdout().is_terminal(),
+                "you must redirect the output to a file and/or stream"
+            );
+        }
+
+        let now = std::time::Instant::now();
+        re_log::info!(src = ?path_to_input_rrd, "repair started");
+
+        let rrd_in = std::fs::File::open(path_to_input_rrd)
+            .with_context(|| format!("couldn't open {path_to_input_rrd:?}"))?;
+        let rrd_in_size = rrd_in
+            .metadata()
+            .with_context(|| format!("couldn't read {path_to_input_rrd:?}"))?
+            .len();
+
+        let mut rrd_out = if let Some(path) = path_to_output_rrd.as_ref() {
+            Either::Left(std::io::BufWriter::new(
+                std::fs::File::create(path).with_context(|| format!("{path:?}"))?,
+            ))
+        } else {
+            Either::Right(std::io::BufWriter::new(std::io::stdout().lock()))
+        };
+
+        let mut num_messages = 0;
+        let mut rrd_out_size = 0;
+        let damaged = {
+            let version = CrateVersion::LOCAL;
+            let options = re_log_encoding::rrd::EncodingOptions::PROTOBUF_COMPRESSED;
+            let mut encoder = re_log_encoding::Encoder::new_eager(version, options, &mut rrd_out)
+                .context("encoding failure")?;
+
+            let damaged = salvage(rrd_in, rrd_in_size, |msg| {
+                num_messages += 1;
+                rrd_out_size += encoder.append(&msg).context("encoding failure")?;
+                Ok(())
+            })
+            .with_context(|| format!("couldn't repair {path_to_input_rrd:?}"))?;
+
+            encoder.finish().context("encoding failure")?;
+            damaged
+        };
+        rrd_out.flush().context("couldn't flush output")?;
+
+        for region in &damaged {
+            eprintln!(
+                "{path_to_input_rrd}: lost bytes {}..{} ({}): {}",
+                region.start,
+                region.end,
+                re_format::format_bytes(region.len() as _),
+                region.reason,
+            );
+        }
+
+        let num_bytes_lost: u64 = damaged.iter().map(DamagedRegion::len).sum();
+        if damaged.is_empty() {
+            eprintln!("{path_to_input_rrd}: no damage found, {num_messages} messages recovered.");
+        } else {
+            eprintln!(
+                "{path_to_input_rrd}: {num_messages} messages recovered, {} damaged regions ({} lost).",
+                damaged.len(),
+                re_format::format_bytes(num_bytes_lost as _),
+            );
+        }
+
+        re_log::info!(
+            src_size_bytes = %re_format::format_bytes(rrd_in_size as _),
+            dst_size_bytes = %re_format::format_bytes(rrd_out_size as _),
+            time = ?now.elapsed(),
+            "repair finished"
+        );
+
+        Ok(())
+    }
+}
+
+// ---
+
+/// A contiguous range of input bytes that could not be recovered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DamagedRegion {
+    start: u64,
+    end: u64,
+
+    /// Why the first byte of the region could not be decoded.
+    reason: String,
+}
+
+impl DamagedRegion {
+    fn len(&self) -> u64 {
+        self.end - self.start
+    }
+}
+
+/// What was found at a given position of the input.
+enum Item {
+    StreamHeader { version: CrateVersion },
+    Message(LogMsg),
+    End,
+}
+
+/// A sliding window over the input, so that only the bytes currently being looked at are kept
+/// in memory.
+struct Window<R> {
+    reader: R,
+
+    /// The total size of the input, in bytes.
+    input_len: u64,
+
+    /// The buffered bytes, starting at [`Self::start`] in the input.
+    data: Vec<u8>,
+    start: u64,
+}
+
+impl<R: std::io::Read> Window<R> {
+    /// Past that many bytes of already consumed data, the buffer gets compacted.
+    const MAX_CONSUMED_BYTES: usize = 1024 * 1024;
+
+    /// How many bytes to read from the input at a time, at minimum.
+    const READ_SIZE: usize = 64 * 1024;
+
+    fn new(reader: R, input_len: u64) -> Self {
+        Self {
+            reader,
+            input_len,
+            data: Vec::new(),
+            start: 0,
+        }
+    }
+
+    /// How many bytes of input are left, starting at `pos`.
+    fn remaining(&self, pos: u64) -> u64 {
+        self.input_len.saturating_sub(pos)
+    }
+
+    /// Returns the `len` bytes of input starting at `pos`, or `None` if the input ends before that.
+    ///
+    /// `pos` must not precede the last position given to [`Self::consume_until`].
+    fn get(&mut self, pos: u64, len: usize) -> std::io::Result<Option<&[u8]>> {
+        debug_assert!(self.start <= pos);
+
+        if self.remaining(pos) < len as u64 {
+            // Never even try to read past the end of the input: `len` might come from a corrupt header.
+            return Ok(None);
+        }
+
+        let begin = (pos - self.start) as usize;
+        let end = begin + len;
+        while self.data.len() < end {
+            let num_to_read = (end - self.data.len()).max(Self::READ_SIZE);
+            let num_read = (&mut self.reader)
+                .take(num_to_read as u64)
+                .read_to_end(&mut self.data)?;
+            if num_read == 0 {
+                return Ok(None); // the input is shorter than advertised
+            }
+        }
+
+        Ok(Some(&self.data[begin..end]))
+    }
+
+    /// Lets the window know that the input before `pos` won't be looked at anymore.
+    fn consume_until(&mut self, pos: u64) {
+        let num_consumed = (pos.saturating_sub(self.start) as usize).min(self.data.len());
+        if num_consumed > Self::MAX_CONSUMED_BYTES {
+            self.data.drain(..num_consumed);
+            self.start += num_consumed as u64;
+        }
+    }
+}
+
+/// Walks through the input the same way the regular decoder does, except that it never gives up.
+///
+/// Whenever something cannot be decoded (garbage, truncated or corrupted message…), the
+/// corresponding bytes are recorded as damaged and we resynchronize on the next position that
+/// holds either a valid [`StreamHeader`] or a valid [`MessageHeader`] followed by a fully
+/// decodable message.
+///
+/// Every message that could be fully decoded is handed over to `on_message`, in stream order.
+/// Returns every range of bytes that had to be skipped, in stream order.
+fn salvage(
+    reader: impl std::io::Read,
+    input_len: u64,
+    mut on_message: impl FnMut(LogMsg) -> anyhow::Result<()>,
+) -> anyhow::Result<Vec<DamagedRegion>> {
+    re_tracing::profile_function!();
+
+    let mut window = Window::new(reader, input_len);
+    let mut damaged: Vec<DamagedRegion> = Vec::new();
+
+    let mut app_id_injector = CachingApplicationIdInjector::default();
+    let mut version = None;
+    let mut is_in_stream = false;
+
+    // Start and reason of the damaged region we're currently skipping over, if any.
+    let mut damaged_start: Option<(u64, String)> = None;
+
+    let mut pos = 0;
+    while pos < input_len {
+        window.consume_until(pos);
+
+        let is_resyncing = damaged_start.is_some();
+
+        let result = if is_resyncing {
+            // While resynchronizing, we must be strict: a run of zeroes looks a lot like a
+            // valid end-of-stream marker, and random bytes can look like a valid message header.
+            match try_stream_header(&mut window, pos)? {
+                Ok(found) => Ok(found),
+                Err(_) => match try_message(&mut window, pos, version, &mut app_id_injector)? {
+                    Ok((Item::End, _)) => Err("end-of-stream marker".to_owned()),
+                    found => found,
+                },
+            }
+        } else if is_in_stream {
+            try_message(&mut window, pos, version, &mut app_id_injector)?
+        } else {
+            try_stream_header(&mut window, pos)?
+        };
+
+        match result {
+            Ok((item, len)) => {
+                if let Some((start, reason)) = damaged_start.take() {
+                    damaged.push(DamagedRegion {
+                        start,
+                        end: pos,
+                        reason,
+                    });
+                }
+
+                match item {
+                    Item::StreamHeader {
+                        version: new_version,
+                    } => {
+                        version = Some(new_version);
+                        is_in_stream = true;
+                    }
+
+                    Item::Message(msg) => {
+                        on_message(msg)?;
+                        is_in_stream = true;
+                    }
+
+                    Item::End => is_in_stream = false,
+                }
+
+                pos += len;
+            }
+
+            Err(reason) => {
+                if damaged_start.is_none() {
+                    damaged_start = Some((pos, reason));
+                }
+                pos += 1;
+            }
+        }
+    }
+
+    if let Some((start, reason)) = damaged_start {
+        damaged.push(DamagedRegion {
+            start,
+            end: input_len,
+            reason,
+        });
+    }
+
+    Ok(damaged)
+}
+
+/// What was found at a given position of the input, along with the number of bytes it spans,
+/// or why nothing valid could be found there.
+///
+/// Only I/O errors are returned as actual errors.
+type TryResult = std::io::Result<Result<(Item, u64), String>>;
+
+/// Tries to decode a [`StreamHeader`] at `pos`.
+fn try_stream_header(window: &mut Window<impl std::io::Read>, pos: u64) -> TryResult {
+    let Some(header_data) = window.get(pos, StreamHeader::ENCODED_SIZE_BYTES)? else {
+        return Ok(Err("truncated stream header".to_owned()));
+    };
+
+    let version = match StreamHeader::from_rrd_bytes(header_data)
+        .and_then(|header| header.to_version_and_options())
+    {
+        Ok((version, _options)) => version,
+        Err(err) => return Ok(Err(format!("invalid stream header: {err}"))),
+    };
+
+    Ok(Ok((
+        Item::StreamHeader { version },
+        StreamHeader::ENCODED_SIZE_BYTES as u64,
+    )))
+}
+
+/// Tries to decode a [`MessageHeader`] and its message at `pos`.
+fn try_message(
+    window: &mut Window<impl std::io::Read>,
+    pos: u64,
+    version: Option<CrateVersion>,
+    app_id_injector: &mut CachingApplicationIdInjector,
+) -> TryResult {
+    let Some(header_data) = window.get(pos, MessageHeader::ENCODED_SIZE_BYTES)? else {
+        return Ok(Err("truncated message header".to_owned()));
+    };
+
+    let header = match MessageHeader::from_rrd_bytes(header_data) {
+        Ok(header) => header,
+        Err(err) => return Ok(Err(format!("invalid message header: {err}"))),
+    };
+
+    let header_end = pos + MessageHeader::ENCODED_SIZE_BYTES as u64;
+    let num_bytes_left = window.remaining(header_end);
+    let message_data = match usize::try_from(header.len) {
+        Ok(len) => window.get(header_end, len)?,
+        Err(_) => None,
+    };
+    let Some(message_data) = message_data else {
+        return Ok(Err(format!(
+            "truncated {:?} message (expected {} bytes but only {num_bytes_left} are left)",
+            header.kind, header.len,
+        )));
+    };
+
+    let len = MessageHeader::ENCODED_SIZE_BYTES as u64 + header.len;
+
+    if header.kind == MessageKind::End {
+        // The payload of end-of-stream markers (e.g. footers) is not needed to rebuild the recording.
+        return Ok(Ok((Item::End, len)));
+    }
+
+    let byte_span = re_chunk::Span {
+        start: header_end,
+        len: header.len,
+    };
+    Ok(
+        match LogMsg::decode(
+            bytes::Bytes::copy_from_slice(message_data),
+            byte_span,
+            header.kind,
+            app_id_injector,
+            version,
+        ) {
+            Ok(Some(msg)) => Ok((Item::Message(msg), len)),
+            Ok(None) => Ok((Item::End, len)),
+            Err(err) => Err(format!("corrupt {:?} message: {err}", header.kind)),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use re_chunk::{Chunk, RowId, TimePoint, Timeline};
+    use re_log_encoding::rrd::EncodingOptions;
+    use re_log_types::example_components::{MyPoint, MyPoints};
+    use re_log_types::{SetStoreInfo, StoreInfo};
+
+    use super::*;
+
+    /// A `SetStoreInfo` followed by a few chunks, along with the byte offset of each message.
+    fn encoded_messages() -> (Vec<u8>, Vec<u64>) {
+        let info = StoreInfo::testing();
+        let store_id = info.store_id.clone();
+
+        let mut messages = vec![LogMsg::SetStoreInfo(SetStoreInfo {
+            row_id: *RowId::new(),
+            info,
+        })];
+        for frame in 0..4 {
+            let chunk = Chunk::builder("points")
+                .with_component_batches(
+                    RowId::new(),
+                    TimePoint::default().with(Timeline::new_sequence("frame"), frame),
+                    [(
+                        MyPoints::descriptor_points(),
+                        &MyPoint::from_iter(0..10) as _,
+                    )],
+                )
+                .build()
+                .unwrap();
+            messages.push(LogMsg::ArrowMsg(
+                store_id.clone(),
+                chunk.to_arrow_msg().unwrap(),
+            ));
+        }
+
+        let mut data = Vec::new();
+        let mut offsets = Vec::new();
+        {
+            let mut encoder = re_log_encoding::Encoder::new_eager(
+                CrateVersion::LOCAL,
+                EncodingOptions::PROTOBUF_COMPRESSED,
+                &mut data,
+            )
+            .unwrap();
+            let mut offset = StreamHeader::ENCODED_SIZE_BYTES as u64;
+            for msg in &messages {
+                offsets.push(offset);
+                offset += encoder.append(msg).unwrap();
+            }
+            encoder.finish().unwrap();
+        }
+
+        (data, offsets)
+    }
+
+    fn run_salvage(data: &[u8]) -> (Vec<LogMsg>, Vec<DamagedRegion>) {
+        let mut messages = Vec::new();
+        let damaged = salvage(data, data.len() as u64, |msg| {
+            messages.push(msg);
+            Ok(())
+        })
+        .unwrap();
+        (messages, damaged)
+    }
+
+    #[test]
+    fn undamaged() {
+        let (data, offsets) = encoded_messages();
+
+        let (messages, damaged) = run_salvage(&data);
+        assert_eq!(offsets.len(), messages.len());
+        assert_eq!(Vec::<DamagedRegion>::new(), damaged);
+    }
+
+    #[test]
+    fn truncated_tail() {
+        let (mut data, offsets) = encoded_messages();
+        let (expected, _) = run_salvage(&data);
+
+        // Cut the last message short.
+        let last_offset = offsets[offsets.len() - 1];
+        data.truncate(last_offset as usize + MessageHeader::ENCODED_SIZE_BYTES + 8);
+
+        let (messages, damaged) = run_salvage(&data);
+        assert_eq!(expected[..expected.len() - 1], messages[..]);
+
+        assert_eq!(1, damaged.len());
+        assert_eq!(last_offset, damaged[0].start);
+        assert_eq!(data.len() as u64, damaged[0].end);
+        assert!(
+            damaged[0].reason.starts_with("truncated"),
+            "{}",
+            damaged[0].reason
+        );
+    }
+
+    #[test]
+    fn corrupt_message_resync() {
+        let (mut data, offsets) = encoded_messages();
+        let (expected, _) = run_salvage(&data);
+
+        // Garble the header of the third message: decoding must pick up again at the fourth one.
+        let start = offsets[2] as usize;
+        data[start..start + MessageHeader::ENCODED_SIZE_BYTES].fill(0xFF);
+
+        let (messages, damaged) = run_salvage(&data);
+        assert_eq!([&expected[..2], &expected[3..]].concat(), messages);
+
+        assert_eq!(1, damaged.len());
+        assert_eq!(offsets[2], damaged[0].start);
+        assert_eq!(offsets[3], damaged[0].end);
+        assert!(
+            damaged[0].reason.starts_with("invalid message header"),
+            "{}",
+            damaged[0].reason
+        );
+    }
+
+    #[test]
+    fn corrupt_stream_header() {
+        let (mut data, offsets) = encoded_messages();
+        let (expected, _) = run_salvage(&data);
+
+        data[..4].copy_from_slice(b"XXXX");
+
+        // Without a stream header, the version of the data is unknown, but everything else is intact.
+        let (messages, damaged) = run_salvage(&data);
+        assert_eq!(expected.len(), messages.len());
+        assert_eq!(expected[1..], messages[1..]);
+
+        assert_eq!(1, damaged.len());
+        assert_eq!(0, damaged[0].start);
+        assert_eq!(offsets[0], damaged[0].end);
+        assert!(
+            damaged[0].reason.starts_with("invalid stream header"),
+            "{}",
+            damaged[0].reason
+        );
+    }
+}
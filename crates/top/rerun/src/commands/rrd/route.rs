@@ -9,6 +9,7 @@ use re_protos::{
     },
 };
 
+use super::remap::{EntityPathRemap, EntityPathRemaps};
 use crate::commands::{read_raw_rrd_streams_from_file_or_stdin, stdio::InputSource};
 
 #[derive(Debug, Clone, clap::Parser)]
@@ -35,11 +36,23 @@ pub struct RouteCommand {
     /// output.
     #[clap(long = "recording-id")]
     recording_id: Option<String>,
+
+    /// Rewrites entity paths, e.g. `--remap-entity "/camera/** -> /robot_a/camera/**"`.
+    ///
+    /// Can be specified multiple times, the first matching rule wins.
+    /// `/** -> /robot_a/**` prefixes every entity, `/** -> /{input}/**` prefixes every entity with
+    /// the name of the file it comes from.
+    /// References to entities in blueprints (view origins, view contents, overrides) are rewritten too.
+    ///
+    /// Note: the payloads of the affected messages have to be decoded and re-encoded.
+    #[clap(long = "remap-entity", value_name = "FROM -> TO")]
+    remap_entity: Vec<EntityPathRemap>,
 }
 
 struct Rewrites {
     application_id: Option<ApplicationId>,
     recording_id: Option<String>,
+    entity_paths: EntityPathRemaps,
 }
 
 impl RouteCommand {
@@ -50,6 +63,7 @@ impl RouteCommand {
             continue_on_error,
             application_id,
             recording_id,
+            remap_entity,
         } = self;
 
         let rewrites = Rewrites {
@@ -57,6 +71,7 @@ impl RouteCommand {
                 .as_ref()
                 .map(|id| ApplicationId { id: id.clone() }),
             recording_id: recording_id.clone(),
+            entity_paths: EntityPathRemaps::new(remap_entity.clone()),
         };
 
         let (rx, _) = read_raw_rrd_streams_from_file_or_stdin(path_to_input_rrds);
@@ -110,13 +125,24 @@ fn process_messages<W: std::io::Write>(
     let version = re_build_info::CrateVersion::LOCAL;
     let mut encoder = Encoder::new_eager(version, options, writer)?;
 
-    while let Ok((_input, res)) = receiver.recv() {
+    while let Ok((input, res)) = receiver.recv() {
         let mut is_success = true;
 
         match res {
             Ok(mut msg) => {
                 num_total_msgs += 1;
 
+                if let Msg::ArrowMsg(arrow_msg) = &mut msg
+                    && !rewrites.entity_paths.is_empty()
+                    && let Err(err) = rewrites
+                        .entity_paths
+                        .for_input(&input)
+                        .remap_transport_arrow_msg(arrow_msg)
+                {
+                    re_log::error_once!("couldn't remap entity paths: {}", re_error::format(err));
+                    is_success = false;
+                }
+
                 #[expect(deprecated)]
                 match &mut msg {
                     // This needs to come first, as an
@@ -174,7 +200,7 @@ fn process_messages<W: std::io::Write>(
                 }
 
                 // Safety: we're just forwarding an existing message, we didn't change its payload
-                // in any meaningful way.
+                // in any meaningful way (remapped payloads are re-encoded by the regular codec).
                 #[expect(unsafe_code)]
                 unsafe {
                     encoder.append_transport(&msg)?;
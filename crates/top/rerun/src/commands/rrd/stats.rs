@@ -1,13 +1,66 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
 use ahash::{HashMap, HashMapExt as _};
+use anyhow::Context as _;
+use arrow::array::Array as _;
+use arrow::datatypes::{DataType, Field, Fields, Schema};
 use itertools::Itertools as _;
 
+use re_byte_size::SizeBytes as _;
+use re_chunk::{Chunk, ComponentIdentifier, EntityPath, TimelineName};
+use re_chunk_store::{ChunkStore, ChunkStoreChunkStats, ChunkStoreConfig};
 use re_log_encoding::ToApplication as _;
+use re_log_types::{AbsoluteTimeRange, LogMsg, StoreId};
 use re_protos::log_msg::v1alpha1::log_msg::Msg;
 
-use crate::commands::read_raw_rrd_streams_from_file_or_stdin;
+use super::query::{BatchWriter, OutputFormat};
+use crate::commands::{
+    read_raw_rrd_streams_from_file_or_stdin, read_rrd_streams_from_file_or_stdin,
+};
 
 // ---
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum StatsFormat {
+    /// Human-readable summary.
+    Text,
+
+    /// Structured report, as JSON.
+    Json,
+
+    /// Structured report, as an Apache Parquet table with one row per entity and component.
+    Parquet,
+
+    /// Structured report, as an Arrow IPC table with one row per entity and component.
+    Ipc,
+
+    /// Structured report, as a CSV table with one row per entity and component.
+    Csv,
+}
+
+impl StatsFormat {
+    /// The format of the structured report, if any.
+    fn report_format(self) -> Option<ReportFormat> {
+        match self {
+            Self::Text => None,
+            Self::Json => Some(ReportFormat::Json),
+            Self::Parquet => Some(ReportFormat::Table(OutputFormat::Parquet)),
+            Self::Ipc => Some(ReportFormat::Table(OutputFormat::Ipc)),
+            Self::Csv => Some(ReportFormat::Table(OutputFormat::Csv)),
+        }
+    }
+}
+
+/// See [`StatsFormat`].
+#[derive(Debug, Clone, Copy)]
+enum ReportFormat {
+    Json,
+
+    /// One row per entity and component.
+    Table(OutputFormat),
+}
+
 #[derive(Debug, Clone, clap::Parser)]
 pub struct StatsCommand {
     /// If set, the data will never be decoded.
@@ -23,6 +76,17 @@ pub struct StatsCommand {
     /// If set, will try to proceed even in the face of IO and/or decoding errors in the input data.
     #[clap(long = "continue-on-error", default_value_t = true)]
     continue_on_error: bool,
+
+    /// Output format.
+    ///
+    /// All formats but `text` emit a structured report with row counts, sizes, time ranges,
+    /// sortedness and null ratios per entity and component, which requires decoding the data.
+    #[clap(long, value_enum, default_value_t = StatsFormat::Text)]
+    format: StatsFormat,
+
+    /// Path to write the structured report to. Writes to standard output if unspecified.
+    #[clap(short = 'o', long = "output", value_name = "dst")]
+    path_to_output: Option<String>,
 }
 
 impl StatsCommand {
@@ -31,8 +95,27 @@ impl StatsCommand {
             no_decode,
             path_to_input_rrds,
             continue_on_error,
+            format,
+            path_to_output,
         } = self;
 
+        if let Some(report_format) = format.report_format() {
+            anyhow::ensure!(
+                !*no_decode,
+                "structured reports require decoding the data, remove `--no-decode`"
+            );
+            return write_report(
+                report_format,
+                path_to_input_rrds,
+                path_to_output.as_deref(),
+                *continue_on_error,
+            );
+        }
+        anyhow::ensure!(
+            path_to_output.is_none(),
+            "`--output` is only supported for structured reports, see `--format`"
+        );
+
         let mut num_chunks = 0u64;
         let mut num_chunks_per_entity: HashMap<String, u64> = HashMap::new();
         let mut num_chunks_per_index: HashMap<String, u64> = HashMap::new();
@@ -64,6 +147,8 @@ impl StatsCommand {
                         re_protos::log_msg::v1alpha1::Compression::None as _;
                     const COMPRESSION_LZ4: i32 =
                         re_protos::log_msg::v1alpha1::Compression::Lz4 as _;
+                    const COMPRESSION_ZSTD: i32 =
+                        re_protos::log_msg::v1alpha1::Compression::Zstd as _;
 
                     match msg.compression {
                         COMPRESSION_NONE => {}
@@ -78,6 +163,15 @@ impl StatsCommand {
                             msg.compression = COMPRESSION_NONE;
                         }
 
+                        COMPRESSION_ZSTD => {
+                            uncompressed = re_log_encoding::external::zstd::bulk::decompress(
+                                &msg.payload,
+                                msg.uncompressed_size as _,
+                            )?;
+                            msg.payload = uncompressed.into();
+                            msg.compression = COMPRESSION_NONE;
+                        }
+
                         huh => anyhow::bail!("unknown Compression: {huh}"),
                     }
 
@@ -472,3 +566,506 @@ fn compute_stats(app: bool, msg: &Msg) -> anyhow::Result<Option<ChunkStats>> {
 
     Ok(None)
 }
+
+// --- Structured report ---
+
+#[derive(Debug, serde::Serialize)]
+struct StatsReport {
+    recordings: Vec<RecordingReport>,
+}
+
+/// Statistics for a single store.
+#[derive(Debug, serde::Serialize)]
+struct RecordingReport {
+    application_id: String,
+    recording_id: String,
+    store_kind: String,
+
+    /// Includes the static chunks that were overwritten by later ones.
+    static_chunks: ChunkStatsReport,
+    temporal_chunks: ChunkStatsReport,
+
+    columns: Vec<ColumnReport>,
+}
+
+/// See [`ChunkStoreChunkStats`].
+#[derive(Debug, serde::Serialize)]
+struct ChunkStatsReport {
+    num_chunks: u64,
+    total_size_bytes: u64,
+    num_rows: u64,
+    num_events: u64,
+}
+
+impl From<ChunkStoreChunkStats> for ChunkStatsReport {
+    fn from(stats: ChunkStoreChunkStats) -> Self {
+        let ChunkStoreChunkStats {
+            num_chunks,
+            total_size_bytes,
+            num_rows,
+            num_events,
+        } = stats;
+
+        Self {
+            num_chunks,
+            total_size_bytes,
+            num_rows,
+            num_events,
+        }
+    }
+}
+
+/// Statistics for a single component column, see [`re_sorbet::ComponentColumnDescriptor`].
+///
+/// This is also the row type of the tabular formats.
+#[derive(Debug, Clone, serde::Serialize)]
+struct ColumnReport {
+    application_id: String,
+    recording_id: String,
+
+    entity_path: String,
+    archetype: Option<String>,
+    component: String,
+    component_type: Option<String>,
+    datatype: String,
+    is_static: bool,
+
+    /// Number of chunks that contain this component.
+    num_chunks: u64,
+
+    /// Number of rows in the chunks that contain this component.
+    num_rows: u64,
+
+    /// Number of rows with a non-null value for this component.
+    num_events: u64,
+
+    /// Ratio of null values for this component, within the chunks that contain it.
+    null_ratio: f64,
+
+    /// Size of the component data in memory.
+    size_bytes: u64,
+
+    /// Whether all the chunks that contain this component are sorted by `RowId`.
+    is_sorted: bool,
+
+    time_ranges: Vec<TimeRangeReport>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct TimeRangeReport {
+    timeline: String,
+    min: i64,
+    max: i64,
+
+    /// Whether all the chunks that contain this component are sorted on this timeline.
+    is_sorted: bool,
+}
+
+impl ColumnReport {
+    fn arrow_fields() -> Fields {
+        let time_range_fields = Fields::from(vec![
+            Field::new("timeline", DataType::Utf8, false),
+            Field::new("min", DataType::Int64, false),
+            Field::new("max", DataType::Int64, false),
+            Field::new("is_sorted", DataType::Boolean, false),
+        ]);
+
+        Fields::from(vec![
+            Field::new("application_id", DataType::Utf8, false),
+            Field::new("recording_id", DataType::Utf8, false),
+            Field::new("entity_path", DataType::Utf8, false),
+            Field::new("archetype", DataType::Utf8, true),
+            Field::new("component", DataType::Utf8, false),
+            Field::new("component_type", DataType::Utf8, true),
+            Field::new("datatype", DataType::Utf8, false),
+            Field::new("is_static", DataType::Boolean, false),
+            Field::new("num_chunks", DataType::UInt64, false),
+            Field::new("num_rows", DataType::UInt64, false),
+            Field::new("num_events", DataType::UInt64, false),
+            Field::new("null_ratio", DataType::Float64, false),
+            Field::new("size_bytes", DataType::UInt64, false),
+            Field::new("is_sorted", DataType::Boolean, false),
+            Field::new_list(
+                "time_ranges",
+                Field::new_list_field(DataType::Struct(time_range_fields), false),
+                false,
+            ),
+        ])
+    }
+}
+
+fn write_report(
+    format: ReportFormat,
+    path_to_input_rrds: &[String],
+    path_to_output: Option<&str>,
+    continue_on_error: bool,
+) -> anyhow::Result<()> {
+    let stores = load_stores(path_to_input_rrds, continue_on_error)?;
+
+    re_log::info!("computing stats…");
+    let report = StatsReport {
+        recordings: stores.into_values().map(recording_report).collect(),
+    };
+
+    let output: Box<dyn std::io::Write + Send> = if let Some(path) = path_to_output {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("couldn't create output file {path:?}"))?;
+        Box::new(std::io::BufWriter::new(file))
+    } else {
+        Box::new(std::io::BufWriter::new(std::io::stdout()))
+    };
+
+    let table_format = match format {
+        ReportFormat::Json => {
+            let mut output = output;
+            serde_json::to_writer_pretty(&mut output, &report)
+                .context("couldn't serialize report")?;
+            writeln!(output)?;
+            output.flush()?;
+            return Ok(());
+        }
+
+        ReportFormat::Table(table_format) => table_format,
+    };
+
+    let schema = Arc::new(Schema::new_with_metadata(
+        ColumnReport::arrow_fields(),
+        Default::default(),
+    ));
+
+    let rows = report
+        .recordings
+        .iter()
+        .flat_map(|recording| recording.columns.iter())
+        .collect_vec();
+
+    let mut decoder = arrow::json::ReaderBuilder::new(schema.clone()).build_decoder()?;
+    decoder.serialize(&rows)?;
+
+    let mut writer = BatchWriter::new(table_format, output, &schema)?;
+    while let Some(batch) = decoder.flush()? {
+        writer.write(&batch)?;
+    }
+    writer.finish()?;
+
+    Ok(())
+}
+
+/// Loads every store found in the inputs, as-is (i.e. without compaction).
+fn load_stores(
+    path_to_input_rrds: &[String],
+    continue_on_error: bool,
+) -> anyhow::Result<BTreeMap<StoreId, LoadedStore>> {
+    let mut stores: BTreeMap<StoreId, LoadedStore> = BTreeMap::default();
+
+    let (rx, _) = read_rrd_streams_from_file_or_stdin(path_to_input_rrds);
+    for (_source, res) in rx {
+        let mut is_success = true;
+
+        match res {
+            Ok(LogMsg::ArrowMsg(store_id, msg)) => {
+                let store = stores
+                    .entry(store_id.clone())
+                    .or_insert_with(|| LoadedStore::new(store_id));
+
+                let res = Chunk::from_arrow_msg(&msg)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|chunk| store.add_chunk(chunk));
+                if let Err(err) = res {
+                    re_log::error_once!("{}", re_error::format(err));
+                    is_success = false;
+                }
+            }
+
+            Ok(LogMsg::SetStoreInfo(_) | LogMsg::BlueprintActivationCommand(_)) => {}
+
+            Err(err) => {
+                re_log::error_once!("{}", re_error::format(err));
+                is_success = false;
+            }
+        }
+
+        if !continue_on_error && !is_success {
+            anyhow::bail!(
+                "one or more IO and/or decoding failures in the input stream (check logs)"
+            )
+        }
+    }
+
+    Ok(stores)
+}
+
+/// A store, along with the statistics of every chunk that was added to it.
+///
+/// The [`ChunkStore`] only keeps the latest static chunk of each component, so anything static is
+/// accounted for as the chunks come in instead.
+struct LoadedStore {
+    store: ChunkStore,
+
+    /// Every static chunk that was added, including the ones that were overwritten since.
+    static_chunks: ChunkStoreChunkStats,
+
+    stats_per_column: HashMap<(EntityPath, ComponentIdentifier), ColumnStats>,
+}
+
+#[derive(Default)]
+struct ColumnStats {
+    num_chunks: u64,
+    num_rows: u64,
+    num_nulls: u64,
+    size_bytes: u64,
+    is_sorted: bool,
+    time_ranges: BTreeMap<TimelineName, (AbsoluteTimeRange, bool)>,
+}
+
+impl LoadedStore {
+    fn new(store_id: StoreId) -> Self {
+        Self {
+            store: ChunkStore::new(store_id, ChunkStoreConfig::ALL_DISABLED),
+            static_chunks: ChunkStoreChunkStats::default(),
+            stats_per_column: HashMap::new(),
+        }
+    }
+
+    fn add_chunk(&mut self, chunk: Chunk) -> anyhow::Result<()> {
+        let chunk = Arc::new(chunk);
+        self.store.insert_chunk(&chunk)?;
+
+        if chunk.is_static() {
+            self.static_chunks += ChunkStoreChunkStats::from_chunk(&chunk);
+        }
+
+        let time_range_per_component = chunk.time_range_per_component();
+
+        for (component, column) in chunk.components().iter() {
+            let stats = self
+                .stats_per_column
+                .entry((chunk.entity_path().clone(), *component))
+                .or_insert_with(|| ColumnStats {
+                    is_sorted: true,
+                    ..Default::default()
+                });
+
+            stats.num_chunks += 1;
+            stats.num_rows += chunk.num_rows() as u64;
+            stats.num_nulls += column.list_array.null_count() as u64;
+            stats.size_bytes += column.list_array.total_size_bytes();
+            stats.is_sorted &= chunk.is_sorted();
+
+            for (timeline, time_column) in chunk.timelines() {
+                let Some(time_range) = time_range_per_component
+                    .get(timeline)
+                    .and_then(|time_ranges| time_ranges.get(component))
+                else {
+                    continue;
+                };
+
+                stats
+                    .time_ranges
+                    .entry(*timeline)
+                    .and_modify(|(range, is_sorted)| {
+                        *range = range.union(*time_range);
+                        *is_sorted &= time_column.is_sorted();
+                    })
+                    .or_insert((*time_range, time_column.is_sorted()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn recording_report(loaded: LoadedStore) -> RecordingReport {
+    re_tracing::profile_function!();
+
+    let LoadedStore {
+        store,
+        static_chunks,
+        mut stats_per_column,
+    } = loaded;
+
+    let store_id = store.id();
+    let application_id = store_id.application_id().to_string();
+    let recording_id = store_id.recording_id().to_string();
+
+    let columns = store
+        .schema()
+        .components
+        .into_iter()
+        .filter_map(|descr| {
+            let stats = stats_per_column.remove(&(descr.entity_path.clone(), descr.component))?;
+
+            Some(ColumnReport {
+                application_id: application_id.clone(),
+                recording_id: recording_id.clone(),
+
+                entity_path: descr.entity_path.to_string(),
+                archetype: descr.archetype.map(|archetype| archetype.to_string()),
+                component: descr.component.to_string(),
+                component_type: descr
+                    .component_type
+                    .map(|component_type| component_type.to_string()),
+                datatype: re_arrow_util::format_data_type(&descr.store_datatype),
+                is_static: descr.is_static,
+
+                num_chunks: stats.num_chunks,
+                num_rows: stats.num_rows,
+                num_events: stats.num_rows - stats.num_nulls,
+                null_ratio: if stats.num_rows == 0 {
+                    0.0
+                } else {
+                    stats.num_nulls as f64 / stats.num_rows as f64
+                },
+                size_bytes: stats.size_bytes,
+                is_sorted: stats.is_sorted,
+
+                time_ranges: stats
+                    .time_ranges
+                    .into_iter()
+                    .map(|(timeline, (range, is_sorted))| TimeRangeReport {
+                        timeline: timeline.to_string(),
+                        min: range.min().as_i64(),
+                        max: range.max().as_i64(),
+                        is_sorted,
+                    })
+                    .collect(),
+            })
+        })
+        .collect();
+
+    let stats = store.stats();
+
+    RecordingReport {
+        application_id,
+        recording_id,
+        store_kind: store_id.kind().to_string(),
+
+        static_chunks: static_chunks.into(),
+        temporal_chunks: stats.temporal_chunks.into(),
+
+        columns,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use re_chunk::{RowId, TimePoint, Timeline};
+    use re_log_types::StoreKind;
+    use re_log_types::example_components::{MyPoint, MyPoints};
+
+    use super::*;
+
+    fn points_chunk(entity_path: &str, time_points: impl IntoIterator<Item = TimePoint>) -> Chunk {
+        let mut builder = Chunk::builder(entity_path);
+        for time_point in time_points {
+            builder = builder.with_component_batches(
+                RowId::new(),
+                time_point,
+                [(
+                    MyPoints::descriptor_points(),
+                    &MyPoint::from_iter(0..3) as _,
+                )],
+            );
+        }
+        builder.build().unwrap()
+    }
+
+    fn frame(frame_nr: i64) -> TimePoint {
+        TimePoint::default().with(Timeline::new_sequence("frame"), frame_nr)
+    }
+
+    /// Two static chunks for the same component, the second overwriting the first, and a
+    /// temporal chunk.
+    fn test_report() -> RecordingReport {
+        let mut loaded = LoadedStore::new(StoreId::random(StoreKind::Recording, "test"));
+        loaded
+            .add_chunk(points_chunk("static", [TimePoint::default()]))
+            .unwrap();
+        loaded
+            .add_chunk(points_chunk("static", [TimePoint::default()]))
+            .unwrap();
+        loaded
+            .add_chunk(points_chunk("points", [frame(0), frame(2), frame(1)]))
+            .unwrap();
+
+        recording_report(loaded)
+    }
+
+    #[test]
+    fn report_format() {
+        assert!(StatsFormat::Text.report_format().is_none());
+        assert!(matches!(
+            StatsFormat::Json.report_format(),
+            Some(ReportFormat::Json)
+        ));
+        assert!(matches!(
+            StatsFormat::Csv.report_format(),
+            Some(ReportFormat::Table(OutputFormat::Csv))
+        ));
+    }
+
+    #[test]
+    fn overwritten_static_chunks() {
+        let report = test_report();
+
+        assert_eq!(report.static_chunks.num_chunks, 2);
+        assert_eq!(report.static_chunks.num_rows, 2);
+        assert_eq!(report.static_chunks.num_events, 2);
+        assert!(report.static_chunks.total_size_bytes > 0);
+
+        assert_eq!(report.temporal_chunks.num_chunks, 1);
+        assert_eq!(report.temporal_chunks.num_rows, 3);
+
+        let column = report
+            .columns
+            .iter()
+            .find(|column| column.entity_path == "/static")
+            .unwrap();
+        assert!(column.is_static);
+        assert_eq!(column.num_chunks, 2);
+        assert_eq!(column.num_rows, 2);
+        assert_eq!(column.num_events, 2);
+        assert!(column.time_ranges.is_empty());
+    }
+
+    #[test]
+    fn temporal_columns() {
+        let report = test_report();
+
+        let column = report
+            .columns
+            .iter()
+            .find(|column| column.entity_path == "/points")
+            .unwrap();
+        assert!(!column.is_static);
+        assert_eq!(column.num_chunks, 1);
+        assert_eq!(column.num_rows, 3);
+        assert_eq!(column.num_events, 3);
+        assert_eq!(column.null_ratio, 0.0);
+
+        let [time_range] = column.time_ranges.as_slice() else {
+            panic!("expected a single time range, got {:?}", column.time_ranges);
+        };
+        assert_eq!(time_range.timeline, "frame");
+        assert_eq!((time_range.min, time_range.max), (0, 2));
+        assert!(!time_range.is_sorted);
+    }
+
+    #[test]
+    fn columns_as_table() {
+        let report = test_report();
+
+        let schema = Arc::new(Schema::new_with_metadata(
+            ColumnReport::arrow_fields(),
+            Default::default(),
+        ));
+        let mut decoder = arrow::json::ReaderBuilder::new(schema)
+            .build_decoder()
+            .unwrap();
+        decoder.serialize(&report.columns).unwrap();
+
+        let batch = decoder.flush().unwrap().unwrap();
+        assert_eq!(batch.num_rows(), report.columns.len());
+        assert_eq!(batch.num_rows(), 2);
+    }
+}
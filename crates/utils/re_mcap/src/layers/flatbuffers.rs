@@ -0,0 +1,585 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        ArrayBuilder, BinaryBuilder, BooleanBuilder, FixedSizeListBuilder, Float32Builder,
+        Float64Builder, Int8Builder, Int16Builder, Int32Builder, Int64Builder, ListBuilder,
+        StringBuilder, StructBuilder, UInt8Builder, UInt16Builder, UInt32Builder, UInt64Builder,
+    },
+    datatypes::{DataType, Field, Fields},
+};
+use re_chunk::{Chunk, ChunkId};
+use re_types::{ComponentDescriptor, reflection::ComponentDescriptorExt as _};
+
+use crate::parsers::{
+    MessageParser, ParserContext,
+    dynamic::Value,
+    flatbuffers::{self, BaseType, FlatBuffersError, Object, Schema, Type},
+};
+use crate::{Error, LayerIdentifier, MessageLayer};
+
+/// Schemas whose tables are nested deeper than this are considered to be recursive.
+const MAX_SCHEMA_DEPTH: usize = 64;
+
+/// The schema encoding, as well as the message encoding, of `FlatBuffers` channels.
+pub(crate) const FLATBUFFER_ENCODING: &str = "flatbuffer";
+
+#[derive(Debug, thiserror::Error)]
+enum FlatBuffersLayerError {
+    #[error("invalid message on channel {channel} for schema {schema}: {source}")]
+    InvalidMessage {
+        schema: String,
+        channel: String,
+        source: FlatBuffersError,
+    },
+
+    #[error("expected a value of type {expected}, but found {actual:?}")]
+    UnexpectedValue {
+        expected: DataType,
+        actual: Option<Value>,
+    },
+
+    #[error("failed to downcast builder for type {0}")]
+    Downcast(DataType),
+}
+
+/// A binary schema together with the table that describes the messages of a channel.
+#[derive(Clone, Debug)]
+struct MessageSchema {
+    schema: Arc<Schema>,
+    object: usize,
+}
+
+impl MessageSchema {
+    fn object(&self) -> &Object {
+        &self.schema.objects[self.object]
+    }
+}
+
+struct FlatBuffersMessageParser {
+    message_schema: MessageSchema,
+    datatype: DataType,
+    builder: FixedSizeListBuilder<StructBuilder>,
+}
+
+impl FlatBuffersMessageParser {
+    fn new(num_rows: usize, message_schema: MessageSchema) -> anyhow::Result<Self> {
+        let fields = fields_from_object(&message_schema.schema, message_schema.object(), 0)?;
+        let struct_builder = StructBuilder::from_fields(fields.clone(), num_rows);
+
+        Ok(Self {
+            message_schema,
+            datatype: DataType::Struct(fields),
+            builder: FixedSizeListBuilder::with_capacity(struct_builder, 1, num_rows),
+        })
+    }
+}
+
+impl MessageParser for FlatBuffersMessageParser {
+    fn append(&mut self, _ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        re_tracing::profile_function!();
+        let object = self.message_schema.object();
+
+        let value = flatbuffers::decode_message(&self.message_schema.schema, object, &msg.data)
+            .map_err(|err| FlatBuffersLayerError::InvalidMessage {
+                schema: object.name.clone(),
+                channel: msg.channel.topic.clone(),
+                source: err,
+            })?;
+
+        append_value(self.builder.values(), &self.datatype, Some(&value))?;
+        self.builder.append(true);
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<re_chunk::Chunk>> {
+        re_tracing::profile_function!();
+        let entity_path = ctx.entity_path().clone();
+        let timelines = ctx.build_timelines();
+
+        let Self {
+            message_schema,
+            datatype: _,
+            mut builder,
+        } = *self;
+
+        let message_chunk = Chunk::from_auto_row_ids(
+            ChunkId::new(),
+            entity_path,
+            timelines,
+            std::iter::once((
+                ComponentDescriptor::partial("message")
+                    .with_builtin_archetype(message_schema.object().name.as_str()),
+                builder.finish().into(),
+            ))
+            .collect(),
+        )
+        .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
+
+        Ok(vec![message_chunk])
+    }
+}
+
+fn downcast_builder<'a, T: std::any::Any>(
+    builder: &'a mut dyn ArrayBuilder,
+    datatype: &DataType,
+) -> Result<&'a mut T, FlatBuffersLayerError> {
+    builder
+        .as_any_mut()
+        .downcast_mut::<T>()
+        .ok_or_else(|| FlatBuffersLayerError::Downcast(datatype.clone()))
+}
+
+/// Appends `value` to a builder that was created for `datatype`, `None` is appended as null.
+fn append_value(
+    builder: &mut dyn ArrayBuilder,
+    datatype: &DataType,
+    value: Option<&Value>,
+) -> Result<(), FlatBuffersLayerError> {
+    let unexpected = || FlatBuffersLayerError::UnexpectedValue {
+        expected: datatype.clone(),
+        actual: value.cloned(),
+    };
+
+    macro_rules! append_primitive {
+        ($builder:ty, $variant:ident, $x:ident => $value:expr) => {{
+            let builder = downcast_builder::<$builder>(builder, datatype)?;
+            match value {
+                Some(Value::$variant($x)) => builder.append_value($value),
+                None => builder.append_null(),
+                Some(_) => return Err(unexpected()),
+            }
+        }};
+        ($builder:ty, $variant:ident) => {
+            append_primitive!($builder, $variant, x => *x)
+        };
+    }
+
+    match datatype {
+        DataType::Boolean => append_primitive!(BooleanBuilder, Bool),
+        DataType::Int8 => append_primitive!(Int8Builder, I8),
+        DataType::UInt8 => append_primitive!(UInt8Builder, U8),
+        DataType::Int16 => append_primitive!(Int16Builder, I16),
+        DataType::UInt16 => append_primitive!(UInt16Builder, U16),
+        DataType::Int32 => append_primitive!(Int32Builder, I32),
+        DataType::UInt32 => append_primitive!(UInt32Builder, U32),
+        DataType::Int64 => append_primitive!(Int64Builder, I64),
+        DataType::UInt64 => append_primitive!(UInt64Builder, U64),
+        DataType::Float32 => append_primitive!(Float32Builder, F32),
+        DataType::Float64 => append_primitive!(Float64Builder, F64),
+        DataType::Utf8 => append_primitive!(StringBuilder, String, x => x),
+        DataType::Binary => append_primitive!(BinaryBuilder, Bytes, x => x),
+        DataType::List(field) => {
+            let list_builder =
+                downcast_builder::<ListBuilder<Box<dyn ArrayBuilder>>>(builder, datatype)?;
+            match value {
+                Some(Value::List(values)) => {
+                    for value in values {
+                        append_value(list_builder.values(), field.data_type(), Some(value))?;
+                    }
+                    list_builder.append(true);
+                }
+                None => list_builder.append_null(),
+                Some(_) => return Err(unexpected()),
+            }
+        }
+        DataType::Struct(fields) => {
+            let struct_builder = downcast_builder::<StructBuilder>(builder, datatype)?;
+            match value {
+                Some(Value::Message(values)) if values.len() == fields.len() => {
+                    for ((field, (_, value)), field_builder) in fields
+                        .iter()
+                        .zip(values)
+                        .zip(struct_builder.field_builders_mut())
+                    {
+                        append_value(field_builder.as_mut(), field.data_type(), value.as_ref())?;
+                    }
+                    struct_builder.append(true);
+                }
+                None => {
+                    // The children of a struct need to have the same length as the struct itself.
+                    for (field, field_builder) in
+                        fields.iter().zip(struct_builder.field_builders_mut())
+                    {
+                        append_value(field_builder.as_mut(), field.data_type(), None)?;
+                    }
+                    struct_builder.append_null();
+                }
+                Some(_) => return Err(unexpected()),
+            }
+        }
+        _ => return Err(unexpected()),
+    }
+
+    Ok(())
+}
+
+fn fields_from_object(schema: &Schema, object: &Object, depth: usize) -> anyhow::Result<Fields> {
+    anyhow::ensure!(
+        depth < MAX_SCHEMA_DEPTH,
+        "recursive table {} is not supported",
+        object.name
+    );
+
+    if object.decoded_fields().count() != object.fields.len() {
+        re_log::warn_once!(
+            "Unions in FlatBuffers table {} are not supported yet and will be skipped.",
+            object.name
+        );
+    }
+
+    object
+        .decoded_fields()
+        .map(|field| {
+            Ok(Field::new(
+                field.name.as_str(),
+                datatype_from_type(schema, &field.ty, depth)?,
+                true,
+            ))
+        })
+        .collect()
+}
+
+fn datatype_from_type(schema: &Schema, ty: &Type, depth: usize) -> anyhow::Result<DataType> {
+    match ty.base_type {
+        BaseType::Vector if ty.element == BaseType::UByte => Ok(DataType::Binary),
+        BaseType::Vector | BaseType::Array => Ok(DataType::new_list(
+            datatype_from_base_type(schema, ty.element, ty.index, depth)?,
+            true,
+        )),
+        base_type => datatype_from_base_type(schema, base_type, ty.index, depth),
+    }
+}
+
+fn datatype_from_base_type(
+    schema: &Schema,
+    base_type: BaseType,
+    index: i32,
+    depth: usize,
+) -> anyhow::Result<DataType> {
+    Ok(match base_type {
+        BaseType::Bool => DataType::Boolean,
+        BaseType::Byte => DataType::Int8,
+        BaseType::UType | BaseType::UByte => DataType::UInt8,
+        BaseType::Short => DataType::Int16,
+        BaseType::UShort => DataType::UInt16,
+        BaseType::Int => DataType::Int32,
+        BaseType::UInt => DataType::UInt32,
+        BaseType::Long => DataType::Int64,
+        BaseType::ULong => DataType::UInt64,
+        BaseType::Float => DataType::Float32,
+        BaseType::Double => DataType::Float64,
+        BaseType::String => DataType::Utf8,
+        BaseType::Obj => DataType::Struct(fields_from_object(
+            schema,
+            schema.object(index)?,
+            depth + 1,
+        )?),
+        BaseType::None
+        | BaseType::Vector
+        | BaseType::Union
+        | BaseType::Array
+        | BaseType::Vector64 => anyhow::bail!("unsupported type {base_type:?}"),
+    })
+}
+
+/// Provides reflection-based conversion of `FlatBuffers`-encoded MCAP messages.
+///
+/// The binary schema (`.bfbs`) of each channel is used to decode its messages into a direct
+/// Arrow representation of their fields, similar to the [`super::McapProtobufLayer`].
+/// This does not result in semantic types that can be picked up by the Rerun viewer.
+#[derive(Debug, Default)]
+pub struct McapFlatBuffersLayer {
+    schemas_per_topic: ahash::HashMap<String, MessageSchema>,
+}
+
+impl MessageLayer for McapFlatBuffersLayer {
+    fn identifier() -> LayerIdentifier {
+        "flatbuffer".into()
+    }
+
+    fn init(&mut self, summary: &mcap::Summary) -> Result<(), Error> {
+        let mut parsed_schemas = ahash::HashMap::<u16, Arc<Schema>>::default();
+
+        for channel in summary.channels.values() {
+            let Some(schema) = channel.schema.as_ref() else {
+                continue;
+            };
+
+            if schema.encoding.as_str() != FLATBUFFER_ENCODING {
+                continue;
+            }
+
+            let parsed = if let Some(parsed) = parsed_schemas.get(&schema.id) {
+                parsed.clone()
+            } else {
+                let parsed = Arc::new(Schema::parse(schema.data.as_ref()).map_err(|err| {
+                    Error::InvalidSchema {
+                        schema: schema.name.clone(),
+                        source: err.into(),
+                    }
+                })?);
+                parsed_schemas.insert(schema.id, parsed.clone());
+                parsed
+            };
+
+            let object = parsed
+                .find_object(&schema.name)
+                .ok_or_else(|| Error::NoSchema(schema.name.clone()))?;
+
+            self.schemas_per_topic.insert(
+                channel.topic.clone(),
+                MessageSchema {
+                    schema: parsed,
+                    object,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    fn supports_channel(&self, channel: &mcap::Channel<'_>) -> bool {
+        let Some(schema) = channel.schema.as_ref() else {
+            return false;
+        };
+
+        if schema.encoding.as_str() != FLATBUFFER_ENCODING {
+            return false;
+        }
+
+        self.schemas_per_topic.contains_key(&channel.topic)
+    }
+
+    fn message_parser(
+        &self,
+        channel: &mcap::Channel<'_>,
+        num_rows: usize,
+    ) -> Option<Box<dyn MessageParser>> {
+        let message_schema = self.schemas_per_topic.get(&channel.topic)?;
+        match FlatBuffersMessageParser::new(num_rows, message_schema.clone()) {
+            Ok(parser) => Some(Box::new(parser)),
+            Err(err) => {
+                re_log::warn_once!(
+                    "Failed to create parser for FlatBuffers schema {}: {err}",
+                    message_schema.object().name
+                );
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+
+    use flatbuffers::{FlatBufferBuilder, WIPOffset};
+    use re_chunk::Chunk;
+
+    use crate::{LayerRegistry, layers::McapFlatBuffersLayer};
+
+    /// The vtable offset of the field in the given slot.
+    const fn slot(id: u16) -> u16 {
+        4 + 2 * id
+    }
+
+    /// The `base_type` values of `reflection.fbs`.
+    const INT: u8 = 7;
+    const FLOAT: u8 = 11;
+    const STRING: u8 = 13;
+    const VECTOR: u8 = 14;
+    const OBJ: u8 = 15;
+
+    struct FieldDef {
+        name: &'static str,
+        base_type: u8,
+        element: u8,
+        index: i32,
+        default_integer: i64,
+    }
+
+    impl FieldDef {
+        fn new(name: &'static str, base_type: u8) -> Self {
+            Self {
+                name,
+                base_type,
+                element: 0,
+                index: -1,
+                default_integer: 0,
+            }
+        }
+    }
+
+    fn create_object(
+        fbb: &mut FlatBufferBuilder<'_>,
+        name: &str,
+        fields: &[FieldDef],
+    ) -> WIPOffset<flatbuffers::TableFinishedWIPOffset> {
+        let fields = fields
+            .iter()
+            .zip(0u16..)
+            .map(|(field, id)| {
+                let ty = fbb.start_table();
+                fbb.push_slot_always::<u8>(slot(0), field.base_type);
+                fbb.push_slot_always::<u8>(slot(1), field.element);
+                fbb.push_slot_always::<i32>(slot(2), field.index);
+                let ty = fbb.end_table(ty);
+
+                let name = fbb.create_string(field.name);
+                let start = fbb.start_table();
+                fbb.push_slot_always(slot(0), name);
+                fbb.push_slot_always(slot(1), ty);
+                fbb.push_slot_always::<u16>(slot(2), id);
+                fbb.push_slot::<i64>(slot(4), field.default_integer, 0);
+                fbb.end_table(start)
+            })
+            .collect::<Vec<_>>();
+
+        let fields = fbb.create_vector(&fields);
+        let name = fbb.create_string(name);
+        let start = fbb.start_table();
+        fbb.push_slot_always(slot(0), name);
+        fbb.push_slot_always(slot(1), fields);
+        fbb.end_table(start)
+    }
+
+    /// Creates the binary schema of:
+    ///
+    /// ```fbs
+    /// namespace com.example;
+    /// table Person { name: string; id: int = 7; scores: [float]; position: Vec2; }
+    /// table Vec2 { x: float; y: float; }
+    /// root_type Person;
+    /// ```
+    fn create_schema() -> Vec<u8> {
+        let mut fbb = FlatBufferBuilder::new();
+
+        let person = create_object(
+            &mut fbb,
+            "com.example.Person",
+            &[
+                FieldDef::new("name", STRING),
+                FieldDef {
+                    default_integer: 7,
+                    ..FieldDef::new("id", INT)
+                },
+                FieldDef {
+                    element: FLOAT,
+                    ..FieldDef::new("scores", VECTOR)
+                },
+                FieldDef {
+                    index: 1,
+                    ..FieldDef::new("position", OBJ)
+                },
+            ],
+        );
+        let vec2 = create_object(
+            &mut fbb,
+            "com.example.Vec2",
+            &[FieldDef::new("x", FLOAT), FieldDef::new("y", FLOAT)],
+        );
+
+        let objects = fbb.create_vector(&[person, vec2]);
+        let start = fbb.start_table();
+        fbb.push_slot_always(slot(0), objects);
+        fbb.push_slot_always(slot(4), person);
+        let schema = fbb.end_table(start);
+
+        fbb.finish(schema, Some("BFBS"));
+        fbb.finished_data().to_vec()
+    }
+
+    fn create_person(
+        name: &str,
+        id: Option<i32>,
+        scores: &[f32],
+        position: Option<[f32; 2]>,
+    ) -> Vec<u8> {
+        let mut fbb = FlatBufferBuilder::new();
+
+        let position = position.map(|[x, y]| {
+            let start = fbb.start_table();
+            fbb.push_slot_always(slot(0), x);
+            fbb.push_slot_always(slot(1), y);
+            fbb.end_table(start)
+        });
+        let scores = fbb.create_vector(scores);
+        let name = fbb.create_string(name);
+
+        let start = fbb.start_table();
+        fbb.push_slot_always(slot(0), name);
+        if let Some(id) = id {
+            fbb.push_slot_always(slot(1), id);
+        }
+        fbb.push_slot_always(slot(2), scores);
+        if let Some(position) = position {
+            fbb.push_slot_always(slot(3), position);
+        }
+        let person = fbb.end_table(start);
+
+        fbb.finish(person, None);
+        fbb.finished_data().to_vec()
+    }
+
+    fn run_layer(summary: &mcap::Summary, buffer: &[u8]) -> Vec<Chunk> {
+        let mut chunks = Vec::new();
+
+        let mut send_chunk = |chunk| {
+            chunks.push(chunk);
+        };
+
+        let registry = LayerRegistry::empty().register_message_layer::<McapFlatBuffersLayer>();
+        registry
+            .plan(summary)
+            .expect("failed to plan")
+            .run(buffer, summary, &mut send_chunk)
+            .expect("failed to run layer");
+
+        chunks
+    }
+
+    #[test]
+    fn two_simple_rows() {
+        // Writing to the MCAP buffer.
+        let (summary, buffer) = {
+            let buffer = Vec::new();
+            let cursor = io::Cursor::new(buffer);
+            let mut writer = mcap::Writer::new(cursor).expect("failed to create writer");
+
+            let schema_id = writer
+                .add_schema("com.example.Person", "flatbuffer", &create_schema())
+                .expect("failed to add schema");
+            let channel_id = writer
+                .add_channel(schema_id, "test_topic", "flatbuffer", &Default::default())
+                .expect("failed to add channel");
+
+            let messages = [
+                create_person("Bob", None, &[1.0, 2.5], Some([1.5, -2.0])),
+                create_person("Alice", Some(123), &[], None),
+            ];
+
+            for (timestamp, message) in (42..).zip(messages) {
+                let header = mcap::records::MessageHeader {
+                    channel_id,
+                    sequence: 0,
+                    log_time: timestamp,
+                    publish_time: timestamp,
+                };
+                writer
+                    .write_to_known_channel(&header, &message)
+                    .expect("failed to write message");
+            }
+
+            let summary = writer.finish().expect("finishing writer failed");
+
+            (summary, writer.into_inner().into_inner())
+        };
+
+        let chunks = run_layer(&summary, buffer.as_slice());
+        assert_eq!(chunks.len(), 1);
+
+        insta::assert_snapshot!("two_simple_rows", format!("{:-240}", &chunks[0]));
+    }
+}
@@ -0,0 +1,657 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use prost_reflect::DescriptorPool;
+
+use super::{MessageLayer, flatbuffers::FLATBUFFER_ENCODING};
+use crate::{
+    Error,
+    parsers::{
+        MessageParser, flatbuffers,
+        foxglove::{
+            CompressedImageMessageParser, CompressedVideoMessageParser, FoxgloveMessageParser,
+            FrameTransformMessageParser, FrameTransformsMessageParser, LogMessageParser,
+            MessageDecoder, PointCloudMessageParser,
+        },
+    },
+};
+
+type ParserFactory = fn(usize, MessageDecoder) -> Box<dyn MessageParser>;
+
+/// Provides semantic interpretation of the well-known [Foxglove schemas](https://docs.foxglove.dev/docs/visualization/message-schemas/introduction),
+/// e.g. `foxglove.PointCloud`.
+///
+/// Messages can be encoded as JSON, `FlatBuffers`, or protobuf.
+#[derive(Debug)]
+pub struct McapFoxgloveLayer {
+    registry: BTreeMap<String, ParserFactory>,
+    decoders_per_topic: ahash::HashMap<String, MessageDecoder>,
+}
+
+impl McapFoxgloveLayer {
+    fn empty() -> Self {
+        Self {
+            registry: BTreeMap::new(),
+            decoders_per_topic: Default::default(),
+        }
+    }
+
+    /// Creates a new [`McapFoxgloveLayer`] with all supported message types pre-registered
+    pub fn new() -> Self {
+        Self::empty()
+            .register_parser::<CompressedImageMessageParser>("foxglove.CompressedImage")
+            .register_parser::<CompressedVideoMessageParser>("foxglove.CompressedVideo")
+            .register_parser::<FrameTransformMessageParser>("foxglove.FrameTransform")
+            .register_parser::<FrameTransformsMessageParser>("foxglove.FrameTransforms")
+            .register_parser::<LogMessageParser>("foxglove.Log")
+            .register_parser::<PointCloudMessageParser>("foxglove.PointCloud")
+    }
+
+    /// Registers a new message parser for the given schema name
+    pub fn register_parser<T: FoxgloveMessageParser + 'static>(
+        mut self,
+        schema_name: &str,
+    ) -> Self {
+        self.registry.insert(schema_name.to_owned(), |n, decoder| {
+            Box::new(T::new(n, decoder))
+        });
+        self
+    }
+
+    /// Returns true if the given schema is supported by this layer
+    pub fn supports_schema(&self, schema_name: &str) -> bool {
+        self.registry.contains_key(schema_name)
+    }
+
+    /// Creates the decoder for the message encoding of the channel.
+    fn decoder(channel: &mcap::Channel<'_>) -> anyhow::Result<Option<MessageDecoder>> {
+        let Some(schema) = channel.schema.as_ref() else {
+            return Ok(None);
+        };
+
+        Ok(Some(match channel.message_encoding.as_str() {
+            "json" => MessageDecoder::Json,
+            FLATBUFFER_ENCODING => {
+                let parsed = flatbuffers::Schema::parse(schema.data.as_ref())?;
+                let object = parsed
+                    .find_object(&schema.name)
+                    .ok_or_else(|| anyhow::anyhow!("schema does not contain {}", schema.name))?;
+                MessageDecoder::FlatBuffer {
+                    schema: Arc::new(parsed),
+                    object,
+                }
+            }
+            "protobuf" => {
+                let pool = DescriptorPool::decode(schema.data.as_ref())?;
+                let descriptor = pool
+                    .get_message_by_name(&schema.name)
+                    .ok_or_else(|| anyhow::anyhow!("schema does not contain {}", schema.name))?;
+                MessageDecoder::Protobuf(descriptor)
+            }
+            _ => return Ok(None),
+        }))
+    }
+}
+
+impl Default for McapFoxgloveLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageLayer for McapFoxgloveLayer {
+    fn identifier() -> super::LayerIdentifier {
+        "foxglove".into()
+    }
+
+    fn init(&mut self, summary: &mcap::Summary) -> Result<(), Error> {
+        for channel in summary.channels.values() {
+            if !channel
+                .schema
+                .as_ref()
+                .is_some_and(|schema| self.supports_schema(&schema.name))
+            {
+                continue;
+            }
+
+            // Invalid schemas are reported by the layers that decode the message encoding itself.
+            match Self::decoder(channel) {
+                Ok(Some(decoder)) => {
+                    self.decoders_per_topic
+                        .insert(channel.topic.clone(), decoder);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    re_log::warn_once!(
+                        "Failed to read the schema of topic {}, it will not be interpreted as a Foxglove message: {err}",
+                        channel.topic
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn supports_channel(&self, channel: &mcap::Channel<'_>) -> bool {
+        self.decoders_per_topic.contains_key(&channel.topic)
+            && channel
+                .schema
+                .as_ref()
+                .is_some_and(|s| self.registry.contains_key(&s.name))
+    }
+
+    fn message_parser(
+        &self,
+        channel: &mcap::Channel<'_>,
+        num_rows: usize,
+    ) -> Option<Box<dyn MessageParser>> {
+        let decoder = self.decoders_per_topic.get(&channel.topic)?;
+        let schema = channel.schema.as_ref()?;
+        let make = self.registry.get(&schema.name)?;
+        Some(make(num_rows, decoder.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+
+    use base64::Engine as _;
+    use flatbuffers::{FlatBufferBuilder, WIPOffset};
+    use prost_reflect::{
+        DescriptorPool, DynamicMessage,
+        prost::Message as _,
+        prost_types::{
+            DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+            field_descriptor_proto::Type,
+        },
+    };
+    use re_chunk::{Chunk, TimelineName};
+    use re_types::{
+        archetypes::{Points3D, Transform3D},
+        components::{RotationQuat, TransformFrameId, Translation3D},
+        datatypes::Quaternion,
+    };
+
+    use crate::{LayerRegistry, layers::McapFoxgloveLayer};
+
+    fn write_json_messages<W: io::Write + io::Seek>(
+        writer: &mut mcap::Writer<W>,
+        schema_name: &str,
+        topic: &str,
+        messages: &[serde_json::Value],
+    ) -> mcap::McapResult<()> {
+        // The layer does not need the JSON schema itself, only its name.
+        let schema_id = writer.add_schema(schema_name, "jsonschema", b"{}")?;
+        let channel_id = writer.add_channel(schema_id, topic, "json", &Default::default())?;
+
+        for (timestamp, message) in (42..).zip(messages) {
+            let header = mcap::records::MessageHeader {
+                channel_id,
+                sequence: 0,
+                log_time: timestamp,
+                publish_time: timestamp,
+            };
+            writer.write_to_known_channel(&header, message.to_string().as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    fn run_layer(summary: &mcap::Summary, buffer: &[u8]) -> Vec<Chunk> {
+        let mut chunks = Vec::new();
+
+        let mut send_chunk = |chunk| {
+            chunks.push(chunk);
+        };
+
+        let registry = LayerRegistry::empty().register_message_layer::<McapFoxgloveLayer>();
+        registry
+            .plan(summary)
+            .expect("failed to plan")
+            .run(buffer, summary, &mut send_chunk)
+            .expect("failed to run layer");
+
+        chunks
+    }
+
+    #[test]
+    fn json_frame_transform_and_point_cloud() {
+        let points = [[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]]
+            .iter()
+            .flat_map(|point| point.iter().flat_map(|x| x.to_le_bytes()))
+            .collect::<Vec<_>>();
+
+        // Writing to the MCAP buffer.
+        let (summary, buffer) = {
+            let buffer = Vec::new();
+            let cursor = io::Cursor::new(buffer);
+            let mut writer = mcap::Writer::new(cursor).expect("failed to create writer");
+
+            write_json_messages(
+                &mut writer,
+                "foxglove.FrameTransform",
+                "tf",
+                &[serde_json::json!({
+                    "timestamp": { "sec": 1, "nsec": 500 },
+                    "parent_frame_id": "world",
+                    "child_frame_id": "base_link",
+                    "translation": { "x": 1.0, "y": 2.0, "z": 3.0 },
+                    "rotation": { "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0 }
+                })],
+            )
+            .expect("failed to write transforms");
+
+            write_json_messages(
+                &mut writer,
+                "foxglove.PointCloud",
+                "points",
+                &[serde_json::json!({
+                    "timestamp": { "sec": 1, "nsec": 0 },
+                    "frame_id": "base_link",
+                    // The pose is applied to all points.
+                    "pose": { "position": { "x": 10.0, "y": 0.0, "z": 0.0 } },
+                    "point_stride": 12,
+                    "fields": [
+                        { "name": "x", "offset": 0, "type": 7 },
+                        { "name": "y", "offset": 4, "type": 7 },
+                        { "name": "z", "offset": 8, "type": 7 }
+                    ],
+                    "data": base64::engine::general_purpose::STANDARD.encode(&points)
+                })],
+            )
+            .expect("failed to write point cloud");
+
+            let summary = writer.finish().expect("finishing writer failed");
+
+            (summary, writer.into_inner().into_inner())
+        };
+
+        let chunks = run_layer(&summary, buffer.as_slice());
+
+        let transform_chunk = chunks
+            .iter()
+            .find(|chunk| chunk.entity_path() == &"/tf/base_link".into())
+            .expect("missing transform chunk");
+        for descriptor in [
+            Transform3D::descriptor_translation(),
+            Transform3D::descriptor_quaternion(),
+            Transform3D::descriptor_child_frame(),
+            Transform3D::descriptor_parent_frame(),
+        ] {
+            assert!(
+                transform_chunk
+                    .components()
+                    .contains_component(descriptor.component),
+                "missing {descriptor}"
+            );
+        }
+
+        let points_chunk = chunks
+            .iter()
+            .find(|chunk| {
+                chunk
+                    .components()
+                    .contains_component(Points3D::descriptor_positions().component)
+            })
+            .expect("missing point cloud chunk");
+        assert_eq!(points_chunk.entity_path(), &"/points".into());
+
+        insta::assert_snapshot!(
+            "json_frame_transform_and_point_cloud",
+            format!("{:-240}", points_chunk)
+        );
+    }
+
+    fn write_message<W: io::Write + io::Seek>(
+        writer: &mut mcap::Writer<W>,
+        (schema_encoding, schema_data): (&str, &[u8]),
+        message_encoding: &str,
+        message: &[u8],
+    ) -> mcap::McapResult<()> {
+        let schema_id =
+            writer.add_schema("foxglove.FrameTransform", schema_encoding, schema_data)?;
+        let channel_id =
+            writer.add_channel(schema_id, "tf", message_encoding, &Default::default())?;
+
+        let header = mcap::records::MessageHeader {
+            channel_id,
+            sequence: 0,
+            log_time: 42,
+            publish_time: 42,
+        };
+        writer.write_to_known_channel(&header, message)
+    }
+
+    /// Runs the layer on a single `foxglove.FrameTransform` message from `world` to `base_link`,
+    /// with a translation of `(1, 2, 3)`, a rotation of `(0, 0, 1, 0)` and a timestamp of `1.0000005s`.
+    fn run_frame_transform(schema: (&str, &[u8]), message_encoding: &str, message: &[u8]) {
+        let (summary, buffer) = {
+            let cursor = io::Cursor::new(Vec::new());
+            let mut writer = mcap::Writer::new(cursor).expect("failed to create writer");
+            write_message(&mut writer, schema, message_encoding, message)
+                .expect("failed to write transform");
+            let summary = writer.finish().expect("finishing writer failed");
+            (summary, writer.into_inner().into_inner())
+        };
+
+        let chunks = run_layer(&summary, buffer.as_slice());
+
+        let chunk = chunks
+            .iter()
+            .find(|chunk| chunk.entity_path() == &"/tf/base_link".into())
+            .expect("missing transform chunk");
+        assert_eq!(chunk.num_rows(), 1);
+
+        assert_eq!(
+            chunk
+                .component_mono::<Translation3D>(Transform3D::descriptor_translation().component, 0)
+                .unwrap()
+                .unwrap(),
+            Translation3D::new(1.0, 2.0, 3.0)
+        );
+        assert_eq!(
+            chunk
+                .component_mono::<RotationQuat>(Transform3D::descriptor_quaternion().component, 0)
+                .unwrap()
+                .unwrap(),
+            RotationQuat::from(Quaternion::from_xyzw([0.0, 0.0, 1.0, 0.0]))
+        );
+        assert_eq!(
+            chunk
+                .component_mono::<TransformFrameId>(
+                    Transform3D::descriptor_parent_frame().component,
+                    0
+                )
+                .unwrap()
+                .unwrap(),
+            TransformFrameId::new("world")
+        );
+
+        // Way before 1990, so this is interpreted as a duration.
+        let timestamps = chunk
+            .timelines()
+            .get(&TimelineName::new("duration"))
+            .expect("missing timestamp");
+        assert_eq!(timestamps.times_raw(), &[1_000_000_500]);
+    }
+
+    #[test]
+    fn json_frame_transform() {
+        let message = serde_json::json!({
+            "timestamp": { "sec": 1, "nsec": 500 },
+            "parent_frame_id": "world",
+            "child_frame_id": "base_link",
+            "translation": { "x": 1.0, "y": 2.0, "z": 3.0 },
+            "rotation": { "x": 0.0, "y": 0.0, "z": 1.0, "w": 0.0 }
+        });
+
+        run_frame_transform(
+            ("jsonschema", b"{}"),
+            "json",
+            message.to_string().as_bytes(),
+        );
+    }
+
+    /// The descriptors of `foxglove.FrameTransform` and its dependencies.
+    fn frame_transform_descriptors() -> DescriptorPool {
+        let field =
+            |name: &str, number: i32, ty: Type, type_name: Option<&str>| FieldDescriptorProto {
+                name: Some(name.into()),
+                number: Some(number),
+                r#type: Some(ty as i32),
+                type_name: type_name.map(Into::into),
+                ..Default::default()
+            };
+        let message = |name: &str, fields: Vec<FieldDescriptorProto>| DescriptorProto {
+            name: Some(name.into()),
+            field: fields,
+            ..Default::default()
+        };
+
+        let timestamp_file = FileDescriptorProto {
+            name: Some("google/protobuf/timestamp.proto".into()),
+            package: Some("google.protobuf".into()),
+            message_type: vec![message(
+                "Timestamp",
+                vec![
+                    field("seconds", 1, Type::Int64, None),
+                    field("nanos", 2, Type::Int32, None),
+                ],
+            )],
+            syntax: Some("proto3".into()),
+            ..Default::default()
+        };
+
+        let foxglove_file = FileDescriptorProto {
+            name: Some("foxglove/FrameTransform.proto".into()),
+            package: Some("foxglove".into()),
+            dependency: vec!["google/protobuf/timestamp.proto".into()],
+            message_type: vec![
+                message(
+                    "Vector3",
+                    vec![
+                        field("x", 1, Type::Double, None),
+                        field("y", 2, Type::Double, None),
+                        field("z", 3, Type::Double, None),
+                    ],
+                ),
+                message(
+                    "Quaternion",
+                    vec![
+                        field("x", 1, Type::Double, None),
+                        field("y", 2, Type::Double, None),
+                        field("z", 3, Type::Double, None),
+                        field("w", 4, Type::Double, None),
+                    ],
+                ),
+                message(
+                    "FrameTransform",
+                    vec![
+                        field(
+                            "timestamp",
+                            1,
+                            Type::Message,
+                            Some(".google.protobuf.Timestamp"),
+                        ),
+                        field("parent_frame_id", 2, Type::String, None),
+                        field("child_frame_id", 3, Type::String, None),
+                        field("translation", 4, Type::Message, Some(".foxglove.Vector3")),
+                        field("rotation", 5, Type::Message, Some(".foxglove.Quaternion")),
+                    ],
+                ),
+            ],
+            syntax: Some("proto3".into()),
+            ..Default::default()
+        };
+
+        let encoded = FileDescriptorSet {
+            file: vec![timestamp_file, foxglove_file],
+        }
+        .encode_to_vec();
+
+        DescriptorPool::decode(encoded.as_slice()).expect("failed to decode descriptor pool")
+    }
+
+    #[test]
+    fn protobuf_frame_transform() {
+        let pool = frame_transform_descriptors();
+        let descriptor = pool
+            .get_message_by_name("foxglove.FrameTransform")
+            .expect("missing message descriptor");
+
+        let message = DynamicMessage::parse_text_format(
+            descriptor,
+            r#"
+                timestamp { seconds: 1 nanos: 500 }
+                parent_frame_id: "world"
+                child_frame_id: "base_link"
+                translation { x: 1 y: 2 z: 3 }
+                rotation { z: 1 }
+            "#,
+        )
+        .expect("failed to parse text format");
+
+        run_frame_transform(
+            ("protobuf", pool.encode_to_vec().as_slice()),
+            "protobuf",
+            &message.encode_to_vec(),
+        );
+    }
+
+    /// The vtable offset of the field in the given slot.
+    const fn slot(id: u16) -> u16 {
+        4 + 2 * id
+    }
+
+    /// The `base_type` values of `reflection.fbs`.
+    const UINT: u8 = 8;
+    const DOUBLE: u8 = 12;
+    const STRING: u8 = 13;
+    const OBJ: u8 = 15;
+
+    /// Creates a `reflection.fbs` object, `fields` being `(name, base_type, object index)`.
+    ///
+    /// Structs are only made of 4 bytes wide fields.
+    fn create_object(
+        fbb: &mut FlatBufferBuilder<'_>,
+        name: &str,
+        fields: &[(&str, u8, i32)],
+        is_struct: bool,
+    ) -> WIPOffset<flatbuffers::TableFinishedWIPOffset> {
+        let fields = fields
+            .iter()
+            .zip(0u16..)
+            .map(|(&(name, base_type, index), id)| {
+                let ty = fbb.start_table();
+                fbb.push_slot_always::<u8>(slot(0), base_type);
+                fbb.push_slot_always::<i32>(slot(2), index);
+                let ty = fbb.end_table(ty);
+
+                let name = fbb.create_string(name);
+                let start = fbb.start_table();
+                fbb.push_slot_always(slot(0), name);
+                fbb.push_slot_always(slot(1), ty);
+                fbb.push_slot_always::<u16>(slot(2), id);
+                if is_struct {
+                    fbb.push_slot_always::<u16>(slot(3), 4 * id);
+                }
+                fbb.end_table(start)
+            })
+            .collect::<Vec<_>>();
+        let num_fields = fields.len() as i32;
+
+        let fields = fbb.create_vector(&fields);
+        let name = fbb.create_string(name);
+        let start = fbb.start_table();
+        fbb.push_slot_always(slot(0), name);
+        fbb.push_slot_always(slot(1), fields);
+        if is_struct {
+            fbb.push_slot_always::<bool>(slot(2), true);
+            fbb.push_slot_always::<i32>(slot(4), 4 * num_fields);
+        }
+        fbb.end_table(start)
+    }
+
+    /// Creates the binary schema of:
+    ///
+    /// ```fbs
+    /// namespace foxglove;
+    /// table FrameTransform {
+    ///   timestamp: Time; parent_frame_id: string; child_frame_id: string;
+    ///   translation: Vector3; rotation: Quaternion;
+    /// }
+    /// table Quaternion { x: double; y: double; z: double; w: double; }
+    /// struct Time { sec: uint; nsec: uint; }
+    /// table Vector3 { x: double; y: double; z: double; }
+    /// root_type FrameTransform;
+    /// ```
+    fn create_frame_transform_schema() -> Vec<u8> {
+        let mut fbb = FlatBufferBuilder::new();
+
+        // Objects are sorted by name.
+        let frame_transform = create_object(
+            &mut fbb,
+            "foxglove.FrameTransform",
+            &[
+                ("timestamp", OBJ, 2),
+                ("parent_frame_id", STRING, -1),
+                ("child_frame_id", STRING, -1),
+                ("translation", OBJ, 3),
+                ("rotation", OBJ, 1),
+            ],
+            false,
+        );
+        let quaternion = create_object(
+            &mut fbb,
+            "foxglove.Quaternion",
+            &[
+                ("x", DOUBLE, -1),
+                ("y", DOUBLE, -1),
+                ("z", DOUBLE, -1),
+                ("w", DOUBLE, -1),
+            ],
+            false,
+        );
+        let time = create_object(
+            &mut fbb,
+            "foxglove.Time",
+            &[("sec", UINT, -1), ("nsec", UINT, -1)],
+            true,
+        );
+        let vector3 = create_object(
+            &mut fbb,
+            "foxglove.Vector3",
+            &[("x", DOUBLE, -1), ("y", DOUBLE, -1), ("z", DOUBLE, -1)],
+            false,
+        );
+
+        let objects = fbb.create_vector(&[frame_transform, quaternion, time, vector3]);
+        let start = fbb.start_table();
+        fbb.push_slot_always(slot(0), objects);
+        fbb.push_slot_always(slot(4), frame_transform);
+        let schema = fbb.end_table(start);
+
+        fbb.finish(schema, Some("BFBS"));
+        fbb.finished_data().to_vec()
+    }
+
+    fn create_frame_transform() -> Vec<u8> {
+        let mut fbb = FlatBufferBuilder::new();
+
+        let mut create_doubles = |values: &[f64]| {
+            let start = fbb.start_table();
+            for (value, id) in values.iter().zip(0u16..) {
+                fbb.push_slot_always(slot(id), *value);
+            }
+            fbb.end_table(start)
+        };
+        let translation = create_doubles(&[1.0, 2.0, 3.0]);
+        let rotation = create_doubles(&[0.0, 0.0, 1.0, 0.0]);
+
+        let parent_frame_id = fbb.create_string("world");
+        let child_frame_id = fbb.create_string("base_link");
+
+        let start = fbb.start_table();
+        // `Time { sec: 1, nsec: 500 }` is a struct, i.e. stored inline: its two `uint`s are laid
+        // out exactly like a single little-endian `ulong`.
+        fbb.push_slot_always::<u64>(slot(0), (500 << 32) | 1);
+        fbb.push_slot_always(slot(1), parent_frame_id);
+        fbb.push_slot_always(slot(2), child_frame_id);
+        fbb.push_slot_always(slot(3), translation);
+        fbb.push_slot_always(slot(4), rotation);
+        let frame_transform = fbb.end_table(start);
+
+        fbb.finish(frame_transform, None);
+        fbb.finished_data().to_vec()
+    }
+
+    #[test]
+    fn flatbuffers_frame_transform() {
+        run_frame_transform(
+            ("flatbuffer", &create_frame_transform_schema()),
+            "flatbuffer",
+            &create_frame_transform(),
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array as _, ArrayRef, FixedSizeListArray, StructArray},
+    datatypes::{DataType, Field, Fields, Schema},
+    json::reader::{Decoder, ReaderBuilder},
+};
+use re_chunk::{Chunk, ChunkId};
+use re_types::{ComponentDescriptor, reflection::ComponentDescriptorExt as _};
+use serde_json::Value;
+
+use crate::parsers::{MessageParser, ParserContext};
+use crate::{Error, LayerIdentifier, MessageLayer};
+
+/// Schemas whose objects are nested deeper than this are considered to be recursive.
+const MAX_SCHEMA_DEPTH: usize = 64;
+
+/// The schema encoding of JSON channels.
+pub(crate) const JSON_SCHEMA_ENCODING: &str = "jsonschema";
+
+#[derive(Debug, thiserror::Error)]
+enum JsonError {
+    #[error("invalid message on channel {channel} for schema {schema}: {source}")]
+    InvalidMessage {
+        schema: String,
+        channel: String,
+        source: arrow::error::ArrowError,
+    },
+
+    #[error("expected a single JSON object per message, but found {0}")]
+    UnexpectedRows(usize),
+}
+
+/// The schema of the messages of a channel, as Arrow fields.
+#[derive(Clone, Debug)]
+struct MessageSchema {
+    name: String,
+    fields: Fields,
+}
+
+struct JsonMessageParser {
+    schema: MessageSchema,
+    decoder: Decoder,
+
+    /// Each message is decoded into its own single-row array.
+    rows: Vec<ArrayRef>,
+}
+
+impl JsonMessageParser {
+    fn new(num_rows: usize, schema: MessageSchema) -> Result<Self, arrow::error::ArrowError> {
+        Ok(Self {
+            decoder: Self::decoder(&schema.fields)?,
+            schema,
+            rows: Vec::with_capacity(num_rows),
+        })
+    }
+
+    fn decoder(fields: &Fields) -> Result<Decoder, arrow::error::ArrowError> {
+        // Properties that are not part of the schema are ignored.
+        ReaderBuilder::new(Arc::new(Schema::new_with_metadata(
+            fields.clone(),
+            Default::default(),
+        )))
+        .with_strict_mode(false)
+        .build_decoder()
+    }
+
+    fn decode(&mut self, data: &[u8]) -> Result<Option<StructArray>, arrow::error::ArrowError> {
+        let num_bytes_read = self.decoder.decode(data)?;
+        if num_bytes_read != data.len() {
+            return Err(arrow::error::ArrowError::JsonError(format!(
+                "unexpected trailing data after {num_bytes_read} of {} bytes",
+                data.len()
+            )));
+        }
+
+        Ok(self.decoder.flush()?.map(StructArray::from))
+    }
+}
+
+impl MessageParser for JsonMessageParser {
+    fn append(&mut self, _ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        re_tracing::profile_function!();
+
+        let row = match self.decode(&msg.data) {
+            Ok(row) => row,
+            Err(err) => {
+                // The decoder may still hold parts of the invalid message.
+                self.decoder = Self::decoder(&self.schema.fields)?;
+                return Err(JsonError::InvalidMessage {
+                    schema: self.schema.name.clone(),
+                    channel: msg.channel.topic.clone(),
+                    source: err,
+                }
+                .into());
+            }
+        };
+
+        let num_rows = row.as_ref().map_or(0, |row| row.len());
+        let Some(row) = row.filter(|_| num_rows == 1) else {
+            return Err(JsonError::UnexpectedRows(num_rows).into());
+        };
+
+        self.rows.push(Arc::new(row));
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<re_chunk::Chunk>> {
+        re_tracing::profile_function!();
+        let entity_path = ctx.entity_path().clone();
+        let timelines = ctx.build_timelines();
+
+        let Self { schema, rows, .. } = *self;
+
+        let datatype = DataType::Struct(schema.fields);
+        let values = if rows.is_empty() {
+            arrow::array::new_empty_array(&datatype)
+        } else {
+            re_arrow_util::concat_arrays(&rows.iter().map(|row| row.as_ref()).collect::<Vec<_>>())?
+        };
+
+        let message_array = FixedSizeListArray::try_new(
+            Arc::new(Field::new_list_field(datatype, true)),
+            1,
+            values,
+            None,
+        )?;
+
+        let message_chunk = Chunk::from_auto_row_ids(
+            ChunkId::new(),
+            entity_path,
+            timelines,
+            std::iter::once((
+                ComponentDescriptor::partial("message").with_builtin_archetype(schema.name),
+                message_array.into(),
+            ))
+            .collect(),
+        )
+        .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
+
+        Ok(vec![message_chunk])
+    }
+}
+
+/// Derives the Arrow datatype for a (sub-)schema, `None` if it can not be represented.
+///
+/// Only local references (`#/...`) are resolved. Fields with schemas that can't be represented
+/// in Arrow, such as objects without properties, are skipped.
+fn datatype_from_schema(schema: &Value, root: &Value, depth: usize) -> Option<DataType> {
+    if depth >= MAX_SCHEMA_DEPTH {
+        re_log::warn_once!("Recursive JSON schemas are not supported.");
+        return None;
+    }
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let resolved = reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer));
+        if resolved.is_none() {
+            re_log::warn_once!("Could not resolve JSON schema reference {reference:?}.");
+        }
+        return datatype_from_schema(resolved?, root, depth + 1);
+    }
+
+    let ty = match schema.get("type") {
+        Some(Value::String(ty)) => Some(ty.as_str()),
+        // Nullable types are written as e.g. `["string", "null"]`.
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|ty| *ty != "null"),
+        _ => None,
+    };
+
+    match ty {
+        Some("boolean") => Some(DataType::Boolean),
+        Some("integer") => Some(DataType::Int64),
+        Some("number") => Some(DataType::Float64),
+        Some("string") => Some(DataType::Utf8),
+        Some("array") => {
+            let items = schema.get("items")?;
+            Some(DataType::new_list(
+                datatype_from_schema(items, root, depth + 1)?,
+                true,
+            ))
+        }
+        Some("object") => {
+            let properties = schema.get("properties")?.as_object()?;
+            let fields = properties
+                .iter()
+                .filter_map(|(name, property)| {
+                    let datatype = datatype_from_schema(property, root, depth + 1);
+                    if datatype.is_none() {
+                        re_log::warn_once!(
+                            "JSON schema of property {name:?} is not supported, it will be skipped."
+                        );
+                    }
+                    Some(Field::new(name, datatype?, true))
+                })
+                .collect::<Fields>();
+            Some(DataType::Struct(fields))
+        }
+        Some(_) => None,
+        None => {
+            // Enums are usually described by their possible values, e.g. via `oneOf` and `const`.
+            if let Some(value) = schema.get("const") {
+                return datatype_from_value(value);
+            }
+
+            let alternatives = schema
+                .get("enum")
+                .or_else(|| schema.get("oneOf"))
+                .or_else(|| schema.get("anyOf"))?
+                .as_array()?;
+
+            let mut datatypes = alternatives.iter().map(|alternative| {
+                if schema.get("enum").is_some() {
+                    datatype_from_value(alternative)
+                } else {
+                    datatype_from_schema(alternative, root, depth + 1)
+                }
+            });
+
+            let first = datatypes.next()??;
+            datatypes
+                .all(|datatype| datatype.as_ref() == Some(&first))
+                .then_some(first)
+        }
+    }
+}
+
+/// The Arrow datatype of a value that is part of a schema, e.g. as `const`.
+fn datatype_from_value(value: &Value) -> Option<DataType> {
+    match value {
+        Value::Bool(_) => Some(DataType::Boolean),
+        Value::Number(number) if number.is_f64() => Some(DataType::Float64),
+        Value::Number(_) => Some(DataType::Int64),
+        Value::String(_) => Some(DataType::Utf8),
+        Value::Null | Value::Array(_) | Value::Object(_) => None,
+    }
+}
+
+/// Provides JSON Schema-based conversion of JSON-encoded MCAP messages.
+///
+/// The JSON Schema of each channel is used to decode its messages into a direct Arrow
+/// representation of their properties, similar to the [`super::McapProtobufLayer`].
+/// This does not result in semantic types that can be picked up by the Rerun viewer.
+#[derive(Debug, Default)]
+pub struct McapJsonLayer {
+    schemas_per_topic: ahash::HashMap<String, MessageSchema>,
+}
+
+impl MessageLayer for McapJsonLayer {
+    fn identifier() -> LayerIdentifier {
+        "json".into()
+    }
+
+    fn init(&mut self, summary: &mcap::Summary) -> Result<(), Error> {
+        for channel in summary.channels.values() {
+            let Some(schema) = channel.schema.as_ref() else {
+                continue;
+            };
+
+            if schema.encoding.as_str() != JSON_SCHEMA_ENCODING {
+                continue;
+            }
+
+            let json_schema =
+                serde_json::from_slice::<Value>(schema.data.as_ref()).map_err(|err| {
+                    Error::InvalidSchema {
+                        schema: schema.name.clone(),
+                        source: err.into(),
+                    }
+                })?;
+
+            let Some(DataType::Struct(fields)) =
+                datatype_from_schema(&json_schema, &json_schema, 0)
+            else {
+                re_log::warn_once!(
+                    "JSON schema {} does not describe an object with properties, topic {} will be skipped.",
+                    schema.name,
+                    channel.topic
+                );
+                continue;
+            };
+
+            self.schemas_per_topic.insert(
+                channel.topic.clone(),
+                MessageSchema {
+                    name: schema.name.clone(),
+                    fields,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    fn supports_channel(&self, channel: &mcap::Channel<'_>) -> bool {
+        let Some(schema) = channel.schema.as_ref() else {
+            return false;
+        };
+
+        if schema.encoding.as_str() != JSON_SCHEMA_ENCODING {
+            return false;
+        }
+
+        self.schemas_per_topic.contains_key(&channel.topic)
+    }
+
+    fn message_parser(
+        &self,
+        channel: &mcap::Channel<'_>,
+        num_rows: usize,
+    ) -> Option<Box<dyn MessageParser>> {
+        let schema = self.schemas_per_topic.get(&channel.topic)?;
+        match JsonMessageParser::new(num_rows, schema.clone()) {
+            Ok(parser) => Some(Box::new(parser)),
+            Err(err) => {
+                re_log::warn_once!(
+                    "Failed to create parser for JSON schema {}: {err}",
+                    schema.name
+                );
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+
+    use arrow::datatypes::{DataType, Field, Fields};
+    use re_chunk::Chunk;
+
+    use super::{JsonMessageParser, MessageSchema};
+    use crate::{LayerRegistry, layers::McapJsonLayer};
+
+    const PERSON_SCHEMA: &str = r##"{
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "id": { "type": ["integer", "null"] },
+            "position": { "$ref": "#/$defs/Vector2" },
+            "tags": { "type": "array", "items": { "type": "string" } },
+            "status": { "enum": ["ACTIVE", "INACTIVE"] }
+        },
+        "$defs": {
+            "Vector2": {
+                "type": "object",
+                "properties": {
+                    "x": { "type": "number" },
+                    "y": { "type": "number" }
+                }
+            }
+        }
+    }"##;
+
+    fn run_layer(summary: &mcap::Summary, buffer: &[u8]) -> Vec<Chunk> {
+        let mut chunks = Vec::new();
+
+        let mut send_chunk = |chunk| {
+            chunks.push(chunk);
+        };
+
+        let registry = LayerRegistry::empty().register_message_layer::<McapJsonLayer>();
+        registry
+            .plan(summary)
+            .expect("failed to plan")
+            .run(buffer, summary, &mut send_chunk)
+            .expect("failed to run layer");
+
+        chunks
+    }
+
+    #[test]
+    fn two_simple_rows() {
+        // Writing to the MCAP buffer.
+        let (summary, buffer) = {
+            let buffer = Vec::new();
+            let cursor = io::Cursor::new(buffer);
+            let mut writer = mcap::Writer::new(cursor).expect("failed to create writer");
+
+            let schema_id = writer
+                .add_schema("com.example.Person", "jsonschema", PERSON_SCHEMA.as_bytes())
+                .expect("failed to add schema");
+            let channel_id = writer
+                .add_channel(schema_id, "test_topic", "json", &Default::default())
+                .expect("failed to add channel");
+
+            let messages = [
+                r#"{"name": "Bob", "position": {"x": 1.5, "y": -2}, "status": "INACTIVE"}"#,
+                r#"{"name": "Alice", "id": 123, "tags": ["a", "b"], "unknown": true}"#,
+            ];
+
+            for (timestamp, message) in (42..).zip(messages) {
+                let header = mcap::records::MessageHeader {
+                    channel_id,
+                    sequence: 0,
+                    log_time: timestamp,
+                    publish_time: timestamp,
+                };
+                writer
+                    .write_to_known_channel(&header, message.as_bytes())
+                    .expect("failed to write message");
+            }
+
+            let summary = writer.finish().expect("finishing writer failed");
+
+            (summary, writer.into_inner().into_inner())
+        };
+
+        let chunks = run_layer(&summary, buffer.as_slice());
+        assert_eq!(chunks.len(), 1);
+
+        insta::assert_snapshot!("two_simple_rows", format!("{:-240}", &chunks[0]));
+    }
+
+    #[test]
+    fn trailing_data() {
+        let parser = || {
+            let schema = MessageSchema {
+                name: "com.example.Value".to_owned(),
+                fields: Fields::from(vec![Field::new("value", DataType::Int64, true)]),
+            };
+            JsonMessageParser::new(1, schema).expect("failed to create parser")
+        };
+
+        let row = parser()
+            .decode(b"{\"value\": 1}\n")
+            .expect("trailing whitespace is fine")
+            .expect("missing row");
+        assert_eq!(row.len(), 1);
+
+        assert!(parser().decode(b"{\"value\": 1} garbage").is_err());
+    }
+}
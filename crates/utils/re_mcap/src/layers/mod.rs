@@ -1,6 +1,10 @@
+mod flatbuffers;
+mod foxglove;
+mod json;
 mod protobuf;
 mod raw;
 mod recording_info;
+mod ros1;
 mod ros2;
 mod ros2_reflection;
 mod schema;
@@ -10,9 +14,10 @@ use re_chunk::{Chunk, EntityPath, external::nohash_hasher::IntMap};
 use std::collections::{BTreeMap, BTreeSet};
 
 pub use self::{
+    flatbuffers::McapFlatBuffersLayer, foxglove::McapFoxgloveLayer, json::McapJsonLayer,
     protobuf::McapProtobufLayer, raw::McapRawLayer, recording_info::McapRecordingInfoLayer,
-    ros2::McapRos2Layer, ros2_reflection::McapRos2ReflectionLayer, schema::McapSchemaLayer,
-    stats::McapStatisticLayer,
+    ros1::McapRos1Layer, ros2::McapRos2Layer, ros2_reflection::McapRos2ReflectionLayer,
+    schema::McapSchemaLayer, stats::McapStatisticLayer,
 };
 
 use crate::{
@@ -181,13 +186,23 @@ pub enum Fallback {
 
 /// A runner that constrains a [`MessageLayer`] to a specific set of channels.
 pub struct MessageLayerRunner {
+    id: LayerIdentifier,
     inner: Box<dyn MessageLayer>,
     allowed: BTreeSet<ChannelId>,
 }
 
 impl MessageLayerRunner {
-    fn new(inner: Box<dyn MessageLayer>, allowed: BTreeSet<ChannelId>) -> Self {
-        Self { inner, allowed }
+    fn new(
+        id: LayerIdentifier,
+        inner: Box<dyn MessageLayer>,
+        allowed: BTreeSet<ChannelId>,
+    ) -> Self {
+        Self { id, inner, allowed }
+    }
+
+    /// The identifier of the wrapped [`MessageLayer`].
+    pub fn layer_identifier(&self) -> &LayerIdentifier {
+        &self.id
     }
 }
 
@@ -209,47 +224,81 @@ impl Layer for MessageLayerRunner {
         self.inner.init(summary)?;
 
         for chunk in &summary.chunk_indexes {
-            let parsers = summary
-                .read_message_indexes(mcap_bytes, chunk)?
-                .iter()
-                .filter_map(|(channel, msg_offsets)| {
-                    let channel_id = ChannelId::from(channel.id);
-                    if !self.allowed.contains(&channel_id) {
-                        return None;
-                    }
+            let message_indexes = summary.read_message_indexes(mcap_bytes, chunk)?;
+            self.decode_chunk(
+                message_indexes
+                    .iter()
+                    .map(|(channel, msg_offsets)| (channel.as_ref(), msg_offsets.len())),
+                summary.stream_chunk(mcap_bytes, chunk)?,
+                emit,
+            );
+        }
+
+        Ok(())
+    }
+}
 
-                    let parser = self.inner.message_parser(channel, msg_offsets.len())?;
-                    let entity_path = EntityPath::from(channel.topic.as_str());
-                    let ctx = ParserContext::new(entity_path);
-                    Some((channel_id, (ctx, parser)))
-                })
-                .collect::<IntMap<_, _>>();
-
-            let mut decoder = McapChunkDecoder::new(parsers);
-
-            for msg in summary.stream_chunk(mcap_bytes, chunk)? {
-                match msg {
-                    Ok(message) => {
-                        if let Err(err) = decoder.decode_next(&message) {
-                            re_log::error!(
-                                "Failed to decode message on channel {}: {err}",
-                                message.channel.topic
-                            );
-                        }
+impl MessageLayerRunner {
+    /// Decodes the messages of a single chunk, given the number of messages of each of its channels.
+    fn decode_chunk<'c, 'm>(
+        &self,
+        channels: impl Iterator<Item = (&'c mcap::Channel<'c>, usize)>,
+        messages: impl Iterator<Item = Result<mcap::Message<'m>, mcap::McapError>>,
+        emit: &mut dyn FnMut(Chunk),
+    ) {
+        let parsers = channels
+            .filter_map(|(channel, num_rows)| {
+                let channel_id = ChannelId::from(channel.id);
+                if !self.allowed.contains(&channel_id) {
+                    return None;
+                }
+
+                let parser = self.inner.message_parser(channel, num_rows)?;
+                let entity_path = EntityPath::from(channel.topic.as_str());
+                let ctx = ParserContext::new(entity_path);
+                Some((channel_id, (ctx, parser)))
+            })
+            .collect::<IntMap<_, _>>();
+
+        let mut decoder = McapChunkDecoder::new(parsers);
+
+        for msg in messages {
+            match msg {
+                Ok(message) => {
+                    if let Err(err) = decoder.decode_next(&message) {
+                        re_log::error!(
+                            "Failed to decode message on channel {}: {err}",
+                            message.channel.topic
+                        );
                     }
-                    Err(err) => re_log::error!("Failed to read message from MCAP file: {err}"),
                 }
+                Err(err) => re_log::error!("Failed to read message from MCAP file: {err}"),
             }
+        }
 
-            for chunk in decoder.finish() {
-                match chunk {
-                    Ok(c) => emit(c),
-                    Err(err) => re_log::error!("Failed to decode chunk: {err}"),
-                }
+        for chunk in decoder.finish() {
+            match chunk {
+                Ok(c) => emit(c),
+                Err(err) => re_log::error!("Failed to decode chunk: {err}"),
             }
         }
+    }
 
-        Ok(())
+    /// Decodes a batch of messages as if they were the messages of a single chunk.
+    fn decode_batch(&self, messages: &[mcap::Message<'_>], emit: &mut dyn FnMut(Chunk)) {
+        let mut channels = IntMap::<ChannelId, (&mcap::Channel<'_>, usize)>::default();
+        for message in messages {
+            channels
+                .entry(ChannelId::from(message.channel.id))
+                .or_insert((message.channel.as_ref(), 0))
+                .1 += 1;
+        }
+
+        self.decode_chunk(
+            channels.into_values(),
+            messages.iter().cloned().map(Ok),
+            emit,
+        );
     }
 }
 
@@ -265,27 +314,69 @@ pub struct LayerAssignment {
 
 /// A concrete execution plan for a given MCAP source.
 pub struct ExecutionPlan {
-    pub file_layers: Vec<Box<dyn Layer>>,
+    pub file_layers: Vec<(LayerIdentifier, Box<dyn Layer>)>,
     pub runners: Vec<MessageLayerRunner>,
     pub assignments: Vec<LayerAssignment>,
 }
 
 impl ExecutionPlan {
     pub fn run(
-        mut self,
+        self,
         mcap_bytes: &[u8],
         summary: &mcap::Summary,
         emit: &mut dyn FnMut(Chunk),
     ) -> anyhow::Result<()> {
-        for mut layer in self.file_layers {
-            layer.process(mcap_bytes, summary, emit)?;
+        self.run_per_layer(mcap_bytes, summary, &mut |_, chunk| emit(chunk))
+    }
+
+    /// Same as [`Self::run`], but also reports which layer each chunk was extracted by.
+    pub fn run_per_layer(
+        mut self,
+        mcap_bytes: &[u8],
+        summary: &mcap::Summary,
+        emit: &mut dyn FnMut(&LayerIdentifier, Chunk),
+    ) -> anyhow::Result<()> {
+        for (id, mut layer) in self.file_layers {
+            layer.process(mcap_bytes, summary, &mut |chunk| emit(&id, chunk))?;
         }
 
         for runner in &mut self.runners {
-            runner.process(mcap_bytes, summary, emit)?;
+            let id = runner.layer_identifier().clone();
+            runner.process(mcap_bytes, summary, &mut |chunk| emit(&id, chunk))?;
         }
         Ok(())
     }
+
+    /// Same as [`Self::run`], but for messages that are not read from an MCAP file, e.g. because
+    /// they are converted from another format on the fly.
+    ///
+    /// `summary` has to describe the channels of all messages. `for_each_batch` is expected to pass
+    /// all messages, in consecutive batches, to the callback it is given. Like the chunks of an MCAP
+    /// file, each batch is decoded into its own set of Rerun chunks, so there is no need to hold all
+    /// messages in memory at once.
+    ///
+    /// File layers only get to see the `summary`, they are given no MCAP bytes.
+    pub fn run_batches(
+        mut self,
+        summary: &mcap::Summary,
+        for_each_batch: impl FnOnce(&mut dyn FnMut(&[mcap::Message<'_>])) -> anyhow::Result<()>,
+        emit: &mut dyn FnMut(Chunk),
+    ) -> anyhow::Result<()> {
+        for (_, mut layer) in self.file_layers {
+            layer.process(&[], summary, emit)?;
+        }
+
+        for runner in &mut self.runners {
+            runner.inner.init(summary)?;
+        }
+
+        let runners = &self.runners;
+        for_each_batch(&mut |messages| {
+            for runner in runners {
+                runner.decode_batch(messages, emit);
+            }
+        })
+    }
 }
 
 /// Holds a set of all known layers, split into file-scoped and message-scoped.
@@ -326,8 +417,12 @@ impl LayerRegistry {
             .register_file_layer::<McapStatisticLayer>()
             // message layers (priority order):
             .register_message_layer::<McapRos2Layer>()
+            .register_message_layer::<McapRos1Layer>()
             .register_message_layer::<McapRos2ReflectionLayer>()
-            .register_message_layer::<McapProtobufLayer>();
+            .register_message_layer::<McapFoxgloveLayer>()
+            .register_message_layer::<McapProtobufLayer>()
+            .register_message_layer::<McapJsonLayer>()
+            .register_message_layer::<McapFlatBuffersLayer>();
 
         if raw_fallback_enabled {
             registry = registry
@@ -374,6 +469,11 @@ impl LayerRegistry {
         self
     }
 
+    /// Identifiers of all the layers in this registry, file-scoped ones first.
+    pub fn layer_identifiers(&self) -> impl Iterator<Item = &LayerIdentifier> {
+        self.file_factories.keys().chain(&self.msg_order)
+    }
+
     /// Produce a filtered registry that only contains `selected` layers.
     pub fn select(&self, selected: &SelectedLayers) -> Self {
         let file_factories = self
@@ -418,8 +518,8 @@ impl LayerRegistry {
     pub fn plan(&self, summary: &mcap::Summary) -> anyhow::Result<ExecutionPlan> {
         let file_layers = self
             .file_factories
-            .values()
-            .map(|f| f())
+            .iter()
+            .map(|(id, f)| (id.clone(), f()))
             .collect::<Vec<_>>();
 
         // instantiate message layers and init them (supports_channel may depend on init)
@@ -490,7 +590,7 @@ impl LayerRegistry {
         for (layer_id, allowed) in by_layer {
             if let Some(factory) = self.msg_factories.get(&layer_id) {
                 let inner = factory();
-                runners.push(MessageLayerRunner::new(inner, allowed));
+                runners.push(MessageLayerRunner::new(layer_id, inner, allowed));
             }
         }
 
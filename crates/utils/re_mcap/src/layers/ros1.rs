@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+use super::MessageLayer;
+use crate::parsers::{
+    MessageParser,
+    ros2msg::{
+        ROS1_MESSAGE_ENCODING, Ros2MessageParser,
+        geometry_msgs::{PoseStampedMessageParser, TwistStampedMessageParser},
+        nav_msgs::{OccupancyGridMessageParser, OdometryMessageParser, PathMessageParser},
+        sensor_msgs::{
+            BatteryStateMessageParser, CameraInfoMessageParser, CompressedImageMessageParser,
+            FluidPressureMessageParser, IlluminanceMessageParser, ImageMessageParser,
+            ImuMessageParser, JointStateMessageParser, LaserScanMessageParser,
+            MagneticFieldMessageParser, NavSatFixMessageParser, PointCloud2MessageParser,
+            RangeMessageParser, RelativeHumidityMessageParser, TemperatureMessageParser,
+        },
+        std_msgs::StringMessageParser,
+        tf2_msgs::TfMessageParser,
+    },
+};
+
+type ParserFactory = fn(usize) -> Box<dyn MessageParser>;
+
+/// Provides semantic interpretation of ROS 1 messages.
+///
+/// ROS 1 messages are decoded into the same definitions, and by the same parsers, as their
+/// ROS 2 counterparts in [`super::McapRos2Layer`]. Therefore only message types whose
+/// layout is the same in ROS 1 and ROS 2 are supported.
+#[derive(Debug)]
+pub struct McapRos1Layer {
+    registry: BTreeMap<String, ParserFactory>,
+}
+
+impl McapRos1Layer {
+    const ENCODING: &str = "ros1msg";
+
+    fn empty() -> Self {
+        Self {
+            registry: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a new [`McapRos1Layer`] with all supported message types pre-registered
+    pub fn new() -> Self {
+        // Note: `rosgraph_msgs/Log` and `visualization_msgs/Marker` differ from their ROS 2 counterparts.
+        Self::empty()
+            // geometry_msgs
+            .register_parser::<PoseStampedMessageParser>("geometry_msgs/PoseStamped")
+            .register_parser::<TwistStampedMessageParser>("geometry_msgs/TwistStamped")
+            // nav_msgs
+            .register_parser::<OccupancyGridMessageParser>("nav_msgs/OccupancyGrid")
+            .register_parser::<OdometryMessageParser>("nav_msgs/Odometry")
+            .register_parser::<PathMessageParser>("nav_msgs/Path")
+            // sensor_msgs
+            .register_parser::<BatteryStateMessageParser>("sensor_msgs/BatteryState")
+            .register_parser::<CameraInfoMessageParser>("sensor_msgs/CameraInfo")
+            .register_parser::<CompressedImageMessageParser>("sensor_msgs/CompressedImage")
+            .register_parser::<FluidPressureMessageParser>("sensor_msgs/FluidPressure")
+            .register_parser::<IlluminanceMessageParser>("sensor_msgs/Illuminance")
+            .register_parser::<ImageMessageParser>("sensor_msgs/Image")
+            .register_parser::<ImuMessageParser>("sensor_msgs/Imu")
+            .register_parser::<JointStateMessageParser>("sensor_msgs/JointState")
+            .register_parser::<LaserScanMessageParser>("sensor_msgs/LaserScan")
+            .register_parser::<MagneticFieldMessageParser>("sensor_msgs/MagneticField")
+            .register_parser::<NavSatFixMessageParser>("sensor_msgs/NavSatFix")
+            .register_parser::<PointCloud2MessageParser>("sensor_msgs/PointCloud2")
+            .register_parser::<RangeMessageParser>("sensor_msgs/Range")
+            .register_parser::<RelativeHumidityMessageParser>("sensor_msgs/RelativeHumidity")
+            .register_parser::<TemperatureMessageParser>("sensor_msgs/Temperature")
+            // std_msgs
+            .register_parser::<StringMessageParser>("std_msgs/String")
+            // tf2_msgs
+            .register_parser::<TfMessageParser>("tf2_msgs/TFMessage")
+    }
+
+    /// Registers a new message parser for the given schema name
+    pub fn register_parser<T: Ros2MessageParser + 'static>(mut self, schema_name: &str) -> Self {
+        self.registry
+            .insert(schema_name.to_owned(), |n| Box::new(T::new(n)));
+        self
+    }
+
+    /// Registers a message parser with a custom factory function
+    pub fn register_parser_with_factory(
+        mut self,
+        schema_name: &str,
+        factory: ParserFactory,
+    ) -> Self {
+        self.registry.insert(schema_name.to_owned(), factory);
+        self
+    }
+
+    /// Returns true if the given schema is supported by this layer
+    pub fn supports_schema(&self, schema_name: &str) -> bool {
+        self.registry.contains_key(schema_name)
+    }
+
+    fn is_ros1_channel(channel: &mcap::Channel<'_>) -> bool {
+        channel.message_encoding == ROS1_MESSAGE_ENCODING
+            && channel
+                .schema
+                .as_ref()
+                .is_some_and(|s| s.encoding.as_str() == Self::ENCODING)
+    }
+}
+
+impl Default for McapRos1Layer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageLayer for McapRos1Layer {
+    fn identifier() -> super::LayerIdentifier {
+        "ros1msg".into()
+    }
+
+    fn supports_channel(&self, channel: &mcap::Channel<'_>) -> bool {
+        Self::is_ros1_channel(channel)
+            && channel
+                .schema
+                .as_ref()
+                .is_some_and(|s| self.registry.contains_key(&s.name))
+    }
+
+    fn message_parser(
+        &self,
+        channel: &mcap::Channel<'_>,
+        num_rows: usize,
+    ) -> Option<Box<dyn MessageParser>> {
+        if !Self::is_ros1_channel(channel) {
+            return None;
+        }
+
+        let schema = channel.schema.as_ref()?;
+        if let Some(make) = self.registry.get(&schema.name) {
+            Some(make(num_rows))
+        } else {
+            re_log::warn_once!(
+                "Message schema {:?} is currently not supported",
+                schema.name
+            );
+
+            None
+        }
+    }
+}
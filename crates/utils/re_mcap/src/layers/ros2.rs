@@ -5,15 +5,19 @@ use crate::parsers::{
     MessageParser,
     ros2msg::{
         Ros2MessageParser,
+        geometry_msgs::{PoseStampedMessageParser, TwistStampedMessageParser},
+        nav_msgs::{OccupancyGridMessageParser, OdometryMessageParser, PathMessageParser},
         rcl_interfaces::LogMessageParser,
         sensor_msgs::{
             BatteryStateMessageParser, CameraInfoMessageParser, CompressedImageMessageParser,
             FluidPressureMessageParser, IlluminanceMessageParser, ImageMessageParser,
-            ImuMessageParser, JointStateMessageParser, MagneticFieldMessageParser,
-            NavSatFixMessageParser, PointCloud2MessageParser, RangeMessageParser,
-            RelativeHumidityMessageParser, TemperatureMessageParser,
+            ImuMessageParser, JointStateMessageParser, LaserScanMessageParser,
+            MagneticFieldMessageParser, NavSatFixMessageParser, PointCloud2MessageParser,
+            RangeMessageParser, RelativeHumidityMessageParser, TemperatureMessageParser,
         },
         std_msgs::StringMessageParser,
+        tf2_msgs::TfMessageParser,
+        visualization_msgs::{MarkerArrayMessageParser, MarkerMessageParser},
     },
 };
 
@@ -36,6 +40,13 @@ impl McapRos2Layer {
     /// Creates a new [`McapRos2Layer`] with all supported message types pre-registered
     pub fn new() -> Self {
         Self::empty()
+            // geometry_msgs
+            .register_parser::<PoseStampedMessageParser>("geometry_msgs/msg/PoseStamped")
+            .register_parser::<TwistStampedMessageParser>("geometry_msgs/msg/TwistStamped")
+            // nav_msgs
+            .register_parser::<OccupancyGridMessageParser>("nav_msgs/msg/OccupancyGrid")
+            .register_parser::<OdometryMessageParser>("nav_msgs/msg/Odometry")
+            .register_parser::<PathMessageParser>("nav_msgs/msg/Path")
             // rcl_interfaces
             .register_parser::<LogMessageParser>("rcl_interfaces/msg/Log")
             // sensor_msgs
@@ -47,6 +58,7 @@ impl McapRos2Layer {
             .register_parser::<ImageMessageParser>("sensor_msgs/msg/Image")
             .register_parser::<ImuMessageParser>("sensor_msgs/msg/Imu")
             .register_parser::<JointStateMessageParser>("sensor_msgs/msg/JointState")
+            .register_parser::<LaserScanMessageParser>("sensor_msgs/msg/LaserScan")
             .register_parser::<MagneticFieldMessageParser>("sensor_msgs/msg/MagneticField")
             .register_parser::<NavSatFixMessageParser>("sensor_msgs/msg/NavSatFix")
             .register_parser::<PointCloud2MessageParser>("sensor_msgs/msg/PointCloud2")
@@ -55,6 +67,11 @@ impl McapRos2Layer {
             .register_parser::<TemperatureMessageParser>("sensor_msgs/msg/Temperature")
             // std_msgs
             .register_parser::<StringMessageParser>("std_msgs/msg/String")
+            // tf2_msgs
+            .register_parser::<TfMessageParser>("tf2_msgs/msg/TFMessage")
+            // visualization_msgs
+            .register_parser::<MarkerMessageParser>("visualization_msgs/msg/Marker")
+            .register_parser::<MarkerArrayMessageParser>("visualization_msgs/msg/MarkerArray")
     }
 
     /// Registers a new message parser for the given schema name
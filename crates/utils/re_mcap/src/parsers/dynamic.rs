@@ -0,0 +1,132 @@
+//! Dynamically typed messages, as produced by decoders that interpret messages based on their
+//! schema at runtime.
+
+use serde::de::{
+    self, IntoDeserializer, Visitor,
+    value::{MapDeserializer, SeqDeserializer},
+};
+
+/// A single value of a message that was decoded at runtime.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    I8(i8),
+    U8(u8),
+    I16(i16),
+    U16(u16),
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    List(Vec<Value>),
+
+    /// The fields of a (nested) message in schema order, `None` if a field is not set.
+    Message(Vec<(String, Option<Value>)>),
+}
+
+impl Value {
+    /// Converts a protobuf message.
+    ///
+    /// Fields that are not set take on their default value. Maps are not supported and are skipped.
+    pub fn from_protobuf(message: &prost_reflect::DynamicMessage) -> Self {
+        use prost_reflect::ReflectMessage as _;
+
+        Self::Message(
+            message
+                .descriptor()
+                .fields()
+                .map(|field| {
+                    let value = Self::from_protobuf_value(&message.get_field(&field));
+                    (field.name().to_owned(), value)
+                })
+                .collect(),
+        )
+    }
+
+    fn from_protobuf_value(value: &prost_reflect::Value) -> Option<Self> {
+        use prost_reflect::Value as Proto;
+
+        Some(match value {
+            Proto::Bool(x) => Self::Bool(*x),
+            Proto::I32(x) | Proto::EnumNumber(x) => Self::I32(*x),
+            Proto::I64(x) => Self::I64(*x),
+            Proto::U32(x) => Self::U32(*x),
+            Proto::U64(x) => Self::U64(*x),
+            Proto::F32(x) => Self::F32(*x),
+            Proto::F64(x) => Self::F64(*x),
+            Proto::String(x) => Self::String(x.clone()),
+            Proto::Bytes(x) => Self::Bytes(x.to_vec()),
+            Proto::Message(message) => Self::from_protobuf(message),
+            Proto::List(values) => Self::List(
+                values
+                    .iter()
+                    .filter_map(Self::from_protobuf_value)
+                    .collect(),
+            ),
+            Proto::Map(_) => return None,
+        })
+    }
+}
+
+/// Allows deserializing known message definitions from dynamically decoded messages.
+///
+/// Messages are treated as maps from field names to values, fields that are not set are skipped.
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Self::Bool(x) => visitor.visit_bool(x),
+            Self::I8(x) => visitor.visit_i8(x),
+            Self::U8(x) => visitor.visit_u8(x),
+            Self::I16(x) => visitor.visit_i16(x),
+            Self::U16(x) => visitor.visit_u16(x),
+            Self::I32(x) => visitor.visit_i32(x),
+            Self::U32(x) => visitor.visit_u32(x),
+            Self::I64(x) => visitor.visit_i64(x),
+            Self::U64(x) => visitor.visit_u64(x),
+            Self::F32(x) => visitor.visit_f32(x),
+            Self::F64(x) => visitor.visit_f64(x),
+            Self::String(x) => visitor.visit_string(x),
+            Self::Bytes(x) => visitor.visit_byte_buf(x),
+            Self::List(values) => {
+                let mut seq = SeqDeserializer::new(values.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Self::Message(fields) => {
+                let mut map = MapDeserializer::new(
+                    fields
+                        .into_iter()
+                        .filter_map(|(name, value)| Some((name, value?))),
+                );
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl IntoDeserializer<'_, de::value::Error> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
@@ -0,0 +1,351 @@
+//! Reflection-based decoding of `FlatBuffers` messages, using binary schemas (`.bfbs`).
+//!
+//! See the [FlatBuffers internals](https://flatbuffers.dev/internals/) for a description of
+//! the binary format. All reads are bounds-checked, so malformed messages result in an error.
+
+mod reflection;
+
+pub use reflection::{BaseType, Field, Object, Schema, Type};
+
+use super::dynamic::Value;
+
+/// Nesting of tables and structs beyond this depth is rejected, same as the `FlatBuffers` verifier.
+const MAX_DEPTH: usize = 64;
+
+/// Errors that can occur when decoding a `FlatBuffers` message or schema.
+#[derive(thiserror::Error, Debug)]
+pub enum FlatBuffersError {
+    #[error("reading {len} bytes at offset {offset} exceeds the buffer of {buffer_len} bytes")]
+    OutOfBounds {
+        offset: usize,
+        len: usize,
+        buffer_len: usize,
+    },
+
+    #[error("invalid UTF-8 in string: {0}")]
+    InvalidUtf8(#[from] std::str::Utf8Error),
+
+    #[error("tables are nested deeper than {MAX_DEPTH} levels")]
+    TooDeep,
+
+    #[error("invalid schema: {0}")]
+    InvalidSchema(String),
+}
+
+/// A bounds-checked view into a `FlatBuffers` message.
+#[derive(Clone, Copy)]
+struct Buffer<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Buffer<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn bytes(&self, offset: usize, len: usize) -> Result<&'a [u8], FlatBuffersError> {
+        offset
+            .checked_add(len)
+            .and_then(|end| self.data.get(offset..end))
+            .ok_or(FlatBuffersError::OutOfBounds {
+                offset,
+                len,
+                buffer_len: self.data.len(),
+            })
+    }
+
+    fn array<const N: usize>(&self, offset: usize) -> Result<[u8; N], FlatBuffersError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.bytes(offset, N)?);
+        Ok(array)
+    }
+
+    fn u32(&self, offset: usize) -> Result<usize, FlatBuffersError> {
+        Ok(u32::from_le_bytes(self.array(offset)?) as usize)
+    }
+
+    /// Follows the unsigned offset that is stored at `offset`.
+    fn follow(&self, offset: usize) -> Result<usize, FlatBuffersError> {
+        let relative = self.u32(offset)?;
+        offset
+            .checked_add(relative)
+            .ok_or(FlatBuffersError::OutOfBounds {
+                offset,
+                len: relative,
+                buffer_len: self.data.len(),
+            })
+    }
+
+    fn string(&self, offset: usize) -> Result<&'a str, FlatBuffersError> {
+        let (start, len) = self.vector(offset)?;
+        Ok(std::str::from_utf8(self.bytes(start, len)?)?)
+    }
+
+    /// Returns the offset of the first element and the number of elements.
+    fn vector(&self, offset: usize) -> Result<(usize, usize), FlatBuffersError> {
+        let start = self.follow(offset)?;
+        Ok((start + 4, self.u32(start)?))
+    }
+
+    fn table(&self, offset: usize) -> Result<Table<'a>, FlatBuffersError> {
+        Table::new(*self, self.follow(offset)?)
+    }
+}
+
+/// A table, whose fields are looked up in its vtable.
+struct Table<'a> {
+    buf: Buffer<'a>,
+    offset: usize,
+    vtable: usize,
+    vtable_len: usize,
+}
+
+impl<'a> Table<'a> {
+    fn new(buf: Buffer<'a>, offset: usize) -> Result<Self, FlatBuffersError> {
+        // The vtable is located at the offset minus the signed offset stored in the table.
+        let relative = i32::from_le_bytes(buf.array(offset)?);
+        let vtable = offset.checked_add_signed(-(relative as isize)).ok_or(
+            FlatBuffersError::OutOfBounds {
+                offset,
+                len: 0,
+                buffer_len: buf.data.len(),
+            },
+        )?;
+
+        Ok(Self {
+            buf,
+            offset,
+            vtable,
+            vtable_len: u16::from_le_bytes(buf.array(vtable)?) as usize,
+        })
+    }
+
+    /// The offset of the field in the given vtable slot, `None` if the field is not set.
+    fn field(&self, slot: u16) -> Result<Option<usize>, FlatBuffersError> {
+        let entry = 4 + 2 * slot as usize;
+        if entry + 2 > self.vtable_len {
+            return Ok(None);
+        }
+
+        let relative = u16::from_le_bytes(self.buf.array(self.vtable + entry)?) as usize;
+        Ok((relative != 0).then_some(self.offset + relative))
+    }
+
+    fn scalar<const N: usize>(&self, slot: u16) -> Result<Option<[u8; N]>, FlatBuffersError> {
+        self.field(slot)?
+            .map(|offset| self.buf.array(offset))
+            .transpose()
+    }
+
+    fn required_string(&self, slot: u16) -> Result<&'a str, FlatBuffersError> {
+        let offset = self.field(slot)?.ok_or_else(|| {
+            FlatBuffersError::InvalidSchema(format!("missing required string in slot {slot}"))
+        })?;
+        self.buf.string(offset)
+    }
+
+    fn table(&self, slot: u16) -> Result<Option<Self>, FlatBuffersError> {
+        self.field(slot)?
+            .map(|offset| self.buf.table(offset))
+            .transpose()
+    }
+
+    /// Reads a vector of tables, an unset vector is empty.
+    fn tables(&self, slot: u16) -> Result<Vec<Self>, FlatBuffersError> {
+        let Some(offset) = self.field(slot)? else {
+            return Ok(Vec::new());
+        };
+
+        let (start, len) = self.buf.vector(offset)?;
+        self.buf.bytes(start, len.saturating_mul(4))?;
+        (0..len).map(|i| self.buf.table(start + 4 * i)).collect()
+    }
+}
+
+/// Decodes a message whose root table is described by `object`.
+///
+/// Scalars that are not set take on their default value, unset strings, vectors and tables are
+/// `None`. Vectors of `ubyte` are decoded as [`Value::Bytes`].
+pub fn decode_message(
+    schema: &Schema,
+    object: &Object,
+    data: &[u8],
+) -> Result<Value, FlatBuffersError> {
+    let buf = Buffer::new(data);
+    decode_table(schema, object, &buf.table(0)?, 0)
+}
+
+fn decode_table(
+    schema: &Schema,
+    object: &Object,
+    table: &Table<'_>,
+    depth: usize,
+) -> Result<Value, FlatBuffersError> {
+    object
+        .decoded_fields()
+        .map(|field| {
+            let value = match table.field(field.id)? {
+                Some(offset) => decode_field(schema, &field.ty, table.buf, offset, depth)?,
+                None => default_value(field),
+            };
+            Ok((field.name.clone(), value))
+        })
+        .collect::<Result<_, _>>()
+        .map(Value::Message)
+}
+
+fn decode_struct(
+    schema: &Schema,
+    object: &Object,
+    buf: Buffer<'_>,
+    offset: usize,
+    depth: usize,
+) -> Result<Value, FlatBuffersError> {
+    object
+        .decoded_fields()
+        .map(|field| {
+            let value = decode_field(
+                schema,
+                &field.ty,
+                buf,
+                offset + field.offset as usize,
+                depth,
+            )?;
+            Ok((field.name.clone(), value))
+        })
+        .collect::<Result<_, _>>()
+        .map(Value::Message)
+}
+
+fn decode_field(
+    schema: &Schema,
+    ty: &Type,
+    buf: Buffer<'_>,
+    offset: usize,
+    depth: usize,
+) -> Result<Option<Value>, FlatBuffersError> {
+    match ty.base_type {
+        BaseType::Vector => {
+            let (start, len) = buf.vector(offset)?;
+            if ty.element == BaseType::UByte {
+                Ok(Some(Value::Bytes(buf.bytes(start, len)?.to_vec())))
+            } else {
+                decode_elements(schema, ty, buf, start, len, depth)
+            }
+        }
+        BaseType::Array => {
+            decode_elements(schema, ty, buf, offset, ty.fixed_length as usize, depth)
+        }
+        base_type => decode_value(schema, base_type, ty.index, buf, offset, depth),
+    }
+}
+
+fn decode_elements(
+    schema: &Schema,
+    ty: &Type,
+    buf: Buffer<'_>,
+    start: usize,
+    len: usize,
+    depth: usize,
+) -> Result<Option<Value>, FlatBuffersError> {
+    let stride = match ty.element {
+        BaseType::String => 4,
+        BaseType::Obj => {
+            let object = schema.object(ty.index)?;
+            if object.is_struct { object.bytesize } else { 4 }
+        }
+        element => element.scalar_size().ok_or_else(|| {
+            FlatBuffersError::InvalidSchema(format!("unsupported element type {element:?}"))
+        })?,
+    };
+
+    // Make sure that the length is sane, before we allocate anything.
+    buf.bytes(start, len.saturating_mul(stride))?;
+
+    (0..len)
+        .map(|i| decode_value(schema, ty.element, ty.index, buf, start + i * stride, depth))
+        .collect::<Result<Option<Vec<_>>, _>>()
+        .map(|values| values.map(Value::List))
+}
+
+fn decode_value(
+    schema: &Schema,
+    base_type: BaseType,
+    index: i32,
+    buf: Buffer<'_>,
+    offset: usize,
+    depth: usize,
+) -> Result<Option<Value>, FlatBuffersError> {
+    match base_type {
+        BaseType::String => Ok(Some(Value::String(buf.string(offset)?.to_owned()))),
+        BaseType::Obj => {
+            if depth >= MAX_DEPTH {
+                return Err(FlatBuffersError::TooDeep);
+            }
+
+            let object = schema.object(index)?;
+            if object.is_struct {
+                decode_struct(schema, object, buf, offset, depth + 1).map(Some)
+            } else {
+                decode_table(schema, object, &buf.table(offset)?, depth + 1).map(Some)
+            }
+        }
+        base_type => decode_scalar(base_type, buf, offset),
+    }
+}
+
+fn decode_scalar(
+    base_type: BaseType,
+    buf: Buffer<'_>,
+    offset: usize,
+) -> Result<Option<Value>, FlatBuffersError> {
+    Ok(Some(match base_type {
+        BaseType::Bool => Value::Bool(buf.array::<1>(offset)?[0] != 0),
+        BaseType::Byte => Value::I8(i8::from_le_bytes(buf.array(offset)?)),
+        BaseType::UType | BaseType::UByte => Value::U8(u8::from_le_bytes(buf.array(offset)?)),
+        BaseType::Short => Value::I16(i16::from_le_bytes(buf.array(offset)?)),
+        BaseType::UShort => Value::U16(u16::from_le_bytes(buf.array(offset)?)),
+        BaseType::Int => Value::I32(i32::from_le_bytes(buf.array(offset)?)),
+        BaseType::UInt => Value::U32(u32::from_le_bytes(buf.array(offset)?)),
+        BaseType::Long => Value::I64(i64::from_le_bytes(buf.array(offset)?)),
+        BaseType::ULong => Value::U64(u64::from_le_bytes(buf.array(offset)?)),
+        BaseType::Float => Value::F32(f32::from_le_bytes(buf.array(offset)?)),
+        BaseType::Double => Value::F64(f64::from_le_bytes(buf.array(offset)?)),
+        BaseType::None
+        | BaseType::String
+        | BaseType::Vector
+        | BaseType::Obj
+        | BaseType::Union
+        | BaseType::Array
+        | BaseType::Vector64 => return Ok(None),
+    }))
+}
+
+/// The value of a scalar field that is not set.
+fn default_value(field: &Field) -> Option<Value> {
+    if field.optional {
+        return None;
+    }
+
+    let integer = field.default_integer;
+    Some(match field.ty.base_type {
+        BaseType::Bool => Value::Bool(integer != 0),
+        BaseType::Byte => Value::I8(integer as i8),
+        BaseType::UType | BaseType::UByte => Value::U8(integer as u8),
+        BaseType::Short => Value::I16(integer as i16),
+        BaseType::UShort => Value::U16(integer as u16),
+        BaseType::Int => Value::I32(integer as i32),
+        BaseType::UInt => Value::U32(integer as u32),
+        BaseType::Long => Value::I64(integer),
+        BaseType::ULong => Value::U64(integer as u64),
+        BaseType::Float => Value::F32(field.default_real as f32),
+        BaseType::Double => Value::F64(field.default_real),
+        BaseType::None
+        | BaseType::String
+        | BaseType::Vector
+        | BaseType::Obj
+        | BaseType::Union
+        | BaseType::Array
+        | BaseType::Vector64 => return None,
+    })
+}
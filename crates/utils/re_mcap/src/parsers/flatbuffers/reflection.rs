@@ -0,0 +1,250 @@
+//! Binary `FlatBuffers` schemas (`.bfbs`), as described by
+//! [`reflection.fbs`](https://github.com/google/flatbuffers/blob/master/reflection/reflection.fbs).
+//!
+//! Only the parts of the schema that are needed to decode messages are read.
+
+use super::{Buffer, FlatBuffersError, Table};
+
+/// The type of a field, or of the elements of a vector or array.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BaseType {
+    None,
+    UType,
+    Bool,
+    Byte,
+    UByte,
+    Short,
+    UShort,
+    Int,
+    UInt,
+    Long,
+    ULong,
+    Float,
+    Double,
+    String,
+    Vector,
+    Obj,
+    Union,
+    Array,
+    Vector64,
+}
+
+impl BaseType {
+    fn from_u8(value: u8) -> Result<Self, FlatBuffersError> {
+        Ok(match value {
+            0 => Self::None,
+            1 => Self::UType,
+            2 => Self::Bool,
+            3 => Self::Byte,
+            4 => Self::UByte,
+            5 => Self::Short,
+            6 => Self::UShort,
+            7 => Self::Int,
+            8 => Self::UInt,
+            9 => Self::Long,
+            10 => Self::ULong,
+            11 => Self::Float,
+            12 => Self::Double,
+            13 => Self::String,
+            14 => Self::Vector,
+            15 => Self::Obj,
+            16 => Self::Union,
+            17 => Self::Array,
+            18 => Self::Vector64,
+            _ => {
+                return Err(FlatBuffersError::InvalidSchema(format!(
+                    "unknown base type {value}"
+                )));
+            }
+        })
+    }
+
+    /// The size in bytes of a scalar, `None` for all other types.
+    pub fn scalar_size(self) -> Option<usize> {
+        match self {
+            Self::UType | Self::Bool | Self::Byte | Self::UByte => Some(1),
+            Self::Short | Self::UShort => Some(2),
+            Self::Int | Self::UInt | Self::Float => Some(4),
+            Self::Long | Self::ULong | Self::Double => Some(8),
+            Self::None
+            | Self::String
+            | Self::Vector
+            | Self::Obj
+            | Self::Union
+            | Self::Array
+            | Self::Vector64 => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Type {
+    pub base_type: BaseType,
+
+    /// The type of the elements if this is a vector or an array.
+    pub element: BaseType,
+
+    /// Index into [`Schema::objects`] for tables and structs, or into the enums for
+    /// enums and unions. `-1` otherwise.
+    pub index: i32,
+
+    /// The number of elements if this is an array.
+    pub fixed_length: u16,
+}
+
+impl Type {
+    fn parse(table: &Table<'_>) -> Result<Self, FlatBuffersError> {
+        Ok(Self {
+            base_type: BaseType::from_u8(table.scalar::<1>(0)?.map_or(0, |[x]| x))?,
+            element: BaseType::from_u8(table.scalar::<1>(1)?.map_or(0, |[x]| x))?,
+            index: table.scalar(2)?.map_or(-1, i32::from_le_bytes),
+            fixed_length: table.scalar(3)?.map_or(0, u16::from_le_bytes),
+        })
+    }
+
+    /// Unions and 64-bit vectors can not be decoded yet.
+    pub fn is_supported(&self) -> bool {
+        let unsupported = |ty| matches!(ty, BaseType::None | BaseType::Union | BaseType::Vector64);
+
+        match self.base_type {
+            BaseType::Vector | BaseType::Array => !unsupported(self.element),
+            base_type => !unsupported(base_type),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Field {
+    pub name: String,
+    pub ty: Type,
+
+    /// The slot of this field in the vtable of a table.
+    pub id: u16,
+
+    /// The offset of this field within a struct.
+    pub offset: u16,
+
+    pub default_integer: i64,
+    pub default_real: f64,
+    pub deprecated: bool,
+
+    /// Scalars that are optional don't have a default value.
+    pub optional: bool,
+}
+
+impl Field {
+    fn parse(table: &Table<'_>) -> Result<Self, FlatBuffersError> {
+        let bool_at = |slot| -> Result<bool, FlatBuffersError> {
+            Ok(table.scalar::<1>(slot)?.is_some_and(|[x]| x != 0))
+        };
+
+        Ok(Self {
+            name: table.required_string(0)?.to_owned(),
+            ty: Type::parse(
+                &table
+                    .table(1)?
+                    .ok_or_else(|| FlatBuffersError::InvalidSchema("field without type".into()))?,
+            )?,
+            id: table.scalar(2)?.map_or(0, u16::from_le_bytes),
+            offset: table.scalar(3)?.map_or(0, u16::from_le_bytes),
+            default_integer: table.scalar(4)?.map_or(0, i64::from_le_bytes),
+            default_real: table.scalar(5)?.map_or(0.0, f64::from_le_bytes),
+            deprecated: bool_at(6)?,
+            optional: bool_at(11)?,
+        })
+    }
+}
+
+/// A table or a struct.
+#[derive(Clone, Debug)]
+pub struct Object {
+    /// The fully qualified name, e.g. `foxglove.PointCloud`.
+    pub name: String,
+
+    /// All fields, ordered by their id, which is the order of declaration.
+    pub fields: Vec<Field>,
+
+    pub is_struct: bool,
+
+    /// The size of a struct in bytes.
+    pub bytesize: usize,
+}
+
+impl Object {
+    fn parse(table: &Table<'_>) -> Result<Self, FlatBuffersError> {
+        let mut fields = table
+            .tables(1)?
+            .iter()
+            .map(Field::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        fields.sort_by_key(|field| field.id);
+
+        Ok(Self {
+            name: table.required_string(0)?.to_owned(),
+            fields,
+            is_struct: table.scalar::<1>(2)?.is_some_and(|[x]| x != 0),
+            bytesize: table
+                .scalar(4)?
+                .map_or(0, i32::from_le_bytes)
+                .try_into()
+                .unwrap_or_default(),
+        })
+    }
+
+    /// The fields that can be decoded.
+    ///
+    /// Deprecated fields are skipped, as well as fields with unsupported types.
+    pub fn decoded_fields(&self) -> impl Iterator<Item = &Field> {
+        self.fields
+            .iter()
+            .filter(|field| !field.deprecated && field.ty.is_supported())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Schema {
+    /// All tables and structs, sorted by name.
+    pub objects: Vec<Object>,
+
+    /// The name of the `root_type` of the schema.
+    pub root_table: Option<String>,
+}
+
+impl Schema {
+    /// Reads a binary schema.
+    pub fn parse(bfbs: &[u8]) -> Result<Self, FlatBuffersError> {
+        let buf = Buffer::new(bfbs);
+        let schema = buf.table(0)?;
+
+        let objects = schema
+            .tables(0)?
+            .iter()
+            .map(Object::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let root_table = schema
+            .table(4)?
+            .map(|root| root.required_string(0).map(ToOwned::to_owned))
+            .transpose()?;
+
+        Ok(Self {
+            objects,
+            root_table,
+        })
+    }
+
+    pub fn object(&self, index: i32) -> Result<&Object, FlatBuffersError> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.objects.get(index))
+            .ok_or_else(|| FlatBuffersError::InvalidSchema(format!("unknown object {index}")))
+    }
+
+    /// Finds the object with the given fully qualified name.
+    ///
+    /// Falls back to the `root_type` of the schema, in case there is no such object.
+    pub fn find_object(&self, name: &str) -> Option<usize> {
+        let position = |name: &str| self.objects.iter().position(|object| object.name == name);
+        position(name).or_else(|| position(self.root_table.as_deref()?))
+    }
+}
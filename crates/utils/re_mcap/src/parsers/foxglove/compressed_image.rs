@@ -0,0 +1,75 @@
+use re_chunk::{Chunk, ChunkId};
+use re_types::archetypes::EncodedImage;
+
+use super::{FoxgloveMessageParser, MessageDecoder, definitions::CompressedImage};
+use crate::{
+    parsers::{MessageParser, ParserContext, ros2msg::std_msgs::HeaderFrameIds},
+    util::TimestampCell,
+};
+
+/// Parses `foxglove.CompressedImage` messages into [`EncodedImage`]s.
+pub struct CompressedImageMessageParser {
+    decoder: MessageDecoder,
+
+    /// The raw image data blobs.
+    ///
+    /// Note: These blobs are directly moved into a `Blob`, without copying.
+    blobs: Vec<Vec<u8>>,
+    frame_ids: HeaderFrameIds,
+}
+
+impl FoxgloveMessageParser for CompressedImageMessageParser {
+    fn new(num_rows: usize, decoder: MessageDecoder) -> Self {
+        Self {
+            decoder,
+            blobs: Vec::with_capacity(num_rows),
+            frame_ids: HeaderFrameIds::new(num_rows),
+        }
+    }
+}
+
+impl MessageParser for CompressedImageMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        re_tracing::profile_function!();
+        let CompressedImage {
+            timestamp,
+            frame_id,
+            data,
+            format: _, // the format is detected from the data itself
+        } = self.decoder.decode(msg)?;
+
+        // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
+        ctx.add_timestamp_cell(TimestampCell::guess_from_nanos(timestamp.as_nanos()));
+        self.frame_ids.push_frame_id(&frame_id);
+
+        self.blobs.push(data);
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        re_tracing::profile_function!();
+        let Self {
+            decoder: _,
+            blobs,
+            frame_ids,
+        } = *self;
+
+        let entity_path = ctx.entity_path().clone();
+        let timelines = ctx.build_timelines();
+
+        let chunk = Chunk::from_auto_row_ids(
+            ChunkId::new(),
+            entity_path.clone(),
+            timelines.clone(),
+            EncodedImage::update_fields()
+                .with_many_blob(blobs)
+                .columns_of_unit_batches()?
+                .collect(),
+        )?;
+
+        Ok(std::iter::once(chunk)
+            .chain(frame_ids.into_chunk(entity_path, timelines)?)
+            .collect())
+    }
+}
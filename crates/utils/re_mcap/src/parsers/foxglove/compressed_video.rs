@@ -0,0 +1,100 @@
+use re_chunk::{Chunk, ChunkId, RowId, TimePoint};
+use re_types::{archetypes::VideoStream, components::VideoCodec};
+
+use super::{FoxgloveMessageParser, MessageDecoder, definitions::CompressedVideo};
+use crate::{
+    parsers::{MessageParser, ParserContext, ros2msg::std_msgs::HeaderFrameIds},
+    util::TimestampCell,
+};
+
+/// Parses `foxglove.CompressedVideo` messages into a [`VideoStream`].
+///
+/// Only the `h264` and `h265` formats are supported.
+pub struct CompressedVideoMessageParser {
+    decoder: MessageDecoder,
+    samples: Vec<Vec<u8>>,
+    codec: Option<VideoCodec>,
+    frame_ids: HeaderFrameIds,
+}
+
+impl FoxgloveMessageParser for CompressedVideoMessageParser {
+    fn new(num_rows: usize, decoder: MessageDecoder) -> Self {
+        Self {
+            decoder,
+            samples: Vec::with_capacity(num_rows),
+            codec: None,
+            frame_ids: HeaderFrameIds::new(num_rows),
+        }
+    }
+}
+
+impl MessageParser for CompressedVideoMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        re_tracing::profile_function!();
+        let CompressedVideo {
+            timestamp,
+            frame_id,
+            data,
+            format,
+        } = self.decoder.decode(msg)?;
+
+        let codec = if format.eq_ignore_ascii_case("h264") {
+            VideoCodec::H264
+        } else if format.eq_ignore_ascii_case("h265") {
+            VideoCodec::H265
+        } else {
+            anyhow::bail!("unsupported video format {format:?}");
+        };
+
+        // We assume that the codec doesn't change within a topic.
+        self.codec.get_or_insert(codec);
+
+        // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
+        ctx.add_timestamp_cell(TimestampCell::guess_from_nanos(timestamp.as_nanos()));
+        self.frame_ids.push_frame_id(&frame_id);
+
+        self.samples.push(data);
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        re_tracing::profile_function!();
+        let Self {
+            decoder: _,
+            samples,
+            codec,
+            frame_ids,
+        } = *self;
+
+        let entity_path = ctx.entity_path().clone();
+        let timelines = ctx.build_timelines();
+
+        let chunk = Chunk::from_auto_row_ids(
+            ChunkId::new(),
+            entity_path.clone(),
+            timelines.clone(),
+            VideoStream::update_fields()
+                .with_many_sample(samples)
+                .columns_of_unit_batches()?
+                .collect(),
+        )?;
+
+        let mut chunks = vec![chunk];
+        chunks.extend(frame_ids.into_chunk(entity_path.clone(), timelines)?);
+
+        if let Some(codec) = codec {
+            // codec should be logged once per entity, as static data.
+            let codec_chunk = Chunk::builder(entity_path)
+                .with_archetype(
+                    RowId::new(),
+                    TimePoint::default(),
+                    &VideoStream::update_fields().with_codec(codec),
+                )
+                .build()?;
+            chunks.push(codec_chunk);
+        }
+
+        Ok(chunks)
+    }
+}
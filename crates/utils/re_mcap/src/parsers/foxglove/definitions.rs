@@ -0,0 +1,282 @@
+//! Definitions of the well-known [Foxglove schemas](https://docs.foxglove.dev/docs/visualization/message-schemas/introduction).
+//!
+//! The same definitions are used for all message encodings, fields that are not set take on
+//! their default value.
+
+use base64::Engine as _;
+use serde::{
+    Deserialize, Deserializer,
+    de::{self, SeqAccess, Visitor},
+};
+
+/// Deserializes `bytes` fields, which are base64-encoded strings in JSON.
+fn bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    struct BytesVisitor;
+
+    impl<'de> Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            formatter.write_str("bytes or a base64-encoded string")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            base64::engine::general_purpose::STANDARD
+                .decode(v)
+                .map_err(E::custom)
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            Ok(v.to_vec())
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+            Ok(v)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut bytes = Vec::new();
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+            Ok(bytes)
+        }
+    }
+
+    deserializer.deserialize_any(BytesVisitor)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Timestamp {
+    // Protobuf messages use `google.protobuf.Timestamp` instead.
+    #[serde(alias = "seconds")]
+    pub sec: i64,
+
+    #[serde(alias = "nanos")]
+    pub nsec: i64,
+}
+
+impl Timestamp {
+    pub fn as_nanos(&self) -> u64 {
+        self.sec
+            .saturating_mul(1_000_000_000)
+            .saturating_add(self.nsec)
+            .max(0) as u64
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Vector3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        }
+    }
+}
+
+impl Quaternion {
+    /// The quaternion as `[x, y, z, w]`.
+    ///
+    /// Protobuf messages that don't set the rotation contain all zeros, which is interpreted
+    /// as the identity rotation.
+    pub fn xyzw(&self) -> [f64; 4] {
+        if [self.x, self.y, self.z, self.w] == [0.0; 4] {
+            [0.0, 0.0, 0.0, 1.0]
+        } else {
+            [self.x, self.y, self.z, self.w]
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Pose {
+    pub position: Vector3,
+    pub orientation: Quaternion,
+}
+
+/// `foxglove.CompressedImage`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CompressedImage {
+    pub timestamp: Timestamp,
+    pub frame_id: String,
+    #[serde(deserialize_with = "bytes")]
+    pub data: Vec<u8>,
+
+    /// Image format, e.g. `jpeg`, `png` or `webp`.
+    pub format: String,
+}
+
+/// `foxglove.CompressedVideo`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CompressedVideo {
+    pub timestamp: Timestamp,
+    pub frame_id: String,
+    #[serde(deserialize_with = "bytes")]
+    pub data: Vec<u8>,
+
+    /// Video format, e.g. `h264` or `h265`.
+    pub format: String,
+}
+
+/// `foxglove.FrameTransform`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct FrameTransform {
+    pub timestamp: Timestamp,
+    pub parent_frame_id: String,
+    pub child_frame_id: String,
+    pub translation: Vector3,
+    pub rotation: Quaternion,
+}
+
+/// `foxglove.FrameTransforms`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct FrameTransforms {
+    pub transforms: Vec<FrameTransform>,
+}
+
+/// `foxglove.Log`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Log {
+    pub timestamp: Timestamp,
+    pub level: LogLevel,
+    pub message: String,
+    pub name: String,
+    pub file: String,
+    pub line: u32,
+}
+
+/// `foxglove.LogLevel`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    #[default]
+    Unknown,
+    Debug,
+    Info,
+    Warning,
+    Error,
+    Fatal,
+}
+
+impl<'de> Deserialize<'de> for LogLevel {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match u8::deserialize(deserializer)? {
+            1 => Self::Debug,
+            2 => Self::Info,
+            3 => Self::Warning,
+            4 => Self::Error,
+            5 => Self::Fatal,
+            _ => Self::Unknown,
+        })
+    }
+}
+
+/// `foxglove.PackedElementField`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct PackedElementField {
+    pub name: String,
+
+    /// Byte offset from the start of the element.
+    pub offset: u32,
+
+    #[serde(rename = "type")]
+    pub numeric_type: NumericType,
+}
+
+/// `foxglove.NumericType`, all types are little-endian.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NumericType {
+    #[default]
+    Unknown,
+    UInt8,
+    Int8,
+    UInt16,
+    Int16,
+    UInt32,
+    Int32,
+    Float32,
+    Float64,
+}
+
+impl<'de> Deserialize<'de> for NumericType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match u8::deserialize(deserializer)? {
+            1 => Self::UInt8,
+            2 => Self::Int8,
+            3 => Self::UInt16,
+            4 => Self::Int16,
+            5 => Self::UInt32,
+            6 => Self::Int32,
+            7 => Self::Float32,
+            8 => Self::Float64,
+            _ => Self::Unknown,
+        })
+    }
+}
+
+impl NumericType {
+    /// Reads a value of this type at the start of `bytes`.
+    ///
+    /// Returns `None` if `bytes` is too short, or if the type is unknown.
+    pub fn read(self, bytes: &[u8]) -> Option<f64> {
+        fn le<const N: usize>(bytes: &[u8]) -> Option<[u8; N]> {
+            bytes.get(..N)?.try_into().ok()
+        }
+
+        Some(match self {
+            Self::Unknown => return None,
+            Self::UInt8 => u8::from_le_bytes(le(bytes)?) as f64,
+            Self::Int8 => i8::from_le_bytes(le(bytes)?) as f64,
+            Self::UInt16 => u16::from_le_bytes(le(bytes)?) as f64,
+            Self::Int16 => i16::from_le_bytes(le(bytes)?) as f64,
+            Self::UInt32 => u32::from_le_bytes(le(bytes)?) as f64,
+            Self::Int32 => i32::from_le_bytes(le(bytes)?) as f64,
+            Self::Float32 => f32::from_le_bytes(le(bytes)?) as f64,
+            Self::Float64 => f64::from_le_bytes(le(bytes)?),
+        })
+    }
+}
+
+/// `foxglove.PointCloud`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct PointCloud {
+    pub timestamp: Timestamp,
+    pub frame_id: String,
+
+    /// The origin of the point cloud relative to the frame of reference.
+    pub pose: Pose,
+
+    /// Number of bytes between points in `data`.
+    pub point_stride: u32,
+
+    pub fields: Vec<PackedElementField>,
+    #[serde(deserialize_with = "bytes")]
+    pub data: Vec<u8>,
+}
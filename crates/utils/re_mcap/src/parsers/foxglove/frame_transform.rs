@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+
+use re_chunk::{Chunk, ChunkId, EntityPath, TimePoint};
+use re_types::{
+    archetypes::Transform3D,
+    components::{RotationQuat, TransformFrameId, Translation3D},
+    datatypes::{Quaternion, Vec3D},
+};
+
+use super::{
+    FoxgloveMessageParser, MessageDecoder,
+    definitions::{FrameTransform, FrameTransforms},
+};
+use crate::{
+    parsers::{MessageParser, ParserContext},
+    util::TimestampCell,
+};
+
+/// The transforms received for a single child frame.
+struct ChildFrameTransforms {
+    /// Each child frame gets its own entity, and therefore its own timelines.
+    ctx: ParserContext,
+    translations: Vec<Translation3D>,
+    quaternions: Vec<RotationQuat>,
+    parent_frames: Vec<TransformFrameId>,
+}
+
+/// Parses `foxglove.FrameTransform` messages into [`Transform3D`]s between named frames.
+///
+/// Same as for `tf2_msgs/msg/TFMessage`, the transforms of each child frame are logged to a child
+/// entity of the topic, e.g. `/tf/base_link`.
+pub struct FrameTransformMessageParser {
+    decoder: MessageDecoder,
+    per_child_frame: BTreeMap<String, ChildFrameTransforms>,
+}
+
+impl FrameTransformMessageParser {
+    fn push(&mut self, ctx: &ParserContext, msg: &mcap::Message<'_>, transform: &FrameTransform) {
+        // The timepoint that is added automatically to `ctx` only accounts for a single row per
+        // message, so we have to keep track of `log_time` and `publish_time` ourselves.
+        let timepoint = TimePoint::from([
+            (
+                "log_time",
+                TimestampCell::guess_from_nanos(msg.log_time).into_time_cell(),
+            ),
+            (
+                "publish_time",
+                TimestampCell::guess_from_nanos(msg.publish_time).into_time_cell(),
+            ),
+        ]);
+
+        let child_frame = self
+            .per_child_frame
+            .entry(transform.child_frame_id.clone())
+            .or_insert_with(|| ChildFrameTransforms {
+                ctx: ParserContext::new(ctx.entity_path().join(&EntityPath::from_single_string(
+                    transform.child_frame_id.as_str(),
+                ))),
+                translations: Vec::new(),
+                quaternions: Vec::new(),
+                parent_frames: Vec::new(),
+            });
+
+        child_frame.ctx.add_timepoint(timepoint).add_timestamp_cell(
+            TimestampCell::guess_from_nanos(transform.timestamp.as_nanos()),
+        );
+
+        let translation = &transform.translation;
+        let [x, y, z, w] = transform.rotation.xyzw();
+
+        child_frame
+            .translations
+            .push(Vec3D::from([translation.x, translation.y, translation.z]).into());
+        child_frame
+            .quaternions
+            .push(Quaternion::from_xyzw([x as f32, y as f32, z as f32, w as f32]).into());
+        child_frame
+            .parent_frames
+            .push(TransformFrameId::new(&transform.parent_frame_id));
+    }
+}
+
+impl FoxgloveMessageParser for FrameTransformMessageParser {
+    fn new(_num_rows: usize, decoder: MessageDecoder) -> Self {
+        Self {
+            decoder,
+            per_child_frame: BTreeMap::new(),
+        }
+    }
+}
+
+impl MessageParser for FrameTransformMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        let transform = self.decoder.decode::<FrameTransform>(msg)?;
+        self.push(ctx, msg, &transform);
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, _ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        let Self {
+            decoder: _,
+            per_child_frame,
+        } = *self;
+
+        per_child_frame
+            .into_iter()
+            .map(|(child_frame_id, transforms)| {
+                let ChildFrameTransforms {
+                    ctx,
+                    translations,
+                    quaternions,
+                    parent_frames,
+                } = transforms;
+
+                let child_frames = vec![TransformFrameId::new(&child_frame_id); translations.len()];
+
+                let chunk = Chunk::from_auto_row_ids(
+                    ChunkId::new(),
+                    ctx.entity_path().clone(),
+                    ctx.build_timelines(),
+                    Transform3D::update_fields()
+                        .with_many_translation(translations)
+                        .with_many_quaternion(quaternions)
+                        .with_many_child_frame(child_frames)
+                        .with_many_parent_frame(parent_frames)
+                        .columns_of_unit_batches()?
+                        .collect(),
+                )?;
+
+                Ok(chunk)
+            })
+            .collect()
+    }
+}
+
+/// Parses `foxglove.FrameTransforms` messages, see [`FrameTransformMessageParser`].
+pub struct FrameTransformsMessageParser(FrameTransformMessageParser);
+
+impl FoxgloveMessageParser for FrameTransformsMessageParser {
+    fn new(num_rows: usize, decoder: MessageDecoder) -> Self {
+        Self(FrameTransformMessageParser::new(num_rows, decoder))
+    }
+}
+
+impl MessageParser for FrameTransformsMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        let FrameTransforms { transforms } = self.0.decoder.decode(msg)?;
+        for transform in &transforms {
+            self.0.push(ctx, msg, transform);
+        }
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        Box::new(self.0).finalize(ctx)
+    }
+}
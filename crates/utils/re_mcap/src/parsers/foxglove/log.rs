@@ -0,0 +1,90 @@
+use re_chunk::{Chunk, ChunkId};
+use re_types::{
+    archetypes::TextLog,
+    components::{Text, TextLogLevel},
+};
+
+use super::{
+    FoxgloveMessageParser, MessageDecoder,
+    definitions::{Log, LogLevel},
+};
+use crate::{
+    parsers::{MessageParser, ParserContext},
+    util::TimestampCell,
+};
+
+/// Parses `foxglove.Log` messages into [`TextLog`]s.
+pub struct LogMessageParser {
+    decoder: MessageDecoder,
+    text_entries: Vec<Text>,
+    levels: Vec<TextLogLevel>,
+}
+
+impl LogMessageParser {
+    fn level(level: LogLevel) -> TextLogLevel {
+        TextLogLevel::from(match level {
+            LogLevel::Debug => TextLogLevel::DEBUG,
+            LogLevel::Info => TextLogLevel::INFO,
+            LogLevel::Warning => TextLogLevel::WARN,
+            LogLevel::Error => TextLogLevel::ERROR,
+            LogLevel::Fatal => TextLogLevel::CRITICAL,
+            LogLevel::Unknown => TextLogLevel::TRACE,
+        })
+    }
+}
+
+impl FoxgloveMessageParser for LogMessageParser {
+    fn new(num_rows: usize, decoder: MessageDecoder) -> Self {
+        Self {
+            decoder,
+            text_entries: Vec::with_capacity(num_rows),
+            levels: Vec::with_capacity(num_rows),
+        }
+    }
+}
+
+impl MessageParser for LogMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        re_tracing::profile_function!();
+        let Log {
+            timestamp,
+            level,
+            message,
+            name,
+            file: _,
+            line: _,
+        } = self.decoder.decode(msg)?;
+
+        // add the log timestamp to the context, `log_time` and `publish_time` are added automatically
+        ctx.add_timestamp_cell(TimestampCell::guess_from_nanos(timestamp.as_nanos()));
+
+        self.text_entries.push(Text::from(if name.is_empty() {
+            message
+        } else {
+            format!("[{name}] {message}")
+        }));
+        self.levels.push(Self::level(level));
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        re_tracing::profile_function!();
+        let Self {
+            decoder: _,
+            text_entries,
+            levels,
+        } = *self;
+
+        Ok(vec![Chunk::from_auto_row_ids(
+            ChunkId::new(),
+            ctx.entity_path().clone(),
+            ctx.build_timelines(),
+            TextLog::update_fields()
+                .with_many_text(text_entries)
+                .with_many_level(levels)
+                .columns_of_unit_batches()?
+                .collect(),
+        )?])
+    }
+}
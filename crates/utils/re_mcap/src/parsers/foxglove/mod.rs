@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use prost_reflect::{DynamicMessage, MessageDescriptor};
+use serde::de::DeserializeOwned;
+
+use crate::parsers::{
+    MessageParser,
+    dynamic::Value,
+    flatbuffers::{self, FlatBuffersError},
+};
+
+mod definitions;
+
+mod compressed_image;
+mod compressed_video;
+mod frame_transform;
+mod log;
+mod point_cloud;
+
+pub use compressed_image::*;
+pub use compressed_video::*;
+pub use frame_transform::*;
+pub use log::*;
+pub use point_cloud::*;
+
+/// Trait for parsers of Foxglove messages, which can be constructed with a row count and the
+/// decoder for the message encoding of the channel.
+pub trait FoxgloveMessageParser: MessageParser {
+    /// Create a new parser instance.
+    fn new(num_rows: usize, decoder: MessageDecoder) -> Self;
+}
+
+/// Errors from decoding a Foxglove message.
+#[derive(thiserror::Error, Debug)]
+pub enum DecodeError {
+    #[error("Failed to decode JSON message: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Failed to decode FlatBuffers message: {0}")]
+    FlatBuffers(#[from] FlatBuffersError),
+
+    #[error("Failed to decode protobuf message: {0}")]
+    Protobuf(#[from] prost_reflect::prost::DecodeError),
+
+    #[error("Message does not match the Foxglove schema: {0}")]
+    Schema(#[from] serde::de::value::Error),
+}
+
+/// Decodes the messages of a channel into the Foxglove definitions, based on the message
+/// encoding of the channel.
+#[derive(Clone, Debug)]
+pub enum MessageDecoder {
+    Json,
+
+    FlatBuffer {
+        schema: Arc<flatbuffers::Schema>,
+
+        /// Index of the root table of the messages in the schema.
+        object: usize,
+    },
+
+    Protobuf(MessageDescriptor),
+}
+
+impl MessageDecoder {
+    pub fn decode<T: DeserializeOwned>(&self, msg: &mcap::Message<'_>) -> Result<T, DecodeError> {
+        match self {
+            Self::Json => Ok(serde_json::from_slice(&msg.data)?),
+            Self::FlatBuffer { schema, object } => {
+                let value =
+                    flatbuffers::decode_message(schema, &schema.objects[*object], &msg.data)?;
+                Ok(T::deserialize(value)?)
+            }
+            Self::Protobuf(descriptor) => {
+                let message = DynamicMessage::decode(descriptor.clone(), msg.data.as_ref())?;
+                Ok(T::deserialize(Value::from_protobuf(&message))?)
+            }
+        }
+    }
+}
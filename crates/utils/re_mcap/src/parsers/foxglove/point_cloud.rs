@@ -0,0 +1,121 @@
+use re_chunk::{Chunk, ChunkId};
+use re_types::{
+    archetypes::Points3D,
+    datatypes::Vec3D,
+    external::glam::{DQuat, DVec3},
+};
+
+use super::{
+    FoxgloveMessageParser, MessageDecoder,
+    definitions::{NumericType, PointCloud},
+};
+use crate::{
+    parsers::{MessageParser, ParserContext, ros2msg::std_msgs::HeaderFrameIds},
+    util::TimestampCell,
+};
+
+/// Parses `foxglove.PointCloud` messages into [`Points3D`], using the `x`, `y` and `z` fields.
+///
+/// The points are transformed by the `pose` of the point cloud, so that they are expressed in
+/// its `frame_id`.
+pub struct PointCloudMessageParser {
+    decoder: MessageDecoder,
+    positions: Vec<Vec3D>,
+
+    /// The number of points of each point cloud.
+    num_points: Vec<usize>,
+
+    frame_ids: HeaderFrameIds,
+}
+
+impl FoxgloveMessageParser for PointCloudMessageParser {
+    fn new(num_rows: usize, decoder: MessageDecoder) -> Self {
+        Self {
+            decoder,
+            positions: Vec::new(),
+            num_points: Vec::with_capacity(num_rows),
+            frame_ids: HeaderFrameIds::new(num_rows),
+        }
+    }
+}
+
+impl MessageParser for PointCloudMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        re_tracing::profile_function!();
+        let point_cloud = self.decoder.decode::<PointCloud>(msg)?;
+
+        let accessor = |name: &str| {
+            point_cloud
+                .fields
+                .iter()
+                .find(|field| field.name == name)
+                .map(|field| (field.offset as usize, field.numeric_type))
+        };
+
+        let (Some(x), Some(y), Some(z)) = (accessor("x"), accessor("y"), accessor("z")) else {
+            anyhow::bail!("point cloud does not contain `x`, `y` and `z` fields");
+        };
+        anyhow::ensure!(
+            point_cloud.point_stride > 0,
+            "point stride must not be zero"
+        );
+
+        // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
+        ctx.add_timestamp_cell(TimestampCell::guess_from_nanos(
+            point_cloud.timestamp.as_nanos(),
+        ));
+        self.frame_ids.push_frame_id(&point_cloud.frame_id);
+
+        let position = &point_cloud.pose.position;
+        let translation = DVec3::new(position.x, position.y, position.z);
+        let rotation = DQuat::from_array(point_cloud.pose.orientation.xyzw()).normalize();
+
+        let read = |point: &[u8], (offset, numeric_type): (usize, NumericType)| {
+            numeric_type.read(point.get(offset..)?)
+        };
+
+        let num_points_before = self.positions.len();
+
+        self.positions.extend(
+            point_cloud
+                .data
+                .chunks_exact(point_cloud.point_stride as usize)
+                .filter_map(|point| {
+                    let point = DVec3::new(read(point, x)?, read(point, y)?, read(point, z)?);
+                    let point = translation + rotation * point;
+                    Some(Vec3D::new(point.x as f32, point.y as f32, point.z as f32))
+                }),
+        );
+
+        self.num_points
+            .push(self.positions.len() - num_points_before);
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        let Self {
+            decoder: _,
+            positions,
+            num_points,
+            frame_ids,
+        } = *self;
+
+        let entity_path = ctx.entity_path().clone();
+        let timelines = ctx.build_timelines();
+
+        let data_chunk = Chunk::from_auto_row_ids(
+            ChunkId::new(),
+            entity_path.clone(),
+            timelines.clone(),
+            Points3D::update_fields()
+                .with_positions(positions)
+                .columns(num_points)?
+                .collect(),
+        )?;
+
+        Ok(std::iter::once(data_chunk)
+            .chain(frame_ids.into_chunk(entity_path, timelines)?)
+            .collect())
+    }
+}
@@ -1,6 +1,9 @@
 pub mod cdr;
 pub(crate) mod dds;
 mod decode;
+pub(crate) mod dynamic;
+pub(crate) mod flatbuffers;
+pub(crate) mod foxglove;
 pub(crate) mod ros2msg;
 
 pub use decode::{ChannelId, MessageParser, ParserContext};
@@ -23,6 +23,13 @@ pub struct Duration {
     pub nanosec: u32,
 }
 
+impl Duration {
+    /// Converts the duration to total nanoseconds as a signed 64-bit integer.
+    pub fn as_nanos(&self) -> i64 {
+        (self.sec as i64) * 1_000_000_000 + (self.nanosec as i64)
+    }
+}
+
 /// Represents a specific point in ROS Time.
 ///
 /// Messages of this datatype follow the ROS Time design:
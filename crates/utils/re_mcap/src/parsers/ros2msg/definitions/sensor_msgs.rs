@@ -599,6 +599,51 @@ pub struct Range {
     pub range: f32,
 }
 
+/// Single scan from a planar laser range-finder.
+///
+/// If you have another ranging device with different behavior (e.g. a sonar
+/// array), please find or create a different message, since applications
+/// will make fairly laser-specific assumptions about this data.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LaserScan {
+    /// Timestamp in the header is the acquisition time of the first ray in the scan.
+    ///
+    /// In frame `frame_id`, angles are measured around the positive Z axis
+    /// (counterclockwise, if Z is up) with zero angle being forward along the x axis.
+    pub header: Header,
+
+    /// Start angle of the scan (rad).
+    pub angle_min: f32,
+
+    /// End angle of the scan (rad).
+    pub angle_max: f32,
+
+    /// Angular distance between measurements (rad).
+    pub angle_increment: f32,
+
+    /// Time between measurements (seconds).
+    pub time_increment: f32,
+
+    /// Time between scans (seconds).
+    pub scan_time: f32,
+
+    /// Minimum range value (m).
+    pub range_min: f32,
+
+    /// Maximum range value (m).
+    pub range_max: f32,
+
+    /// Range data (m).
+    ///
+    /// Values `< range_min` or `> range_max` should be discarded.
+    pub ranges: Vec<f32>,
+
+    /// Intensity data (device-specific units).
+    ///
+    /// If your device does not provide intensities, please leave the array empty.
+    pub intensities: Vec<f32>,
+}
+
 /// Power supply status values.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 #[serde(from = "u8", into = "u8")]
@@ -0,0 +1,186 @@
+//! Definitions for the ROS2 `visualization_msgs` package.
+//!
+//! Based on definitions taken from <https://github.com/ros2/common_interfaces/tree/rolling/visualization_msgs>
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    builtin_interfaces::Duration,
+    geometry_msgs::{Point, Pose, Vector3},
+    sensor_msgs::CompressedImage,
+    std_msgs::{ColorRGBA, Header},
+};
+
+/// The shape of a [`Marker`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(i32)]
+pub enum MarkerType {
+    Arrow = 0,
+    Cube = 1,
+    Sphere = 2,
+    Cylinder = 3,
+    LineStrip = 4,
+    LineList = 5,
+    CubeList = 6,
+    SphereList = 7,
+    Points = 8,
+    TextViewFacing = 9,
+    MeshResource = 10,
+    TriangleList = 11,
+    ArrowStrip = 12,
+}
+
+impl TryFrom<i32> for MarkerType {
+    type Error = i32;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Self::Arrow,
+            1 => Self::Cube,
+            2 => Self::Sphere,
+            3 => Self::Cylinder,
+            4 => Self::LineStrip,
+            5 => Self::LineList,
+            6 => Self::CubeList,
+            7 => Self::SphereList,
+            8 => Self::Points,
+            9 => Self::TextViewFacing,
+            10 => Self::MeshResource,
+            11 => Self::TriangleList,
+            12 => Self::ArrowStrip,
+            other => return Err(other),
+        })
+    }
+}
+
+/// What to do with a [`Marker`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(i32)]
+pub enum MarkerAction {
+    /// Adds the marker, or replaces the marker with the same namespace and id.
+    ///
+    /// The deprecated `MODIFY` action (`1`) is equivalent.
+    Add = 0,
+
+    /// Deletes the marker with the same namespace and id.
+    Delete = 2,
+
+    /// Deletes all markers, or only those in the namespace of the marker if any.
+    DeleteAll = 3,
+}
+
+impl TryFrom<i32> for MarkerAction {
+    type Error = i32;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 | 1 => Self::Add,
+            2 => Self::Delete,
+            3 => Self::DeleteAll,
+            other => return Err(other),
+        })
+    }
+}
+
+/// Texture coordinates of a vertex of a mesh [`Marker`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UVCoordinate {
+    pub u: f32,
+    pub v: f32,
+}
+
+/// A mesh file, embedded in a [`Marker`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MeshFile<'a> {
+    /// The filename is used for both debug purposes and to provide a file extension
+    /// for whatever parser is used.
+    pub filename: String,
+
+    /// This stores the raw text of the mesh file.
+    #[serde(with = "serde_bytes")]
+    #[serde(borrow)]
+    pub data: Cow<'a, [u8]>,
+}
+
+/// A primitive shape (or collection of shapes) to be displayed in 3D.
+///
+/// See <http://wiki.ros.org/rviz/DisplayTypes/Marker> for how each type uses the fields.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Marker<'a> {
+    /// Header for timestamp and frame id.
+    pub header: Header,
+
+    /// Namespace in which to place the object.
+    ///
+    /// Used in conjunction with `id` to create a unique name for the object.
+    pub ns: String,
+
+    /// Object ID used in conjunction with the namespace for manipulating and deleting the object later.
+    pub id: i32,
+
+    /// Type of object, see [`MarkerType`].
+    #[serde(rename = "type")]
+    pub marker_type: i32,
+
+    /// Action to take, see [`MarkerAction`].
+    pub action: i32,
+
+    /// Pose of the object with respect to the frame of the header.
+    pub pose: Pose,
+
+    /// Scale of the object; 1,1,1 means default (usually 1 meter square).
+    pub scale: Vector3,
+
+    /// Color of the object, in the range `[0, 1]`.
+    pub color: ColorRGBA,
+
+    /// How long the object should last before being automatically deleted. 0 indicates forever.
+    pub lifetime: Duration,
+
+    /// If this marker should be frame-locked, i.e. retransformed into its frame every timestep.
+    pub frame_locked: bool,
+
+    /// Only used if the type specified has some use for them (eg. `POINTS`, `LINE_STRIP`, …).
+    pub points: Vec<Point>,
+
+    /// Only used if the type specified has some use for them (eg. `POINTS`, `LINE_STRIP`, …).
+    ///
+    /// The number of colors provided must either be 0 or equal to the number of points provided.
+    pub colors: Vec<ColorRGBA>,
+
+    /// Texture resource is a special URI that can either reference a texture file in
+    /// a format acceptable to [resource retriever](https://docs.ros.org/en/rolling/p/resource_retriever/)
+    /// or an embedded texture via a string matching the format: `"embedded://texture_name"`.
+    pub texture_resource: String,
+
+    /// An image to be loaded into the rendering engine as the texture for this marker.
+    #[serde(borrow)]
+    pub texture: CompressedImage<'a>,
+
+    /// Location of each vertex within the texture, in the range `[0, 1]`.
+    pub uv_coordinates: Vec<UVCoordinate>,
+
+    /// Only used for text markers.
+    pub text: String,
+
+    /// Only used for `MESH_RESOURCE` markers.
+    ///
+    /// Similar to `texture_resource`, this is a URI to either a mesh file, or an embedded
+    /// mesh via a string matching the format: `"embedded://mesh_name"`.
+    pub mesh_resource: String,
+
+    /// The embedded mesh, if `mesh_resource` refers to one.
+    #[serde(borrow)]
+    pub mesh_file: MeshFile<'a>,
+
+    pub mesh_use_embedded_materials: bool,
+}
+
+/// A list of [`Marker`]s.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarkerArray<'a> {
+    #[serde(borrow)]
+    pub markers: Vec<Marker<'a>>,
+}
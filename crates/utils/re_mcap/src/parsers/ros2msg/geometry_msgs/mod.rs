@@ -0,0 +1,7 @@
+mod pose;
+mod pose_stamped;
+mod twist_stamped;
+
+pub use pose::*;
+pub use pose_stamped::*;
+pub use twist_stamped::*;
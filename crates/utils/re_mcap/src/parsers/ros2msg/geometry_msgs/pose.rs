@@ -0,0 +1,72 @@
+use re_types::{
+    SerializedComponentColumn,
+    archetypes::Transform3D,
+    components::{RotationQuat, TransformFrameId, Translation3D},
+    datatypes::{Quaternion, Vec3D},
+};
+
+use super::super::definitions::{geometry_msgs::Pose, std_msgs::Header};
+
+/// Collects poses as [`Transform3D`]s, relative to the frame of their header.
+///
+/// The data of the topic then lives in the frame implied by its entity path, which is placed at the
+/// pose within the frame of the header.
+pub struct PoseTransforms {
+    translations: Vec<Translation3D>,
+    quaternions: Vec<RotationQuat>,
+    parent_frames: Vec<TransformFrameId>,
+    any_parent_frame: bool,
+}
+
+impl PoseTransforms {
+    pub fn new(num_rows: usize) -> Self {
+        Self {
+            translations: Vec::with_capacity(num_rows),
+            quaternions: Vec::with_capacity(num_rows),
+            parent_frames: Vec::with_capacity(num_rows),
+            any_parent_frame: false,
+        }
+    }
+
+    pub fn push(&mut self, header: &Header, pose: &Pose) {
+        let Pose {
+            position,
+            orientation,
+        } = pose;
+
+        self.translations
+            .push(Vec3D::from([position.x, position.y, position.z]).into());
+        self.quaternions.push(
+            Quaternion::from_xyzw([
+                orientation.x as f32,
+                orientation.y as f32,
+                orientation.z as f32,
+                orientation.w as f32,
+            ])
+            .into(),
+        );
+
+        self.any_parent_frame |= !header.frame_id.is_empty();
+        self.parent_frames
+            .push(TransformFrameId::new(&header.frame_id));
+    }
+
+    /// Returns one [`Transform3D`] per pose.
+    pub fn into_columns(self) -> anyhow::Result<Vec<SerializedComponentColumn>> {
+        let Self {
+            translations,
+            quaternions,
+            parent_frames,
+            any_parent_frame,
+        } = self;
+
+        let mut transforms = Transform3D::update_fields()
+            .with_many_translation(translations)
+            .with_many_quaternion(quaternions);
+        if any_parent_frame {
+            transforms = transforms.with_many_parent_frame(parent_frames);
+        }
+
+        Ok(transforms.columns_of_unit_batches()?.collect())
+    }
+}
@@ -0,0 +1,55 @@
+use re_chunk::{Chunk, ChunkId};
+
+use super::{super::definitions::geometry_msgs, PoseTransforms};
+use crate::{
+    Error,
+    parsers::{
+        MessageParser, ParserContext,
+        ros2msg::{Ros2MessageParser, decode_message},
+    },
+};
+
+/// Parses `geometry_msgs/msg/PoseStamped` messages into [`re_types::archetypes::Transform3D`]s.
+pub struct PoseStampedMessageParser {
+    poses: PoseTransforms,
+}
+
+impl Ros2MessageParser for PoseStampedMessageParser {
+    fn new(num_rows: usize) -> Self {
+        Self {
+            poses: PoseTransforms::new(num_rows),
+        }
+    }
+}
+
+impl MessageParser for PoseStampedMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        let pose_stamped = decode_message::<geometry_msgs::PoseStamped>(msg)
+            .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
+
+        // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
+        ctx.add_timestamp_cell(crate::util::TimestampCell::guess_from_nanos_ros2(
+            pose_stamped.header.stamp.as_nanos() as u64,
+        ));
+
+        self.poses.push(&pose_stamped.header, &pose_stamped.pose);
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        let Self { poses } = *self;
+
+        let entity_path = ctx.entity_path().clone();
+        let timelines = ctx.build_timelines();
+
+        let data_chunk = Chunk::from_auto_row_ids(
+            ChunkId::new(),
+            entity_path,
+            timelines,
+            poses.into_columns()?.into_iter().collect(),
+        )?;
+
+        Ok(vec![data_chunk])
+    }
+}
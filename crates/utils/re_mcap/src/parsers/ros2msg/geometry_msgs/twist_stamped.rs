@@ -0,0 +1,32 @@
+use crate::parsers::ros2msg::{
+    definitions::{
+        geometry_msgs::{Twist, TwistStamped},
+        std_msgs::Header,
+    },
+    scalar_parser::{ScalarExtractor, ScalarMessageParser},
+};
+
+pub type TwistStampedMessageParser = ScalarMessageParser<TwistStamped>;
+
+impl ScalarExtractor for TwistStamped {
+    fn extract_scalars(&self) -> Vec<(&str, f64)> {
+        twist_scalars(&self.twist).to_vec()
+    }
+
+    fn header(&self) -> &Header {
+        &self.header
+    }
+}
+
+/// The linear and angular velocities of a [`Twist`], as named scalars.
+pub fn twist_scalars(twist: &Twist) -> [(&'static str, f64); 6] {
+    let Twist { linear, angular } = twist;
+    [
+        ("linear/x", linear.x),
+        ("linear/y", linear.y),
+        ("linear/z", linear.z),
+        ("angular/x", angular.x),
+        ("angular/y", angular.y),
+        ("angular/z", angular.z),
+    ]
+}
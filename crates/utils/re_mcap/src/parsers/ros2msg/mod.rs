@@ -1,14 +1,52 @@
-use crate::parsers::MessageParser;
+use crate::parsers::{
+    MessageParser,
+    cdr::{self, CdrError},
+};
 
 mod definitions;
 
+pub mod geometry_msgs;
+pub mod nav_msgs;
 pub mod rcl_interfaces;
 pub mod scalar_parser;
 pub mod sensor_msgs;
 pub mod std_msgs;
+pub mod tf2_msgs;
+pub mod visualization_msgs;
+
+#[cfg(test)]
+mod test_util;
 
 /// Trait for ROS2 message parsers that can be constructed with just a row count.
 pub trait Ros2MessageParser: MessageParser {
     /// Create a new parser instance.
     fn new(num_rows: usize) -> Self;
 }
+
+/// The message encoding used by ROS 1 channels.
+pub const ROS1_MESSAGE_ENCODING: &str = "ros1";
+
+/// Errors from decoding a ROS message.
+#[derive(thiserror::Error, Debug)]
+pub enum DecodeError {
+    #[error(transparent)]
+    Cdr(#[from] CdrError),
+
+    #[error("Failed to deserialize ROS 1 message: {0}")]
+    Ros1(#[from] re_ros_msg::ros1::Ros1Error),
+}
+
+/// Decode a ROS message into a `T`, based on the message encoding of its channel.
+///
+/// Messages that use the ROS 1 serialization format are decoded into the same definitions as
+/// their ROS 2 counterparts, this only works for messages where the layout didn't change.
+/// All other messages are expected to be CDR-encoded.
+pub(crate) fn decode_message<'d, T: serde::Deserialize<'d>>(
+    msg: &'d mcap::Message<'_>,
+) -> Result<T, DecodeError> {
+    if msg.channel.message_encoding == ROS1_MESSAGE_ENCODING {
+        Ok(re_ros_msg::ros1::from_bytes(&msg.data)?)
+    } else {
+        Ok(cdr::try_decode_message(&msg.data)?)
+    }
+}
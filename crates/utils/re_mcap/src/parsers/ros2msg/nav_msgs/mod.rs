@@ -0,0 +1,7 @@
+mod occupancy_grid;
+mod odometry;
+mod path;
+
+pub use occupancy_grid::*;
+pub use odometry::*;
+pub use path::*;
@@ -0,0 +1,182 @@
+use re_chunk::{Chunk, ChunkId};
+use re_types::{archetypes::Image, datatypes::ImageFormat};
+
+use super::super::{Ros2MessageParser, definitions::nav_msgs, std_msgs::HeaderFrameIds};
+use crate::{
+    Error,
+    parsers::{MessageParser, ParserContext, ros2msg::decode_message},
+};
+
+/// Value used for cells with an unknown occupancy.
+const UNKNOWN_CELL: u8 = 128;
+
+/// Parses `nav_msgs/msg/OccupancyGrid` messages into grayscale [`Image`]s.
+///
+/// Free cells are white, occupied cells are black, and unknown cells are gray.
+/// The grid is flipped vertically, such that the map is shown as seen from above.
+pub struct OccupancyGridMessageParser {
+    blobs: Vec<Vec<u8>>,
+    image_formats: Vec<ImageFormat>,
+    frame_ids: HeaderFrameIds,
+}
+
+impl Ros2MessageParser for OccupancyGridMessageParser {
+    fn new(num_rows: usize) -> Self {
+        Self {
+            blobs: Vec::with_capacity(num_rows),
+            image_formats: Vec::with_capacity(num_rows),
+            frame_ids: HeaderFrameIds::new(num_rows),
+        }
+    }
+}
+
+impl MessageParser for OccupancyGridMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        let nav_msgs::OccupancyGrid { header, info, data } =
+            decode_message::<nav_msgs::OccupancyGrid>(msg)
+                .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
+
+        // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
+        ctx.add_timestamp_cell(crate::util::TimestampCell::guess_from_nanos_ros2(
+            header.stamp.as_nanos() as u64,
+        ));
+
+        let (width, height) = (info.width as usize, info.height as usize);
+        anyhow::ensure!(
+            data.len() == width * height,
+            "occupancy grid has {} cells, expected {width}x{height}",
+            data.len()
+        );
+
+        // The first cell of the grid is at its origin, rows are stored along the y-axis.
+        let pixels = if width == 0 {
+            Vec::new()
+        } else {
+            data.chunks_exact(width)
+                .rev()
+                .flatten()
+                .map(|&occupancy| occupancy_to_luminance(occupancy))
+                .collect()
+        };
+
+        self.frame_ids.push(&header);
+        self.blobs.push(pixels);
+        self.image_formats
+            .push(ImageFormat::l8([info.width, info.height]));
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        let Self {
+            blobs,
+            image_formats,
+            frame_ids,
+        } = *self;
+
+        let entity_path = ctx.entity_path().clone();
+        let timelines = ctx.build_timelines();
+
+        let data_chunk = Chunk::from_auto_row_ids(
+            ChunkId::new(),
+            entity_path.clone(),
+            timelines.clone(),
+            Image::update_fields()
+                .with_many_buffer(blobs)
+                .with_many_format(image_formats)
+                .columns_of_unit_batches()?
+                .collect(),
+        )?;
+
+        Ok(std::iter::once(data_chunk)
+            .chain(frame_ids.into_chunk(entity_path, timelines)?)
+            .collect())
+    }
+}
+
+/// Maps an occupancy probability in `[0, 100]` to a luminance, where free space is white.
+fn occupancy_to_luminance(occupancy: i8) -> u8 {
+    match u8::try_from(occupancy) {
+        Ok(probability @ 0..=100) => 255 - (probability as u16 * 255 / 100) as u8,
+        _ => UNKNOWN_CELL,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use re_types::{
+        archetypes::CoordinateFrame,
+        components::{self, ImageBuffer, TransformFrameId},
+    };
+
+    use super::*;
+    use crate::parsers::ros2msg::{
+        definitions::{
+            builtin_interfaces::Time,
+            geometry_msgs::{Point, Pose, Quaternion},
+            nav_msgs::{MapMetaData, OccupancyGrid},
+        },
+        test_util::{cdr_encode, find_chunk_with, header, run_ros2_layer},
+    };
+
+    #[test]
+    fn occupancy_grid_roundtrip() {
+        let grid = OccupancyGrid {
+            header: header("map"),
+            info: MapMetaData {
+                map_load_time: Time { sec: 0, nanosec: 0 },
+                resolution: 0.05,
+                width: 3,
+                height: 2,
+                origin: Pose {
+                    position: Point {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 0.0,
+                    },
+                    orientation: Quaternion {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 0.0,
+                        w: 1.0,
+                    },
+                },
+            },
+            data: vec![0, 100, -1, 0, 0, 20],
+        };
+
+        let chunks = run_ros2_layer("nav_msgs/msg/OccupancyGrid", "/map", &[cdr_encode(&grid)]);
+
+        let image = find_chunk_with(&chunks, Image::descriptor_buffer().component);
+        assert_eq!(image.num_rows(), 1);
+        assert_eq!(
+            image
+                .component_mono::<components::ImageFormat>(Image::descriptor_format().component, 0)
+                .unwrap()
+                .unwrap(),
+            components::ImageFormat(ImageFormat::l8([3, 2]))
+        );
+
+        // The last row of the grid comes first, unknown cells are gray.
+        let buffer = image
+            .component_mono::<ImageBuffer>(Image::descriptor_buffer().component, 0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            buffer.0.0.to_vec(),
+            vec![255, 255, 204, 255, 0, UNKNOWN_CELL]
+        );
+
+        let frames = find_chunk_with(&chunks, CoordinateFrame::descriptor_frame_id().component);
+        assert_eq!(
+            frames
+                .component_mono::<TransformFrameId>(
+                    CoordinateFrame::descriptor_frame_id().component,
+                    0
+                )
+                .unwrap()
+                .unwrap(),
+            TransformFrameId::new("map")
+        );
+    }
+}
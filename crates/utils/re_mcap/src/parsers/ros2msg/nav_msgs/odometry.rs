@@ -0,0 +1,209 @@
+use arrow::array::{FixedSizeListBuilder, Float64Builder};
+use re_chunk::{Chunk, ChunkId, ChunkResult, EntityPath, RowId, TimePoint};
+use re_types::archetypes::{Scalars, SeriesLines};
+
+use super::super::{
+    Ros2MessageParser,
+    definitions::nav_msgs,
+    geometry_msgs::{PoseTransforms, twist_scalars},
+};
+use crate::{
+    Error,
+    parsers::{
+        MessageParser, ParserContext, ros2msg::decode_message, util::fixed_size_list_builder,
+    },
+};
+
+/// Parses `nav_msgs/msg/Odometry` messages.
+///
+/// The pose is logged as a [`re_types::archetypes::Transform3D`] relative to the frame of the
+/// header, the twist as [`Scalars`].
+///
+/// The `child_frame_id` is not used as the target frame of the transform, as the same transform is
+/// usually also published on `/tf` and would otherwise be specified twice.
+pub struct OdometryMessageParser {
+    poses: PoseTransforms,
+    twists: FixedSizeListBuilder<Float64Builder>,
+}
+
+impl OdometryMessageParser {
+    /// Helper function to create a metadata chunk for the twist series.
+    fn metadata_chunk(entity_path: EntityPath) -> ChunkResult<Chunk> {
+        Chunk::builder(entity_path)
+            .with_archetype(
+                RowId::new(),
+                TimePoint::default(),
+                &SeriesLines::new().with_names([
+                    "linear/x",
+                    "linear/y",
+                    "linear/z",
+                    "angular/x",
+                    "angular/y",
+                    "angular/z",
+                ]),
+            )
+            .build()
+    }
+}
+
+impl Ros2MessageParser for OdometryMessageParser {
+    fn new(num_rows: usize) -> Self {
+        Self {
+            poses: PoseTransforms::new(num_rows),
+            twists: fixed_size_list_builder(6, num_rows),
+        }
+    }
+}
+
+impl MessageParser for OdometryMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        let odometry = decode_message::<nav_msgs::Odometry>(msg)
+            .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
+
+        // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
+        ctx.add_timestamp_cell(crate::util::TimestampCell::guess_from_nanos_ros2(
+            odometry.header.stamp.as_nanos() as u64,
+        ));
+
+        // TODO(#10728): Figure out what to do with the covariance matrices.
+        self.poses.push(&odometry.header, &odometry.pose.pose);
+
+        for (_, value) in twist_scalars(&odometry.twist.twist) {
+            self.twists.values().append_value(value);
+        }
+        self.twists.append(true);
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        let entity_path = ctx.entity_path().clone();
+        let timelines = ctx.build_timelines();
+        let meta_chunk = Self::metadata_chunk(entity_path.clone())?;
+
+        let Self { poses, mut twists } = *self;
+
+        let data_chunk = Chunk::from_auto_row_ids(
+            ChunkId::new(),
+            entity_path,
+            timelines,
+            poses
+                .into_columns()?
+                .into_iter()
+                .map(|column| (column.descriptor, column.list_array))
+                .chain(std::iter::once((
+                    Scalars::descriptor_scalars(),
+                    twists.finish().into(),
+                )))
+                .collect(),
+        )?;
+
+        Ok(vec![data_chunk, meta_chunk])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use re_types::{
+        archetypes::Transform3D,
+        components::{Name, Scalar, TransformFrameId, Translation3D},
+    };
+
+    use super::*;
+    use crate::parsers::ros2msg::{
+        definitions::{
+            geometry_msgs::{
+                Point, Pose, PoseWithCovariance, Quaternion, Twist, TwistWithCovariance, Vector3,
+            },
+            nav_msgs::Odometry,
+        },
+        test_util::{cdr_encode, find_chunk_with, header, run_ros2_layer},
+    };
+
+    #[test]
+    fn odometry_roundtrip() {
+        let odometry = Odometry {
+            header: header("odom"),
+            child_frame_id: "base_link".to_owned(),
+            pose: PoseWithCovariance {
+                pose: Pose {
+                    position: Point {
+                        x: 1.0,
+                        y: 2.0,
+                        z: 3.0,
+                    },
+                    orientation: Quaternion {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 0.0,
+                        w: 1.0,
+                    },
+                },
+                covariance: [[0.0; 6]; 6],
+            },
+            twist: TwistWithCovariance {
+                twist: Twist {
+                    linear: Vector3 {
+                        x: 0.5,
+                        y: 0.0,
+                        z: 0.0,
+                    },
+                    angular: Vector3 {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 0.25,
+                    },
+                },
+                covariance: [[0.0; 6]; 6],
+            },
+        };
+
+        let chunks = run_ros2_layer("nav_msgs/msg/Odometry", "/odom", &[cdr_encode(&odometry)]);
+
+        let data = find_chunk_with(&chunks, Scalars::descriptor_scalars().component);
+        assert_eq!(data.num_rows(), 1);
+        assert_eq!(
+            data.component_mono::<Translation3D>(
+                Transform3D::descriptor_translation().component,
+                0
+            )
+            .unwrap()
+            .unwrap(),
+            Translation3D::new(1.0, 2.0, 3.0)
+        );
+
+        // The child frame is not used, see the docs of the parser.
+        assert_eq!(
+            data.component_mono::<TransformFrameId>(
+                Transform3D::descriptor_parent_frame().component,
+                0
+            )
+            .unwrap()
+            .unwrap(),
+            TransformFrameId::new("odom")
+        );
+        assert!(
+            !data
+                .components()
+                .contains_component(Transform3D::descriptor_child_frame().component)
+        );
+
+        let scalars = data
+            .component_batch::<Scalar>(Scalars::descriptor_scalars().component, 0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            scalars.iter().map(|scalar| scalar.0.0).collect::<Vec<_>>(),
+            vec![0.5, 0.0, 0.0, 0.0, 0.0, 0.25]
+        );
+
+        let meta = find_chunk_with(&chunks, SeriesLines::descriptor_names().component);
+        assert!(meta.is_static());
+        let names = meta
+            .component_batch::<Name>(SeriesLines::descriptor_names().component, 0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(names.len(), scalars.len());
+        assert_eq!(names[5].as_str(), "angular/z");
+    }
+}
@@ -0,0 +1,147 @@
+use re_chunk::{Chunk, ChunkId};
+use re_types::{archetypes::LineStrips3D, components::LineStrip3D};
+
+use super::super::{Ros2MessageParser, definitions::nav_msgs, std_msgs::HeaderFrameIds};
+use crate::{
+    Error,
+    parsers::{MessageParser, ParserContext, ros2msg::decode_message},
+};
+
+/// Parses `nav_msgs/msg/Path` messages into a single line strip per message.
+///
+/// The orientations of the individual poses are ignored.
+pub struct PathMessageParser {
+    strips: Vec<LineStrip3D>,
+    frame_ids: HeaderFrameIds,
+}
+
+impl Ros2MessageParser for PathMessageParser {
+    fn new(num_rows: usize) -> Self {
+        Self {
+            strips: Vec::with_capacity(num_rows),
+            frame_ids: HeaderFrameIds::new(num_rows),
+        }
+    }
+}
+
+impl MessageParser for PathMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        let path = decode_message::<nav_msgs::Path>(msg)
+            .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
+
+        // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
+        ctx.add_timestamp_cell(crate::util::TimestampCell::guess_from_nanos_ros2(
+            path.header.stamp.as_nanos() as u64,
+        ));
+        self.frame_ids.push(&path.header);
+
+        self.strips
+            .push(LineStrip3D::from(path.poses.iter().map(|pose| {
+                let position = &pose.pose.position;
+                [position.x as f32, position.y as f32, position.z as f32]
+            })));
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        let Self { strips, frame_ids } = *self;
+
+        let entity_path = ctx.entity_path().clone();
+        let timelines = ctx.build_timelines();
+
+        let data_chunk = Chunk::from_auto_row_ids(
+            ChunkId::new(),
+            entity_path.clone(),
+            timelines.clone(),
+            LineStrips3D::update_fields()
+                .with_strips(strips)
+                .columns_of_unit_batches()?
+                .collect(),
+        )?;
+
+        Ok(std::iter::once(data_chunk)
+            .chain(frame_ids.into_chunk(entity_path, timelines)?)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use re_types::{archetypes::CoordinateFrame, components::TransformFrameId};
+
+    use super::*;
+    use crate::parsers::ros2msg::{
+        definitions::{
+            geometry_msgs::{Point, Pose, PoseStamped, Quaternion},
+            nav_msgs::Path,
+        },
+        test_util::{cdr_encode, find_chunk_with, header, run_ros2_layer},
+    };
+
+    fn pose_stamped(x: f64, y: f64) -> PoseStamped {
+        PoseStamped {
+            header: header("map"),
+            pose: Pose {
+                position: Point { x, y, z: 0.0 },
+                orientation: Quaternion {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                    w: 1.0,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn path_roundtrip() {
+        let paths = [
+            Path {
+                header: header("map"),
+                poses: vec![
+                    pose_stamped(0.0, 0.0),
+                    pose_stamped(1.0, 0.0),
+                    pose_stamped(1.0, 2.0),
+                ],
+            },
+            Path {
+                header: header("map"),
+                poses: Vec::new(),
+            },
+        ];
+
+        let chunks = run_ros2_layer(
+            "nav_msgs/msg/Path",
+            "/plan",
+            &paths.iter().map(cdr_encode).collect::<Vec<_>>(),
+        );
+
+        let strips = find_chunk_with(&chunks, LineStrips3D::descriptor_strips().component);
+        assert_eq!(strips.num_rows(), 2);
+
+        let strip = |row| {
+            strips
+                .component_mono::<LineStrip3D>(LineStrips3D::descriptor_strips().component, row)
+                .unwrap()
+                .unwrap()
+        };
+        assert_eq!(
+            strip(0),
+            LineStrip3D::from([[0.0_f32, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 2.0, 0.0]])
+        );
+        assert_eq!(strip(1), LineStrip3D::default());
+
+        let frames = find_chunk_with(&chunks, CoordinateFrame::descriptor_frame_id().component);
+        assert_eq!(
+            frames
+                .component_mono::<TransformFrameId>(
+                    CoordinateFrame::descriptor_frame_id().component,
+                    1
+                )
+                .unwrap()
+                .unwrap(),
+            TransformFrameId::new("map")
+        );
+    }
+}
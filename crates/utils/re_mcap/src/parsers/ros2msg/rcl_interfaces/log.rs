@@ -9,10 +9,9 @@ use re_types::{
 };
 
 use crate::parsers::{
-    cdr,
     decode::{MessageParser, ParserContext},
     ros2msg::{
-        Ros2MessageParser,
+        Ros2MessageParser, decode_message,
         definitions::rcl_interfaces::{self, LogLevel},
     },
     util::fixed_size_list_builder,
@@ -79,8 +78,8 @@ impl MessageParser for LogMessageParser {
             file,
             function,
             line,
-        } = cdr::try_decode_message::<rcl_interfaces::Log>(&msg.data)
-            .context("Failed to decode `rcl_interfaces::Log` message from CDR data")?;
+        } = decode_message::<rcl_interfaces::Log>(msg)
+            .context("Failed to decode `rcl_interfaces::Log` message")?;
 
         // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
         ctx.add_timestamp_cell(crate::util::TimestampCell::guess_from_nanos_ros2(
@@ -4,9 +4,8 @@ use re_chunk::{Chunk, ChunkId, ChunkResult, EntityPath, RowId, TimePoint};
 use re_types::archetypes::{Scalars, SeriesLines};
 
 use crate::parsers::{
-    cdr,
     decode::{MessageParser, ParserContext},
-    ros2msg::{Ros2MessageParser, definitions::std_msgs::Header},
+    ros2msg::{Ros2MessageParser, decode_message, definitions::std_msgs::Header},
     util::fixed_size_list_builder,
 };
 
@@ -81,12 +80,8 @@ impl<T: ScalarExtractor> MessageParser for ScalarMessageParser<T> {
     fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
         re_tracing::profile_function!();
 
-        let message = cdr::try_decode_message::<T>(&msg.data).with_context(|| {
-            format!(
-                "Failed to decode {} message from CDR data",
-                std::any::type_name::<T>()
-            )
-        })?;
+        let message = decode_message::<T>(msg)
+            .with_context(|| format!("Failed to decode {} message", std::any::type_name::<T>()))?;
 
         // Add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
         ctx.add_timestamp_cell(crate::util::TimestampCell::guess_from_nanos_ros2(
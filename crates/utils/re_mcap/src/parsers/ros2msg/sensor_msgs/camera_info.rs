@@ -2,12 +2,12 @@ use super::super::definitions::sensor_msgs;
 use re_chunk::{Chunk, ChunkId};
 use re_types::archetypes::Pinhole;
 
-use super::super::Ros2MessageParser;
+use super::super::{Ros2MessageParser, std_msgs::HeaderFrameIds};
 use crate::{
     Error,
     parsers::{
-        cdr,
         decode::{MessageParser, ParserContext},
+        ros2msg::decode_message,
     },
 };
 
@@ -18,6 +18,7 @@ pub struct CameraInfoSchemaPlugin;
 pub struct CameraInfoMessageParser {
     image_from_cameras: Vec<[f32; 9]>,
     resolutions: Vec<(f32, f32)>,
+    frame_ids: HeaderFrameIds,
 }
 
 impl Ros2MessageParser for CameraInfoMessageParser {
@@ -25,6 +26,7 @@ impl Ros2MessageParser for CameraInfoMessageParser {
         Self {
             image_from_cameras: Vec::with_capacity(num_rows),
             resolutions: Vec::with_capacity(num_rows),
+            frame_ids: HeaderFrameIds::new(num_rows),
         }
     }
 }
@@ -37,12 +39,13 @@ impl MessageParser for CameraInfoMessageParser {
             height,
             k,
             ..
-        } = cdr::try_decode_message::<sensor_msgs::CameraInfo>(&msg.data)?;
+        } = decode_message::<sensor_msgs::CameraInfo>(msg)?;
 
         // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
         ctx.add_timestamp_cell(crate::util::TimestampCell::guess_from_nanos_ros2(
             header.stamp.as_nanos() as u64,
         ));
+        self.frame_ids.push(&header);
 
         // ROS2 stores the intrinsic matrix K as a row-major 9-element array:
         // [fx, 0, cx, 0, fy, cy, 0, 0, 1]
@@ -71,6 +74,7 @@ impl MessageParser for CameraInfoMessageParser {
         let Self {
             image_from_cameras,
             resolutions,
+            frame_ids,
         } = *self;
 
         let entity_path = ctx.entity_path().clone();
@@ -88,6 +92,8 @@ impl MessageParser for CameraInfoMessageParser {
                 .collect(),
         )?;
 
-        Ok(vec![pinhole_chunk])
+        Ok(std::iter::once(pinhole_chunk)
+            .chain(frame_ids.into_chunk(entity_path, timelines)?)
+            .collect())
     }
 }
@@ -5,10 +5,10 @@ use re_types::{
     components::VideoCodec,
 };
 
-use super::super::Ros2MessageParser;
+use super::super::{Ros2MessageParser, std_msgs::HeaderFrameIds};
 use crate::parsers::{
-    cdr,
     decode::{MessageParser, ParserContext},
+    ros2msg::decode_message,
 };
 use crate::util::TimestampCell;
 
@@ -19,6 +19,7 @@ pub struct CompressedImageMessageParser {
     /// Note: These blobs are directly moved into a `Blob`, without copying.
     blobs: Vec<Vec<u8>>,
     is_h264: bool,
+    frame_ids: HeaderFrameIds,
 }
 
 impl Ros2MessageParser for CompressedImageMessageParser {
@@ -26,6 +27,7 @@ impl Ros2MessageParser for CompressedImageMessageParser {
         Self {
             blobs: Vec::with_capacity(num_rows),
             is_h264: false,
+            frame_ids: HeaderFrameIds::new(num_rows),
         }
     }
 }
@@ -37,12 +39,13 @@ impl MessageParser for CompressedImageMessageParser {
             header,
             data,
             format,
-        } = cdr::try_decode_message::<sensor_msgs::CompressedImage<'_>>(&msg.data)?;
+        } = decode_message::<sensor_msgs::CompressedImage<'_>>(msg)?;
 
         // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
         ctx.add_timestamp_cell(TimestampCell::guess_from_nanos_ros2(
             header.stamp.as_nanos() as u64,
         ));
+        self.frame_ids.push(&header);
 
         self.blobs.push(data.into_owned());
 
@@ -56,7 +59,11 @@ impl MessageParser for CompressedImageMessageParser {
 
     fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<re_chunk::Chunk>> {
         re_tracing::profile_function!();
-        let Self { blobs, is_h264 } = *self;
+        let Self {
+            blobs,
+            is_h264,
+            frame_ids,
+        } = *self;
 
         let entity_path = ctx.entity_path().clone();
         let timelines = ctx.build_timelines();
@@ -80,6 +87,9 @@ impl MessageParser for CompressedImageMessageParser {
             components,
         )?;
 
+        let mut chunks = vec![chunk];
+        chunks.extend(frame_ids.into_chunk(entity_path.clone(), timelines)?);
+
         if is_h264 {
             // codec should be logged once per entity, as static data.
             let codec_chunk = Chunk::builder(entity_path.clone())
@@ -89,9 +99,9 @@ impl MessageParser for CompressedImageMessageParser {
                     &VideoStream::update_fields().with_codec(VideoCodec::H264),
                 )
                 .build()?;
-            Ok(vec![chunk, codec_chunk])
-        } else {
-            Ok(vec![chunk])
+            chunks.push(codec_chunk);
         }
+
+        Ok(chunks)
     }
 }
@@ -5,11 +5,10 @@ use re_types::{
     datatypes::{ChannelDatatype, ColorModel, ImageFormat, PixelFormat},
 };
 
-use super::super::Ros2MessageParser;
+use super::super::{Ros2MessageParser, std_msgs::HeaderFrameIds};
 use crate::parsers::{
-    cdr,
     decode::{MessageParser, ParserContext},
-    ros2msg::definitions::sensor_msgs,
+    ros2msg::{decode_message, definitions::sensor_msgs},
 };
 
 /// Plugin that parses `sensor_msgs/msg/CompressedImage` messages.
@@ -23,6 +22,7 @@ pub struct ImageMessageParser {
     blobs: Vec<Vec<u8>>,
     image_formats: Vec<ImageFormat>,
     is_depth_image: bool,
+    frame_ids: HeaderFrameIds,
 }
 
 impl Ros2MessageParser for ImageMessageParser {
@@ -31,6 +31,7 @@ impl Ros2MessageParser for ImageMessageParser {
             blobs: Vec::with_capacity(num_rows),
             image_formats: Vec::with_capacity(num_rows),
             is_depth_image: false,
+            frame_ids: HeaderFrameIds::new(num_rows),
         }
     }
 }
@@ -45,13 +46,14 @@ impl MessageParser for ImageMessageParser {
             width,
             encoding,
             ..
-        } = cdr::try_decode_message::<sensor_msgs::Image<'_>>(&msg.data)
-            .context("Failed to decode sensor_msgs::Image message from CDR data")?;
+        } = decode_message::<sensor_msgs::Image<'_>>(msg)
+            .context("Failed to decode sensor_msgs::Image message")?;
 
         // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
         ctx.add_timestamp_cell(crate::util::TimestampCell::guess_from_nanos_ros2(
             header.stamp.as_nanos() as u64,
         ));
+        self.frame_ids.push(&header);
 
         let dimensions = [width, height];
         let img_format = decode_image_format(&encoding, dimensions)
@@ -73,6 +75,7 @@ impl MessageParser for ImageMessageParser {
             blobs,
             image_formats,
             is_depth_image,
+            frame_ids,
         } = *self;
 
         let entity_path = ctx.entity_path().clone();
@@ -92,12 +95,16 @@ impl MessageParser for ImageMessageParser {
                 .collect()
         };
 
-        Ok(vec![Chunk::from_auto_row_ids(
+        let chunk = Chunk::from_auto_row_ids(
             ChunkId::new(),
             entity_path.clone(),
             timelines.clone(),
             chunk_components.into_iter().collect(),
-        )?])
+        )?;
+
+        Ok(std::iter::once(chunk)
+            .chain(frame_ids.into_chunk(entity_path, timelines)?)
+            .collect())
     }
 }
 
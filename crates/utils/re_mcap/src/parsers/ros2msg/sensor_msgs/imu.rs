@@ -12,7 +12,7 @@ use re_types::{
 use super::super::Ros2MessageParser;
 use crate::{
     Error,
-    parsers::{MessageParser, ParserContext, cdr},
+    parsers::{MessageParser, ParserContext, ros2msg::decode_message},
 };
 
 /// Plugin that parses `sensor_msgs/msg/Imu` messages.
@@ -64,7 +64,7 @@ impl Ros2MessageParser for ImuMessageParser {
 
 impl MessageParser for ImuMessageParser {
     fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
-        let imu = cdr::try_decode_message::<sensor_msgs::Imu>(msg.data.as_ref())
+        let imu = decode_message::<sensor_msgs::Imu>(msg)
             .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
 
         // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
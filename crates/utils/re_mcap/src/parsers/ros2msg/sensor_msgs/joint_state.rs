@@ -8,7 +8,7 @@ use re_types::archetypes::{Scalars, SeriesLines};
 use super::super::Ros2MessageParser;
 use crate::{
     Error,
-    parsers::{MessageParser, ParserContext, cdr},
+    parsers::{MessageParser, ParserContext, ros2msg::decode_message},
 };
 
 /// Plugin that parses `sensor_msgs/msg/JointState` messages.
@@ -41,7 +41,7 @@ impl MessageParser for JointStateMessageParser {
             position,
             velocity,
             effort,
-        } = cdr::try_decode_message::<sensor_msgs::JointState>(msg.data.as_ref())
+        } = decode_message::<sensor_msgs::JointState>(msg)
             .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
 
         // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
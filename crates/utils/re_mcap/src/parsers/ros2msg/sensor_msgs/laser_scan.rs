@@ -0,0 +1,153 @@
+use re_chunk::{Chunk, ChunkId};
+use re_types::{archetypes::Points3D, datatypes::Vec3D};
+
+use super::super::definitions::sensor_msgs;
+use crate::{
+    Error,
+    parsers::{
+        MessageParser, ParserContext,
+        ros2msg::{Ros2MessageParser, decode_message, std_msgs::HeaderFrameIds},
+    },
+};
+
+/// Parses `sensor_msgs/msg/LaserScan` messages into [`Points3D`], in the frame of the scanner.
+///
+/// Out of range measurements are discarded.
+pub struct LaserScanMessageParser {
+    positions: Vec<Vec3D>,
+
+    /// The number of valid measurements of each scan.
+    num_points: Vec<usize>,
+
+    frame_ids: HeaderFrameIds,
+}
+
+impl Ros2MessageParser for LaserScanMessageParser {
+    fn new(num_rows: usize) -> Self {
+        Self {
+            positions: Vec::new(),
+            num_points: Vec::with_capacity(num_rows),
+            frame_ids: HeaderFrameIds::new(num_rows),
+        }
+    }
+}
+
+impl MessageParser for LaserScanMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        let scan = decode_message::<sensor_msgs::LaserScan>(msg)
+            .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
+
+        // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
+        ctx.add_timestamp_cell(crate::util::TimestampCell::guess_from_nanos_ros2(
+            scan.header.stamp.as_nanos() as u64,
+        ));
+        self.frame_ids.push(&scan.header);
+
+        let num_points_before = self.positions.len();
+
+        // Angles are measured around the z-axis, starting from the x-axis.
+        self.positions.extend(
+            scan.ranges
+                .iter()
+                .enumerate()
+                .filter(|(_, range)| (scan.range_min..=scan.range_max).contains(*range))
+                .map(|(i, range)| {
+                    let angle = scan.angle_min + i as f32 * scan.angle_increment;
+                    Vec3D::new(range * angle.cos(), range * angle.sin(), 0.0)
+                }),
+        );
+
+        self.num_points
+            .push(self.positions.len() - num_points_before);
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        let Self {
+            positions,
+            num_points,
+            frame_ids,
+        } = *self;
+
+        let entity_path = ctx.entity_path().clone();
+        let timelines = ctx.build_timelines();
+
+        let data_chunk = Chunk::from_auto_row_ids(
+            ChunkId::new(),
+            entity_path.clone(),
+            timelines.clone(),
+            Points3D::update_fields()
+                .with_positions(positions)
+                .columns(num_points)?
+                .collect(),
+        )?;
+
+        Ok(std::iter::once(data_chunk)
+            .chain(frame_ids.into_chunk(entity_path, timelines)?)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use re_types::components::Position3D;
+
+    use super::*;
+    use crate::parsers::ros2msg::{
+        definitions::sensor_msgs::LaserScan,
+        test_util::{cdr_encode, find_chunk_with, header, run_ros2_layer},
+    };
+
+    fn scan(ranges: Vec<f32>) -> LaserScan {
+        LaserScan {
+            header: header("laser"),
+            angle_min: 0.0,
+            angle_max: FRAC_PI_2 * (ranges.len() - 1) as f32,
+            angle_increment: FRAC_PI_2,
+            time_increment: 0.0,
+            scan_time: 0.1,
+            range_min: 0.1,
+            range_max: 3.0,
+            ranges,
+            intensities: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn laser_scan_roundtrip() {
+        let scans = [
+            // The second and last measurements are out of range.
+            scan(vec![1.0, 5.0, 2.0, 0.05]),
+            scan(vec![f32::INFINITY, 0.0]),
+        ];
+
+        let chunks = run_ros2_layer(
+            "sensor_msgs/msg/LaserScan",
+            "/scan",
+            &scans.iter().map(cdr_encode).collect::<Vec<_>>(),
+        );
+
+        let points = find_chunk_with(&chunks, Points3D::descriptor_positions().component);
+        assert_eq!(points.num_rows(), 2);
+
+        let positions = |row| {
+            points
+                .component_batch::<Position3D>(Points3D::descriptor_positions().component, row)
+                .unwrap()
+                .unwrap()
+        };
+
+        let first = positions(0);
+        assert_eq!(first.len(), 2);
+        for (position, expected) in first.iter().zip([[1.0, 0.0, 0.0], [-2.0, 0.0, 0.0]]) {
+            for (actual, expected) in position.0.0.iter().zip(expected) {
+                assert!((actual - expected).abs() < 1e-6, "{position:?}");
+            }
+        }
+
+        assert!(positions(1).is_empty());
+    }
+}
@@ -5,7 +5,10 @@ use re_types::{archetypes::Arrows3D, datatypes::Vec3D};
 
 use crate::{
     Error,
-    parsers::{MessageParser, ParserContext, cdr, ros2msg::Ros2MessageParser},
+    parsers::{
+        MessageParser, ParserContext,
+        ros2msg::{Ros2MessageParser, decode_message, std_msgs::HeaderFrameIds},
+    },
 };
 
 /// Plugin that parses `sensor_msgs/msg/MagneticField` messages.
@@ -14,26 +17,28 @@ pub struct MagneticFieldSchemaPlugin;
 
 pub struct MagneticFieldMessageParser {
     vectors: Vec<Vec3D>,
+    frame_ids: HeaderFrameIds,
 }
 
 impl Ros2MessageParser for MagneticFieldMessageParser {
     fn new(num_rows: usize) -> Self {
         Self {
             vectors: Vec::with_capacity(num_rows),
+            frame_ids: HeaderFrameIds::new(num_rows),
         }
     }
 }
 
 impl MessageParser for MagneticFieldMessageParser {
     fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
-        let magnetic_field =
-            cdr::try_decode_message::<sensor_msgs::MagneticField>(msg.data.as_ref())
-                .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
+        let magnetic_field = decode_message::<sensor_msgs::MagneticField>(msg)
+            .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
 
         // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
         ctx.add_timestamp_cell(crate::util::TimestampCell::guess_from_nanos_ros2(
             magnetic_field.header.stamp.as_nanos() as u64,
         ));
+        self.frame_ids.push(&magnetic_field.header);
 
         // Convert magnetic field vector to Vector3D and store
         self.vectors.push(Vec3D([
@@ -49,18 +54,20 @@ impl MessageParser for MagneticFieldMessageParser {
         let entity_path = ctx.entity_path().clone();
         let timelines = ctx.build_timelines();
 
-        let Self { vectors } = *self;
+        let Self { vectors, frame_ids } = *self;
 
         let data_chunk = Chunk::from_auto_row_ids(
             ChunkId::new(),
             entity_path.clone(),
-            timelines,
+            timelines.clone(),
             Arrows3D::update_fields()
                 .with_vectors(vectors)
                 .columns_of_unit_batches()?
                 .collect(),
         )?;
 
-        Ok(vec![data_chunk])
+        Ok(std::iter::once(data_chunk)
+            .chain(frame_ids.into_chunk(entity_path, timelines)?)
+            .collect())
     }
 }
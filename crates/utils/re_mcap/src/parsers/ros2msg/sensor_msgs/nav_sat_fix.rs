@@ -7,9 +7,8 @@ use re_types::{
 
 use super::super::Ros2MessageParser;
 use crate::parsers::{
-    cdr,
     decode::{MessageParser, ParserContext},
-    ros2msg::definitions::sensor_msgs,
+    ros2msg::{decode_message, definitions::sensor_msgs},
     util::fixed_size_list_builder,
 };
 
@@ -52,8 +51,8 @@ impl MessageParser for NavSatFixMessageParser {
             longitude,
             altitude,
             ..
-        } = cdr::try_decode_message::<sensor_msgs::NavSatFix>(&msg.data)
-            .context("Failed to decode sensor_msgs::NavSatFix message from CDR data")?;
+        } = decode_message::<sensor_msgs::NavSatFix>(msg)
+            .context("Failed to decode sensor_msgs::NavSatFix message")?;
 
         // add the sensor timestamp to the context, `log_time` and `publish_time` are added automatically
         ctx.add_timestamp_cell(crate::util::TimestampCell::guess_from_nanos_ros2(
@@ -18,12 +18,12 @@ use re_types::{
 };
 use std::collections::HashMap;
 
-use super::super::Ros2MessageParser;
+use super::super::{Ros2MessageParser, std_msgs::HeaderFrameIds};
 use crate::{
     Error,
     parsers::{
-        cdr,
         decode::{MessageParser, ParserContext},
+        ros2msg::decode_message,
         util::{blob_list_builder, fixed_size_list_builder},
     },
 };
@@ -45,6 +45,8 @@ pub struct PointCloud2MessageParser {
     // We lazily create this, only if we can interpret the point cloud semantically.
     // For now, this is the case if there are fields with names `x`,`y`, and `z` present.
     points_3ds: Option<Vec<archetypes::Points3D>>,
+
+    frame_ids: HeaderFrameIds,
 }
 
 impl PointCloud2MessageParser {
@@ -87,6 +89,8 @@ impl Ros2MessageParser for PointCloud2MessageParser {
             extracted_fields: Default::default(),
 
             points_3ds: None,
+
+            frame_ids: HeaderFrameIds::new(num_rows),
         }
     }
 }
@@ -328,12 +332,13 @@ fn add_field_value(
 
 impl MessageParser for PointCloud2MessageParser {
     fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
-        let point_cloud = cdr::try_decode_message::<sensor_msgs::PointCloud2>(msg.data.as_ref())
+        let point_cloud = decode_message::<sensor_msgs::PointCloud2>(msg)
             .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
 
         ctx.add_timestamp_cell(crate::util::TimestampCell::guess_from_nanos_ros2(
             point_cloud.header.stamp.as_nanos() as u64,
         ));
+        self.frame_ids.push(&point_cloud.header);
 
         let Self {
             num_rows,
@@ -350,6 +355,8 @@ impl MessageParser for PointCloud2MessageParser {
             extracted_fields,
 
             points_3ds,
+
+            frame_ids: _,
         } = self;
 
         height.values().append_slice(&[point_cloud.height]);
@@ -476,6 +483,8 @@ impl MessageParser for PointCloud2MessageParser {
             extracted_fields: points,
 
             points_3ds,
+
+            frame_ids,
         } = *self;
 
         let mut chunks = Vec::new();
@@ -502,6 +511,8 @@ impl MessageParser for PointCloud2MessageParser {
             chunks.push(c);
         }
 
+        chunks.extend(frame_ids.into_chunk(entity_path.clone(), timelines.clone())?);
+
         let data_chunk = Chunk::from_auto_row_ids(
             ChunkId::new(),
             entity_path.clone(),
@@ -0,0 +1,63 @@
+use re_chunk::{
+    Chunk, ChunkId, EntityPath, TimeColumn, TimelineName, external::nohash_hasher::IntMap,
+};
+use re_types::{archetypes::CoordinateFrame, components::TransformFrameId};
+
+use super::super::definitions::std_msgs::Header;
+
+/// Collects the `header.frame_id` of every message of a topic.
+///
+/// Logging them as [`CoordinateFrame`]s attaches the data of the topic to the frames described by
+/// the `tf2_msgs/msg/TFMessage` transforms, instead of the frame implied by its entity path.
+pub struct HeaderFrameIds {
+    frame_ids: Vec<TransformFrameId>,
+    any_frame_id: bool,
+}
+
+impl HeaderFrameIds {
+    pub fn new(num_rows: usize) -> Self {
+        Self {
+            frame_ids: Vec::with_capacity(num_rows),
+            any_frame_id: false,
+        }
+    }
+
+    pub fn push(&mut self, header: &Header) {
+        self.push_frame_id(&header.frame_id);
+    }
+
+    pub fn push_frame_id(&mut self, frame_id: &str) {
+        self.any_frame_id |= !frame_id.is_empty();
+        self.frame_ids.push(TransformFrameId::new(frame_id));
+    }
+
+    /// Returns the chunk of [`CoordinateFrame`]s for the collected messages.
+    ///
+    /// Returns `None` if none of the messages specified a frame.
+    pub fn into_chunk(
+        self,
+        entity_path: EntityPath,
+        timelines: IntMap<TimelineName, TimeColumn>,
+    ) -> anyhow::Result<Option<Chunk>> {
+        let Self {
+            frame_ids,
+            any_frame_id,
+        } = self;
+
+        if !any_frame_id {
+            return Ok(None);
+        }
+
+        let chunk = Chunk::from_auto_row_ids(
+            ChunkId::new(),
+            entity_path,
+            timelines,
+            CoordinateFrame::update_fields()
+                .with_many_frame_id(frame_ids)
+                .columns_of_unit_batches()?
+                .collect(),
+        )?;
+
+        Ok(Some(chunk))
+    }
+}
@@ -1,3 +1,5 @@
+mod header;
 mod string;
 
+pub use header::*;
 pub use string::*;
@@ -3,8 +3,7 @@ use re_chunk::{Chunk, ChunkId};
 use re_types::archetypes::TextDocument;
 
 use crate::parsers::{
-    cdr,
-    ros2msg::Ros2MessageParser,
+    ros2msg::{Ros2MessageParser, decode_message},
     {MessageParser, ParserContext},
 };
 
@@ -24,8 +23,7 @@ impl Ros2MessageParser for StringMessageParser {
 
 impl MessageParser for StringMessageParser {
     fn append(&mut self, _ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
-        let std_msgs::StringMessage { data } =
-            cdr::try_decode_message::<std_msgs::StringMessage>(&msg.data)?;
+        let std_msgs::StringMessage { data } = decode_message::<std_msgs::StringMessage>(msg)?;
         self.texts.push(data);
         Ok(())
     }
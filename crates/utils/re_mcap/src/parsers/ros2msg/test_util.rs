@@ -0,0 +1,85 @@
+//! Helpers to test the ROS 2 message parsers end-to-end, from CDR-encoded messages to chunks.
+
+use std::io;
+
+use re_chunk::{Chunk, ComponentIdentifier};
+
+use super::definitions::{builtin_interfaces::Time, std_msgs::Header};
+use crate::{LayerRegistry, layers::McapRos2Layer, parsers::dds::RepresentationIdentifier};
+
+/// A header in the given frame, stamped in November 2023.
+pub fn header(frame_id: &str) -> Header {
+    Header {
+        stamp: Time {
+            sec: 1_700_000_000,
+            nanosec: 0,
+        },
+        frame_id: frame_id.to_owned(),
+    }
+}
+
+/// Encodes a message the way ROS 2 publishes it: a little-endian CDR payload, prefixed with its
+/// representation identifier and options.
+pub fn cdr_encode<T: serde::Serialize>(msg: &T) -> Vec<u8> {
+    let mut data = RepresentationIdentifier::CdrLittleEndian
+        .to_bytes()
+        .to_vec();
+    data.extend([0x00, 0x00]); // options
+    data.extend(
+        cdr_encoding::to_vec::<T, byteorder::LittleEndian>(msg)
+            .expect("failed to encode CDR message"),
+    );
+    data
+}
+
+/// Writes the given CDR-encoded `messages` of type `schema_name` to an MCAP file, on `topic`, and
+/// runs the [`McapRos2Layer`] on it.
+///
+/// Both the `log_time` and `publish_time` of the n-th message are `42 + n`.
+pub fn run_ros2_layer(schema_name: &str, topic: &str, messages: &[Vec<u8>]) -> Vec<Chunk> {
+    let (summary, buffer) = {
+        let cursor = io::Cursor::new(Vec::new());
+        let mut writer = mcap::Writer::new(cursor).expect("failed to create writer");
+
+        // The layer does not need the message definition itself, only its name.
+        let schema_id = writer
+            .add_schema(schema_name, "ros2msg", b"")
+            .expect("failed to add schema");
+        let channel_id = writer
+            .add_channel(schema_id, topic, "cdr", &Default::default())
+            .expect("failed to add channel");
+
+        for (timestamp, data) in (42..).zip(messages) {
+            let header = mcap::records::MessageHeader {
+                channel_id,
+                sequence: 0,
+                log_time: timestamp,
+                publish_time: timestamp,
+            };
+            writer
+                .write_to_known_channel(&header, data)
+                .expect("failed to write message");
+        }
+
+        let summary = writer.finish().expect("finishing writer failed");
+        (summary, writer.into_inner().into_inner())
+    };
+
+    let mut chunks = Vec::new();
+    LayerRegistry::empty()
+        .register_message_layer::<McapRos2Layer>()
+        .plan(&summary)
+        .expect("failed to plan")
+        .run(&buffer, &summary, &mut |chunk| chunks.push(chunk))
+        .expect("failed to run layer");
+
+    chunks
+}
+
+/// The first of the `chunks` that contains the given component.
+pub fn find_chunk_with(chunks: &[Chunk], component: ComponentIdentifier) -> &Chunk {
+    chunks
+        .iter()
+        .find(|chunk| chunk.components().contains_component(component))
+        .unwrap_or_else(|| panic!("no chunk contains {component}"))
+}
@@ -0,0 +1,305 @@
+use std::collections::BTreeMap;
+
+use re_chunk::{Chunk, ChunkId, EntityPath, TimePoint, external::nohash_hasher::IntMap};
+use re_types::{
+    archetypes::Transform3D,
+    components::{RotationQuat, TransformFrameId, Translation3D},
+    datatypes::{Quaternion, Vec3D},
+};
+
+use super::super::definitions::tf2_msgs;
+use crate::{
+    Error,
+    parsers::{
+        MessageParser, ParserContext,
+        ros2msg::{Ros2MessageParser, decode_message},
+    },
+    util::TimestampCell,
+};
+
+/// The transforms received for a single child frame.
+struct ChildFrameTransforms {
+    /// Each child frame gets its own entity, and therefore its own timelines.
+    ctx: ParserContext,
+    translations: Vec<Translation3D>,
+    quaternions: Vec<RotationQuat>,
+    parent_frames: Vec<TransformFrameId>,
+}
+
+impl ChildFrameTransforms {
+    fn new(entity_path: EntityPath) -> Self {
+        Self {
+            ctx: ParserContext::new(entity_path),
+            translations: Vec::new(),
+            quaternions: Vec::new(),
+            parent_frames: Vec::new(),
+        }
+    }
+}
+
+/// Parses `tf2_msgs/msg/TFMessage` messages into [`Transform3D`]s between named frames.
+///
+/// A single message may contain transforms for any number of child frames. Since an entity can only
+/// ever describe a single child frame at a time, the transforms of each child frame are logged to a
+/// child entity of the topic, e.g. `/tf/base_link`.
+///
+/// Transforms received on a `tf_static` topic are logged as static data.
+pub struct TfMessageParser {
+    per_child_frame: BTreeMap<String, ChildFrameTransforms>,
+}
+
+impl TfMessageParser {
+    /// Is this the topic on which ROS publishes transforms that never change?
+    fn is_static_topic(entity_path: &EntityPath) -> bool {
+        entity_path
+            .last()
+            .is_some_and(|part| part.unescaped_str() == "tf_static")
+    }
+}
+
+impl Ros2MessageParser for TfMessageParser {
+    fn new(_num_rows: usize) -> Self {
+        Self {
+            per_child_frame: BTreeMap::new(),
+        }
+    }
+}
+
+impl MessageParser for TfMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        let tf_message = decode_message::<tf2_msgs::TFMessage>(msg)
+            .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
+
+        // The timepoint that is added automatically to `ctx` only accounts for a single row per
+        // message, so we have to keep track of `log_time` and `publish_time` ourselves.
+        let timepoint = TimePoint::from([
+            (
+                "log_time",
+                TimestampCell::guess_from_nanos(msg.log_time).into_time_cell(),
+            ),
+            (
+                "publish_time",
+                TimestampCell::guess_from_nanos(msg.publish_time).into_time_cell(),
+            ),
+        ]);
+
+        for transform in tf_message.transforms {
+            let child_frame = self
+                .per_child_frame
+                .entry(transform.child_frame_id.clone())
+                .or_insert_with(|| {
+                    ChildFrameTransforms::new(ctx.entity_path().join(
+                        &EntityPath::from_single_string(transform.child_frame_id.as_str()),
+                    ))
+                });
+
+            child_frame
+                .ctx
+                .add_timepoint(timepoint.clone())
+                .add_timestamp_cell(TimestampCell::guess_from_nanos_ros2(
+                    transform.header.stamp.as_nanos() as u64,
+                ));
+
+            let translation = &transform.transform.translation;
+            let rotation = &transform.transform.rotation;
+
+            child_frame
+                .translations
+                .push(Vec3D::from([translation.x, translation.y, translation.z]).into());
+            child_frame.quaternions.push(
+                Quaternion::from_xyzw([
+                    rotation.x as f32,
+                    rotation.y as f32,
+                    rotation.z as f32,
+                    rotation.w as f32,
+                ])
+                .into(),
+            );
+            child_frame
+                .parent_frames
+                .push(TransformFrameId::new(&transform.header.frame_id));
+        }
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        let is_static = Self::is_static_topic(ctx.entity_path());
+
+        let Self { per_child_frame } = *self;
+
+        per_child_frame
+            .into_iter()
+            .map(|(child_frame_id, transforms)| {
+                let ChildFrameTransforms {
+                    ctx,
+                    translations,
+                    quaternions,
+                    parent_frames,
+                } = transforms;
+
+                let entity_path = ctx.entity_path().clone();
+                let timelines = if is_static {
+                    IntMap::default()
+                } else {
+                    ctx.build_timelines()
+                };
+
+                let child_frames = vec![TransformFrameId::new(&child_frame_id); translations.len()];
+
+                let chunk = Chunk::from_auto_row_ids(
+                    ChunkId::new(),
+                    entity_path,
+                    timelines,
+                    Transform3D::update_fields()
+                        .with_many_translation(translations)
+                        .with_many_quaternion(quaternions)
+                        .with_many_child_frame(child_frames)
+                        .with_many_parent_frame(parent_frames)
+                        .columns_of_unit_batches()?
+                        .collect(),
+                )?;
+
+                Ok(chunk)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use re_chunk::TimelineName;
+
+    use super::*;
+    use crate::parsers::ros2msg::{
+        definitions::{
+            builtin_interfaces::Time,
+            geometry_msgs::{self, Transform, TransformStamped, Vector3},
+            std_msgs::Header,
+            tf2_msgs::TFMessage,
+        },
+        test_util::{cdr_encode, run_ros2_layer},
+    };
+
+    fn transform(parent: &str, child: &str, x: f64) -> TransformStamped {
+        TransformStamped {
+            header: Header {
+                stamp: Time {
+                    sec: 1_700_000_000,
+                    nanosec: 0,
+                },
+                frame_id: parent.to_owned(),
+            },
+            child_frame_id: child.to_owned(),
+            transform: Transform {
+                translation: Vector3 { x, y: 2.0, z: 3.0 },
+                rotation: geometry_msgs::Quaternion {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                    w: 1.0,
+                },
+            },
+        }
+    }
+
+    fn messages() -> Vec<Vec<u8>> {
+        vec![
+            cdr_encode(&TFMessage {
+                transforms: vec![
+                    transform("odom", "base_link", 1.0),
+                    transform("base_link", "camera", 10.0),
+                ],
+            }),
+            cdr_encode(&TFMessage {
+                transforms: vec![transform("odom", "base_link", 5.0)],
+            }),
+        ]
+    }
+
+    fn find_chunk<'a>(chunks: &'a [Chunk], entity_path: &str) -> &'a Chunk {
+        chunks
+            .iter()
+            .find(|chunk| chunk.entity_path() == &EntityPath::from(entity_path))
+            .unwrap_or_else(|| panic!("missing chunk for {entity_path}"))
+    }
+
+    #[test]
+    fn tf_roundtrip() {
+        let chunks = run_ros2_layer("tf2_msgs/msg/TFMessage", "/tf", &messages());
+
+        let base_link = find_chunk(&chunks, "/tf/base_link");
+        assert!(!base_link.is_static());
+        assert_eq!(base_link.num_rows(), 2);
+        for timeline in ["log_time", "publish_time", "ros2_timestamp"] {
+            assert!(
+                base_link
+                    .timelines()
+                    .contains_key(&TimelineName::new(timeline)),
+                "missing {timeline} timeline"
+            );
+        }
+
+        let translations = (0..2)
+            .map(|row| {
+                base_link
+                    .component_mono::<Translation3D>(
+                        Transform3D::descriptor_translation().component,
+                        row,
+                    )
+                    .unwrap()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            translations,
+            vec![
+                Translation3D::new(1.0, 2.0, 3.0),
+                Translation3D::new(5.0, 2.0, 3.0)
+            ]
+        );
+
+        let camera = find_chunk(&chunks, "/tf/camera");
+        assert_eq!(camera.num_rows(), 1);
+        assert_eq!(
+            camera
+                .component_mono::<TransformFrameId>(
+                    Transform3D::descriptor_parent_frame().component,
+                    0
+                )
+                .unwrap()
+                .unwrap(),
+            TransformFrameId::new("base_link")
+        );
+        assert_eq!(
+            camera
+                .component_mono::<TransformFrameId>(
+                    Transform3D::descriptor_child_frame().component,
+                    0
+                )
+                .unwrap()
+                .unwrap(),
+            TransformFrameId::new("camera")
+        );
+        assert_eq!(
+            camera
+                .component_mono::<RotationQuat>(Transform3D::descriptor_quaternion().component, 0)
+                .unwrap()
+                .unwrap(),
+            RotationQuat::IDENTITY
+        );
+    }
+
+    #[test]
+    fn tf_static_roundtrip() {
+        let chunks = run_ros2_layer("tf2_msgs/msg/TFMessage", "/tf_static", &messages());
+
+        let base_link = find_chunk(&chunks, "/tf_static/base_link");
+        assert!(base_link.is_static());
+        assert_eq!(base_link.num_rows(), 2);
+
+        let camera = find_chunk(&chunks, "/tf_static/camera");
+        assert!(camera.is_static());
+        assert_eq!(camera.num_rows(), 1);
+    }
+}
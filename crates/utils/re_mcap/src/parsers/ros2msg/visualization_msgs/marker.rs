@@ -0,0 +1,739 @@
+use std::collections::{BTreeMap, HashMap};
+
+use re_chunk::{Chunk, EntityPath, RowId, TimePoint, TimelineName};
+use re_log_types::TimeCell;
+use re_types::{
+    AsComponents as _, SerializedComponentBatch,
+    archetypes::{
+        Arrows3D, Asset3D, Boxes3D, Clear, Cylinders3D, Ellipsoids3D, LineStrips3D, Mesh3D,
+        Points3D, Transform3D,
+    },
+    components::{Color, FillMode, MediaType, TransformFrameId},
+    datatypes::{Quaternion, Vec3D},
+};
+
+use super::super::definitions::{
+    geometry_msgs::Point,
+    std_msgs::ColorRGBA,
+    visualization_msgs::{Marker, MarkerAction, MarkerArray, MarkerType},
+};
+use crate::{
+    Error,
+    parsers::{
+        MessageParser, ParserContext,
+        ros2msg::{Ros2MessageParser, decode_message},
+    },
+    util::TimestampCell,
+};
+
+/// Parses `visualization_msgs/msg/Marker` messages.
+///
+/// See [`MarkerArrayMessageParser`] for how markers are mapped onto entities.
+pub struct MarkerMessageParser {
+    markers: MarkerRows,
+}
+
+impl Ros2MessageParser for MarkerMessageParser {
+    fn new(_num_rows: usize) -> Self {
+        Self {
+            markers: MarkerRows::default(),
+        }
+    }
+}
+
+impl MessageParser for MarkerMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        let marker =
+            decode_message::<Marker<'_>>(msg).map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
+
+        self.markers.add(ctx.entity_path(), msg, &marker);
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, _ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        self.markers.into_chunks()
+    }
+}
+
+/// Parses `visualization_msgs/msg/MarkerArray` messages.
+///
+/// Every marker is logged to its own entity below the topic, identified by its namespace and id,
+/// e.g. `/markers/obstacles/3`:
+/// * The geometry is logged using the closest Rerun archetype (e.g. [`Boxes3D`] for cubes).
+/// * The pose of the marker is logged as a [`Transform3D`] whose parent is the frame of the marker's header.
+/// * `DELETE` and `DELETEALL` actions are logged as [`Clear`]s.
+/// * Markers with a lifetime are cleared when they expire, unless they were updated in the meantime.
+pub struct MarkerArrayMessageParser {
+    markers: MarkerRows,
+}
+
+impl Ros2MessageParser for MarkerArrayMessageParser {
+    fn new(_num_rows: usize) -> Self {
+        Self {
+            markers: MarkerRows::default(),
+        }
+    }
+}
+
+impl MessageParser for MarkerArrayMessageParser {
+    fn append(&mut self, ctx: &mut ParserContext, msg: &mcap::Message<'_>) -> anyhow::Result<()> {
+        let marker_array = decode_message::<MarkerArray<'_>>(msg)
+            .map_err(|err| Error::Other(anyhow::anyhow!(err)))?;
+
+        for marker in &marker_array.markers {
+            self.markers.add(ctx.entity_path(), msg, marker);
+        }
+
+        Ok(())
+    }
+
+    fn finalize(self: Box<Self>, _ctx: ParserContext) -> anyhow::Result<Vec<Chunk>> {
+        self.markers.into_chunks()
+    }
+}
+
+// ---
+
+/// A single row of marker data (or a clear) for a given entity.
+struct MarkerRow {
+    /// Row ids are assigned in the order in which the markers were received, so that e.g. markers
+    /// that are added right after a `DELETEALL` with the same timestamp are not cleared.
+    row_id: RowId,
+    timepoint: TimePoint,
+
+    /// The `log_time` of the message, in nanoseconds.
+    log_time: u64,
+
+    /// How long the marker lasts, in nanoseconds, if it is not forever.
+    lifetime: Option<u64>,
+
+    components: Vec<SerializedComponentBatch>,
+}
+
+/// Accumulates the rows of all the markers of a topic, one entity per marker.
+///
+/// Since a single message may contain many markers, each with its own header, the rows are not
+/// aligned with the messages: we keep track of the timelines of every row ourselves.
+#[derive(Default)]
+struct MarkerRows {
+    per_entity: BTreeMap<EntityPath, Vec<MarkerRow>>,
+
+    /// The type of the last marker added to each entity.
+    marker_types: HashMap<EntityPath, MarkerType>,
+}
+
+impl MarkerRows {
+    fn add(&mut self, topic: &EntityPath, msg: &mcap::Message<'_>, marker: &Marker<'_>) {
+        let action = match MarkerAction::try_from(marker.action) {
+            Ok(action) => action,
+            Err(action) => {
+                re_log::warn_once!("Ignoring marker with unknown action {action} on {topic}");
+                return;
+            }
+        };
+
+        let namespace = (!marker.ns.is_empty())
+            .then(|| topic.join(&EntityPath::from_single_string(marker.ns.as_str())));
+
+        let entity_path = namespace
+            .clone()
+            .unwrap_or_else(|| topic.clone())
+            .join(&EntityPath::from_single_string(marker.id.to_string()));
+
+        let rows = match action {
+            MarkerAction::DeleteAll => {
+                let entity_path = namespace.unwrap_or_else(|| topic.clone());
+                self.marker_types
+                    .retain(|path, _| !path.starts_with(&entity_path));
+                vec![(entity_path, Clear::recursive().as_serialized_batches())]
+            }
+
+            MarkerAction::Delete => {
+                self.marker_types.remove(&entity_path);
+                vec![(entity_path, Clear::flat().as_serialized_batches())]
+            }
+
+            MarkerAction::Add => {
+                let marker_type = match MarkerType::try_from(marker.marker_type) {
+                    Ok(marker_type) => marker_type,
+                    Err(marker_type) => {
+                        re_log::warn_once!(
+                            "Ignoring marker with unknown type {marker_type} on {topic}"
+                        );
+                        return;
+                    }
+                };
+
+                let Some(geometry) = marker_geometry(marker, marker_type) else {
+                    return;
+                };
+
+                let mut rows = Vec::with_capacity(2);
+
+                // Don't let the components of a marker of another type linger around.
+                if let Some(previous_type) =
+                    self.marker_types.insert(entity_path.clone(), marker_type)
+                    && previous_type != marker_type
+                {
+                    rows.push((entity_path.clone(), Clear::flat().as_serialized_batches()));
+                }
+
+                let mut components = marker_pose(marker, marker_type).as_serialized_batches();
+                components.extend(geometry);
+                rows.push((entity_path, components));
+
+                rows
+            }
+        };
+
+        let stamp = TimestampCell::guess_from_nanos_ros2(marker.header.stamp.as_nanos() as u64);
+        let timepoint = TimePoint::from([
+            (
+                "log_time",
+                TimestampCell::guess_from_nanos(msg.log_time).into_time_cell(),
+            ),
+            (
+                "publish_time",
+                TimestampCell::guess_from_nanos(msg.publish_time).into_time_cell(),
+            ),
+        ])
+        .with_index(
+            TimelineName::from(stamp.timeline_name()),
+            stamp.into_time_cell(),
+        );
+
+        let lifetime = u64::try_from(marker.lifetime.as_nanos())
+            .ok()
+            .filter(|lifetime| *lifetime > 0 && action == MarkerAction::Add);
+
+        for (entity_path, components) in rows {
+            self.per_entity
+                .entry(entity_path)
+                .or_default()
+                .push(MarkerRow {
+                    row_id: RowId::new(),
+                    timepoint: timepoint.clone(),
+                    log_time: msg.log_time,
+                    lifetime,
+                    components,
+                });
+        }
+    }
+
+    fn into_chunks(self) -> anyhow::Result<Vec<Chunk>> {
+        let Self {
+            per_entity,
+            marker_types: _,
+        } = self;
+
+        per_entity
+            .into_iter()
+            .map(|(entity_path, rows)| {
+                let mut builder = Chunk::builder(entity_path);
+
+                for (i, row) in rows.iter().enumerate() {
+                    builder = builder.with_serialized_batches(
+                        row.row_id,
+                        row.timepoint.clone(),
+                        row.components.clone(),
+                    );
+
+                    // Clear the marker when it expires, unless it was updated in the meantime.
+                    if let Some(lifetime) = row.lifetime {
+                        let expires_at = row.log_time.saturating_add(lifetime);
+                        if rows
+                            .get(i + 1)
+                            .is_none_or(|next| next.log_time > expires_at)
+                        {
+                            builder = builder.with_serialized_batches(
+                                RowId::new(),
+                                shift_timepoint(&row.timepoint, lifetime),
+                                Clear::flat().as_serialized_batches(),
+                            );
+                        }
+                    }
+                }
+
+                Ok(builder.build()?)
+            })
+            .collect()
+    }
+}
+
+fn shift_timepoint(timepoint: &TimePoint, nanos: u64) -> TimePoint {
+    let nanos = i64::try_from(nanos).unwrap_or(i64::MAX);
+    timepoint
+        .iter()
+        .map(|(timeline, cell)| {
+            (
+                *timeline,
+                TimeCell::new(cell.typ, cell.value.get().saturating_add(nanos)),
+            )
+        })
+        .collect()
+}
+
+// ---
+
+/// The pose of the marker, relative to the frame of its header.
+fn marker_pose(marker: &Marker<'_>, marker_type: MarkerType) -> Transform3D {
+    let position = &marker.pose.position;
+    let orientation = &marker.pose.orientation;
+
+    let mut transform = Transform3D::update_fields()
+        .with_translation(Vec3D::from([position.x, position.y, position.z]))
+        .with_quaternion(Quaternion::from_xyzw([
+            orientation.x as f32,
+            orientation.y as f32,
+            orientation.z as f32,
+            orientation.w as f32,
+        ]));
+
+    // Meshes are the only markers for which the scale is an actual scale.
+    if marker_type == MarkerType::MeshResource {
+        transform = transform.with_scale(scale(marker));
+    }
+
+    if !marker.header.frame_id.is_empty() {
+        transform = transform.with_parent_frame(TransformFrameId::new(&marker.header.frame_id));
+    }
+
+    transform
+}
+
+/// The geometry of the marker, in its own frame.
+///
+/// Returns `None` if the marker cannot be represented.
+fn marker_geometry(
+    marker: &Marker<'_>,
+    marker_type: MarkerType,
+) -> Option<Vec<SerializedComponentBatch>> {
+    let scale = &marker.scale;
+    let half_size = Vec3D::from([scale.x / 2.0, scale.y / 2.0, scale.z / 2.0]);
+    let points = marker.points.iter().map(point).collect::<Vec<_>>();
+
+    let batches = match marker_type {
+        MarkerType::Arrow => {
+            // Either from the first to the second point, or along the x-axis of the pose.
+            let (origin, vector, radius) = match marker.points.as_slice() {
+                [start, end, ..] => (
+                    point(start),
+                    Vec3D::from([end.x - start.x, end.y - start.y, end.z - start.z]),
+                    scale.x / 2.0,
+                ),
+                _ => (Vec3D::ZERO, Vec3D::from([scale.x, 0.0, 0.0]), scale.y / 2.0),
+            };
+
+            Arrows3D::from_vectors([vector])
+                .with_origins([origin])
+                .with_radii([radius as f32])
+                .with_colors([color(&marker.color)])
+                .as_serialized_batches()
+        }
+
+        MarkerType::ArrowStrip => Arrows3D::from_vectors(marker.points.windows(2).map(|pair| {
+            Vec3D::from([
+                pair[1].x - pair[0].x,
+                pair[1].y - pair[0].y,
+                pair[1].z - pair[0].z,
+            ])
+        }))
+        .with_origins(points.iter().take(points.len().saturating_sub(1)).copied())
+        .with_radii([(scale.x / 2.0) as f32])
+        .with_colors([color(&marker.color)])
+        .as_serialized_batches(),
+
+        MarkerType::Cube => Boxes3D::from_half_sizes([half_size])
+            .with_colors([color(&marker.color)])
+            .with_fill_mode(FillMode::Solid)
+            .as_serialized_batches(),
+
+        MarkerType::CubeList => Boxes3D::from_centers_and_half_sizes(points, [half_size])
+            .with_colors(point_colors(marker))
+            .with_fill_mode(FillMode::Solid)
+            .as_serialized_batches(),
+
+        MarkerType::Sphere => Ellipsoids3D::from_half_sizes([half_size])
+            .with_colors([color(&marker.color)])
+            .with_fill_mode(FillMode::Solid)
+            .as_serialized_batches(),
+
+        MarkerType::SphereList => Ellipsoids3D::from_centers_and_half_sizes(points, [half_size])
+            .with_colors(point_colors(marker))
+            .with_fill_mode(FillMode::Solid)
+            .as_serialized_batches(),
+
+        // Elliptic cylinders are not supported, we use the largest radius instead.
+        MarkerType::Cylinder => Cylinders3D::from_lengths_and_radii(
+            [scale.z as f32],
+            [(scale.x.max(scale.y) / 2.0) as f32],
+        )
+        .with_colors([color(&marker.color)])
+        .with_fill_mode(FillMode::Solid)
+        .as_serialized_batches(),
+
+        // Line strips can only have a single color.
+        MarkerType::LineStrip => LineStrips3D::new([points])
+            .with_radii([(scale.x / 2.0) as f32])
+            .with_colors([marker
+                .colors
+                .first()
+                .map_or_else(|| color(&marker.color), color)])
+            .as_serialized_batches(),
+
+        MarkerType::LineList => {
+            let colors = if marker.colors.len() == marker.points.len() {
+                marker.colors.iter().step_by(2).map(color).collect()
+            } else {
+                vec![color(&marker.color)]
+            };
+
+            LineStrips3D::new(points.chunks_exact(2).map(|segment| segment.to_vec()))
+                .with_radii([(scale.x / 2.0) as f32])
+                .with_colors(colors)
+                .as_serialized_batches()
+        }
+
+        MarkerType::Points => Points3D::new(points)
+            .with_radii([(scale.x / 2.0) as f32])
+            .with_colors(point_colors(marker))
+            .as_serialized_batches(),
+
+        // There is no 3D text primitive, the closest thing is a labeled point.
+        MarkerType::TextViewFacing => Points3D::new([Vec3D::ZERO])
+            .with_labels([marker.text.as_str()])
+            .with_show_labels(true)
+            .with_colors([color(&marker.color)])
+            .as_serialized_batches(),
+
+        MarkerType::MeshResource => {
+            if marker.mesh_file.data.is_empty() {
+                re_log::warn_once!(
+                    "Mesh resource {:?} can't be loaded: only meshes embedded in the marker are supported",
+                    marker.mesh_resource
+                );
+                return None;
+            }
+
+            let mut asset = Asset3D::from_file_contents(
+                marker.mesh_file.data.to_vec(),
+                MediaType::guess_from_path(&marker.mesh_file.filename),
+            );
+            if !marker.mesh_use_embedded_materials {
+                asset = asset.with_albedo_factor(color(&marker.color).0);
+            }
+            asset.as_serialized_batches()
+        }
+
+        // Unlike for other types, the scale applies to the points themselves.
+        MarkerType::TriangleList => {
+            let positions = marker
+                .points
+                .iter()
+                .map(|p| Vec3D::from([p.x * scale.x, p.y * scale.y, p.z * scale.z]));
+
+            let mesh = Mesh3D::new(positions);
+            let mesh = if marker.colors.len() == marker.points.len() {
+                mesh.with_vertex_colors(marker.colors.iter().map(color))
+            } else if !marker.colors.is_empty() && marker.colors.len() * 3 == marker.points.len() {
+                // One color per triangle.
+                mesh.with_vertex_colors(
+                    marker
+                        .colors
+                        .iter()
+                        .flat_map(|c| std::iter::repeat_n(color(c), 3)),
+                )
+            } else {
+                mesh.with_albedo_factor(color(&marker.color).0)
+            };
+            mesh.as_serialized_batches()
+        }
+    };
+
+    Some(batches)
+}
+
+fn scale(marker: &Marker<'_>) -> Vec3D {
+    Vec3D::from([marker.scale.x, marker.scale.y, marker.scale.z])
+}
+
+fn point(point: &Point) -> Vec3D {
+    Vec3D::from([point.x, point.y, point.z])
+}
+
+fn color(color: &ColorRGBA) -> Color {
+    let to_u8 = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Color::from_unmultiplied_rgba(
+        to_u8(color.r),
+        to_u8(color.g),
+        to_u8(color.b),
+        to_u8(color.a),
+    )
+}
+
+/// One color per point if there are as many, the color of the marker otherwise.
+fn point_colors(marker: &Marker<'_>) -> Vec<Color> {
+    if !marker.colors.is_empty() && marker.colors.len() == marker.points.len() {
+        marker.colors.iter().map(color).collect()
+    } else {
+        vec![color(&marker.color)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use re_types::components::{ClearIsRecursive, HalfSize3D, Translation3D};
+
+    use super::*;
+    use crate::parsers::ros2msg::{
+        definitions::{
+            builtin_interfaces::{Duration, Time},
+            geometry_msgs::{self, Pose, Vector3},
+            sensor_msgs::CompressedImage,
+            std_msgs::Header,
+            visualization_msgs::MeshFile,
+        },
+        test_util::{cdr_encode, run_ros2_layer},
+    };
+
+    fn marker(ns: &str, id: i32, marker_type: MarkerType, action: MarkerAction) -> Marker<'static> {
+        let header = || Header {
+            stamp: Time {
+                sec: 1_700_000_000,
+                nanosec: 0,
+            },
+            frame_id: "map".to_owned(),
+        };
+
+        Marker {
+            header: header(),
+            ns: ns.to_owned(),
+            id,
+            marker_type: marker_type as i32,
+            action: action as i32,
+            pose: Pose {
+                position: geometry_msgs::Point {
+                    x: 1.0,
+                    y: 2.0,
+                    z: 3.0,
+                },
+                orientation: geometry_msgs::Quaternion {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                    w: 1.0,
+                },
+            },
+            scale: Vector3 {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+            },
+            color: ColorRGBA {
+                r: 1.0,
+                g: 0.0,
+                b: 0.0,
+                a: 1.0,
+            },
+            lifetime: Duration { sec: 0, nanosec: 0 },
+            frame_locked: false,
+            points: Vec::new(),
+            colors: Vec::new(),
+            texture_resource: String::new(),
+            texture: CompressedImage {
+                header: header(),
+                format: String::new(),
+                data: Cow::Borrowed(&[]),
+            },
+            uv_coordinates: Vec::new(),
+            text: String::new(),
+            mesh_resource: String::new(),
+            mesh_file: MeshFile {
+                filename: String::new(),
+                data: Cow::Borrowed(&[]),
+            },
+            mesh_use_embedded_materials: false,
+        }
+    }
+
+    /// Runs the layer on one `MarkerArray` message per item of `marker_arrays`, on `/markers`.
+    fn run(marker_arrays: Vec<Vec<Marker<'static>>>) -> Vec<Chunk> {
+        let messages = marker_arrays
+            .into_iter()
+            .map(|markers| cdr_encode(&MarkerArray { markers }))
+            .collect::<Vec<_>>();
+        run_ros2_layer("visualization_msgs/msg/MarkerArray", "/markers", &messages)
+    }
+
+    fn find_chunk<'a>(chunks: &'a [Chunk], entity_path: &str) -> &'a Chunk {
+        chunks
+            .iter()
+            .find(|chunk| chunk.entity_path() == &EntityPath::from(entity_path))
+            .unwrap_or_else(|| panic!("missing chunk for {entity_path}"))
+    }
+
+    fn log_times(chunk: &Chunk) -> Vec<i64> {
+        chunk.timelines()[&TimelineName::log_time()]
+            .times_raw()
+            .to_vec()
+    }
+
+    /// Whether each row of the chunk is a clear, and if so, whether it is recursive.
+    fn clears(chunk: &Chunk) -> Vec<Option<bool>> {
+        (0..chunk.num_rows())
+            .map(|row| {
+                chunk
+                    .component_mono::<ClearIsRecursive>(
+                        Clear::descriptor_is_recursive().component,
+                        row,
+                    )
+                    .map(|is_recursive| is_recursive.unwrap().0.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn add() {
+        let chunks = run(vec![vec![
+            marker("obstacles", 3, MarkerType::Cube, MarkerAction::Add),
+            marker("", 1, MarkerType::Sphere, MarkerAction::Add),
+        ]]);
+        assert_eq!(chunks.len(), 2);
+
+        let cube = find_chunk(&chunks, "/markers/obstacles/3");
+        assert_eq!(cube.num_rows(), 1);
+        assert_eq!(clears(cube), vec![None]);
+        assert_eq!(
+            cube.component_mono::<HalfSize3D>(Boxes3D::descriptor_half_sizes().component, 0)
+                .unwrap()
+                .unwrap(),
+            HalfSize3D::new(0.5, 1.0, 1.5)
+        );
+        assert_eq!(
+            cube.component_mono::<Translation3D>(
+                Transform3D::descriptor_translation().component,
+                0
+            )
+            .unwrap()
+            .unwrap(),
+            Translation3D::new(1.0, 2.0, 3.0)
+        );
+        assert_eq!(
+            cube.component_mono::<TransformFrameId>(
+                Transform3D::descriptor_parent_frame().component,
+                0
+            )
+            .unwrap()
+            .unwrap(),
+            TransformFrameId::new("map")
+        );
+
+        let sphere = find_chunk(&chunks, "/markers/1");
+        assert!(
+            sphere
+                .components()
+                .contains_component(Ellipsoids3D::descriptor_half_sizes().component)
+        );
+        assert!(
+            !sphere
+                .components()
+                .contains_component(Boxes3D::descriptor_half_sizes().component)
+        );
+    }
+
+    #[test]
+    fn replace_with_other_type() {
+        let chunks = run(vec![
+            vec![marker("", 1, MarkerType::Cube, MarkerAction::Add)],
+            vec![marker("", 1, MarkerType::Sphere, MarkerAction::Add)],
+        ]);
+
+        // The components of the cube must not linger around.
+        let chunk = find_chunk(&chunks, "/markers/1");
+        assert_eq!(clears(chunk), vec![None, Some(false), None]);
+        assert_eq!(log_times(chunk), vec![42, 43, 43]);
+    }
+
+    #[test]
+    fn delete() {
+        let chunks = run(vec![
+            vec![marker("obstacles", 3, MarkerType::Cube, MarkerAction::Add)],
+            vec![marker(
+                "obstacles",
+                3,
+                MarkerType::Cube,
+                MarkerAction::Delete,
+            )],
+        ]);
+        assert_eq!(chunks.len(), 1);
+
+        let chunk = find_chunk(&chunks, "/markers/obstacles/3");
+        assert_eq!(clears(chunk), vec![None, Some(false)]);
+        assert_eq!(log_times(chunk), vec![42, 43]);
+    }
+
+    #[test]
+    fn delete_all() {
+        let chunks = run(vec![
+            vec![
+                marker("obstacles", 3, MarkerType::Cube, MarkerAction::Add),
+                marker("lanes", 1, MarkerType::LineStrip, MarkerAction::Add),
+            ],
+            // Only deletes the markers in its namespace.
+            vec![marker(
+                "obstacles",
+                0,
+                MarkerType::Cube,
+                MarkerAction::DeleteAll,
+            )],
+            // Deletes everything.
+            vec![marker("", 0, MarkerType::Cube, MarkerAction::DeleteAll)],
+        ]);
+
+        let namespace = find_chunk(&chunks, "/markers/obstacles");
+        assert_eq!(clears(namespace), vec![Some(true)]);
+        assert_eq!(log_times(namespace), vec![43]);
+
+        let topic = find_chunk(&chunks, "/markers");
+        assert_eq!(clears(topic), vec![Some(true)]);
+        assert_eq!(log_times(topic), vec![44]);
+
+        // The markers themselves are untouched.
+        assert_eq!(
+            clears(find_chunk(&chunks, "/markers/obstacles/3")),
+            vec![None]
+        );
+        assert_eq!(clears(find_chunk(&chunks, "/markers/lanes/1")), vec![None]);
+    }
+
+    #[test]
+    fn lifetime() {
+        let with_lifetime = |nanosec| Marker {
+            lifetime: Duration { sec: 0, nanosec },
+            ..marker("", 1, MarkerType::Cube, MarkerAction::Add)
+        };
+
+        let chunks = run(vec![
+            // Both updated before they expire, so never cleared.
+            vec![with_lifetime(5)],
+            vec![with_lifetime(5)],
+            // Expires at 45, before the next update.
+            vec![with_lifetime(1)],
+            vec![],
+            vec![],
+            // Lasts forever.
+            vec![with_lifetime(0)],
+        ]);
+
+        let chunk = find_chunk(&chunks, "/markers/1");
+        assert_eq!(log_times(chunk), vec![42, 43, 44, 45, 47]);
+        assert_eq!(clears(chunk), vec![None, None, None, Some(false), None]);
+
+        // The clears are shifted on every timeline.
+        let ros2_times = chunk.timelines()[&TimelineName::new("ros2_timestamp")].times_raw();
+        assert_eq!(ros2_times[3] - ros2_times[2], 1);
+    }
+}
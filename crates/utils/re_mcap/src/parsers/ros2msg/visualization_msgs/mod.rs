@@ -0,0 +1,3 @@
+mod marker;
+
+pub use marker::*;
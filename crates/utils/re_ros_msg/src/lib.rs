@@ -5,12 +5,16 @@
 //! generic and does not rely on any pre-baked message definitions, so it can be
 //! used to parse unknown types and still extract semantic meaning (types,
 //! arrays, names, constants, default values).
+//!
+//! Messages that were serialized using the ROS 1 wire format can be decoded with the
+//! deserializer in [`ros1`].
 use anyhow::Context as _;
 
 use crate::message_spec::MessageSpecification;
 
 pub mod deserialize;
 pub mod message_spec;
+pub mod ros1;
 
 /// Parse a schema name from a line starting with "MSG: ".
 fn parse_schema_name(line: &str) -> Option<&str> {
@@ -0,0 +1,351 @@
+//! Deserializer for the ROS 1 serialization format.
+//!
+//! ROS 1 messages are serialized as a flat, little-endian byte stream without any
+//! alignment or padding:
+//!
+//! * Primitives are written using their natural size, `bool` takes up a single byte.
+//! * Strings and variable-length arrays are prefixed with their length as `uint32`.
+//! * Fixed-size arrays have no length prefix.
+//! * `time` and `duration` are two 32-bit integers for seconds and nanoseconds.
+//!
+//! See the [ROS 1 serialization documentation](https://wiki.ros.org/msg#Serialization_format)
+//! for more details.
+//!
+//! The ROS 1 `std_msgs/Header` contains a `seq` field that was dropped in ROS 2. So that
+//! ROS 2 message definitions can be reused to decode ROS 1 messages, structs named `Header`
+//! that don't declare a `seq` field skip it when deserializing.
+
+use serde::de::{self, DeserializeSeed, IntoDeserializer as _, Visitor};
+
+/// Errors that can occur when deserializing a ROS 1 message.
+#[derive(thiserror::Error, Debug)]
+pub enum Ros1Error {
+    #[error("unexpected end of input, needed {needed} more bytes but only {remaining} are left")]
+    UnexpectedEof { needed: usize, remaining: usize },
+
+    #[error("invalid UTF-8 in string: {0}")]
+    InvalidUtf8(#[from] std::str::Utf8Error),
+
+    #[error("`{0}` is not supported by the ROS 1 serialization format")]
+    Unsupported(&'static str),
+
+    #[error("{0}")]
+    Message(String),
+}
+
+impl de::Error for Ros1Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Self::Message(msg.to_string())
+    }
+}
+
+/// Deserialize a ROS 1 serialized message into a `T`.
+///
+/// Trailing bytes that are not consumed by `T` are ignored.
+pub fn from_bytes<'de, T: de::Deserialize<'de>>(bytes: &'de [u8]) -> Result<T, Ros1Error> {
+    T::deserialize(&mut Deserializer::new(bytes))
+}
+
+/// A [`serde::Deserializer`] for ROS 1 serialized messages.
+pub struct Deserializer<'de> {
+    input: &'de [u8],
+}
+
+impl<'de> Deserializer<'de> {
+    pub fn new(input: &'de [u8]) -> Self {
+        Self { input }
+    }
+
+    /// The bytes that have not been consumed yet.
+    pub fn remaining(&self) -> &'de [u8] {
+        self.input
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'de [u8], Ros1Error> {
+        if self.input.len() < len {
+            return Err(Ros1Error::UnexpectedEof {
+                needed: len,
+                remaining: self.input.len(),
+            });
+        }
+
+        let (bytes, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], Ros1Error> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn read_len(&mut self) -> Result<usize, Ros1Error> {
+        Ok(u32::from_le_bytes(self.take_array()?) as usize)
+    }
+
+    fn read_str(&mut self) -> Result<&'de str, Ros1Error> {
+        let len = self.read_len()?;
+        Ok(std::str::from_utf8(self.take(len)?)?)
+    }
+}
+
+macro_rules! deserialize_primitive {
+    ($method:ident, $visit:ident, $ty:ty) => {
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            visitor.$visit(<$ty>::from_le_bytes(self.take_array()?))
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Ros1Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(Ros1Error::Unsupported("deserialize_any"))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_bool(self.take(1)?[0] != 0)
+    }
+
+    deserialize_primitive!(deserialize_i8, visit_i8, i8);
+    deserialize_primitive!(deserialize_i16, visit_i16, i16);
+    deserialize_primitive!(deserialize_i32, visit_i32, i32);
+    deserialize_primitive!(deserialize_i64, visit_i64, i64);
+    deserialize_primitive!(deserialize_u8, visit_u8, u8);
+    deserialize_primitive!(deserialize_u16, visit_u16, u16);
+    deserialize_primitive!(deserialize_u32, visit_u32, u32);
+    deserialize_primitive!(deserialize_u64, visit_u64, u64);
+    deserialize_primitive!(deserialize_f32, visit_f32, f32);
+    deserialize_primitive!(deserialize_f64, visit_f64, f64);
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        // ROS 1 `char` is an alias for `uint8`.
+        visitor.visit_char(char::from(self.take(1)?[0]))
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_borrowed_str(self.read_str()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let len = self.read_len()?;
+        visitor.visit_borrowed_bytes(self.take(len)?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(Ros1Error::Unsupported("option"))
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let len = self.read_len()?;
+        visitor.visit_seq(ElementAccess {
+            de: self,
+            remaining: len,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(ElementAccess {
+            de: self,
+            remaining: len,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(Ros1Error::Unsupported("map"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if name == "Header" && !fields.contains(&"seq") {
+            self.take(std::mem::size_of::<u32>())?;
+        }
+
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        // Enums are not part of the ROS 1 message format, we follow CDR and use a `uint32` discriminant.
+        let variant = u32::from_le_bytes(self.take_array()?);
+        visitor.visit_enum(variant.into_deserializer())
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(Ros1Error::Unsupported("identifier"))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(Ros1Error::Unsupported("ignored_any"))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Gives access to the elements of arrays, sequences, and fields of structs.
+struct ElementAccess<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for ElementAccess<'_, 'de> {
+    type Error = Ros1Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Time {
+        sec: i32,
+        nanosec: u32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Header {
+        stamp: Time,
+        frame_id: String,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Message {
+        header: Header,
+        flag: bool,
+        values: Vec<f32>,
+        fixed: [u16; 2],
+        #[serde(with = "serde_bytes")]
+        data: Vec<u8>,
+        names: Vec<String>,
+    }
+
+    fn push_str(buf: &mut Vec<u8>, s: &str) {
+        buf.extend((s.len() as u32).to_le_bytes());
+        buf.extend(s.as_bytes());
+    }
+
+    #[test]
+    fn message_with_header() {
+        let mut buf = Vec::new();
+        buf.extend(7u32.to_le_bytes()); // seq
+        buf.extend(12u32.to_le_bytes());
+        buf.extend(34u32.to_le_bytes());
+        push_str(&mut buf, "base_link");
+        buf.push(1);
+        buf.extend(2u32.to_le_bytes());
+        buf.extend(1.5f32.to_le_bytes());
+        buf.extend((-2.0f32).to_le_bytes());
+        buf.extend(3u16.to_le_bytes());
+        buf.extend(4u16.to_le_bytes());
+        buf.extend(3u32.to_le_bytes());
+        buf.extend([5, 6, 7]);
+        buf.extend(2u32.to_le_bytes());
+        push_str(&mut buf, "a");
+        push_str(&mut buf, "bc");
+
+        let message: Message = from_bytes(&buf).unwrap();
+        assert_eq!(
+            message,
+            Message {
+                header: Header {
+                    stamp: Time {
+                        sec: 12,
+                        nanosec: 34
+                    },
+                    frame_id: "base_link".to_owned(),
+                },
+                flag: true,
+                values: vec![1.5, -2.0],
+                fixed: [3, 4],
+                data: vec![5, 6, 7],
+                names: vec!["a".to_owned(), "bc".to_owned()],
+            }
+        );
+    }
+
+    #[test]
+    fn truncated_input() {
+        let buf = 8u32.to_le_bytes();
+        assert!(matches!(
+            from_bytes::<String>(&buf),
+            Err(Ros1Error::UnexpectedEof {
+                needed: 8,
+                remaining: 0
+            })
+        ));
+    }
+}
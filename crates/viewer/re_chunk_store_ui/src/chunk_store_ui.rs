@@ -1,5 +1,4 @@
 use std::collections::BTreeMap;
-use std::sync::Arc;
 
 use egui_extras::{Column, TableRow};
 use itertools::{Either, Itertools as _};
@@ -133,7 +132,7 @@ impl DatastoreUi {
         //
 
         let chunk_iterator = match &self.chunk_list_mode {
-            ChunkListMode::All => Either::Left(chunk_store.iter_chunks().map(Arc::clone)),
+            ChunkListMode::All => Either::Left(chunk_store.iter_chunks()),
             ChunkListMode::Query {
                 timeline,
                 entity_path,
@@ -1062,7 +1062,7 @@ mod tests {
                 store_id: store.store_id().clone(),
                 store_generation: store.generation(),
                 event_id: 0, // Wrong but don't care.
-                diff: ChunkStoreDiff::deletion(chunk_store.iter_chunks().next().unwrap().clone()),
+                diff: ChunkStoreDiff::deletion(chunk_store.iter_chunks().next().unwrap()),
             }],
             &store,
         );
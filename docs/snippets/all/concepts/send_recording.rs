@@ -17,7 +17,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Forward all chunks to the new recording stream.
     for chunk in store.iter_chunks() {
-        new_recording.send_chunk((**chunk).clone());
+        new_recording.send_chunk(std::sync::Arc::unwrap_or_clone(chunk));
     }
 
     Ok(())
@@ -375,6 +375,34 @@ class RecordingView:
 
         """
 
+    def filter_by(self, predicate: str) -> RecordingView:
+        """
+        Filter the view to only include rows for which the given predicate holds.
+
+        The predicate is evaluated on the rows as they are returned, i.e. after any
+        [`.fill_latest_at()`][rerun.dataframe.RecordingView.fill_latest_at].
+
+        Predicates are written as `column IS NOT NULL` or `column <op> <value>`, where `column`
+        is a component column name (e.g. `/gripper:Gripper:state`), optionally followed by
+        `.field` for struct components, `<op>` is one of `=`, `!=`, `<`, `<=`, `>`, `>=`, and
+        `<value>` is `true`, `false`, a number, or a double-quoted string. They can be combined with
+        `AND`, `OR` and parentheses.
+
+        Parameters
+        ----------
+        predicate : str
+            The predicate to filter by, e.g.
+            `'/camera:Points3D:positions IS NOT NULL AND /gripper:Gripper:state = "closed"'`.
+
+        Returns
+        -------
+        RecordingView
+            A new view containing only the rows for which the predicate holds.
+
+            The original view will not be modified.
+
+        """
+
     def using_index_values(self, values: IndexValuesLike) -> RecordingView:
         """
         Create a new view that contains the provided index values.
@@ -399,10 +427,45 @@ class RecordingView:
 
         """
 
-    def fill_latest_at(self) -> RecordingView:
+    def fill_latest_at(self, *, restrict_to_view: bool = False) -> RecordingView:
         """
         Populate any null values in a row with the latest valid data according to the index.
 
+        Parameters
+        ----------
+        restrict_to_view : bool
+            If set, only static data and data indexed within the view's index range (as set by
+            [`.filter_range_*()`][rerun.dataframe.RecordingView.filter_range_sequence],
+            [`.filter_index_values()`][rerun.dataframe.RecordingView.filter_index_values] or
+            [`.using_index_values()`][rerun.dataframe.RecordingView.using_index_values]) are used
+            to fill null values. Otherwise, data from before the view can be used as well.
+
+        Returns
+        -------
+        RecordingView
+            A new view with the null values filled in.
+
+            The original view will not be modified.
+
+        """
+
+    def fill_interpolated(self, *, restrict_to_view: bool = False) -> RecordingView:
+        """
+        Populate any null values in a row by interpolating between the surrounding valid data.
+
+        Floating-point components (scalars, translations, …) are linearly interpolated, and
+        rotation quaternions are spherically interpolated. Other components, as well as values
+        without valid data after them, are filled like [`.fill_latest_at()`][rerun.dataframe.RecordingView.fill_latest_at].
+
+        This is most useful to resample data at a fixed rate using
+        [`.using_index_values()`][rerun.dataframe.RecordingView.using_index_values].
+
+        Parameters
+        ----------
+        restrict_to_view : bool
+            If set, only static data and data indexed within the view's index range are used to
+            fill null values, as with [`.fill_latest_at()`][rerun.dataframe.RecordingView.fill_latest_at].
+
         Returns
         -------
         RecordingView
@@ -523,6 +586,10 @@ class Recording:
         ----------
         index : str | None
             The index to use for the view. This is typically a timeline name. Use `None` to query static data only.
+
+            Several timelines can be chained with `else`, e.g. `"sensor_time else log_time"`: each entity is then
+            indexed by the first of these timelines it has data on, and all values are reported in the index column
+            of the first one.
         contents : ViewContentsLike
             The content specification for the view.
 
@@ -1494,6 +1561,10 @@ class DatasetEntry(Entry):
         ----------
         index : str | None
             The index to use for the view. This is typically a timeline name. Use `None` to query static data only.
+
+            Several timelines can be chained with `else`, e.g. `"sensor_time else log_time"`: each entity is then
+            indexed by the first of these timelines it has data on, and all values are reported in the index column
+            of the first one.
         contents : ViewContentsLike
             The content specification for the view.
 
@@ -1770,6 +1841,34 @@ class DataframeQueryView:
 
         """
 
+    def filter_by(self, predicate: str) -> Self:
+        """
+        Filter the view to only include rows for which the given predicate holds.
+
+        The predicate is evaluated on the rows as they are returned, i.e. after any
+        [`.fill_latest_at()`][rerun.dataframe.RecordingView.fill_latest_at].
+
+        Predicates are written as `column IS NOT NULL` or `column <op> <value>`, where `column`
+        is a component column name (e.g. `/gripper:Gripper:state`), optionally followed by
+        `.field` for struct components, `<op>` is one of `=`, `!=`, `<`, `<=`, `>`, `>=`, and
+        `<value>` is `true`, `false`, a number, or a double-quoted string. They can be combined with
+        `AND`, `OR` and parentheses.
+
+        Parameters
+        ----------
+        predicate : str
+            The predicate to filter by, e.g.
+            `'/camera:Points3D:positions IS NOT NULL AND /gripper:Gripper:state = "closed"'`.
+
+        Returns
+        -------
+        RecordingView
+            A new view containing only the rows for which the predicate holds.
+
+            The original view will not be modified.
+
+        """
+
     def using_index_values(self, values: IndexValuesLike) -> Self:
         """
         Create a new view that contains the provided index values.
@@ -1794,10 +1893,39 @@ class DataframeQueryView:
 
         """
 
-    def fill_latest_at(self) -> Self:
+    def fill_latest_at(self, *, restrict_to_view: bool = False) -> Self:
         """
         Populate any null values in a row with the latest valid data according to the index.
 
+        Parameters
+        ----------
+        restrict_to_view : bool
+            If set, only static data and data indexed within the view's index range are used to
+            fill null values. Otherwise, data from before the view can be used as well.
+
+        Returns
+        -------
+        RecordingView
+            A new view with the null values filled in.
+
+            The original view will not be modified.
+
+        """
+
+    def fill_interpolated(self, *, restrict_to_view: bool = False) -> Self:
+        """
+        Populate any null values in a row by interpolating between the surrounding valid data.
+
+        Floating-point components (scalars, translations, …) are linearly interpolated, and
+        rotation quaternions are spherically interpolated. Other components, as well as values
+        without valid data after them, are filled like `fill_latest_at()`.
+
+        Parameters
+        ----------
+        restrict_to_view : bool
+            If set, only static data and data indexed within the view's index range are used to
+            fill null values.
+
         Returns
         -------
         RecordingView
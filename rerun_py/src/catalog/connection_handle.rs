@@ -602,7 +602,7 @@ impl ConnectionHandle {
             .map(|ident| ident.to_string())
             .collect();
 
-        let query = query_from_query_expression(query_expression);
+        let query = query_from_query_expression(query_expression).map_err(to_py_err)?;
 
         let request = QueryDatasetRequest {
             partition_ids: partition_ids
@@ -15,7 +15,7 @@ use pyo3::{Bound, Py, PyAny, PyRef, PyResult, Python, pyclass, pymethods};
 use tracing::instrument;
 
 use re_chunk::ComponentIdentifier;
-use re_chunk_store::{QueryExpression, SparseFillStrategy, ViewContentsSelector};
+use re_chunk_store::{QueryExpression, RowFilter, SparseFillStrategy, ViewContentsSelector};
 use re_datafusion::DataframeQueryTableProvider;
 use re_log_types::{AbsoluteTimeRange, EntityPath, EntityPathFilter};
 use re_sdk::ComponentDescriptor;
@@ -60,6 +60,14 @@ impl PyDataframeQueryView {
 
         let view_contents = extract_contents_expr(contents.bind(py), &schema)?;
 
+        let mut indices = index
+            .as_deref()
+            .map(QueryExpression::parse_index_selector)
+            .unwrap_or_default()
+            .into_iter();
+        let filtered_index = indices.next();
+        let filtered_index_fallbacks = indices.collect();
+
         Ok(Self {
             dataset,
 
@@ -72,11 +80,13 @@ impl PyDataframeQueryView {
                 } else {
                     re_chunk_store::StaticColumnSelection::Both
                 },
-                filtered_index: index.map(Into::into),
+                filtered_index,
+                filtered_index_fallbacks,
                 filtered_index_range: None,
                 filtered_index_values: None,
                 using_index_values: None,
                 filtered_is_not_null: None,
+                filtered_by: None,
                 sparse_fill_strategy: SparseFillStrategy::None,
                 selection: None,
             },
@@ -327,6 +337,39 @@ impl PyDataframeQueryView {
         }))
     }
 
+    /// Filter the view to only include rows for which the given predicate holds.
+    ///
+    /// The predicate is evaluated on the rows as they are returned, i.e. after any
+    /// [`.fill_latest_at()`][rerun.dataframe.RecordingView.fill_latest_at].
+    ///
+    /// Predicates are written as `column IS NOT NULL` or `column <op> <value>`, where `column`
+    /// is a component column name (e.g. `/gripper:Gripper:state`), optionally followed by
+    /// `.field` for struct components, `<op>` is one of `=`, `!=`, `<`, `<=`, `>`, `>=`, and
+    /// `<value>` is `true`, `false`, a number, or a double-quoted string. They can be combined with
+    /// `AND`, `OR` and parentheses.
+    ///
+    /// Parameters
+    /// ----------
+    /// predicate : str
+    ///     The predicate to filter by, e.g.
+    ///     `'/camera:Points3D:positions IS NOT NULL AND /gripper:Gripper:state = "closed"'`.
+    ///
+    /// Returns
+    /// -------
+    /// RecordingView
+    ///     A new view containing only the rows for which the predicate holds.
+    ///
+    ///     The original view will not be modified.
+    fn filter_by(&self, py: Python<'_>, predicate: &str) -> PyResult<Self> {
+        let filter = predicate
+            .parse::<RowFilter>()
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+        Ok(self.clone_with_new_query(py, |query_expression| {
+            query_expression.filtered_by = Some(filter);
+        }))
+    }
+
     /// Create a new view that contains the provided index values.
     ///
     /// If they exist in the original data they are selected, otherwise empty rows are added to the view.
@@ -360,15 +403,55 @@ impl PyDataframeQueryView {
 
     /// Populate any null values in a row with the latest valid data according to the index.
     ///
+    /// Parameters
+    /// ----------
+    /// restrict_to_view : bool
+    ///     If set, only static data and data indexed within the view's index range are used to
+    ///     fill null values. Otherwise, data from before the view can be used as well.
+    ///
     /// Returns
     /// -------
     /// RecordingView
     ///     A new view with the null values filled in.
     ///
     ///     The original view will not be modified.
-    fn fill_latest_at(&self, py: Python<'_>) -> Self {
+    #[pyo3(signature = (*, restrict_to_view = false))]
+    fn fill_latest_at(&self, py: Python<'_>, restrict_to_view: bool) -> Self {
         self.clone_with_new_query(py, |query_expression| {
-            query_expression.sparse_fill_strategy = SparseFillStrategy::LatestAtGlobal;
+            query_expression.sparse_fill_strategy = if restrict_to_view {
+                SparseFillStrategy::LatestAtView
+            } else {
+                SparseFillStrategy::LatestAtGlobal
+            };
+        })
+    }
+
+    /// Populate any null values in a row by interpolating between the surrounding valid data.
+    ///
+    /// Floating-point components (scalars, translations, …) are linearly interpolated, and
+    /// rotation quaternions are spherically interpolated. Other components, as well as values
+    /// without valid data after them, are filled like `fill_latest_at()`.
+    ///
+    /// Parameters
+    /// ----------
+    /// restrict_to_view : bool
+    ///     If set, only static data and data indexed within the view's index range are used to
+    ///     fill null values.
+    ///
+    /// Returns
+    /// -------
+    /// RecordingView
+    ///     A new view with the null values filled in.
+    ///
+    ///     The original view will not be modified.
+    #[pyo3(signature = (*, restrict_to_view = false))]
+    fn fill_interpolated(&self, py: Python<'_>, restrict_to_view: bool) -> Self {
+        self.clone_with_new_query(py, |query_expression| {
+            query_expression.sparse_fill_strategy = if restrict_to_view {
+                SparseFillStrategy::InterpolateView
+            } else {
+                SparseFillStrategy::InterpolateGlobal
+            };
         })
     }
 
@@ -504,6 +504,10 @@ impl PyDatasetEntry {
     /// ----------
     /// index : str | None
     ///     The index to use for the view. This is typically a timeline name. Use `None` to query static data only.
+    ///
+    ///     Several timelines can be chained with `else`, e.g. `"sensor_time else log_time"`: each entity is then
+    ///     indexed by the first of these timelines it has data on, and all values are reported in the index column
+    ///     of the first one.
     /// contents : ViewContentsLike
     ///     The content specification for the view.
     ///
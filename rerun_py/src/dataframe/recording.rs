@@ -161,6 +161,10 @@ impl PyRecording {
     /// ----------
     /// index : str | None
     ///     The index to use for the view. This is typically a timeline name. Use `None` to query static data only.
+    ///
+    ///     Several timelines can be chained with `else`, e.g. `"sensor_time else log_time"`: each entity is then
+    ///     indexed by the first of these timelines it has data on, and all values are reported in the index column
+    ///     of the first one.
     /// contents : ViewContentsLike
     ///     The content specification for the view.
     ///
@@ -211,12 +215,18 @@ impl PyRecording {
 
         let borrowed_self = slf.borrow();
 
-        // Look up the type of the timeline
-        let filtered_index = index.map(|index| {
-            let selector = TimeColumnSelector::from(index);
-            let time_column = borrowed_self.store.read().resolve_time_selector(&selector);
-            *time_column.timeline().name()
-        });
+        // Look up the type of the timeline(s)
+        let mut indices = index
+            .map(QueryExpression::parse_index_selector)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|index| {
+                let selector = TimeColumnSelector::from(index);
+                let time_column = borrowed_self.store.read().resolve_time_selector(&selector);
+                *time_column.timeline().name()
+            });
+        let filtered_index = indices.next();
+        let filtered_index_fallbacks = indices.collect();
 
         let contents = borrowed_self.extract_contents_expr(contents)?;
 
@@ -230,10 +240,12 @@ impl PyRecording {
                 StaticColumnSelection::Both
             },
             filtered_index,
+            filtered_index_fallbacks,
             filtered_index_range: None,
             filtered_index_values: None,
             using_index_values: None,
             filtered_is_not_null: None,
+            filtered_by: None,
             sparse_fill_strategy: SparseFillStrategy::None,
             selection: None,
         };
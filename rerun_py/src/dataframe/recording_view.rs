@@ -7,7 +7,7 @@ use pyo3::prelude::{PyAnyMethods as _, PyTupleMethods as _};
 use pyo3::types::PyTuple;
 use pyo3::{Bound, PyRef, PyResult, Python, pyclass, pymethods};
 
-use re_chunk_store::{QueryExpression, SparseFillStrategy};
+use re_chunk_store::{QueryExpression, RowFilter, SparseFillStrategy};
 use re_log_types::AbsoluteTimeRange;
 use re_sorbet::{ColumnDescriptor, ColumnSelector};
 
@@ -492,6 +492,43 @@ impl PyRecordingView {
         })
     }
 
+    /// Filter the view to only include rows for which the given predicate holds.
+    ///
+    /// The predicate is evaluated on the rows as they are returned, i.e. after any
+    /// [`.fill_latest_at()`][rerun.dataframe.RecordingView.fill_latest_at].
+    ///
+    /// Predicates are written as `column IS NOT NULL` or `column <op> <value>`, where `column`
+    /// is a component column name (e.g. `/gripper:Gripper:state`), optionally followed by
+    /// `.field` for struct components, `<op>` is one of `=`, `!=`, `<`, `<=`, `>`, `>=`, and
+    /// `<value>` is `true`, `false`, a number, or a double-quoted string. They can be combined with
+    /// `AND`, `OR` and parentheses.
+    ///
+    /// Parameters
+    /// ----------
+    /// predicate : str
+    ///     The predicate to filter by, e.g.
+    ///     `'/camera:Points3D:positions IS NOT NULL AND /gripper:Gripper:state = "closed"'`.
+    ///
+    /// Returns
+    /// -------
+    /// RecordingView
+    ///     A new view containing only the rows for which the predicate holds.
+    ///
+    ///     The original view will not be modified.
+    fn filter_by(&self, predicate: &str) -> PyResult<Self> {
+        let filter = predicate
+            .parse::<RowFilter>()
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+        let mut query_expression = self.query_expression.clone();
+        query_expression.filtered_by = Some(filter);
+
+        Ok(Self {
+            recording: self.recording.clone(),
+            query_expression,
+        })
+    }
+
     #[expect(rustdoc::private_doc_tests)]
     /// Create a new view that contains the provided index values.
     ///
@@ -527,15 +564,66 @@ impl PyRecordingView {
     #[expect(rustdoc::private_doc_tests)]
     /// Populate any null values in a row with the latest valid data according to the index.
     ///
+    /// Parameters
+    /// ----------
+    /// restrict_to_view : bool
+    ///     If set, only static data and data indexed within the view's index range (as set by
+    ///     [`.filter_range_*()`][rerun.dataframe.RecordingView.filter_range_sequence],
+    ///     [`.filter_index_values()`][rerun.dataframe.RecordingView.filter_index_values] or
+    ///     [`.using_index_values()`][rerun.dataframe.RecordingView.using_index_values]) are used
+    ///     to fill null values. Otherwise, data from before the view can be used as well.
+    ///
     /// Returns
     /// -------
     /// RecordingView
     ///     A new view with the null values filled in.
     ///
     ///     The original view will not be modified.
-    fn fill_latest_at(&self) -> Self {
+    #[pyo3(signature = (*, restrict_to_view = false))]
+    fn fill_latest_at(&self, restrict_to_view: bool) -> Self {
         let mut query_expression = self.query_expression.clone();
-        query_expression.sparse_fill_strategy = SparseFillStrategy::LatestAtGlobal;
+        query_expression.sparse_fill_strategy = if restrict_to_view {
+            SparseFillStrategy::LatestAtView
+        } else {
+            SparseFillStrategy::LatestAtGlobal
+        };
+
+        Self {
+            recording: self.recording.clone(),
+            query_expression,
+        }
+    }
+
+    #[expect(rustdoc::private_doc_tests)]
+    /// Populate any null values in a row by interpolating between the surrounding valid data.
+    ///
+    /// Floating-point components (scalars, translations, …) are linearly interpolated, and
+    /// rotation quaternions are spherically interpolated. Other components, as well as values
+    /// without valid data after them, are filled like [`.fill_latest_at()`][rerun.dataframe.RecordingView.fill_latest_at].
+    ///
+    /// This is most useful to resample data at a fixed rate using
+    /// [`.using_index_values()`][rerun.dataframe.RecordingView.using_index_values].
+    ///
+    /// Parameters
+    /// ----------
+    /// restrict_to_view : bool
+    ///     If set, only static data and data indexed within the view's index range are used to
+    ///     fill null values, as with [`.fill_latest_at()`][rerun.dataframe.RecordingView.fill_latest_at].
+    ///
+    /// Returns
+    /// -------
+    /// RecordingView
+    ///     A new view with the null values filled in.
+    ///
+    ///     The original view will not be modified.
+    #[pyo3(signature = (*, restrict_to_view = false))]
+    fn fill_interpolated(&self, restrict_to_view: bool) -> Self {
+        let mut query_expression = self.query_expression.clone();
+        query_expression.sparse_fill_strategy = if restrict_to_view {
+            SparseFillStrategy::InterpolateView
+        } else {
+            SparseFillStrategy::InterpolateGlobal
+        };
 
         Self {
             recording: self.recording.clone(),
@@ -5,7 +5,7 @@ use std::{
     borrow::Borrow as _,
     io::IsTerminal as _,
     path::PathBuf,
-    sync::{LazyLock, OnceLock},
+    sync::{Arc, LazyLock, OnceLock},
     time::Duration,
 };
 
@@ -2009,7 +2009,7 @@ fn send_recording(rrd: &PyRecording, recording: Option<&PyRecordingStream>) {
 
     let store = rrd.store.read();
     for chunk in store.iter_chunks() {
-        recording.send_chunk((**chunk).clone());
+        recording.send_chunk(Arc::unwrap_or_clone(chunk));
     }
 }
 
@@ -38,6 +38,7 @@ impl PyServerInternal {
             port,
             datasets,
             tables,
+            storage_dir: None,
         };
 
         let address = SocketAddr::new(
@@ -357,6 +357,19 @@ class TestDataframe:
         assert table.column("/points:Points3D:positions")[1].values.equals(self.expected_pos0)
         assert table.column("/points:Points3D:positions")[2].values.equals(self.expected_pos1)
 
+    def test_fill_latest_at_restricted_to_view(self) -> None:
+        view = self.recording.view(index="my_index", contents="points")
+        view = view.using_index_values([5, 9])
+
+        table = view.fill_latest_at().select().read_all().combine_chunks()
+        assert table.column("/points:Points3D:positions")[0].values.equals(self.expected_pos0)
+        assert table.column("/points:Points3D:positions")[1].values.equals(self.expected_pos1)
+
+        # The first points were logged before the view starts.
+        table = view.fill_latest_at(restrict_to_view=True).select().read_all().combine_chunks()
+        assert not table.column("/points:Points3D:positions")[0].is_valid
+        assert table.column("/points:Points3D:positions")[1].values.equals(self.expected_pos1)
+
     def test_filter_is_not_null(self) -> None:
         view = self.recording.view(index="my_index", contents="points")
 
@@ -374,6 +387,29 @@ class TestDataframe:
 
         assert table.column("/points:Points3D:positions")[0].values.equals(self.expected_pos1)
 
+    def test_filter_by(self) -> None:
+        view = self.recording.view(index="my_index", contents="points")
+
+        table = view.filter_by("/points:Points3D:colors IS NOT NULL").select().read_all()
+
+        assert table.num_rows == 1
+        assert table.column("my_index")[0].equals(self.expected_index1[0])
+
+        # The predicate is evaluated after filling.
+        table = (
+            view.using_index_values([1, 7, 9])
+            .fill_latest_at()
+            .filter_by("/points:Points3D:colors IS NOT NULL")
+            .select()
+            .read_all()
+            .combine_chunks()
+        )
+
+        assert table.column("my_index").to_pylist() == [7, 9]
+
+        with pytest.raises(ValueError):
+            view.filter_by("/points:Points3D:colors IS NULL")
+
     def test_view_syntax(self) -> None:
         good_content_expressions: list[ViewContentsLike] = [
             {"points": "Points3D:positions"},
@@ -519,3 +555,50 @@ def test_dataframe_static_new_vs_deprecated(mixed_static_recording: rr.dataframe
     table2 = view2.select_static().read_all()
 
     assert table1 == table2
+
+
+def test_fill_interpolated(tmp_path: pathlib.Path) -> None:
+    rrd_path = tmp_path / "tmp.rrd"
+
+    with rr.RecordingStream(APP_ID, recording_id=uuid.uuid4()) as rec:
+        rec.save(rrd_path)
+        rec.set_time("my_index", sequence=0)
+        rec.log("scalar", rr.Scalars(0.0))
+        rec.set_time("my_index", sequence=10)
+        rec.log("scalar", rr.Scalars(10.0))
+
+    recording = rr.dataframe.load_recording(rrd_path)
+    view = recording.view(index="my_index", contents="scalar").using_index_values([0, 5, 10, 15])
+
+    table = view.fill_interpolated().select("/scalar:Scalars:scalars").read_all()
+    assert table.column(0).to_pylist() == [[0.0], [5.0], [10.0], [10.0]]
+
+    # The value at index 0 is outside of the view, so there is nothing to interpolate from.
+    view = view.using_index_values([5, 10, 15])
+    table = view.fill_interpolated(restrict_to_view=True).select("/scalar:Scalars:scalars").read_all()
+    assert table.column(0).to_pylist() == [None, [10.0], [10.0]]
+
+
+def test_index_fallbacks(tmp_path: pathlib.Path) -> None:
+    rrd_path = tmp_path / "tmp.rrd"
+
+    with rr.RecordingStream(APP_ID, recording_id=uuid.uuid4()) as rec:
+        rec.save(rrd_path)
+        for t in range(1, 4):
+            rec.set_time("sensor_time", sequence=t)
+            rec.set_time("host_time", sequence=t + 10)
+            rec.log("sensor", rr.Scalars(float(t)))
+        rec.reset_time()
+        rec.set_time("host_time", sequence=4)
+        rec.log("host", rr.Scalars(40.0))
+
+    recording = rr.dataframe.load_recording(rrd_path)
+
+    table = recording.view(index="sensor_time", contents="/**").select("sensor_time").read_all()
+    assert table.column(0).to_pylist() == [1, 2, 3]
+
+    # `/host` has no data on `sensor_time`: its `host_time` is used instead.
+    view = recording.view(index="sensor_time else host_time", contents="/**")
+    table = view.select("sensor_time", "/host:Scalars:scalars").read_all()
+    assert table.column(0).to_pylist() == [1, 2, 3, 4]
+    assert table.column(1).to_pylist() == [None, None, None, [40.0]]